@@ -0,0 +1,39 @@
+//! Audit log reason extractor
+//!
+//! Extracts the optional reason attached to moderation requests.
+
+use std::convert::Infallible;
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+/// Header carrying the audit log reason
+pub const AUDIT_LOG_REASON_HEADER: &str = "x-audit-log-reason";
+
+/// Maximum reason length in characters; longer reasons are truncated
+const MAX_REASON_LENGTH: usize = 512;
+
+/// Reason from the `X-Audit-Log-Reason` header
+///
+/// Missing, blank or non-UTF-8 headers yield `None`.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogReason(pub Option<String>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuditLogReason
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let reason = parts
+            .headers
+            .get(AUDIT_LOG_REASON_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .map(|reason| reason.chars().take(MAX_REASON_LENGTH).collect());
+
+        Ok(Self(reason))
+    }
+}
@@ -21,12 +21,17 @@ use crate::state::AppState;
 pub struct AuthUser {
     /// User ID from the JWT token
     pub user_id: Snowflake,
+    /// Login session the token belongs to (absent on older tokens)
+    pub session_id: Option<String>,
 }
 
 impl AuthUser {
     /// Create a new AuthUser
-    pub fn new(user_id: Snowflake) -> Self {
-        Self { user_id }
+    pub fn new(user_id: Snowflake, session_id: Option<String>) -> Self {
+        Self {
+            user_id,
+            session_id,
+        }
     }
 }
 
@@ -63,7 +68,7 @@ where
             ApiError::InvalidAuthFormat
         })?;
 
-        Ok(AuthUser::new(user_id))
+        Ok(AuthUser::new(user_id, claims.session_id))
     }
 }
 
@@ -107,7 +112,7 @@ where
                     ApiError::InvalidAuthFormat
                 })?;
 
-                Ok(OptionalAuthUser(Some(AuthUser::new(user_id))))
+                Ok(OptionalAuthUser(Some(AuthUser::new(user_id, claims.session_id))))
             }
             Err(_) => Ok(OptionalAuthUser(None)),
         }
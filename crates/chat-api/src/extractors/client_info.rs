@@ -0,0 +1,68 @@
+//! Client info extractor
+//!
+//! Extracts the client details recorded with new login sessions.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRef, FromRequestParts},
+    http::{
+        header::{FORWARDED, USER_AGENT},
+        request::Parts,
+    },
+};
+use chat_service::ClientInfo;
+
+use crate::state::AppState;
+
+/// Maximum user agent length in characters; longer ones are truncated
+const MAX_USER_AGENT_LENGTH: usize = 256;
+
+/// User agent and IP address of the requesting client
+///
+/// The IP address is the peer address of the connection, or the client
+/// address forwarded by a trusted proxy, missing when the server runs without
+/// connect info. A missing or non-UTF-8 `User-Agent`
+/// yields no user agent.
+#[derive(Debug, Clone, Default)]
+pub struct RequestClient(pub ClientInfo);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequestClient
+where
+    S: Send + Sync,
+    AppState: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|agent| !agent.is_empty())
+            .map(|agent| agent.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        let app_state = AppState::from_ref(state);
+        let ip_address = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| {
+                let headers = &parts.headers;
+                app_state.config().proxy.client_ip(
+                    addr.ip(),
+                    headers.get_all(FORWARDED).iter().filter_map(|v| v.to_str().ok()),
+                    headers.get_all("x-forwarded-for").iter().filter_map(|v| v.to_str().ok()),
+                )
+            })
+            .map(|ip| ip.to_string());
+
+        Ok(Self(ClientInfo {
+            user_agent,
+            ip_address,
+        }))
+    }
+}
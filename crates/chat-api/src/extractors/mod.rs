@@ -2,12 +2,18 @@
 //!
 //! Custom extractors for authentication, validation, and pagination.
 
+mod audit_reason;
 mod auth;
+mod client_info;
+mod multipart;
 mod pagination;
 mod path;
 mod validated;
 
+pub use audit_reason::{AuditLogReason, AUDIT_LOG_REASON_HEADER};
 pub use auth::{AuthUser, OptionalAuthUser};
+pub use client_info::RequestClient;
+pub use multipart::{JsonOrMultipart, JSON_BODY_LIMIT};
 pub use pagination::{Pagination, PaginationParams};
 pub use path::{
     ChannelIdPath, GuildIdPath, GuildRolePath, GuildUserPath, InviteCodePath, MessageIdPath,
@@ -0,0 +1,74 @@
+//! JSON-or-multipart body extractor
+//!
+//! Endpoints that accept file uploads take either a plain JSON body or a
+//! `multipart/form-data` body with a `payload_json` field and file fields.
+
+use axum::{
+    async_trait,
+    body::{to_bytes, Body},
+    extract::{FromRequest, Multipart, Request},
+    http::header,
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use super::ValidatedJson;
+use crate::response::ApiError;
+
+/// Body limit for JSON requests on routes that lift the default body limit
+pub const JSON_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Request body that is either validated JSON or a multipart form
+///
+/// Routes using this extractor disable axum's default body limit so uploads
+/// can stream; JSON bodies are still capped at [`JSON_BODY_LIMIT`] and file
+/// sizes are enforced by the storage layer.
+pub enum JsonOrMultipart<T> {
+    Json(T),
+    Multipart(Multipart),
+}
+
+impl<T> std::fmt::Debug for JsonOrMultipart<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(value) => f.debug_tuple("Json").field(value).finish(),
+            Self::Multipart(_) => f.debug_tuple("Multipart").finish(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, T> FromRequest<S> for JsonOrMultipart<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_multipart = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("multipart/form-data"));
+
+        if is_multipart {
+            let multipart = Multipart::from_request(req, state)
+                .await
+                .map_err(|e| ApiError::invalid_query(e.body_text()))?;
+            return Ok(Self::Multipart(multipart));
+        }
+
+        let (parts, body) = req.into_parts();
+        let bytes = to_bytes(body, JSON_BODY_LIMIT)
+            .await
+            .map_err(|_| ApiError::invalid_query("Request body too large"))?;
+        let req = Request::from_parts(parts, Body::from(bytes));
+
+        let ValidatedJson(value) = ValidatedJson::from_request(req, state).await?;
+        Ok(Self::Json(value))
+    }
+}
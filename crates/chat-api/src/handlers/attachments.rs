@@ -0,0 +1,55 @@
+//! Attachment CDN handlers
+//!
+//! Serves stored attachment files.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chat_service::AttachmentService;
+
+use crate::response::{ApiError, ApiResult};
+use crate::state::AppState;
+
+/// Get attachment file
+///
+/// GET /attachments/{channel_id}/{attachment_id}/{filename}
+pub async fn get_attachment(
+    State(state): State<AppState>,
+    Path((channel_id, attachment_id, filename)): Path<(String, String, String)>,
+) -> ApiResult<Response> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let attachment_id = attachment_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid attachment_id format"))?;
+
+    let service = AttachmentService::new(state.service_context());
+    let (attachment, object) = service.open(channel_id, attachment_id, &filename).await?;
+
+    // Only raster images identified from the file contents render inline; anything
+    // else, including client-labelled images such as SVG, downloads as opaque bytes
+    let (disposition, content_type) = if attachment.verified_image {
+        let content_type = HeaderValue::from_str(&attachment.content_type)
+            .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream"));
+        ("inline", content_type)
+    } else {
+        ("attachment", HeaderValue::from_static("application/octet-stream"))
+    };
+
+    let headers = [
+        (header::CONTENT_TYPE, content_type),
+        (header::CONTENT_LENGTH, HeaderValue::from(object.size)),
+        (
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("public, max-age=31536000, immutable"),
+        ),
+        (header::CONTENT_DISPOSITION, HeaderValue::from_static(disposition)),
+        (header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+    ];
+
+    Ok((headers, Body::from_stream(object.body)).into_response())
+}
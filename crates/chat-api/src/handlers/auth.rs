@@ -1,13 +1,15 @@
 //! Authentication handlers
 //!
-//! Endpoints for user registration, login, logout, and token refresh.
+//! Endpoints for user registration, login, logout, token refresh, password
+//! reset, and email verification.
 
 use axum::{extract::State, Json};
 use chat_service::{
-    AuthResponse, AuthService, LoginRequest, RefreshTokenRequest, RegisterRequest,
+    AuthResponse, AuthService, ForgotPasswordRequest, LoginRequest, LoginResponse, MfaTotpRequest,
+    RefreshTokenRequest, RegisterRequest, ResetPasswordRequest, VerifyEmailRequest,
 };
 
-use crate::extractors::{AuthUser, ValidatedJson};
+use crate::extractors::{AuthUser, RequestClient, ValidatedJson};
 use crate::response::{ApiResult, Created, NoContent};
 use crate::state::AppState;
 
@@ -16,10 +18,11 @@ use crate::state::AppState;
 /// POST /auth/register
 pub async fn register(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(request): ValidatedJson<RegisterRequest>,
 ) -> ApiResult<Created<Json<AuthResponse>>> {
     let service = AuthService::new(state.service_context());
-    let response = service.register(request).await?;
+    let response = service.register(request, client).await?;
     Ok(Created(Json(response)))
 }
 
@@ -28,10 +31,24 @@ pub async fn register(
 /// POST /auth/login
 pub async fn login(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(request): ValidatedJson<LoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    let service = AuthService::new(state.service_context());
+    let response = service.login(request, client).await?;
+    Ok(Json(response))
+}
+
+/// Finish a two-factor login with a TOTP or backup code
+///
+/// POST /auth/mfa/totp
+pub async fn login_mfa_totp(
+    State(state): State<AppState>,
+    RequestClient(client): RequestClient,
+    ValidatedJson(request): ValidatedJson<MfaTotpRequest>,
 ) -> ApiResult<Json<AuthResponse>> {
     let service = AuthService::new(state.service_context());
-    let response = service.login(request).await?;
+    let response = service.login_mfa_totp(request, client).await?;
     Ok(Json(response))
 }
 
@@ -66,3 +83,51 @@ pub async fn logout(
     service.logout(auth.user_id, refresh_token).await?;
     Ok(NoContent)
 }
+
+/// Request a password reset email
+///
+/// POST /auth/forgot-password
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ForgotPasswordRequest>,
+) -> ApiResult<NoContent> {
+    let service = AuthService::new(state.service_context());
+    service.forgot_password(request).await?;
+    Ok(NoContent)
+}
+
+/// Set a new password with a reset token
+///
+/// POST /auth/reset-password
+pub async fn reset_password(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ResetPasswordRequest>,
+) -> ApiResult<NoContent> {
+    let service = AuthService::new(state.service_context());
+    service.reset_password(request).await?;
+    Ok(NoContent)
+}
+
+/// Confirm an email address
+///
+/// POST /auth/verify-email
+pub async fn verify_email(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<VerifyEmailRequest>,
+) -> ApiResult<NoContent> {
+    let service = AuthService::new(state.service_context());
+    service.verify_email(request).await?;
+    Ok(NoContent)
+}
+
+/// Send a new verification email to the current user
+///
+/// POST /auth/verify-email/resend
+pub async fn resend_verification_email(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> ApiResult<NoContent> {
+    let service = AuthService::new(state.service_context());
+    service.resend_verification_email(auth.user_id).await?;
+    Ok(NoContent)
+}
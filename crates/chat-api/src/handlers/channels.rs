@@ -8,11 +8,13 @@ use axum::{
 };
 use chat_cache::{PubSubChannel, PubSubEvent};
 use chat_service::{
-    ChannelResponse, ChannelService, CreateChannelRequest, TypingResponse, UpdateChannelRequest,
+    ChannelResponse, ChannelService, CreateChannelRequest, DmService,
+    EditPermissionOverwriteRequest, PermissionOverwriteResponse, TypingResponse,
+    UpdateChannelRequest,
 };
 use serde_json::json;
 
-use crate::extractors::{AuthUser, ValidatedJson};
+use crate::extractors::{AuditLogReason, AuthUser, ValidatedJson};
 use crate::response::{ApiError, ApiResult, Created, NoContent};
 use crate::state::AppState;
 
@@ -39,6 +41,7 @@ pub async fn get_guild_channels(
 pub async fn create_channel(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path(guild_id): Path<String>,
     ValidatedJson(request): ValidatedJson<CreateChannelRequest>,
 ) -> ApiResult<Created<Json<ChannelResponse>>> {
@@ -48,7 +51,7 @@ pub async fn create_channel(
 
     let service = ChannelService::new(state.service_context());
     let response = service
-        .create_channel(guild_id, auth.user_id, request)
+        .create_channel(guild_id, auth.user_id, request, reason)
         .await?;
     Ok(Created(Json(response)))
 }
@@ -76,6 +79,7 @@ pub async fn get_channel(
 pub async fn update_channel(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path(channel_id): Path<String>,
     ValidatedJson(request): ValidatedJson<UpdateChannelRequest>,
 ) -> ApiResult<Json<ChannelResponse>> {
@@ -85,7 +89,7 @@ pub async fn update_channel(
 
     let service = ChannelService::new(state.service_context());
     let response = service
-        .update_channel(channel_id, auth.user_id, request)
+        .update_channel(channel_id, auth.user_id, request, reason)
         .await?;
     Ok(Json(response))
 }
@@ -96,6 +100,7 @@ pub async fn update_channel(
 pub async fn delete_channel(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path(channel_id): Path<String>,
 ) -> ApiResult<NoContent> {
     let channel_id = channel_id
@@ -103,7 +108,135 @@ pub async fn delete_channel(
         .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
 
     let service = ChannelService::new(state.service_context());
-    service.delete_channel(channel_id, auth.user_id).await?;
+    service.delete_channel(channel_id, auth.user_id, reason).await?;
+    Ok(NoContent)
+}
+
+/// Add group DM recipient
+///
+/// PUT /channels/{channel_id}/recipients/{user_id}
+pub async fn add_recipient(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, user_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = DmService::new(state.service_context());
+    service.add_recipient(channel_id, auth.user_id, user_id).await?;
+    Ok(NoContent)
+}
+
+/// Remove group DM recipient
+///
+/// DELETE /channels/{channel_id}/recipients/{user_id}
+pub async fn remove_recipient(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, user_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = DmService::new(state.service_context());
+    service.remove_recipient(channel_id, auth.user_id, user_id).await?;
+    Ok(NoContent)
+}
+
+/// Get channel permission overwrites
+///
+/// GET /channels/{channel_id}/permissions
+pub async fn get_permission_overwrites(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<String>,
+) -> ApiResult<Json<Vec<PermissionOverwriteResponse>>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+
+    let service = ChannelService::new(state.service_context());
+    let overwrites = service
+        .get_permission_overwrites(channel_id, auth.user_id)
+        .await?;
+    Ok(Json(overwrites))
+}
+
+/// Get channel permission overwrite
+///
+/// GET /channels/{channel_id}/permissions/{overwrite_id}
+pub async fn get_permission_overwrite(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, overwrite_id)): Path<(String, String)>,
+) -> ApiResult<Json<PermissionOverwriteResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let overwrite_id = overwrite_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid overwrite_id format"))?;
+
+    let service = ChannelService::new(state.service_context());
+    let response = service
+        .get_permission_overwrite(channel_id, overwrite_id, auth.user_id)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Edit channel permission overwrite
+///
+/// PUT /channels/{channel_id}/permissions/{overwrite_id}
+pub async fn edit_permission_overwrite(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((channel_id, overwrite_id)): Path<(String, String)>,
+    ValidatedJson(request): ValidatedJson<EditPermissionOverwriteRequest>,
+) -> ApiResult<Json<PermissionOverwriteResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let overwrite_id = overwrite_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid overwrite_id format"))?;
+
+    let service = ChannelService::new(state.service_context());
+    let response = service
+        .edit_permission_overwrite(channel_id, overwrite_id, auth.user_id, request, reason)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Delete channel permission overwrite
+///
+/// DELETE /channels/{channel_id}/permissions/{overwrite_id}
+pub async fn delete_permission_overwrite(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((channel_id, overwrite_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let overwrite_id = overwrite_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid overwrite_id format"))?;
+
+    let service = ChannelService::new(state.service_context());
+    service
+        .delete_permission_overwrite(channel_id, overwrite_id, auth.user_id, reason)
+        .await?;
     Ok(NoContent)
 }
 
@@ -135,14 +268,13 @@ pub async fn typing_indicator(
         }),
     );
 
-    if let Some(guild_id) = channel.guild_id {
-        state
-            .service_context()
-            .publisher()
-            .publish(&PubSubChannel::guild(guild_id), &event)
-            .await
-            .ok();
-    }
+    // Channel events only reach sessions that can view the channel
+    state
+        .service_context()
+        .publisher()
+        .publish(&PubSubChannel::channel(channel_id), &event)
+        .await
+        .ok();
 
     Ok(Json(TypingResponse {
         channel_id: channel_id.to_string(),
@@ -0,0 +1,24 @@
+//! Gateway handlers
+//!
+//! Endpoints describing how to connect to the WebSocket gateway.
+
+use axum::{extract::State, Json};
+use chat_service::{GatewayBotResponse, GatewayService};
+
+use crate::extractors::AuthUser;
+use crate::response::ApiResult;
+use crate::state::AppState;
+
+/// Get gateway URL, recommended shard count and session start limits
+///
+/// GET /gateway/bot
+pub async fn get_gateway_bot(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> ApiResult<Json<GatewayBotResponse>> {
+    let url = format!("ws://{}/gateway", state.config().gateway.address());
+
+    let service = GatewayService::new(state.service_context());
+    let response = service.get_gateway_bot(auth.user_id, url).await?;
+    Ok(Json(response))
+}
@@ -3,15 +3,17 @@
 //! Endpoints for guild management.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
+use chat_core::entities::AuditLogAction;
+use chat_core::traits::AuditLogQuery;
 use chat_service::{
-    CreateGuildRequest, GuildResponse, GuildService, GuildWithCountsResponse, PermissionService,
-    UpdateGuildRequest,
+    AuditLogEntryResponse, AuditLogService, CreateGuildRequest, GuildResponse, GuildService,
+    GuildWithCountsResponse, PermissionService, UpdateGuildRequest,
 };
 
-use crate::extractors::{AuthUser, ValidatedJson};
+use crate::extractors::{AuditLogReason, AuthUser, ValidatedJson};
 use crate::response::{ApiError, ApiResult, Created, NoContent};
 use crate::state::AppState;
 
@@ -60,6 +62,7 @@ pub async fn get_guild(
 pub async fn update_guild(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path(guild_id): Path<String>,
     ValidatedJson(request): ValidatedJson<UpdateGuildRequest>,
 ) -> ApiResult<Json<GuildResponse>> {
@@ -68,7 +71,7 @@ pub async fn update_guild(
         .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
 
     let service = GuildService::new(state.service_context());
-    let response = service.update_guild(guild_id, auth.user_id, request).await?;
+    let response = service.update_guild(guild_id, auth.user_id, request, reason).await?;
     Ok(Json(response))
 }
 
@@ -88,3 +91,61 @@ pub async fn delete_guild(
     service.delete_guild(guild_id, auth.user_id).await?;
     Ok(NoContent)
 }
+
+/// Audit log query parameters
+#[derive(Debug, serde::Deserialize)]
+pub struct AuditLogParams {
+    /// Only entries by this user
+    pub user_id: Option<String>,
+    /// Only entries of this action type
+    pub action_type: Option<i32>,
+    /// Only entries before this entry ID
+    pub before: Option<String>,
+    /// Maximum number of entries (1-100, default 50)
+    pub limit: Option<i64>,
+}
+
+/// Get guild audit log
+///
+/// GET /guilds/{guild_id}/audit-logs
+pub async fn get_guild_audit_logs(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<String>,
+    Query(params): Query<AuditLogParams>,
+) -> ApiResult<Json<Vec<AuditLogEntryResponse>>> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+
+    let user_id = params
+        .user_id
+        .map(|s| s.parse())
+        .transpose()
+        .map_err(|_| ApiError::invalid_query("Invalid user_id format"))?;
+    let action = params
+        .action_type
+        .map(|code| {
+            AuditLogAction::from_i32(code)
+                .ok_or_else(|| ApiError::invalid_query("Invalid action_type"))
+        })
+        .transpose()?;
+    let before = params
+        .before
+        .map(|s| s.parse())
+        .transpose()
+        .map_err(|_| ApiError::invalid_query("Invalid 'before' cursor format"))?;
+
+    let query = AuditLogQuery {
+        user_id,
+        action,
+        before,
+        limit: params.limit.unwrap_or(50),
+    };
+
+    let service = AuditLogService::new(state.service_context());
+    let entries = service
+        .get_guild_audit_logs(guild_id, auth.user_id, query)
+        .await?;
+    Ok(Json(entries))
+}
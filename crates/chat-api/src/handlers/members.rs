@@ -7,10 +7,11 @@ use axum::{
     Json,
 };
 use chat_service::{
-    GuildService, MemberResponse, MemberService, PermissionService, UpdateMemberRequest,
+    BanResponse, CreateBanRequest, GuildService, MemberResponse, MemberService, PermissionService,
+    UpdateMemberRequest,
 };
 
-use crate::extractors::{AuthUser, Pagination, ValidatedJson};
+use crate::extractors::{AuditLogReason, AuthUser, Pagination, ValidatedJson};
 use crate::response::{ApiError, ApiResult, NoContent};
 use crate::state::AppState;
 
@@ -74,6 +75,7 @@ pub async fn get_guild_member(
 pub async fn update_guild_member(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path((guild_id, user_id)): Path<(String, String)>,
     ValidatedJson(request): ValidatedJson<UpdateMemberRequest>,
 ) -> ApiResult<Json<MemberResponse>> {
@@ -86,7 +88,7 @@ pub async fn update_guild_member(
 
     let service = MemberService::new(state.service_context());
     let response = service
-        .update_member(guild_id, user_id, auth.user_id, request)
+        .update_member(guild_id, user_id, auth.user_id, request, reason)
         .await?;
     Ok(Json(response))
 }
@@ -97,6 +99,7 @@ pub async fn update_guild_member(
 pub async fn remove_guild_member(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path((guild_id, user_id)): Path<(String, String)>,
 ) -> ApiResult<NoContent> {
     let guild_id = guild_id
@@ -108,7 +111,7 @@ pub async fn remove_guild_member(
 
     let service = MemberService::new(state.service_context());
     service
-        .remove_member(guild_id, user_id, auth.user_id)
+        .remove_member(guild_id, user_id, auth.user_id, reason)
         .await?;
     Ok(NoContent)
 }
@@ -129,3 +132,89 @@ pub async fn leave_guild(
     service.leave_guild(guild_id, auth.user_id).await?;
     Ok(NoContent)
 }
+
+/// Get guild bans
+///
+/// GET /guilds/{guild_id}/bans
+pub async fn get_guild_bans(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<String>,
+) -> ApiResult<Json<Vec<BanResponse>>> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+
+    let service = MemberService::new(state.service_context());
+    let bans = service.get_guild_bans(guild_id, auth.user_id).await?;
+    Ok(Json(bans))
+}
+
+/// Get guild ban for a user
+///
+/// GET /guilds/{guild_id}/bans/{user_id}
+pub async fn get_guild_ban(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((guild_id, user_id)): Path<(String, String)>,
+) -> ApiResult<Json<BanResponse>> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = MemberService::new(state.service_context());
+    let ban = service
+        .get_guild_ban(guild_id, user_id, auth.user_id)
+        .await?;
+    Ok(Json(ban))
+}
+
+/// Ban user from guild
+///
+/// PUT /guilds/{guild_id}/bans/{user_id}
+pub async fn create_guild_ban(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((guild_id, user_id)): Path<(String, String)>,
+    ValidatedJson(request): ValidatedJson<CreateBanRequest>,
+) -> ApiResult<NoContent> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = MemberService::new(state.service_context());
+    service
+        .ban_member(guild_id, user_id, auth.user_id, request, reason)
+        .await?;
+    Ok(NoContent)
+}
+
+/// Unban user from guild
+///
+/// DELETE /guilds/{guild_id}/bans/{user_id}
+pub async fn remove_guild_ban(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((guild_id, user_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = MemberService::new(state.service_context());
+    service
+        .unban_member(guild_id, user_id, auth.user_id, reason)
+        .await?;
+    Ok(NoContent)
+}
@@ -3,16 +3,25 @@
 //! Endpoints for message operations.
 
 use axum::{
-    extract::{Path, State},
+    extract::{multipart::Field, Multipart, Path, Query, State},
     Json,
 };
+use chat_core::traits::MessageSearchQuery;
+use chat_core::Snowflake;
 use chat_service::{
-    BulkDeleteMessagesRequest, CreateMessageRequest, MessageResponse, MessageService,
-    UpdateMessageRequest,
+    AttachmentService, BulkDeleteMessagesRequest, CreateMessageRequest, MessageResponse,
+    MessageRevisionResponse, MessageSearchResponse, MessageService, PendingAttachment,
+    ReadStateResponse, ReadStateService, ServiceContext, UpdateMessageRequest,
+    MAX_ATTACHMENTS_PER_MESSAGE,
 };
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, TryStreamExt};
+use validator::Validate;
 
-use crate::extractors::{AuthUser, Pagination, ValidatedJson};
-use crate::response::{ApiResult, Created, NoContent};
+use crate::extractors::{
+    AuditLogReason, AuthUser, JsonOrMultipart, Pagination, ValidatedJson, JSON_BODY_LIMIT,
+};
+use crate::response::{ApiError, ApiResult, Created, NoContent};
 use crate::state::AppState;
 
 /// Get messages in channel
@@ -41,26 +50,237 @@ pub async fn get_messages(
     Ok(Json(messages))
 }
 
+/// Message search query parameters
+#[derive(Debug, serde::Deserialize)]
+pub struct MessageSearchParams {
+    /// Full-text query
+    pub content: Option<String>,
+    /// Only messages by this user
+    pub author_id: Option<String>,
+    /// Only messages mentioning this user
+    pub mentions: Option<String>,
+    /// Only messages that have this kind of content (`attachment`)
+    pub has: Option<String>,
+    /// Only messages sent at or after this time (RFC 3339)
+    pub min_timestamp: Option<DateTime<Utc>>,
+    /// Only messages sent before this time (RFC 3339)
+    pub max_timestamp: Option<DateTime<Utc>>,
+    /// Only messages in this channel (guild search only)
+    pub channel_id: Option<String>,
+    /// Number of results to skip (0-5000)
+    pub offset: Option<i64>,
+    /// Maximum number of results (1-25, default 25)
+    pub limit: Option<i64>,
+}
+
+impl TryFrom<&MessageSearchParams> for MessageSearchQuery {
+    type Error = ApiError;
+
+    fn try_from(params: &MessageSearchParams) -> Result<Self, Self::Error> {
+        let author_id = params
+            .author_id
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| ApiError::invalid_query("Invalid author_id format"))?;
+        let mentions = params
+            .mentions
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| ApiError::invalid_query("Invalid mentions format"))?;
+        let has_attachment = match params.has.as_deref() {
+            None => None,
+            Some("attachment") => Some(true),
+            Some(_) => return Err(ApiError::invalid_query("Invalid 'has' filter")),
+        };
+
+        Ok(Self {
+            content: params
+                .content
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string),
+            author_id,
+            mentions,
+            has_attachment,
+            min_created_at: params.min_timestamp,
+            max_created_at: params.max_timestamp,
+            offset: params.offset.unwrap_or(0),
+            limit: params.limit.unwrap_or(25),
+        })
+    }
+}
+
+/// Search messages in channel
+///
+/// GET /channels/{channel_id}/messages/search
+pub async fn search_channel_messages(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<String>,
+    Query(params): Query<MessageSearchParams>,
+) -> ApiResult<Json<MessageSearchResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let query = MessageSearchQuery::try_from(&params)?;
+
+    let service = MessageService::new(state.service_context());
+    let response = service
+        .search_channel_messages(channel_id, auth.user_id, query)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Search messages in guild
+///
+/// GET /guilds/{guild_id}/messages/search
+pub async fn search_guild_messages(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(guild_id): Path<String>,
+    Query(params): Query<MessageSearchParams>,
+) -> ApiResult<Json<MessageSearchResponse>> {
+    let guild_id = guild_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
+    let channel_id = params
+        .channel_id
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|_| ApiError::invalid_query("Invalid channel_id format"))?;
+    let query = MessageSearchQuery::try_from(&params)?;
+
+    let service = MessageService::new(state.service_context());
+    let response = service
+        .search_guild_messages(guild_id, auth.user_id, channel_id, query)
+        .await?;
+    Ok(Json(response))
+}
+
 /// Create message
 ///
 /// POST /channels/{channel_id}/messages
+///
+/// Accepts a JSON body, or `multipart/form-data` with an optional
+/// `payload_json` field and up to 10 file fields (`files[n]`).
 pub async fn create_message(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(channel_id): Path<String>,
-    ValidatedJson(request): ValidatedJson<CreateMessageRequest>,
+    body: JsonOrMultipart<CreateMessageRequest>,
 ) -> ApiResult<Created<Json<MessageResponse>>> {
     let channel_id = channel_id
         .parse()
         .map_err(|_| crate::response::ApiError::invalid_path("Invalid channel_id format"))?;
 
-    let service = MessageService::new(state.service_context());
-    let response = service
-        .create_message(channel_id, auth.user_id, request)
-        .await?;
+    let ctx = state.service_context();
+    let service = MessageService::new(ctx);
+    let response = match body {
+        JsonOrMultipart::Json(request) => {
+            service
+                .create_message(channel_id, auth.user_id, request)
+                .await?
+        }
+        JsonOrMultipart::Multipart(multipart) => {
+            let (request, uploads) =
+                read_message_form(ctx, channel_id, auth.user_id, multipart).await?;
+
+            match service
+                .create_message_with_attachments(channel_id, auth.user_id, request, uploads.clone())
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    AttachmentService::new(ctx).discard(&uploads).await;
+                    return Err(e.into());
+                }
+            }
+        }
+    };
     Ok(Created(Json(response)))
 }
 
+/// Read a multipart message form, streaming each file to storage
+///
+/// Already stored files are discarded if a later field fails.
+async fn read_message_form(
+    ctx: &ServiceContext,
+    channel_id: Snowflake,
+    user_id: Snowflake,
+    mut multipart: Multipart,
+) -> ApiResult<(CreateMessageRequest, Vec<PendingAttachment>)> {
+    let attachment_service = AttachmentService::new(ctx);
+    let mut request = None;
+    let mut uploads = Vec::new();
+
+    let result: ApiResult<()> = async {
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| ApiError::invalid_query(e.body_text()))?
+        {
+            if field.name() == Some("payload_json") {
+                let payload = read_payload_json(field).await?;
+                payload.validate()?;
+                request = Some(payload);
+                continue;
+            }
+
+            let Some(filename) = field.file_name().map(str::to_string) else {
+                return Err(ApiError::invalid_query(format!(
+                    "Unexpected form field: {}",
+                    field.name().unwrap_or_default()
+                )));
+            };
+
+            if uploads.is_empty() {
+                MessageService::new(ctx)
+                    .verify_can_attach(channel_id, user_id)
+                    .await?;
+            }
+            if uploads.len() >= MAX_ATTACHMENTS_PER_MESSAGE {
+                return Err(ApiError::invalid_query(format!(
+                    "Cannot attach more than {MAX_ATTACHMENTS_PER_MESSAGE} files"
+                )));
+            }
+
+            let content_type = field.content_type().map(str::to_string);
+            let body = field.map_err(std::io::Error::other);
+            let upload = attachment_service
+                .upload(channel_id, &filename, content_type.as_deref(), body)
+                .await?;
+            uploads.push(upload);
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        attachment_service.discard(&uploads).await;
+        return Err(e);
+    }
+
+    Ok((request.unwrap_or_default(), uploads))
+}
+
+/// Read and parse the `payload_json` form field, capped at [`JSON_BODY_LIMIT`]
+async fn read_payload_json(mut field: Field<'_>) -> ApiResult<CreateMessageRequest> {
+    let mut buf = Vec::new();
+    while let Some(chunk) = field.next().await {
+        let chunk = chunk.map_err(|e| ApiError::invalid_query(e.body_text()))?;
+        if buf.len() + chunk.len() > JSON_BODY_LIMIT {
+            return Err(ApiError::invalid_query("payload_json too large"));
+        }
+        buf.extend_from_slice(&chunk);
+    }
+
+    serde_json::from_slice(&buf).map_err(|e| ApiError::invalid_query(e.to_string()))
+}
+
 /// Get message by ID
 ///
 /// GET /channels/{channel_id}/messages/{message_id}
@@ -106,12 +326,57 @@ pub async fn update_message(
     Ok(Json(response))
 }
 
+/// Get message edit history
+///
+/// GET /channels/{channel_id}/messages/{message_id}/revisions
+pub async fn get_message_revisions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, message_id)): Path<(String, String)>,
+) -> ApiResult<Json<Vec<MessageRevisionResponse>>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let message_id = message_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid message_id format"))?;
+
+    let service = MessageService::new(state.service_context());
+    let revisions = service
+        .get_message_revisions(channel_id, message_id, auth.user_id)
+        .await?;
+    Ok(Json(revisions))
+}
+
+/// Acknowledge message (mark channel read up to it)
+///
+/// POST /channels/{channel_id}/messages/{message_id}/ack
+pub async fn ack_message(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, message_id)): Path<(String, String)>,
+) -> ApiResult<Json<ReadStateResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let message_id = message_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid message_id format"))?;
+
+    let service = ReadStateService::new(state.service_context());
+    let read_state = service
+        .ack_message(channel_id, message_id, auth.user_id)
+        .await?;
+    Ok(Json(read_state))
+}
+
 /// Delete message
 ///
 /// DELETE /channels/{channel_id}/messages/{message_id}
 pub async fn delete_message(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path((channel_id, message_id)): Path<(String, String)>,
 ) -> ApiResult<NoContent> {
     let channel_id = channel_id
@@ -123,7 +388,7 @@ pub async fn delete_message(
 
     let service = MessageService::new(state.service_context());
     service
-        .delete_message(channel_id, message_id, auth.user_id)
+        .delete_message(channel_id, message_id, auth.user_id, reason)
         .await?;
     Ok(NoContent)
 }
@@ -147,3 +412,66 @@ pub async fn bulk_delete_messages(
         .await?;
     Ok(NoContent)
 }
+
+/// Get pinned messages
+///
+/// GET /channels/{channel_id}/pins
+pub async fn get_pinned_messages(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<String>,
+) -> ApiResult<Json<Vec<MessageResponse>>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+
+    let service = MessageService::new(state.service_context());
+    let messages = service.get_pinned_messages(channel_id, auth.user_id).await?;
+    Ok(Json(messages))
+}
+
+/// Pin message
+///
+/// PUT /channels/{channel_id}/pins/{message_id}
+pub async fn pin_message(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((channel_id, message_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let message_id = message_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid message_id format"))?;
+
+    let service = MessageService::new(state.service_context());
+    service
+        .pin_message(channel_id, message_id, auth.user_id, reason)
+        .await?;
+    Ok(NoContent)
+}
+
+/// Unpin message
+///
+/// DELETE /channels/{channel_id}/pins/{message_id}
+pub async fn unpin_message(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
+    Path((channel_id, message_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let message_id = message_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid message_id format"))?;
+
+    let service = MessageService::new(state.service_context());
+    service
+        .unpin_message(channel_id, message_id, auth.user_id, reason)
+        .await?;
+    Ok(NoContent)
+}
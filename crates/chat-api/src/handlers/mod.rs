@@ -2,8 +2,10 @@
 //!
 //! All HTTP request handlers organized by domain.
 
+pub mod attachments;
 pub mod auth;
 pub mod channels;
+pub mod gateway;
 pub mod guilds;
 pub mod health;
 pub mod invites;
@@ -11,4 +13,5 @@ pub mod members;
 pub mod messages;
 pub mod reactions;
 pub mod roles;
+pub mod threads;
 pub mod users;
@@ -8,7 +8,7 @@ use axum::{
 };
 use chat_service::{CreateRoleRequest, PermissionService, RoleResponse, RoleService, UpdateRoleRequest};
 
-use crate::extractors::{AuthUser, ValidatedJson};
+use crate::extractors::{AuditLogReason, AuthUser, ValidatedJson};
 use crate::response::{ApiError, ApiResult, Created, NoContent};
 use crate::state::AppState;
 
@@ -35,6 +35,7 @@ pub async fn get_guild_roles(
 pub async fn create_role(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path(guild_id): Path<String>,
     ValidatedJson(request): ValidatedJson<CreateRoleRequest>,
 ) -> ApiResult<Created<Json<RoleResponse>>> {
@@ -43,7 +44,7 @@ pub async fn create_role(
         .map_err(|_| ApiError::invalid_path("Invalid guild_id format"))?;
 
     let service = RoleService::new(state.service_context());
-    let response = service.create_role(guild_id, auth.user_id, request).await?;
+    let response = service.create_role(guild_id, auth.user_id, request, reason).await?;
     Ok(Created(Json(response)))
 }
 
@@ -83,6 +84,7 @@ pub async fn get_role(
 pub async fn update_role(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path((guild_id, role_id)): Path<(String, String)>,
     ValidatedJson(request): ValidatedJson<UpdateRoleRequest>,
 ) -> ApiResult<Json<RoleResponse>> {
@@ -95,7 +97,7 @@ pub async fn update_role(
 
     let service = RoleService::new(state.service_context());
     let response = service
-        .update_role(guild_id, role_id, auth.user_id, request)
+        .update_role(guild_id, role_id, auth.user_id, request, reason)
         .await?;
     Ok(Json(response))
 }
@@ -106,6 +108,7 @@ pub async fn update_role(
 pub async fn delete_role(
     State(state): State<AppState>,
     auth: AuthUser,
+    AuditLogReason(reason): AuditLogReason,
     Path((guild_id, role_id)): Path<(String, String)>,
 ) -> ApiResult<NoContent> {
     let guild_id = guild_id
@@ -117,7 +120,7 @@ pub async fn delete_role(
 
     let service = RoleService::new(state.service_context());
     service
-        .delete_role(guild_id, role_id, auth.user_id)
+        .delete_role(guild_id, role_id, auth.user_id, reason)
         .await?;
     Ok(NoContent)
 }
@@ -0,0 +1,176 @@
+//! Thread handlers
+//!
+//! Endpoints for message threads and thread membership.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chat_service::{
+    StartThreadRequest, ThreadListResponse, ThreadMemberResponse, ThreadResponse, ThreadService,
+};
+use chrono::{DateTime, Utc};
+
+use crate::extractors::{AuthUser, ValidatedJson};
+use crate::response::{ApiError, ApiResult, Created, NoContent};
+use crate::state::AppState;
+
+/// Archived thread query parameters
+#[derive(Debug, serde::Deserialize)]
+pub struct ArchivedThreadsParams {
+    /// Only threads archived before this time (RFC 3339)
+    pub before: Option<DateTime<Utc>>,
+    /// Maximum number of threads (1-100, default 50)
+    pub limit: Option<i64>,
+}
+
+/// Start thread from message
+///
+/// POST /channels/{channel_id}/messages/{message_id}/threads
+pub async fn start_thread(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((channel_id, message_id)): Path<(String, String)>,
+    ValidatedJson(request): ValidatedJson<StartThreadRequest>,
+) -> ApiResult<Created<Json<ThreadResponse>>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+    let message_id = message_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid message_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    let response = service
+        .start_thread(channel_id, message_id, auth.user_id, request)
+        .await?;
+    Ok(Created(Json(response)))
+}
+
+/// Get active threads in channel
+///
+/// GET /channels/{channel_id}/threads/active
+pub async fn get_active_threads(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<String>,
+) -> ApiResult<Json<ThreadListResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    let response = service.get_active_threads(channel_id, auth.user_id).await?;
+    Ok(Json(response))
+}
+
+/// Get archived threads in channel
+///
+/// GET /channels/{channel_id}/threads/archived
+pub async fn get_archived_threads(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(channel_id): Path<String>,
+    Query(params): Query<ArchivedThreadsParams>,
+) -> ApiResult<Json<ThreadListResponse>> {
+    let channel_id = channel_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid channel_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    let response = service
+        .get_archived_threads(channel_id, auth.user_id, params.before, params.limit)
+        .await?;
+    Ok(Json(response))
+}
+
+/// Get thread members
+///
+/// GET /channels/{thread_id}/thread-members
+pub async fn get_thread_members(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(thread_id): Path<String>,
+) -> ApiResult<Json<Vec<ThreadMemberResponse>>> {
+    let thread_id = thread_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid thread_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    let members = service.get_thread_members(thread_id, auth.user_id).await?;
+    Ok(Json(members))
+}
+
+/// Join thread
+///
+/// PUT /channels/{thread_id}/thread-members/@me
+pub async fn join_thread(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(thread_id): Path<String>,
+) -> ApiResult<NoContent> {
+    let thread_id = thread_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid thread_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    service.join_thread(thread_id, auth.user_id).await?;
+    Ok(NoContent)
+}
+
+/// Leave thread
+///
+/// DELETE /channels/{thread_id}/thread-members/@me
+pub async fn leave_thread(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(thread_id): Path<String>,
+) -> ApiResult<NoContent> {
+    let thread_id = thread_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid thread_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    service.leave_thread(thread_id, auth.user_id).await?;
+    Ok(NoContent)
+}
+
+/// Add thread member
+///
+/// PUT /channels/{thread_id}/thread-members/{user_id}
+pub async fn add_thread_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((thread_id, user_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let thread_id = thread_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid thread_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    service.add_member(thread_id, auth.user_id, user_id).await?;
+    Ok(NoContent)
+}
+
+/// Remove thread member
+///
+/// DELETE /channels/{thread_id}/thread-members/{user_id}
+pub async fn remove_thread_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((thread_id, user_id)): Path<(String, String)>,
+) -> ApiResult<NoContent> {
+    let thread_id = thread_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid thread_id format"))?;
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = ThreadService::new(state.service_context());
+    service.remove_member(thread_id, auth.user_id, user_id).await?;
+    Ok(NoContent)
+}
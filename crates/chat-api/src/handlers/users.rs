@@ -1,18 +1,21 @@
 //! User handlers
 //!
-//! Endpoints for user profile management and user's guilds.
+//! Endpoints for user profile management, two-factor authentication, login
+//! sessions, user's guilds, DMs and relationships.
 
 use axum::{
     extract::{Path, State},
     Json,
 };
 use chat_service::{
-    CurrentUserResponse, DmChannelResponse, DmService, GuildResponse, GuildService,
-    PublicUserResponse, UpdateUserRequest, UserService,
+    BackupCodesResponse, CreateDmRequest, CreateRelationshipRequest, CurrentUserResponse,
+    DmChannelResponse, DmService, EnableTotpRequest, GuildResponse, GuildService, MfaCodeRequest,
+    MfaService, PublicUserResponse, RelationshipResponse, RelationshipService, ServiceError,
+    SessionResponse, SessionService, TotpEnrollmentResponse, UpdateRelationshipRequest, UpdateUserRequest, UserService,
 };
 
 use crate::extractors::{AuthUser, ValidatedJson};
-use crate::response::ApiResult;
+use crate::response::{ApiError, ApiResult, NoContent};
 use crate::state::AppState;
 
 /// Get current user
@@ -50,7 +53,7 @@ pub async fn get_user(
 ) -> ApiResult<Json<PublicUserResponse>> {
     let user_id = user_id
         .parse()
-        .map_err(|_| crate::response::ApiError::invalid_path("Invalid user_id format"))?;
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
 
     let service = UserService::new(state.service_context());
     let response = service.get_user(user_id).await?;
@@ -81,26 +84,194 @@ pub async fn get_dm_channels(
     Ok(Json(channels))
 }
 
-/// Create DM channel request
-#[derive(Debug, serde::Deserialize)]
-pub struct CreateDmRequest {
-    pub recipient_id: String,
-}
-
 /// Create DM channel
 ///
 /// POST /users/@me/channels
+///
+/// `recipient_id` opens a DM with one user; `recipients` creates a group DM.
 pub async fn create_dm_channel(
     State(state): State<AppState>,
     auth: AuthUser,
-    Json(request): Json<CreateDmRequest>,
+    ValidatedJson(request): ValidatedJson<CreateDmRequest>,
 ) -> ApiResult<Json<DmChannelResponse>> {
+    let service = DmService::new(state.service_context());
+
+    if let Some(recipients) = request.recipients {
+        let recipient_ids = recipients
+            .iter()
+            .map(|id| id.parse())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ApiError::invalid_query("Invalid recipients format"))?;
+
+        let channel = service
+            .create_group_dm(auth.user_id, recipient_ids, request.name)
+            .await?;
+        return Ok(Json(channel));
+    }
+
     let recipient_id = request
         .recipient_id
+        .ok_or_else(|| ServiceError::validation("recipient_id or recipients is required"))?
         .parse()
-        .map_err(|_| crate::response::ApiError::invalid_query("Invalid recipient_id format"))?;
+        .map_err(|_| ApiError::invalid_query("Invalid recipient_id format"))?;
 
-    let service = DmService::new(state.service_context());
     let channel = service.create_dm(auth.user_id, recipient_id).await?;
     Ok(Json(channel))
 }
+
+/// Get current user's relationships
+///
+/// GET /users/@me/relationships
+pub async fn get_relationships(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> ApiResult<Json<Vec<RelationshipResponse>>> {
+    let service = RelationshipService::new(state.service_context());
+    let relationships = service.get_relationships(auth.user_id).await?;
+    Ok(Json(relationships))
+}
+
+/// Send friend request by tag
+///
+/// POST /users/@me/relationships
+pub async fn create_relationship(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    ValidatedJson(request): ValidatedJson<CreateRelationshipRequest>,
+) -> ApiResult<NoContent> {
+    let service = RelationshipService::new(state.service_context());
+    service
+        .send_friend_request_by_tag(auth.user_id, request)
+        .await?;
+    Ok(NoContent)
+}
+
+/// Send or accept friend request, or block user
+///
+/// PUT /users/@me/relationships/{user_id}
+pub async fn update_relationship(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<String>,
+    ValidatedJson(request): ValidatedJson<UpdateRelationshipRequest>,
+) -> ApiResult<NoContent> {
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = RelationshipService::new(state.service_context());
+    service
+        .update_relationship(auth.user_id, user_id, request)
+        .await?;
+    Ok(NoContent)
+}
+
+/// Remove friend, cancel or decline friend request, or unblock user
+///
+/// DELETE /users/@me/relationships/{user_id}
+pub async fn delete_relationship(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<String>,
+) -> ApiResult<NoContent> {
+    let user_id = user_id
+        .parse()
+        .map_err(|_| ApiError::invalid_path("Invalid user_id format"))?;
+
+    let service = RelationshipService::new(state.service_context());
+    service.remove_relationship(auth.user_id, user_id).await?;
+    Ok(NoContent)
+}
+
+/// Start TOTP enrollment
+///
+/// POST /users/@me/mfa/totp/enable
+pub async fn enable_totp(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    ValidatedJson(request): ValidatedJson<EnableTotpRequest>,
+) -> ApiResult<Json<TotpEnrollmentResponse>> {
+    let service = MfaService::new(state.service_context());
+    let enrollment = service.enable_totp(auth.user_id, request).await?;
+    Ok(Json(enrollment))
+}
+
+/// Finish TOTP enrollment with the first code
+///
+/// POST /users/@me/mfa/totp/confirm
+pub async fn confirm_totp(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    ValidatedJson(request): ValidatedJson<MfaCodeRequest>,
+) -> ApiResult<Json<BackupCodesResponse>> {
+    let service = MfaService::new(state.service_context());
+    let codes = service.confirm_totp(auth.user_id, request).await?;
+    Ok(Json(codes))
+}
+
+/// Turn off two-factor authentication
+///
+/// POST /users/@me/mfa/totp/disable
+pub async fn disable_totp(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    ValidatedJson(request): ValidatedJson<MfaCodeRequest>,
+) -> ApiResult<NoContent> {
+    let service = MfaService::new(state.service_context());
+    service.disable_totp(auth.user_id, request).await?;
+    Ok(NoContent)
+}
+
+/// Replace all backup codes
+///
+/// POST /users/@me/mfa/codes
+pub async fn regenerate_backup_codes(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    ValidatedJson(request): ValidatedJson<MfaCodeRequest>,
+) -> ApiResult<Json<BackupCodesResponse>> {
+    let service = MfaService::new(state.service_context());
+    let codes = service.regenerate_backup_codes(auth.user_id, request).await?;
+    Ok(Json(codes))
+}
+
+/// List the current user's login sessions
+///
+/// GET /users/@me/sessions
+pub async fn get_sessions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> ApiResult<Json<Vec<SessionResponse>>> {
+    let service = SessionService::new(state.service_context());
+    let sessions = service
+        .list_sessions(auth.user_id, auth.session_id.as_deref())
+        .await?;
+    Ok(Json(sessions))
+}
+
+/// Log out one of the current user's sessions
+///
+/// DELETE /users/@me/sessions/{session_id}
+pub async fn delete_session(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(session_id): Path<String>,
+) -> ApiResult<NoContent> {
+    let service = SessionService::new(state.service_context());
+    service.revoke_session(auth.user_id, &session_id).await?;
+    Ok(NoContent)
+}
+
+/// Log out every session except the current one
+///
+/// POST /users/@me/sessions/logout-others
+pub async fn logout_other_sessions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> ApiResult<NoContent> {
+    let service = SessionService::new(state.service_context());
+    service
+        .revoke_other_sessions(auth.user_id, auth.session_id.as_deref())
+        .await?;
+    Ok(NoContent)
+}
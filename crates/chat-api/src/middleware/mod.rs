@@ -20,6 +20,7 @@ use tower_http::{
 };
 use tracing::Level;
 
+use crate::extractors::AUDIT_LOG_REASON_HEADER;
 use crate::state::AppState;
 
 /// Header name for request ID
@@ -145,6 +146,7 @@ fn create_cors_layer_from_config(config: &CorsConfig, is_production: bool) -> Co
             header::CONTENT_TYPE,
             header::ACCEPT,
             header::HeaderName::from_static(REQUEST_ID_HEADER),
+            header::HeaderName::from_static(AUDIT_LOG_REASON_HEADER),
         ])
         .expose_headers([
             header::HeaderName::from_static(REQUEST_ID_HEADER),
@@ -204,6 +206,7 @@ fn create_cors_layer() -> CorsLayer {
             header::CONTENT_TYPE,
             header::ACCEPT,
             header::HeaderName::from_static(REQUEST_ID_HEADER),
+            header::HeaderName::from_static(AUDIT_LOG_REASON_HEADER),
         ])
         .allow_origin(Any)
         .expose_headers([
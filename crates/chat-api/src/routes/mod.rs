@@ -2,9 +2,16 @@
 //!
 //! All API routes organized by domain and mounted under /api/v1.
 
-use axum::{routing::{delete, get, patch, post, put}, Router};
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
+    Router,
+};
 
-use crate::handlers::{auth, channels, guilds, health, invites, members, messages, reactions, roles, users};
+use crate::handlers::{
+    attachments, auth, channels, gateway, guilds, health, invites, members, messages, reactions,
+    roles, threads, users,
+};
 use crate::state::AppState;
 
 /// Create the main API router with all routes (excluding health for separate middleware handling)
@@ -12,6 +19,8 @@ pub fn create_router() -> Router<AppState> {
     Router::new()
         // API v1 endpoints
         .nest("/api/v1", api_v1_routes())
+        // CDN-style file serving
+        .merge(cdn_routes())
 }
 
 /// Health check routes (exported separately to bypass rate limiting)
@@ -29,6 +38,7 @@ fn api_v1_routes() -> Router<AppState> {
         .merge(guild_routes())
         .merge(channel_routes())
         .merge(invite_routes())
+        .merge(gateway_routes())
 }
 
 /// Authentication routes
@@ -36,8 +46,16 @@ fn auth_routes() -> Router<AppState> {
     Router::new()
         .route("/auth/register", post(auth::register))
         .route("/auth/login", post(auth::login))
+        .route("/auth/mfa/totp", post(auth::login_mfa_totp))
         .route("/auth/refresh", post(auth::refresh_token))
         .route("/auth/logout", post(auth::logout))
+        .route("/auth/forgot-password", post(auth::forgot_password))
+        .route("/auth/reset-password", post(auth::reset_password))
+        .route("/auth/verify-email", post(auth::verify_email))
+        .route(
+            "/auth/verify-email/resend",
+            post(auth::resend_verification_email),
+        )
 }
 
 /// User routes
@@ -45,9 +63,20 @@ fn user_routes() -> Router<AppState> {
     Router::new()
         .route("/users/@me", get(users::get_current_user))
         .route("/users/@me", patch(users::update_current_user))
+        .route("/users/@me/mfa/totp/enable", post(users::enable_totp))
+        .route("/users/@me/mfa/totp/confirm", post(users::confirm_totp))
+        .route("/users/@me/mfa/totp/disable", post(users::disable_totp))
+        .route("/users/@me/mfa/codes", post(users::regenerate_backup_codes))
+        .route("/users/@me/sessions", get(users::get_sessions))
+        .route("/users/@me/sessions/logout-others", post(users::logout_other_sessions))
+        .route("/users/@me/sessions/:session_id", delete(users::delete_session))
         .route("/users/@me/guilds", get(users::get_current_user_guilds))
         .route("/users/@me/channels", get(users::get_dm_channels))
         .route("/users/@me/channels", post(users::create_dm_channel))
+        .route("/users/@me/relationships", get(users::get_relationships))
+        .route("/users/@me/relationships", post(users::create_relationship))
+        .route("/users/@me/relationships/:user_id", put(users::update_relationship))
+        .route("/users/@me/relationships/:user_id", delete(users::delete_relationship))
         .route("/users/:user_id", get(users::get_user))
 }
 
@@ -59,6 +88,9 @@ fn guild_routes() -> Router<AppState> {
         .route("/guilds/:guild_id", get(guilds::get_guild))
         .route("/guilds/:guild_id", patch(guilds::update_guild))
         .route("/guilds/:guild_id", delete(guilds::delete_guild))
+        .route("/guilds/:guild_id/audit-logs", get(guilds::get_guild_audit_logs))
+        // Guild message search
+        .route("/guilds/:guild_id/messages/search", get(messages::search_guild_messages))
         // Guild channels
         .route("/guilds/:guild_id/channels", get(channels::get_guild_channels))
         .route("/guilds/:guild_id/channels", post(channels::create_channel))
@@ -68,6 +100,11 @@ fn guild_routes() -> Router<AppState> {
         .route("/guilds/:guild_id/members/:user_id", patch(members::update_guild_member))
         .route("/guilds/:guild_id/members/:user_id", delete(members::remove_guild_member))
         .route("/guilds/:guild_id/members/@me", delete(members::leave_guild))
+        // Guild bans
+        .route("/guilds/:guild_id/bans", get(members::get_guild_bans))
+        .route("/guilds/:guild_id/bans/:user_id", get(members::get_guild_ban))
+        .route("/guilds/:guild_id/bans/:user_id", put(members::create_guild_ban))
+        .route("/guilds/:guild_id/bans/:user_id", delete(members::remove_guild_ban))
         // Guild roles
         .route("/guilds/:guild_id/roles", get(roles::get_guild_roles))
         .route("/guilds/:guild_id/roles", post(roles::create_role))
@@ -85,12 +122,54 @@ fn channel_routes() -> Router<AppState> {
         .route("/channels/:channel_id", get(channels::get_channel))
         .route("/channels/:channel_id", patch(channels::update_channel))
         .route("/channels/:channel_id", delete(channels::delete_channel))
+        // Group DM recipients
+        .route(
+            "/channels/:channel_id/recipients/:user_id",
+            put(channels::add_recipient),
+        )
+        .route(
+            "/channels/:channel_id/recipients/:user_id",
+            delete(channels::remove_recipient),
+        )
+        // Channel permission overwrites
+        .route(
+            "/channels/:channel_id/permissions",
+            get(channels::get_permission_overwrites),
+        )
+        .route(
+            "/channels/:channel_id/permissions/:overwrite_id",
+            get(channels::get_permission_overwrite),
+        )
+        .route(
+            "/channels/:channel_id/permissions/:overwrite_id",
+            put(channels::edit_permission_overwrite),
+        )
+        .route(
+            "/channels/:channel_id/permissions/:overwrite_id",
+            delete(channels::delete_permission_overwrite),
+        )
         // Channel messages
         .route("/channels/:channel_id/messages", get(messages::get_messages))
-        .route("/channels/:channel_id/messages", post(messages::create_message))
+        // Uploads stream past the default body limit; size limits are enforced per file
+        .route(
+            "/channels/:channel_id/messages",
+            post(messages::create_message).layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/channels/:channel_id/messages/search",
+            get(messages::search_channel_messages),
+        )
         .route("/channels/:channel_id/messages/:message_id", get(messages::get_message))
         .route("/channels/:channel_id/messages/:message_id", patch(messages::update_message))
         .route("/channels/:channel_id/messages/:message_id", delete(messages::delete_message))
+        .route(
+            "/channels/:channel_id/messages/:message_id/revisions",
+            get(messages::get_message_revisions),
+        )
+        .route(
+            "/channels/:channel_id/messages/:message_id/ack",
+            post(messages::ack_message),
+        )
         // Message reactions
         .route(
             "/channels/:channel_id/messages/:message_id/reactions/:emoji/@me",
@@ -116,6 +195,28 @@ fn channel_routes() -> Router<AppState> {
             "/channels/:channel_id/messages/:message_id/reactions",
             delete(reactions::delete_all_reactions),
         )
+        // Pinned messages
+        .route("/channels/:channel_id/pins", get(messages::get_pinned_messages))
+        .route("/channels/:channel_id/pins/:message_id", put(messages::pin_message))
+        .route("/channels/:channel_id/pins/:message_id", delete(messages::unpin_message))
+        // Threads
+        .route(
+            "/channels/:channel_id/messages/:message_id/threads",
+            post(threads::start_thread),
+        )
+        .route("/channels/:channel_id/threads/active", get(threads::get_active_threads))
+        .route("/channels/:channel_id/threads/archived", get(threads::get_archived_threads))
+        .route("/channels/:channel_id/thread-members", get(threads::get_thread_members))
+        .route("/channels/:channel_id/thread-members/@me", put(threads::join_thread))
+        .route("/channels/:channel_id/thread-members/@me", delete(threads::leave_thread))
+        .route(
+            "/channels/:channel_id/thread-members/:user_id",
+            put(threads::add_thread_member),
+        )
+        .route(
+            "/channels/:channel_id/thread-members/:user_id",
+            delete(threads::remove_thread_member),
+        )
         // Typing indicator
         .route("/channels/:channel_id/typing", post(channels::typing_indicator))
         // Channel invites
@@ -130,3 +231,16 @@ fn invite_routes() -> Router<AppState> {
         .route("/invites/:invite_code", post(invites::accept_invite))
         .route("/invites/:invite_code", delete(invites::delete_invite))
 }
+
+/// Gateway routes
+fn gateway_routes() -> Router<AppState> {
+    Router::new().route("/gateway/bot", get(gateway::get_gateway_bot))
+}
+
+/// CDN routes for stored files
+fn cdn_routes() -> Router<AppState> {
+    Router::new().route(
+        "/attachments/:channel_id/:attachment_id/:filename",
+        get(attachments::get_attachment),
+    )
+}
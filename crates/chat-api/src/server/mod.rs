@@ -7,12 +7,14 @@ use std::sync::Arc;
 
 use axum::Router;
 use chat_cache::{RedisPool, RedisPoolConfig};
-use chat_common::{AppConfig, AppError, JwtService};
+use chat_common::{AppConfig, AppError, JwtService, TotpService};
 use chat_core::SnowflakeGenerator;
 use chat_db::{
-    create_pool, PgAttachmentRepository, PgBanRepository, PgChannelRepository, PgGuildRepository,
-    PgInviteRepository, PgMemberRepository, PgMessageRepository, PgReactionRepository,
-    PgRoleRepository, PgUserRepository,
+    create_pool, PgAttachmentRepository, PgAuditLogRepository, PgBanRepository, PgChannelRepository,
+    PgGuildRepository, PgInviteRepository, PgMemberRepository, PgMessageRepository,
+    PgMessageRevisionRepository, PgPermissionOverwriteRepository, PgPinRepository,
+    PgReactionRepository, PgReadStateRepository, PgRelationshipRepository, PgRoleRepository,
+    PgThreadRepository, PgUserRepository,
 };
 use chat_service::ServiceContextBuilder;
 use tokio::net::TcpListener;
@@ -78,6 +80,9 @@ pub async fn create_app_state(config: AppConfig) -> Result<AppState, AppError> {
         config.jwt.refresh_token_expiry,
     ));
 
+    // Create TOTP service; authenticator apps show the app name as issuer
+    let totp_service = Arc::new(TotpService::new(&config.app.name));
+
     // Create Snowflake generator
     let snowflake_generator = Arc::new(SnowflakeGenerator::new(config.snowflake.worker_id));
 
@@ -92,6 +97,24 @@ pub async fn create_app_state(config: AppConfig) -> Result<AppState, AppError> {
     let invite_repo = Arc::new(PgInviteRepository::new(pool.clone()));
     let ban_repo = Arc::new(PgBanRepository::new(pool.clone()));
     let attachment_repo = Arc::new(PgAttachmentRepository::new(pool.clone()));
+    let permission_overwrite_repo =
+        Arc::new(PgPermissionOverwriteRepository::new(pool.clone()));
+    let audit_log_repo = Arc::new(PgAuditLogRepository::new(pool.clone()));
+    let thread_repo = Arc::new(PgThreadRepository::new(pool.clone()));
+    let pin_repo = Arc::new(PgPinRepository::new(pool.clone()));
+    let message_revision_repo = Arc::new(PgMessageRevisionRepository::new(pool.clone()));
+    let read_state_repo = Arc::new(PgReadStateRepository::new(pool.clone()));
+    let relationship_repo = Arc::new(PgRelationshipRepository::new(pool.clone()));
+
+    // Create attachment file store
+    let file_store = Arc::new(
+        chat_storage::from_config(&config.storage).map_err(|e| AppError::Config(e.to_string()))?,
+    );
+
+    // Create outgoing mail outbox
+    let outbox = Arc::new(
+        chat_mail::from_config(&config.mail).map_err(|e| AppError::Config(e.to_string()))?,
+    );
 
     // Build service context
     let service_context = ServiceContextBuilder::new()
@@ -107,7 +130,17 @@ pub async fn create_app_state(config: AppConfig) -> Result<AppState, AppError> {
         .invite_repo(invite_repo)
         .ban_repo(ban_repo)
         .attachment_repo(attachment_repo)
+        .permission_overwrite_repo(permission_overwrite_repo)
+        .audit_log_repo(audit_log_repo)
+        .thread_repo(thread_repo)
+        .pin_repo(pin_repo)
+        .message_revision_repo(message_revision_repo)
+        .read_state_repo(read_state_repo)
+        .relationship_repo(relationship_repo)
+        .file_store(file_store)
+        .outbox(outbox)
         .jwt_service(jwt_service)
+        .totp_service(totp_service)
         .snowflake_generator(snowflake_generator)
         .build()
         .map_err(|e| AppError::Config(e.to_string()))?;
@@ -125,7 +158,8 @@ pub async fn run_server(app: Router, addr: SocketAddr) -> Result<(), AppError> {
 
     info!("Server listening on http://{}", addr);
 
-    axum::serve(listener, app)
+    // Connect info gives handlers the client IP recorded with login sessions
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .map_err(|e| AppError::Config(format!("Server error: {e}")))?;
 
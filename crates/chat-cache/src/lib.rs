@@ -6,7 +6,11 @@
 //!
 //! - **Connection Pool**: Managed Redis connection pool with deadpool
 //! - **Session Storage**: Refresh tokens and WebSocket session management
+//! - **Account Tokens**: Single-use password reset and email verification tokens
+//! - **Two-Factor State**: MFA login tickets, pending enrollments and replay guards
+//! - **Gateway Nodes**: Node leases and session ownership for multi-node gateways
 //! - **Presence**: User online status and typing indicators
+//! - **Read States**: Per-channel read positions and mention counts
 //! - **Pub/Sub**: Real-time event distribution across server instances
 //!
 //! ## Example
@@ -34,6 +38,7 @@
 pub mod pool;
 pub mod presence;
 pub mod pubsub;
+pub mod read_state;
 pub mod session;
 
 // Re-export pool types
@@ -43,16 +48,22 @@ pub use pool::{
 
 // Re-export session types
 pub use session::{
-    ClientProperties, RefreshTokenData, RefreshTokenStore, SessionEvent, SessionState,
-    WebSocketSessionData, WebSocketSessionStore,
+    AccountTokenData, AccountTokenKind, AccountTokenStore, ClientProperties, GatewayNodeData,
+    GatewayNodeStore, MfaStore, MfaTicketData, RefreshTokenData, RefreshTokenStore, SessionEvent,
+    SessionState, WebSocketSessionData, WebSocketSessionStore, MFA_TICKET_TTL, NODE_LEASE_TTL,
+    SESSION_START_LIMIT,
 };
 
 // Re-export presence types
 pub use presence::{PresenceData, PresenceStore, TypingData, UserStatus};
 
+// Re-export read state types
+pub use read_state::{ReadStateData, ReadStateStore};
+
 // Re-export pubsub types
 pub use pubsub::{
     EventTarget, PubSubChannel, PubSubEvent, Publisher, ReceivedMessage, Subscriber,
     SubscriberBuilder, SubscriberConfig, SubscriberError, SubscriberResult,
-    BROADCAST_CHANNEL, CHANNEL_PREFIX, GUILD_CHANNEL_PREFIX, USER_CHANNEL_PREFIX,
+    BROADCAST_CHANNEL, CHANNEL_PREFIX, GUILD_CHANNEL_PREFIX, NODE_CHANNEL_PREFIX,
+    SESSION_REVOKED_EVENT, SESSION_TAKEOVER_EVENT, USER_CHANNEL_PREFIX,
 };
@@ -10,6 +10,8 @@ pub const GUILD_CHANNEL_PREFIX: &str = "guild:";
 pub const CHANNEL_PREFIX: &str = "channel:";
 /// Channel prefix for user-specific events
 pub const USER_CHANNEL_PREFIX: &str = "user:";
+/// Channel prefix for events routed to one gateway node
+pub const NODE_CHANNEL_PREFIX: &str = "node:";
 /// Channel for broadcast events (all connected clients)
 pub const BROADCAST_CHANNEL: &str = "broadcast";
 
@@ -22,6 +24,8 @@ pub enum PubSubChannel {
     Channel(Snowflake),
     /// Events for a specific user (all their sessions)
     User(Snowflake),
+    /// Events for the sessions held by one gateway node
+    Node(String),
     /// Broadcast to all connected clients
     Broadcast,
     /// Custom channel name
@@ -47,6 +51,12 @@ impl PubSubChannel {
         Self::User(user_id)
     }
 
+    /// Create a gateway node channel
+    #[must_use]
+    pub fn node(node_id: impl Into<String>) -> Self {
+        Self::Node(node_id.into())
+    }
+
     /// Create a broadcast channel
     #[must_use]
     pub fn broadcast() -> Self {
@@ -66,6 +76,7 @@ impl PubSubChannel {
             Self::Guild(id) => format!("{GUILD_CHANNEL_PREFIX}{id}"),
             Self::Channel(id) => format!("{CHANNEL_PREFIX}{id}"),
             Self::User(id) => format!("{USER_CHANNEL_PREFIX}{id}"),
+            Self::Node(id) => format!("{NODE_CHANNEL_PREFIX}{id}"),
             Self::Broadcast => BROADCAST_CHANNEL.to_string(),
             Self::Custom(name) => name.clone(),
         }
@@ -96,6 +107,12 @@ impl PubSubChannel {
             }
         }
 
+        if let Some(node_id) = name.strip_prefix(NODE_CHANNEL_PREFIX) {
+            if !node_id.is_empty() {
+                return Self::Node(node_id.to_string());
+            }
+        }
+
         Self::Custom(name.to_string())
     }
 }
@@ -119,6 +136,7 @@ mod tests {
         assert_eq!(PubSubChannel::guild(guild_id).name(), "guild:12345");
         assert_eq!(PubSubChannel::channel(channel_id).name(), "channel:67890");
         assert_eq!(PubSubChannel::user(user_id).name(), "user:11111");
+        assert_eq!(PubSubChannel::node("a1").name(), "node:a1");
         assert_eq!(PubSubChannel::broadcast().name(), "broadcast");
         assert_eq!(PubSubChannel::custom("test").name(), "test");
     }
@@ -134,6 +152,9 @@ mod tests {
         let user_channel = PubSubChannel::parse("user:11111");
         assert_eq!(user_channel, PubSubChannel::User(Snowflake::from(11111i64)));
 
+        let node_channel = PubSubChannel::parse("node:a1");
+        assert_eq!(node_channel, PubSubChannel::Node("a1".to_string()));
+
         let broadcast = PubSubChannel::parse("broadcast");
         assert_eq!(broadcast, PubSubChannel::Broadcast);
 
@@ -7,9 +7,12 @@ mod publisher;
 mod subscriber;
 
 pub use channels::{
-    PubSubChannel, BROADCAST_CHANNEL, CHANNEL_PREFIX, GUILD_CHANNEL_PREFIX, USER_CHANNEL_PREFIX,
+    PubSubChannel, BROADCAST_CHANNEL, CHANNEL_PREFIX, GUILD_CHANNEL_PREFIX, NODE_CHANNEL_PREFIX,
+    USER_CHANNEL_PREFIX,
+};
+pub use publisher::{
+    EventTarget, PubSubEvent, Publisher, SESSION_REVOKED_EVENT, SESSION_TAKEOVER_EVENT,
 };
-pub use publisher::{EventTarget, PubSubEvent, Publisher};
 pub use subscriber::{
     ReceivedMessage, Subscriber, SubscriberBuilder, SubscriberConfig, SubscriberError,
     SubscriberResult,
@@ -2,11 +2,25 @@
 //!
 //! Publishes events to Redis channels for distribution to WebSocket clients.
 
+use std::collections::HashMap;
+
 use crate::pool::{RedisPool, RedisResult};
 use crate::pubsub::PubSubChannel;
+use crate::session::GatewayNodeStore;
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 
+/// Event sent to a gateway node to close one of its sessions
+///
+/// Published when a session is resumed on another node while the old
+/// connection is still open. The payload carries the `session_id`.
+pub const SESSION_TAKEOVER_EVENT: &str = "SESSION_TAKEOVER";
+
+/// Event sent to a user's gateway nodes to close sessions whose login was revoked
+///
+/// The payload carries the gateway `session_ids` to close.
+pub const SESSION_REVOKED_EVENT: &str = "SESSION_REVOKED";
+
 /// Event wrapper for Pub/Sub messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PubSubEvent {
@@ -28,6 +42,9 @@ pub struct EventTarget {
     /// Channel ID (if applicable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub channel_id: Option<String>,
+    /// Recipient user ID (events routed through a gateway node channel)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub user_id: Option<String>,
     /// User IDs to exclude from receiving this event
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub exclude_users: Vec<String>,
@@ -64,6 +81,7 @@ impl EventTarget {
         Self {
             guild_id: None,
             channel_id: None,
+            user_id: None,
             exclude_users: Vec::new(),
         }
     }
@@ -82,6 +100,13 @@ impl EventTarget {
         self
     }
 
+    /// Set recipient user ID
+    #[must_use]
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
     /// Add user to exclude list
     #[must_use]
     pub fn exclude_user(mut self, user_id: impl Into<String>) -> Self {
@@ -94,13 +119,17 @@ impl EventTarget {
 #[derive(Clone)]
 pub struct Publisher {
     pool: RedisPool,
+    nodes: GatewayNodeStore,
 }
 
 impl Publisher {
     /// Create a new publisher
     #[must_use]
     pub fn new(pool: RedisPool) -> Self {
-        Self { pool }
+        Self {
+            nodes: GatewayNodeStore::new(pool.clone()),
+            pool,
+        }
     }
 
     /// Publish an event to a channel
@@ -162,6 +191,95 @@ impl Publisher {
 
         Ok(total_receivers)
     }
+
+    /// Publish an event to every session of a user
+    ///
+    /// The event only goes to the gateway nodes holding the user's sessions,
+    /// with the user as target. Nodes that stopped listening and whose lease
+    /// has expired are dropped from the user's nodes.
+    pub async fn publish_user(
+        &self,
+        user_id: chat_core::Snowflake,
+        event: &PubSubEvent,
+    ) -> RedisResult<u32> {
+        let mut event = event.clone();
+        let target = event.target.take().unwrap_or_else(EventTarget::empty);
+        let event = event.with_target(target.with_user(user_id.to_string()));
+
+        let mut total_receivers = 0;
+        for node_id in self.nodes.user_nodes(user_id).await? {
+            let receivers = self.publish(&PubSubChannel::node(&node_id), &event).await?;
+            if receivers == 0 && !self.nodes.is_alive(&node_id).await? {
+                self.nodes.remove_user_node(user_id, &node_id).await?;
+            }
+            total_receivers += receivers;
+        }
+
+        Ok(total_receivers)
+    }
+
+    /// Publish an event to every session of several users
+    ///
+    /// Routes like [`Self::publish_user`], but looks up the users' nodes and
+    /// publishes to them in one pipeline each.
+    pub async fn publish_users(
+        &self,
+        user_ids: &[chat_core::Snowflake],
+        event: &PubSubEvent,
+    ) -> RedisResult<u32> {
+        let users_nodes = self.nodes.users_nodes(user_ids).await?;
+        let target = event.target.clone().unwrap_or_else(EventTarget::empty);
+
+        let mut pipe = redis::pipe();
+        let mut routes = Vec::new();
+        for (user_id, nodes) in user_ids.iter().zip(users_nodes) {
+            if nodes.is_empty() {
+                continue;
+            }
+            let payload = PubSubEvent {
+                target: Some(target.clone().with_user(user_id.to_string())),
+                ..event.clone()
+            }
+            .to_json()?;
+            for node_id in nodes {
+                pipe.publish(PubSubChannel::node(&node_id).name(), &payload);
+                routes.push((*user_id, node_id));
+            }
+        }
+        if routes.is_empty() {
+            return Ok(0);
+        }
+
+        let receivers: Vec<u32> = {
+            let mut conn = self.pool.get().await?;
+            pipe.query_async(&mut conn).await?
+        };
+
+        let mut alive: HashMap<&str, bool> = HashMap::new();
+        for ((user_id, node_id), count) in routes.iter().zip(&receivers) {
+            if *count > 0 {
+                continue;
+            }
+            let is_alive = if let Some(is_alive) = alive.get(node_id.as_str()) {
+                *is_alive
+            } else {
+                let is_alive = self.nodes.is_alive(node_id).await?;
+                alive.insert(node_id, is_alive);
+                is_alive
+            };
+            if !is_alive {
+                self.nodes.remove_user_node(*user_id, node_id).await?;
+            }
+        }
+
+        tracing::debug!(
+            users = user_ids.len(),
+            event_type = %event.event_type,
+            "Published event to users"
+        );
+
+        Ok(receivers.iter().sum())
+    }
 }
 
 /// Convenience methods for common event types
@@ -231,8 +349,33 @@ impl Publisher {
         data: serde_json::Value,
     ) -> RedisResult<u32> {
         let event = PubSubEvent::new(event_type, data);
-        let channel = PubSubChannel::user(user_id);
-        self.publish(&channel, &event).await
+        self.publish_user(user_id, &event).await
+    }
+
+    /// Ask a gateway node to close a session that is being resumed elsewhere
+    pub async fn publish_session_takeover(
+        &self,
+        node_id: &str,
+        session_id: &str,
+    ) -> RedisResult<u32> {
+        let event = PubSubEvent::new(
+            SESSION_TAKEOVER_EVENT,
+            serde_json::json!({ "session_id": session_id }),
+        );
+        self.publish(&PubSubChannel::node(node_id), &event).await
+    }
+
+    /// Ask the gateway nodes of a user to close sessions whose login was revoked
+    pub async fn publish_sessions_revoked(
+        &self,
+        user_id: chat_core::Snowflake,
+        session_ids: &[String],
+    ) -> RedisResult<u32> {
+        let event = PubSubEvent::new(
+            SESSION_REVOKED_EVENT,
+            serde_json::json!({ "session_ids": session_ids }),
+        );
+        self.publish_user(user_id, &event).await
     }
 }
 
@@ -259,6 +402,7 @@ mod tests {
         let target = EventTarget::empty()
             .with_guild("111")
             .with_channel("222")
+            .with_user("444")
             .exclude_user("333");
 
         let event = PubSubEvent::new("MESSAGE_CREATE", data).with_target(target);
@@ -267,6 +411,7 @@ mod tests {
         let target = event.target.unwrap();
         assert_eq!(target.guild_id, Some("111".to_string()));
         assert_eq!(target.channel_id, Some("222".to_string()));
+        assert_eq!(target.user_id, Some("444".to_string()));
         assert_eq!(target.exclude_users, vec!["333".to_string()]);
     }
 
@@ -0,0 +1,7 @@
+//! Read state storage module.
+//!
+//! Caches per-channel read positions and mention counts.
+
+mod read_state_cache;
+
+pub use read_state_cache::{ReadStateData, ReadStateStore};
@@ -0,0 +1,153 @@
+//! Read state cache in Redis.
+//!
+//! Hot copy of a user's read states, backed by the `read_states` table.
+//! Each user has one hash keyed by channel ID.
+
+use crate::pool::{RedisPool, RedisResult};
+use chat_core::traits::ReadState;
+use chat_core::Snowflake;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+/// Key prefix for user read states
+const READ_STATE_PREFIX: &str = "read_states:";
+
+/// Read state cache TTL (24 hours - reloaded from the database on miss)
+const READ_STATE_TTL: u64 = 86400;
+
+/// Placeholder field marking a cached user with no read states
+const EMPTY_MARKER: &str = "_";
+
+/// Cached read state for one channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadStateData {
+    /// Channel ID
+    pub channel_id: Snowflake,
+    /// Last acknowledged message ID
+    pub last_message_id: Option<Snowflake>,
+    /// Mentions since the last acknowledged message
+    pub mention_count: i32,
+}
+
+impl From<&ReadState> for ReadStateData {
+    fn from(state: &ReadState) -> Self {
+        Self {
+            channel_id: state.channel_id,
+            last_message_id: state.last_message_id,
+            mention_count: state.mention_count,
+        }
+    }
+}
+
+/// User read state store
+#[derive(Clone)]
+pub struct ReadStateStore {
+    pool: RedisPool,
+}
+
+impl ReadStateStore {
+    /// Create a new read state store
+    #[must_use]
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Generate Redis key for a user's read states
+    fn read_state_key(user_id: Snowflake) -> String {
+        format!("{READ_STATE_PREFIX}{user_id}")
+    }
+
+    /// Get all cached read states of a user (None if not cached)
+    pub async fn get_all(&self, user_id: Snowflake) -> RedisResult<Option<Vec<ReadStateData>>> {
+        let key = Self::read_state_key(user_id);
+        if !self.pool.exists(&key).await? {
+            return Ok(None);
+        }
+
+        let mut conn = self.pool.get().await?;
+        let values: Vec<String> = conn.hvals(&key).await?;
+
+        let mut states = Vec::with_capacity(values.len());
+        for value in values.iter().filter(|v| !v.is_empty()) {
+            states.push(serde_json::from_str(value)?);
+        }
+        Ok(Some(states))
+    }
+
+    /// Replace all cached read states of a user
+    pub async fn set_all(&self, user_id: Snowflake, states: &[ReadStateData]) -> RedisResult<()> {
+        let key = Self::read_state_key(user_id);
+        let mut fields = Vec::with_capacity(states.len());
+        for state in states {
+            fields.push((state.channel_id.to_string(), serde_json::to_string(state)?));
+        }
+
+        let mut conn = self.pool.get().await?;
+        conn.del::<_, ()>(&key).await?;
+        if fields.is_empty() {
+            // An empty hash cannot exist; keep a marker so the miss is not repeated
+            conn.hset::<_, _, _, ()>(&key, EMPTY_MARKER, "").await?;
+        } else {
+            conn.hset_multiple::<_, _, _, ()>(&key, &fields).await?;
+        }
+        self.pool.expire(&key, READ_STATE_TTL).await?;
+
+        tracing::debug!(user_id = %user_id, count = states.len(), "Cached read states");
+
+        Ok(())
+    }
+
+    /// Update one channel's read state if the user's states are cached
+    pub async fn update(&self, user_id: Snowflake, state: &ReadStateData) -> RedisResult<bool> {
+        let key = Self::read_state_key(user_id);
+        if !self.pool.exists(&key).await? {
+            return Ok(false);
+        }
+
+        let serialized = serde_json::to_string(state)?;
+        let mut conn = self.pool.get().await?;
+        conn.hset::<_, _, _, ()>(&key, state.channel_id.to_string(), &serialized)
+            .await?;
+        Ok(true)
+    }
+
+    /// Drop the cached read states of a user
+    pub async fn invalidate(&self, user_id: Snowflake) -> RedisResult<bool> {
+        let key = Self::read_state_key(user_id);
+        self.pool.delete(&key).await
+    }
+
+    /// Drop the cached read states of several users
+    pub async fn invalidate_many(&self, user_ids: &[Snowflake]) -> RedisResult<i32> {
+        let keys: Vec<String> = user_ids
+            .iter()
+            .map(|id| Self::read_state_key(*id))
+            .collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        self.pool.delete_many(&keys).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_state_key() {
+        let user_id = Snowflake::from(12345i64);
+        assert_eq!(ReadStateStore::read_state_key(user_id), "read_states:12345");
+    }
+
+    #[test]
+    fn test_read_state_data_serialization() {
+        let data = ReadStateData {
+            channel_id: Snowflake::from(1i64),
+            last_message_id: Some(Snowflake::from(2i64)),
+            mention_count: 3,
+        };
+
+        let json = serde_json::to_string(&data).unwrap();
+        let parsed: ReadStateData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, data);
+    }
+}
@@ -0,0 +1,224 @@
+//! Account token storage in Redis.
+//!
+//! Single-use, expiring tokens for password reset and email verification.
+//! Only a SHA-256 hash of each token is stored, so a Redis dump cannot be
+//! used to take over accounts.
+
+use crate::pool::{RedisPool, RedisResult};
+use chat_core::Snowflake;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Key prefix for account tokens (by token hash)
+const ACCOUNT_TOKEN_PREFIX: &str = "account_token:";
+/// Key prefix for a user's current token of each kind
+const USER_ACCOUNT_TOKEN_PREFIX: &str = "user_account_token:";
+
+/// Token length in alphanumeric characters (about 285 bits of entropy)
+const TOKEN_LENGTH: usize = 48;
+
+/// Purpose of an account token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountTokenKind {
+    /// Lets the holder set a new password
+    PasswordReset,
+    /// Confirms ownership of an email address
+    EmailVerification,
+}
+
+impl AccountTokenKind {
+    /// Key segment for this kind
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PasswordReset => "password_reset",
+            Self::EmailVerification => "email_verification",
+        }
+    }
+
+    /// How long a token of this kind stays valid, in seconds
+    #[must_use]
+    pub fn ttl_seconds(self) -> u64 {
+        match self {
+            // 1 hour
+            Self::PasswordReset => 60 * 60,
+            // 24 hours
+            Self::EmailVerification => 24 * 60 * 60,
+        }
+    }
+}
+
+/// Stored account token data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountTokenData {
+    /// User ID this token belongs to
+    pub user_id: Snowflake,
+    /// Email address the token was sent to
+    pub email: String,
+    /// Token creation timestamp (Unix epoch seconds)
+    pub created_at: i64,
+}
+
+impl AccountTokenData {
+    /// Create new account token data
+    #[must_use]
+    pub fn new(user_id: Snowflake, email: impl Into<String>) -> Self {
+        Self {
+            user_id,
+            email: email.into(),
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Account token store for password reset and email verification
+///
+/// Each user has at most one live token of each kind: issuing a new token
+/// revokes the previous one.
+#[derive(Clone)]
+pub struct AccountTokenStore {
+    pool: RedisPool,
+}
+
+impl AccountTokenStore {
+    /// Create a new account token store
+    #[must_use]
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Hash a token for storage
+    fn hash(token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
+
+    /// Generate Redis key for a token hash
+    fn key(kind: AccountTokenKind, hash: &str) -> String {
+        format!("{ACCOUNT_TOKEN_PREFIX}{}:{hash}", kind.as_str())
+    }
+
+    /// Generate Redis key for a user's current token hash
+    fn user_key(kind: AccountTokenKind, user_id: Snowflake) -> String {
+        format!("{USER_ACCOUNT_TOKEN_PREFIX}{}:{user_id}", kind.as_str())
+    }
+
+    /// Issue a new token, revoking the user's previous token of the same kind
+    ///
+    /// Returns the plain token; only its hash is stored.
+    pub async fn issue(
+        &self,
+        kind: AccountTokenKind,
+        data: &AccountTokenData,
+    ) -> RedisResult<String> {
+        self.revoke_for_user(kind, data.user_id).await?;
+
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        let hash = Self::hash(&token);
+
+        let ttl = kind.ttl_seconds();
+        self.pool.set(&Self::key(kind, &hash), data, Some(ttl)).await?;
+        let mut conn = self.pool.get().await?;
+        conn.set_ex::<_, _, ()>(Self::user_key(kind, data.user_id), &hash, ttl)
+            .await?;
+
+        tracing::debug!(
+            kind = kind.as_str(),
+            user_id = %data.user_id,
+            "Issued account token"
+        );
+
+        Ok(token)
+    }
+
+    /// Consume a token, returning its data if it was valid
+    ///
+    /// The token is deleted atomically, so it can be used at most once.
+    pub async fn consume(
+        &self,
+        kind: AccountTokenKind,
+        token: &str,
+    ) -> RedisResult<Option<AccountTokenData>> {
+        let hash = Self::hash(token);
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(Self::key(kind, &hash))
+            .query_async(&mut conn)
+            .await?;
+
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let data: AccountTokenData = serde_json::from_str(&value)?;
+
+        // Clear the user's pointer unless a newer token replaced it meanwhile
+        let user_key = Self::user_key(kind, data.user_id);
+        let current: Option<String> = conn.get(&user_key).await?;
+        if current.as_deref() == Some(hash.as_str()) {
+            conn.del::<_, ()>(&user_key).await?;
+        }
+
+        tracing::debug!(
+            kind = kind.as_str(),
+            user_id = %data.user_id,
+            "Consumed account token"
+        );
+
+        Ok(Some(data))
+    }
+
+    /// Revoke the user's live token of the given kind, if any
+    pub async fn revoke_for_user(
+        &self,
+        kind: AccountTokenKind,
+        user_id: Snowflake,
+    ) -> RedisResult<bool> {
+        let user_key = Self::user_key(kind, user_id);
+        let mut conn = self.pool.get().await?;
+        let hash: Option<String> = redis::cmd("GETDEL")
+            .arg(&user_key)
+            .query_async(&mut conn)
+            .await?;
+
+        match hash {
+            Some(hash) => self.pool.delete(&Self::key(kind, &hash)).await,
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_generation() {
+        let hash = AccountTokenStore::hash("abc");
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            AccountTokenStore::key(AccountTokenKind::PasswordReset, &hash),
+            format!("account_token:password_reset:{hash}")
+        );
+        assert_eq!(
+            AccountTokenStore::user_key(AccountTokenKind::EmailVerification, Snowflake::new(42)),
+            "user_account_token:email_verification:42"
+        );
+    }
+
+    #[test]
+    fn test_reset_tokens_expire_before_verification_tokens() {
+        assert!(
+            AccountTokenKind::PasswordReset.ttl_seconds()
+                < AccountTokenKind::EmailVerification.ttl_seconds()
+        );
+    }
+}
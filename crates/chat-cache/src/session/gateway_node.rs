@@ -0,0 +1,261 @@
+//! Gateway node registry in Redis.
+//!
+//! Lets several gateway nodes share the WebSocket sessions:
+//! - Node leases, renewed by heartbeat and expiring when a node dies
+//! - The sessions each node holds and their users, so a dead node's
+//!   sessions and routes can be released
+//! - The nodes each user is connected to, with the user's session count on
+//!   each, for routing user events
+
+use crate::pool::{RedisPool, RedisResult};
+use chat_core::Snowflake;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+
+/// Key prefix for node leases
+const NODE_PREFIX: &str = "gateway_node:";
+/// Set of registered node IDs
+const NODES_KEY: &str = "gateway_nodes";
+/// Key prefix for the sessions held by a node (session ID -> user ID)
+const NODE_SESSIONS_PREFIX: &str = "gateway_node_sessions:";
+/// Key prefix for the nodes a user is connected to (node ID -> session count)
+const USER_NODES_PREFIX: &str = "user_gateway_nodes:";
+
+/// Record a session on a node and count it for the user
+///
+/// KEYS: node sessions, user nodes. ARGV: session ID, node ID, user ID.
+const ADD_SESSION_SCRIPT: &str = r"
+if redis.call('HSETNX', KEYS[1], ARGV[1], ARGV[3]) == 1 then
+    redis.call('HINCRBY', KEYS[2], ARGV[2], 1)
+end
+";
+
+/// Forget a session on a node, dropping the node from the user's nodes with
+/// its last session
+///
+/// KEYS: node sessions, user nodes. ARGV: session ID, node ID.
+const REMOVE_SESSION_SCRIPT: &str = r"
+if redis.call('HDEL', KEYS[1], ARGV[1]) == 1 then
+    if redis.call('HINCRBY', KEYS[2], ARGV[2], -1) <= 0 then
+        redis.call('HDEL', KEYS[2], ARGV[2])
+    end
+end
+";
+
+/// Forget all sessions on a node, uncounting each from its user's nodes
+///
+/// KEYS: node sessions. ARGV: node ID, user nodes key prefix.
+const RELEASE_NODE_SCRIPT: &str = r"
+local sessions = redis.call('HGETALL', KEYS[1])
+for i = 2, #sessions, 2 do
+    local user_nodes = ARGV[2] .. sessions[i]
+    if redis.call('HINCRBY', user_nodes, ARGV[1], -1) <= 0 then
+        redis.call('HDEL', user_nodes, ARGV[1])
+    end
+end
+redis.call('DEL', KEYS[1])
+";
+
+/// Seconds a node lease lasts without a heartbeat
+pub const NODE_LEASE_TTL: u64 = 30;
+
+/// Registered gateway node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayNodeData {
+    /// Node ID (unique per process)
+    pub node_id: String,
+    /// Address the node listens on
+    pub address: String,
+    /// Start timestamp (Unix epoch seconds)
+    pub started_at: i64,
+}
+
+impl GatewayNodeData {
+    /// Create node data for a node starting now
+    #[must_use]
+    pub fn new(node_id: impl Into<String>, address: impl Into<String>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            address: address.into(),
+            started_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Gateway node registry store
+#[derive(Clone)]
+pub struct GatewayNodeStore {
+    pool: RedisPool,
+}
+
+impl GatewayNodeStore {
+    /// Create a new gateway node store
+    #[must_use]
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Generate Redis key for a node lease
+    fn node_key(node_id: &str) -> String {
+        format!("{NODE_PREFIX}{node_id}")
+    }
+
+    /// Generate Redis key for a node's sessions
+    fn node_sessions_key(node_id: &str) -> String {
+        format!("{NODE_SESSIONS_PREFIX}{node_id}")
+    }
+
+    /// Generate Redis key for a user's nodes
+    fn user_nodes_key(user_id: Snowflake) -> String {
+        format!("{USER_NODES_PREFIX}{user_id}")
+    }
+
+    /// Register a node or renew its lease
+    pub async fn register(&self, node: &GatewayNodeData) -> RedisResult<()> {
+        let key = Self::node_key(&node.node_id);
+        self.pool.set(&key, node, Some(NODE_LEASE_TTL)).await?;
+
+        let mut conn = self.pool.get().await?;
+        conn.sadd::<_, _, ()>(NODES_KEY, &node.node_id).await?;
+
+        Ok(())
+    }
+
+    /// Remove a node and its session list
+    ///
+    /// The node stops receiving the events of the users it held sessions for.
+    pub async fn deregister(&self, node_id: &str) -> RedisResult<()> {
+        self.pool.delete(&Self::node_key(node_id)).await?;
+
+        let mut conn = self.pool.get().await?;
+        redis::Script::new(RELEASE_NODE_SCRIPT)
+            .key(Self::node_sessions_key(node_id))
+            .arg(node_id)
+            .arg(USER_NODES_PREFIX)
+            .invoke_async::<()>(&mut conn)
+            .await?;
+        conn.srem::<_, _, ()>(NODES_KEY, node_id).await?;
+
+        tracing::debug!(node_id = %node_id, "Deregistered gateway node");
+
+        Ok(())
+    }
+
+    /// Get a node if its lease is current
+    pub async fn get(&self, node_id: &str) -> RedisResult<Option<GatewayNodeData>> {
+        self.pool.get_value(&Self::node_key(node_id)).await
+    }
+
+    /// Check if a node's lease is current
+    pub async fn is_alive(&self, node_id: &str) -> RedisResult<bool> {
+        self.pool.exists(&Self::node_key(node_id)).await
+    }
+
+    /// Get all registered node IDs, including nodes whose lease expired
+    pub async fn nodes(&self) -> RedisResult<Vec<String>> {
+        let mut conn = self.pool.get().await?;
+        let nodes: Vec<String> = conn.smembers(NODES_KEY).await?;
+        Ok(nodes)
+    }
+
+    /// Record a session held by a node
+    ///
+    /// Recording the same session again does not count it twice.
+    pub async fn add_session(
+        &self,
+        node_id: &str,
+        session_id: &str,
+        user_id: Snowflake,
+    ) -> RedisResult<()> {
+        let mut conn = self.pool.get().await?;
+        redis::Script::new(ADD_SESSION_SCRIPT)
+            .key(Self::node_sessions_key(node_id))
+            .key(Self::user_nodes_key(user_id))
+            .arg(session_id)
+            .arg(node_id)
+            .arg(user_id.into_inner())
+            .invoke_async::<()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Forget a session held by a node
+    ///
+    /// The node is removed from the user's nodes along with the user's last
+    /// session on it, in the same step, so a session recorded concurrently
+    /// keeps the node.
+    pub async fn remove_session(
+        &self,
+        node_id: &str,
+        session_id: &str,
+        user_id: Snowflake,
+    ) -> RedisResult<()> {
+        let mut conn = self.pool.get().await?;
+        redis::Script::new(REMOVE_SESSION_SCRIPT)
+            .key(Self::node_sessions_key(node_id))
+            .key(Self::user_nodes_key(user_id))
+            .arg(session_id)
+            .arg(node_id)
+            .invoke_async::<()>(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the sessions held by a node
+    pub async fn node_sessions(&self, node_id: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.pool.get().await?;
+        let sessions: Vec<String> = conn.hkeys(Self::node_sessions_key(node_id)).await?;
+        Ok(sessions)
+    }
+
+    /// Get the nodes a user has sessions on
+    pub async fn user_nodes(&self, user_id: Snowflake) -> RedisResult<Vec<String>> {
+        let mut conn = self.pool.get().await?;
+        let nodes: Vec<String> = conn.hkeys(Self::user_nodes_key(user_id)).await?;
+        Ok(nodes)
+    }
+
+    /// Get the nodes of several users, in the order given
+    pub async fn users_nodes(&self, user_ids: &[Snowflake]) -> RedisResult<Vec<Vec<String>>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut pipe = redis::pipe();
+        for user_id in user_ids {
+            pipe.hkeys(Self::user_nodes_key(*user_id));
+        }
+        let mut conn = self.pool.get().await?;
+        let nodes: Vec<Vec<String>> = pipe.query_async(&mut conn).await?;
+        Ok(nodes)
+    }
+
+    /// Remove a node from a user's nodes (when it no longer receives their events)
+    pub async fn remove_user_node(&self, user_id: Snowflake, node_id: &str) -> RedisResult<()> {
+        let mut conn = self.pool.get().await?;
+        conn.hdel::<_, _, ()>(Self::user_nodes_key(user_id), node_id)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys() {
+        let user_id = Snowflake::from(42i64);
+        assert_eq!(GatewayNodeStore::node_key("a"), "gateway_node:a");
+        assert_eq!(GatewayNodeStore::node_sessions_key("a"), "gateway_node_sessions:a");
+        assert_eq!(GatewayNodeStore::user_nodes_key(user_id), "user_gateway_nodes:42");
+    }
+
+    #[test]
+    fn test_node_data_serialization() {
+        let node = GatewayNodeData::new("node-1", "127.0.0.1:8081");
+        let json = serde_json::to_string(&node).unwrap();
+        let parsed: GatewayNodeData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, node);
+    }
+}
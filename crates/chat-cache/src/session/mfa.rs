@@ -0,0 +1,212 @@
+//! Two-factor authentication state in Redis.
+//!
+//! Holds the short-lived pieces of TOTP two-factor authentication:
+//! - Login tickets handed out after a correct password, exchanged for tokens with a code
+//! - Secrets of enrollments waiting for their first code
+//! - Used TOTP steps, so a code cannot be replayed within its validity window
+//! - Failed code counters, to stop brute forcing 6-digit codes
+
+use crate::pool::{RedisPool, RedisResult};
+use chat_core::Snowflake;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Key prefix for login tickets (by ticket hash)
+const MFA_TICKET_PREFIX: &str = "mfa_ticket:";
+/// Key prefix for pending enrollment secrets
+const MFA_ENROLLMENT_PREFIX: &str = "mfa_enrollment:";
+/// Key prefix for used TOTP steps
+const MFA_USED_STEP_PREFIX: &str = "mfa_used_step:";
+/// Key prefix for failed code counters
+const MFA_FAILURES_PREFIX: &str = "mfa_failures:";
+
+/// How long a login ticket stays valid (5 minutes)
+pub const MFA_TICKET_TTL: u64 = 5 * 60;
+/// How long an enrollment waits for its first code (10 minutes)
+const MFA_ENROLLMENT_TTL: u64 = 10 * 60;
+/// How long a used step is remembered; covers the accepted clock drift
+const MFA_USED_STEP_TTL: u64 = 3 * 30;
+/// Window over which failed codes are counted (15 minutes)
+const MFA_FAILURES_TTL: u64 = 15 * 60;
+
+/// Ticket length in alphanumeric characters
+const TICKET_LENGTH: usize = 48;
+
+/// Stored login ticket data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MfaTicketData {
+    /// User who passed the password check
+    pub user_id: Snowflake,
+    /// Ticket creation timestamp (Unix epoch seconds)
+    pub created_at: i64,
+}
+
+impl MfaTicketData {
+    /// Create new ticket data
+    #[must_use]
+    pub fn new(user_id: Snowflake) -> Self {
+        Self {
+            user_id,
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Store for two-factor authentication state
+#[derive(Clone)]
+pub struct MfaStore {
+    pool: RedisPool,
+}
+
+impl MfaStore {
+    /// Create a new MFA store
+    #[must_use]
+    pub fn new(pool: RedisPool) -> Self {
+        Self { pool }
+    }
+
+    /// Generate Redis key for a login ticket; only its hash is stored
+    fn ticket_key(ticket: &str) -> String {
+        format!("{MFA_TICKET_PREFIX}{:x}", Sha256::digest(ticket.as_bytes()))
+    }
+
+    /// Generate Redis key for a pending enrollment
+    fn enrollment_key(user_id: Snowflake) -> String {
+        format!("{MFA_ENROLLMENT_PREFIX}{user_id}")
+    }
+
+    /// Generate Redis key for a used TOTP step
+    fn used_step_key(user_id: Snowflake, step: u64) -> String {
+        format!("{MFA_USED_STEP_PREFIX}{user_id}:{step}")
+    }
+
+    /// Generate Redis key for a user's failed code counter
+    fn failures_key(user_id: Snowflake) -> String {
+        format!("{MFA_FAILURES_PREFIX}{user_id}")
+    }
+
+    // === Login tickets ===
+
+    /// Issue a login ticket for a user who passed the password check
+    pub async fn issue_ticket(&self, user_id: Snowflake) -> RedisResult<String> {
+        let ticket: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(TICKET_LENGTH)
+            .map(char::from)
+            .collect();
+
+        self.pool
+            .set(
+                &Self::ticket_key(&ticket),
+                &MfaTicketData::new(user_id),
+                Some(MFA_TICKET_TTL),
+            )
+            .await?;
+
+        tracing::debug!(user_id = %user_id, "Issued MFA ticket");
+        Ok(ticket)
+    }
+
+    /// Look up a login ticket without using it up
+    pub async fn get_ticket(&self, ticket: &str) -> RedisResult<Option<MfaTicketData>> {
+        self.pool.get_value(&Self::ticket_key(ticket)).await
+    }
+
+    /// Use up a login ticket, returning its data if it was still valid
+    ///
+    /// The ticket is deleted atomically, so it can be exchanged at most once.
+    pub async fn consume_ticket(&self, ticket: &str) -> RedisResult<Option<MfaTicketData>> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(Self::ticket_key(ticket))
+            .query_async(&mut conn)
+            .await?;
+
+        value
+            .map(|value| serde_json::from_str(&value))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    // === Enrollment ===
+
+    /// Remember the secret of an enrollment until it is confirmed
+    pub async fn set_pending_secret(&self, user_id: Snowflake, secret: &str) -> RedisResult<()> {
+        let mut conn = self.pool.get().await?;
+        conn.set_ex::<_, _, ()>(Self::enrollment_key(user_id), secret, MFA_ENROLLMENT_TTL)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the secret of the user's pending enrollment
+    pub async fn get_pending_secret(&self, user_id: Snowflake) -> RedisResult<Option<String>> {
+        let mut conn = self.pool.get().await?;
+        Ok(conn.get(Self::enrollment_key(user_id)).await?)
+    }
+
+    /// Drop the user's pending enrollment
+    pub async fn clear_pending_secret(&self, user_id: Snowflake) -> RedisResult<bool> {
+        self.pool.delete(&Self::enrollment_key(user_id)).await
+    }
+
+    // === Code checks ===
+
+    /// Mark a TOTP step as used by the user
+    ///
+    /// Returns false if a code for this step was already accepted.
+    pub async fn claim_step(&self, user_id: Snowflake, step: u64) -> RedisResult<bool> {
+        let mut conn = self.pool.get().await?;
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(Self::used_step_key(user_id, step))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(MFA_USED_STEP_TTL)
+            .query_async(&mut conn)
+            .await?;
+        Ok(claimed.is_some())
+    }
+
+    /// Number of wrong codes the user entered recently
+    pub async fn failed_attempts(&self, user_id: Snowflake) -> RedisResult<u32> {
+        let mut conn = self.pool.get().await?;
+        let count: Option<u32> = conn.get(Self::failures_key(user_id)).await?;
+        Ok(count.unwrap_or(0))
+    }
+
+    /// Record a wrong code, returning the number of recent failures
+    pub async fn record_failed_attempt(&self, user_id: Snowflake) -> RedisResult<u32> {
+        let key = Self::failures_key(user_id);
+        let mut conn = self.pool.get().await?;
+        let count: u32 = conn.incr(&key, 1).await?;
+        if count == 1 {
+            self.pool.expire(&key, MFA_FAILURES_TTL).await?;
+        }
+        Ok(count)
+    }
+
+    /// Reset the failed code counter after a correct code
+    pub async fn clear_failed_attempts(&self, user_id: Snowflake) -> RedisResult<bool> {
+        self.pool.delete(&Self::failures_key(user_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_generation() {
+        let user_id = Snowflake::new(42);
+        assert_eq!(
+            MfaStore::ticket_key("abc"),
+            "mfa_ticket:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(MfaStore::enrollment_key(user_id), "mfa_enrollment:42");
+        assert_eq!(MfaStore::used_step_key(user_id, 7), "mfa_used_step:42:7");
+        assert_eq!(MfaStore::failures_key(user_id), "mfa_failures:42");
+    }
+}
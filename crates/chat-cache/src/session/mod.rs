@@ -2,12 +2,22 @@
 //!
 //! Provides Redis-backed storage for:
 //! - Refresh tokens (authentication sessions)
+//! - Account tokens (password reset and email verification)
+//! - Two-factor authentication state (login tickets, pending enrollments)
 //! - WebSocket sessions (real-time connection state)
+//! - Gateway nodes (which node holds which sessions)
 
+mod account_token;
+mod gateway_node;
+mod mfa;
 mod refresh_token;
 mod websocket_session;
 
+pub use account_token::{AccountTokenData, AccountTokenKind, AccountTokenStore};
+pub use gateway_node::{GatewayNodeData, GatewayNodeStore, NODE_LEASE_TTL};
+pub use mfa::{MfaStore, MfaTicketData, MFA_TICKET_TTL};
 pub use refresh_token::{RefreshTokenData, RefreshTokenStore};
 pub use websocket_session::{
     ClientProperties, SessionEvent, SessionState, WebSocketSessionData, WebSocketSessionStore,
+    SESSION_START_LIMIT,
 };
@@ -1,6 +1,8 @@
 //! Refresh token storage in Redis.
 //!
 //! Stores refresh tokens with automatic expiration for secure session management.
+//! Each login session points at its current refresh token, so checking that a
+//! session is still active is a single lookup.
 
 use crate::pool::{RedisPool, RedisResult};
 use chat_core::Snowflake;
@@ -9,6 +11,8 @@ use serde::{Deserialize, Serialize};
 
 /// Key prefix for refresh tokens
 const REFRESH_TOKEN_PREFIX: &str = "refresh_token:";
+/// Key prefix for the current refresh token of a login session
+const REFRESH_SESSION_PREFIX: &str = "refresh_session:";
 
 /// Default TTL for refresh tokens (7 days)
 const DEFAULT_REFRESH_TOKEN_TTL: u64 = 7 * 24 * 60 * 60;
@@ -26,6 +30,9 @@ pub struct RefreshTokenData {
     pub device_info: Option<String>,
     /// IP address at token creation (optional)
     pub ip_address: Option<String>,
+    /// When the session last refreshed its tokens (Unix epoch seconds)
+    #[serde(default)]
+    pub last_used_at: Option<i64>,
 }
 
 impl RefreshTokenData {
@@ -38,6 +45,7 @@ impl RefreshTokenData {
             created_at: chrono::Utc::now().timestamp(),
             device_info: None,
             ip_address: None,
+            last_used_at: None,
         }
     }
 
@@ -54,6 +62,17 @@ impl RefreshTokenData {
         self.ip_address = Some(ip.into());
         self
     }
+
+    /// Record that the session was just used
+    pub fn touch(&mut self) {
+        self.last_used_at = Some(chrono::Utc::now().timestamp());
+    }
+
+    /// Last time the session was used, falling back to its creation
+    #[must_use]
+    pub fn last_used(&self) -> i64 {
+        self.last_used_at.unwrap_or(self.created_at)
+    }
 }
 
 /// Refresh token store for managing authentication sessions
@@ -84,21 +103,38 @@ impl RefreshTokenStore {
         format!("{REFRESH_TOKEN_PREFIX}{token_id}")
     }
 
+    /// Generate Redis key for a login session's current token
+    fn session_key(session_id: &str) -> String {
+        format!("{REFRESH_SESSION_PREFIX}{session_id}")
+    }
+
+    /// Generate Redis key for a user's token set
+    fn user_key(user_id: Snowflake) -> String {
+        format!("user_tokens:{user_id}")
+    }
 
-    /// Store a refresh token
+    /// Store a refresh token as the current token of its session
     pub async fn store(
         &self,
         token_id: &str,
         data: &RefreshTokenData,
     ) -> RedisResult<()> {
-        let key = Self::key(token_id);
-        self.pool.set(&key, data, Some(self.ttl_seconds)).await?;
-
-        // Also add to user's token set for tracking
-        let user_set_key = format!("user_tokens:{}", data.user_id);
+        let serialized = serde_json::to_string(data)?;
+        let user_set_key = Self::user_key(data.user_id);
         let mut conn = self.pool.get().await?;
-        conn.sadd::<_, _, ()>(&user_set_key, token_id).await?;
-        conn.expire::<_, ()>(&user_set_key, self.ttl_seconds as i64).await?;
+
+        redis::pipe()
+            .atomic()
+            .set_ex(Self::key(token_id), serialized, self.ttl_seconds)
+            .ignore()
+            .set_ex(Self::session_key(&data.session_id), token_id, self.ttl_seconds)
+            .ignore()
+            .sadd(&user_set_key, token_id)
+            .ignore()
+            .expire(&user_set_key, self.ttl_seconds as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await?;
 
         tracing::debug!(
             token_id = %token_id,
@@ -107,6 +143,9 @@ impl RefreshTokenStore {
             "Stored refresh token"
         );
 
+        // Expired tokens leave the user's set as new ones are stored
+        self.prune_user_tokens(data.user_id).await?;
+
         Ok(())
     }
 
@@ -121,44 +160,77 @@ impl RefreshTokenStore {
         self.get(token_id).await
     }
 
-    /// Revoke (delete) a refresh token
-    pub async fn revoke(&self, token_id: &str) -> RedisResult<bool> {
-        // First get the token to find user_id
-        if let Some(data) = self.get(token_id).await? {
-            // Remove from user's token set
-            let user_set_key = format!("user_tokens:{}", data.user_id);
-            let mut conn = self.pool.get().await?;
-            conn.srem::<_, _, ()>(&user_set_key, token_id).await?;
-        }
+    /// Claim a refresh token for rotation, returning its data if it was valid
+    ///
+    /// The token is deleted atomically, so only one caller can claim it. Its
+    /// session stays active for the replacement token; call
+    /// [`Self::end_session`] if no replacement is stored.
+    pub async fn claim(&self, token_id: &str) -> RedisResult<Option<RefreshTokenData>> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(Self::key(token_id))
+            .query_async(&mut conn)
+            .await?;
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let data: RefreshTokenData = serde_json::from_str(&value)?;
 
-        let key = Self::key(token_id);
-        let deleted = self.pool.delete(&key).await?;
+        conn.srem::<_, _, ()>(Self::user_key(data.user_id), token_id)
+            .await?;
+
+        tracing::debug!(token_id = %token_id, "Claimed refresh token");
+
+        Ok(Some(data))
+    }
 
-        if deleted {
-            tracing::debug!(token_id = %token_id, "Revoked refresh token");
+    /// End a login session whose token was claimed without a replacement
+    pub async fn end_session(&self, session_id: &str) -> RedisResult<bool> {
+        self.pool.delete(&Self::session_key(session_id)).await
+    }
+
+    /// Revoke (delete) a refresh token, ending its session
+    pub async fn revoke(&self, token_id: &str) -> RedisResult<bool> {
+        let mut conn = self.pool.get().await?;
+        let value: Option<String> = redis::cmd("GETDEL")
+            .arg(Self::key(token_id))
+            .query_async(&mut conn)
+            .await?;
+        let Some(value) = value else {
+            return Ok(false);
+        };
+        let data: RefreshTokenData = serde_json::from_str(&value)?;
+
+        // Remove from user's token set
+        conn.srem::<_, _, ()>(Self::user_key(data.user_id), token_id)
+            .await?;
+
+        // End the session unless a newer token replaced this one meanwhile
+        let session_key = Self::session_key(&data.session_id);
+        let current: Option<String> = conn.get(&session_key).await?;
+        if current.as_deref() == Some(token_id) {
+            conn.del::<_, ()>(&session_key).await?;
         }
 
-        Ok(deleted)
+        tracing::debug!(token_id = %token_id, "Revoked refresh token");
+
+        Ok(true)
     }
 
     /// Revoke all tokens for a user (logout from all devices)
     pub async fn revoke_all_for_user(&self, user_id: Snowflake) -> RedisResult<u32> {
-        let user_set_key = format!("user_tokens:{user_id}");
-        let mut conn = self.pool.get().await?;
-
-        // Get all token IDs for this user
-        let token_ids: Vec<String> = conn.smembers(&user_set_key).await?;
-        let count = token_ids.len() as u32;
+        let user_set_key = Self::user_key(user_id);
+        let tokens = self.list_for_user(user_id).await?;
+        let count = tokens.len() as u32;
 
-        if !token_ids.is_empty() {
-            // Delete all tokens
-            let keys: Vec<String> = token_ids.iter().map(|id| Self::key(id)).collect();
-            let keys_refs: Vec<&str> = keys.iter().map(String::as_str).collect();
-            self.pool.delete_many(&keys_refs).await?;
+        // Delete all tokens and their sessions
+        let mut conn = self.pool.get().await?;
+        let mut keys = vec![user_set_key];
+        for (token_id, data) in &tokens {
+            keys.push(Self::key(token_id));
+            keys.push(Self::session_key(&data.session_id));
         }
-
-        // Delete the user's token set
-        conn.del::<_, ()>(&user_set_key).await?;
+        conn.del::<_, ()>(&keys).await?;
 
         tracing::info!(
             user_id = %user_id,
@@ -171,23 +243,113 @@ impl RefreshTokenStore {
 
     /// Get all active session IDs for a user
     pub async fn get_user_sessions(&self, user_id: Snowflake) -> RedisResult<Vec<String>> {
-        let user_set_key = format!("user_tokens:{user_id}");
+        Ok(self
+            .list_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(|(_, data)| data.session_id)
+            .collect())
+    }
+
+    /// Get all active tokens of a user with their data
+    pub async fn list_for_user(
+        &self,
+        user_id: Snowflake,
+    ) -> RedisResult<Vec<(String, RefreshTokenData)>> {
         let mut conn = self.pool.get().await?;
 
-        let token_ids: Vec<String> = conn.smembers(&user_set_key).await?;
-        let mut sessions = Vec::new();
+        let token_ids: Vec<String> = conn.smembers(Self::user_key(user_id)).await?;
+        if token_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = token_ids.iter().map(|id| Self::key(id)).collect();
+        let values: Vec<Option<String>> = conn.mget(&keys).await?;
+
+        // Expired tokens are left for the next store to prune
+        let mut tokens = Vec::with_capacity(token_ids.len());
+        for (token_id, value) in token_ids.into_iter().zip(values) {
+            if let Some(value) = value {
+                tokens.push((token_id, serde_json::from_str(&value)?));
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    /// Find the active token of a user's session
+    pub async fn find_session(
+        &self,
+        user_id: Snowflake,
+        session_id: &str,
+    ) -> RedisResult<Option<(String, RefreshTokenData)>> {
+        let mut conn = self.pool.get().await?;
+        let token_id: Option<String> = conn.get(Self::session_key(session_id)).await?;
+        let Some(token_id) = token_id else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .get(&token_id)
+            .await?
+            .filter(|data| data.user_id == user_id && data.session_id == session_id)
+            .map(|data| (token_id, data)))
+    }
 
-        for token_id in token_ids {
-            if let Some(data) = self.get(&token_id).await? {
-                sessions.push(data.session_id);
+    /// Check if a login session still has a token
+    ///
+    /// Session IDs are unique across users; callers take them from signed
+    /// tokens or check the owner with [`Self::find_session`].
+    pub async fn is_session_active(&self, session_id: &str) -> RedisResult<bool> {
+        self.pool.exists(&Self::session_key(session_id)).await
+    }
+
+    /// Revoke the token of a user's session
+    ///
+    /// Returns false if the session does not exist.
+    pub async fn revoke_session(&self, user_id: Snowflake, session_id: &str) -> RedisResult<bool> {
+        match self.find_session(user_id, session_id).await? {
+            Some((token_id, _)) => self.revoke(&token_id).await,
+            None => Ok(false),
+        }
+    }
+
+    /// Revoke every session of a user except `keep_session_id`
+    ///
+    /// Returns the IDs of the revoked sessions.
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: Snowflake,
+        keep_session_id: Option<&str>,
+    ) -> RedisResult<Vec<String>> {
+        let mut revoked = Vec::new();
+        for (token_id, data) in self.list_for_user(user_id).await? {
+            if keep_session_id == Some(data.session_id.as_str()) {
+                continue;
+            }
+            if self.revoke(&token_id).await? {
+                revoked.push(data.session_id);
             }
         }
 
-        Ok(sessions)
+        tracing::info!(
+            user_id = %user_id,
+            count = revoked.len(),
+            "Revoked other sessions for user"
+        );
+
+        Ok(revoked)
     }
 
     /// Refresh a token (extend TTL)
     pub async fn refresh(&self, token_id: &str) -> RedisResult<bool> {
+        let Some(data) = self.get(token_id).await? else {
+            return Ok(false);
+        };
+        self.pool
+            .expire(&Self::session_key(&data.session_id), self.ttl_seconds)
+            .await?;
+
         let key = Self::key(token_id);
         self.pool.expire(&key, self.ttl_seconds).await
     }
@@ -197,6 +359,34 @@ impl RefreshTokenStore {
         let key = Self::key(token_id);
         self.pool.ttl(&key).await
     }
+
+    /// Drop expired tokens from a user's token set
+    async fn prune_user_tokens(&self, user_id: Snowflake) -> RedisResult<()> {
+        let user_set_key = Self::user_key(user_id);
+        let mut conn = self.pool.get().await?;
+
+        let token_ids: Vec<String> = conn.smembers(&user_set_key).await?;
+        if token_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        for token_id in &token_ids {
+            pipe.exists(Self::key(token_id));
+        }
+        let exists: Vec<bool> = pipe.query_async(&mut conn).await?;
+
+        let expired: Vec<&String> = token_ids
+            .iter()
+            .zip(exists)
+            .filter_map(|(token_id, exists)| (!exists).then_some(token_id))
+            .collect();
+        if !expired.is_empty() {
+            conn.srem::<_, _, ()>(&user_set_key, expired).await?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -214,6 +404,28 @@ mod tests {
         assert_eq!(data.session_id, "session123");
         assert_eq!(data.device_info, Some("Chrome on Windows".to_string()));
         assert_eq!(data.ip_address, Some("192.168.1.1".to_string()));
+        assert_eq!(data.last_used(), data.created_at);
+    }
+
+    #[test]
+    fn test_refresh_token_data_touch() {
+        let mut data = RefreshTokenData::new(Snowflake::from(12345i64), "session123".to_string());
+        data.created_at -= 60;
+        data.touch();
+
+        assert!(data.last_used() > data.created_at);
+    }
+
+    #[test]
+    fn test_refresh_token_data_without_last_used() {
+        // Tokens stored before sessions tracked their last use
+        let data: RefreshTokenData = serde_json::from_str(
+            r#"{"user_id":"12345","session_id":"s","created_at":100,"device_info":null,"ip_address":null}"#,
+        )
+        .unwrap();
+
+        assert_eq!(data.last_used_at, None);
+        assert_eq!(data.last_used(), 100);
     }
 
     #[test]
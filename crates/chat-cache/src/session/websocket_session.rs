@@ -16,11 +16,17 @@ const WS_SESSION_PREFIX: &str = "ws_session:";
 const WS_EVENTS_PREFIX: &str = "ws_events:";
 /// Key prefix for user-to-sessions mapping
 const USER_SESSIONS_PREFIX: &str = "user_ws_sessions:";
+/// Key prefix for per-user session start counters
+const SESSION_STARTS_PREFIX: &str = "ws_session_starts:";
 
 /// Default TTL for disconnected sessions (2 minutes for resume)
 const SESSION_RESUME_TTL: u64 = 120;
 /// Maximum events to store for resume
 const MAX_RESUME_EVENTS: usize = 1000;
+/// Window in which session starts are counted (24 hours)
+const SESSION_START_WINDOW: u64 = 24 * 60 * 60;
+/// Maximum session starts (Identify) per user in one window
+pub const SESSION_START_LIMIT: u64 = 1000;
 
 /// WebSocket session state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -55,6 +61,18 @@ pub struct WebSocketSessionData {
     pub resume_url: Option<String>,
     /// Client properties (os, browser, device)
     pub properties: Option<ClientProperties>,
+    /// Gateway intents bitfield resolved at Identify
+    #[serde(default)]
+    pub intents: Option<u64>,
+    /// Shard `[shard_id, num_shards]` sent in Identify
+    #[serde(default)]
+    pub shard: Option<[u32; 2]>,
+    /// Gateway node holding the connection
+    #[serde(default)]
+    pub node_id: Option<String>,
+    /// Login session of the access token the connection identified with
+    #[serde(default)]
+    pub auth_session_id: Option<String>,
 }
 
 /// Client connection properties
@@ -80,6 +98,10 @@ impl WebSocketSessionData {
             state: SessionState::Connected,
             resume_url: None,
             properties: None,
+            intents: None,
+            shard: None,
+            node_id: None,
+            auth_session_id: None,
         }
     }
 
@@ -97,6 +119,34 @@ impl WebSocketSessionData {
         self
     }
 
+    /// Set gateway intents
+    #[must_use]
+    pub fn with_intents(mut self, intents: u64) -> Self {
+        self.intents = Some(intents);
+        self
+    }
+
+    /// Set gateway shard
+    #[must_use]
+    pub fn with_shard(mut self, shard: [u32; 2]) -> Self {
+        self.shard = Some(shard);
+        self
+    }
+
+    /// Set the gateway node holding the connection
+    #[must_use]
+    pub fn with_node(mut self, node_id: impl Into<String>) -> Self {
+        self.node_id = Some(node_id.into());
+        self
+    }
+
+    /// Set the login session the connection identified with
+    #[must_use]
+    pub fn with_auth_session(mut self, auth_session_id: impl Into<String>) -> Self {
+        self.auth_session_id = Some(auth_session_id.into());
+        self
+    }
+
     /// Add guild subscription
     pub fn add_guild(&mut self, guild_id: Snowflake) {
         if !self.guilds.contains(&guild_id) {
@@ -168,6 +218,33 @@ impl WebSocketSessionStore {
         format!("{USER_SESSIONS_PREFIX}{user_id}")
     }
 
+    /// Generate Redis key for a user's session start counter
+    fn session_starts_key(user_id: Snowflake) -> String {
+        format!("{SESSION_STARTS_PREFIX}{user_id}")
+    }
+
+    /// Count a new session (Identify) for a user
+    ///
+    /// The counter resets [`SESSION_START_WINDOW`] seconds after the first
+    /// start in the window. Returns the number of starts in the window.
+    pub async fn record_session_start(&self, user_id: Snowflake) -> RedisResult<u64> {
+        let key = Self::session_starts_key(user_id);
+        let mut conn = self.pool.get().await?;
+        let count: u64 = conn.incr(&key, 1).await?;
+        if count == 1 {
+            self.pool.expire(&key, SESSION_START_WINDOW).await?;
+        }
+        Ok(count)
+    }
+
+    /// Get a user's session starts in the current window and seconds until it resets
+    pub async fn session_starts(&self, user_id: Snowflake) -> RedisResult<(u64, u64)> {
+        let key = Self::session_starts_key(user_id);
+        let count: Option<u64> = self.pool.get_value(&key).await?;
+        let reset_after = self.pool.ttl(&key).await?.and_then(|t| u64::try_from(t).ok());
+        Ok((count.unwrap_or(0), reset_after.unwrap_or(0)))
+    }
+
     /// Create a new session
     pub async fn create(&self, session: &WebSocketSessionData) -> RedisResult<()> {
         let key = Self::session_key(&session.session_id);
@@ -285,13 +362,34 @@ impl WebSocketSessionStore {
         Ok(true)
     }
 
-    /// Delete all sessions for a user
-    pub async fn delete_all_for_user(&self, user_id: Snowflake) -> RedisResult<u32> {
+    /// Get the IDs of a user's sessions opened with one of the given logins
+    pub async fn find_by_auth_sessions(
+        &self,
+        user_id: Snowflake,
+        auth_session_ids: &[String],
+    ) -> RedisResult<Vec<String>> {
+        let mut session_ids = Vec::new();
+        for session_id in self.get_user_sessions(user_id).await? {
+            let Some(session) = self.get(&session_id).await? else {
+                continue;
+            };
+            if session
+                .auth_session_id
+                .as_ref()
+                .is_some_and(|id| auth_session_ids.contains(id))
+            {
+                session_ids.push(session_id);
+            }
+        }
+        Ok(session_ids)
+    }
+
+    /// Delete all sessions for a user, returning the deleted session IDs
+    pub async fn delete_all_for_user(&self, user_id: Snowflake) -> RedisResult<Vec<String>> {
         let user_key = Self::user_sessions_key(user_id);
         let mut conn = self.pool.get().await?;
 
         let session_ids: Vec<String> = conn.smembers(&user_key).await?;
-        let count = session_ids.len() as u32;
 
         for session_id in &session_ids {
             let key = Self::session_key(session_id);
@@ -305,11 +403,11 @@ impl WebSocketSessionStore {
 
         tracing::info!(
             user_id = %user_id,
-            count = count,
+            count = session_ids.len(),
             "Deleted all WebSocket sessions for user"
         );
 
-        Ok(count)
+        Ok(session_ids)
     }
 
     /// Get all session IDs for a user
@@ -320,42 +418,59 @@ impl WebSocketSessionStore {
         Ok(sessions)
     }
 
-    /// Queue an event for potential session resume
-    pub async fn queue_event(&self, session_id: &str, event: &SessionEvent) -> RedisResult<()> {
+    /// Queue events for potential session resume
+    ///
+    /// Events are given oldest first and written in one atomic pipeline.
+    pub async fn queue_events(&self, session_id: &str, events: &[SessionEvent]) -> RedisResult<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
         let key = Self::events_key(session_id);
         let mut conn = self.pool.get().await?;
 
-        let serialized = serde_json::to_string(event)?;
-        conn.lpush::<_, _, ()>(&key, &serialized).await?;
-
-        // Trim to max length
-        conn.ltrim::<_, ()>(&key, 0, (MAX_RESUME_EVENTS - 1) as isize).await?;
+        let serialized = events
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Push and trim to max length together
+        redis::pipe()
+            .atomic()
+            .lpush(&key, serialized)
+            .ignore()
+            .ltrim(&key, 0, (MAX_RESUME_EVENTS - 1) as isize)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await?;
 
         Ok(())
     }
 
     /// Get queued events for session resume (returns events after given sequence)
+    ///
+    /// Returns `None` when the events after `since_sequence` can no longer be
+    /// replayed in full: some were trimmed from the queue, or `since_sequence`
+    /// is ahead of anything the session sent.
     pub async fn get_events_since(
         &self,
         session_id: &str,
         since_sequence: u64,
-    ) -> RedisResult<Vec<SessionEvent>> {
+        last_sequence: u64,
+    ) -> RedisResult<Option<Vec<SessionEvent>>> {
         let key = Self::events_key(session_id);
         let mut conn = self.pool.get().await?;
 
-        let events: Vec<String> = conn.lrange(&key, 0, -1).await?;
-        let mut result = Vec::new();
+        let stored: Vec<String> = conn.lrange(&key, 0, -1).await?;
 
-        // Events are stored newest-first, so reverse and filter
-        for event_str in events.into_iter().rev() {
-            if let Ok(event) = serde_json::from_str::<SessionEvent>(&event_str) {
-                if event.sequence > since_sequence {
-                    result.push(event);
-                }
-            }
-        }
+        // Events are stored newest-first
+        let events = stored
+            .into_iter()
+            .rev()
+            .filter_map(|event_str| serde_json::from_str::<SessionEvent>(&event_str).ok())
+            .collect();
 
-        Ok(result)
+        Ok(events_after(events, since_sequence, last_sequence))
     }
 
     /// Clear event queue for a session
@@ -425,10 +540,79 @@ impl WebSocketSessionStore {
     }
 }
 
+/// Select the events after `since_sequence` from a queue ordered oldest-first
+///
+/// Returns `None` if the result would not be gapless up to the last sequence.
+fn events_after(
+    events: Vec<SessionEvent>,
+    since_sequence: u64,
+    last_sequence: u64,
+) -> Option<Vec<SessionEvent>> {
+    let last_sequence = events
+        .last()
+        .map_or(last_sequence, |e| e.sequence.max(last_sequence));
+    if since_sequence > last_sequence {
+        return None;
+    }
+
+    let missed: Vec<SessionEvent> = events
+        .into_iter()
+        .filter(|e| e.sequence > since_sequence)
+        .collect();
+
+    let contiguous = missed
+        .iter()
+        .zip(since_sequence + 1..)
+        .all(|(event, expected)| event.sequence == expected);
+    let complete = missed.last().map_or(since_sequence, |e| e.sequence) == last_sequence;
+
+    (contiguous && complete).then_some(missed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn events(sequences: std::ops::RangeInclusive<u64>) -> Vec<SessionEvent> {
+        sequences
+            .map(|sequence| SessionEvent {
+                sequence,
+                event_type: "MESSAGE_CREATE".to_string(),
+                data: serde_json::Value::Null,
+                timestamp: 0,
+            })
+            .collect()
+    }
+
+    fn sequences(events: &[SessionEvent]) -> Vec<u64> {
+        events.iter().map(|e| e.sequence).collect()
+    }
+
+    #[test]
+    fn test_events_after_replays_missed() {
+        let missed = events_after(events(1..=5), 3, 5).unwrap();
+        assert_eq!(sequences(&missed), vec![4, 5]);
+
+        let missed = events_after(events(1..=5), 5, 5).unwrap();
+        assert!(missed.is_empty());
+    }
+
+    #[test]
+    fn test_events_after_trimmed_window() {
+        // Events 1-3 were trimmed from the queue
+        assert!(events_after(events(4..=8), 2, 8).is_none());
+        assert_eq!(sequences(&events_after(events(4..=8), 3, 8).unwrap()), vec![4, 5, 6, 7, 8]);
+
+        // Queue lost entirely while the session had sent events
+        assert!(events_after(Vec::new(), 3, 5).is_none());
+    }
+
+    #[test]
+    fn test_events_after_future_sequence() {
+        assert!(events_after(events(1..=5), 6, 5).is_none());
+        assert!(events_after(Vec::new(), 1, 0).is_none());
+    }
+
     #[test]
     fn test_session_data_creation() {
         let user_id = Snowflake::from(12345i64);
@@ -438,7 +622,8 @@ mod tests {
                 browser: Some("Chrome".to_string()),
                 device: Some("desktop".to_string()),
             })
-            .with_resume_url("wss://gateway.example.com");
+            .with_resume_url("wss://gateway.example.com")
+            .with_auth_session("login1");
 
         assert_eq!(session.session_id, "session123");
         assert_eq!(session.user_id, user_id);
@@ -447,6 +632,7 @@ mod tests {
         assert_eq!(session.state, SessionState::Connected);
         assert!(session.properties.is_some());
         assert!(session.resume_url.is_some());
+        assert_eq!(session.auth_session_id.as_deref(), Some("login1"));
     }
 
     #[test]
@@ -6,6 +6,7 @@ use chat_core::Snowflake;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::error::AppError;
 
@@ -31,6 +32,9 @@ pub struct Claims {
     /// Optional session ID for tracking
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
+    /// Unique token ID, so no two tokens are identical
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
 impl Claims {
@@ -141,6 +145,7 @@ impl JwtService {
             exp: (now + Duration::seconds(expiry)).timestamp(),
             token_type,
             session_id,
+            jti: Some(Uuid::new_v4().to_string()),
         };
 
         encode(&Header::default(), &claims, &self.encoding_key)
@@ -300,7 +305,10 @@ mod tests {
         let pair1 = service.generate_token_pair(user_id).unwrap();
         let pair2 = service.refresh_tokens(&pair1.refresh_token).unwrap();
 
-        // New tokens should be valid (they may be identical if generated in same second)
+        // New tokens are valid and differ from the old ones
+        assert_ne!(pair2.access_token, pair1.access_token);
+        assert_ne!(pair2.refresh_token, pair1.refresh_token);
+
         let claims = service.validate_access_token(&pair2.access_token).unwrap();
         assert_eq!(claims.user_id().unwrap(), user_id);
 
@@ -338,6 +346,7 @@ mod tests {
             exp: i64::MAX,
             token_type: TokenType::Access,
             session_id: None,
+            jti: None,
         };
 
         let user_id = claims.user_id().unwrap();
@@ -2,8 +2,13 @@
 
 mod jwt;
 mod password;
+mod totp;
 
 pub use jwt::{Claims, JwtService, TokenPair, TokenType};
 pub use password::{
     hash_password, validate_password_strength, verify_password, PasswordService,
 };
+pub use totp::{
+    generate_backup_codes, generate_totp_secret, hash_backup_code, is_totp_code, totp_code,
+    TotpService, BACKUP_CODE_COUNT, TOTP_DIGITS, TOTP_STEP_SECONDS,
+};
@@ -2,8 +2,10 @@
 //!
 //! Loads configuration from environment variables and config files.
 
+use ipnet::IpNet;
 use serde::Deserialize;
 use std::env;
+use std::net::{IpAddr, SocketAddr};
 
 /// Main application configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -15,8 +17,10 @@ pub struct AppConfig {
     pub redis: RedisConfig,
     pub jwt: JwtConfig,
     pub rate_limit: RateLimitConfig,
+    pub proxy: ProxyConfig,
     pub cors: CorsConfig,
     pub storage: StorageConfig,
+    pub mail: MailConfig,
     pub snowflake: SnowflakeConfig,
 }
 
@@ -103,6 +107,85 @@ pub struct RateLimitConfig {
     pub burst: u32,
 }
 
+/// Reverse proxy configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProxyConfig {
+    /// Peers whose `Forwarded` and `X-Forwarded-For` headers are trusted
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpNet>,
+}
+
+impl ProxyConfig {
+    /// Check if an address belongs to a trusted proxy
+    #[must_use]
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Resolve the IP address of the client behind the peer of a connection
+    ///
+    /// Forwarding headers are only followed while the hop that added them is
+    /// a trusted proxy, starting from the peer and walking the hops right to
+    /// left. `Forwarded` takes precedence over `X-Forwarded-For`; both take
+    /// the header values in the order they appear in the request.
+    #[must_use]
+    pub fn client_ip<'a>(
+        &self,
+        peer: IpAddr,
+        forwarded: impl IntoIterator<Item = &'a str>,
+        x_forwarded_for: impl IntoIterator<Item = &'a str>,
+    ) -> IpAddr {
+        if !self.is_trusted(peer) {
+            return peer;
+        }
+
+        let mut hops: Vec<&str> = forwarded
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .map(forwarded_for)
+            .collect();
+        if hops.is_empty() {
+            hops = x_forwarded_for
+                .into_iter()
+                .flat_map(|value| value.split(','))
+                .map(str::trim)
+                .collect();
+        }
+
+        let mut client = peer;
+        for hop in hops.into_iter().rev() {
+            if !self.is_trusted(client) {
+                break;
+            }
+            // Obfuscated or malformed hops end the chain
+            match parse_hop(hop) {
+                Some(ip) => client = ip,
+                None => break,
+            }
+        }
+        client
+    }
+}
+
+/// Get the `for` parameter of a `Forwarded` header element
+///
+/// Returns an empty string for elements without one.
+fn forwarded_for(element: &str) -> &str {
+    element
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("for"))
+        .map_or("", |(_, value)| value.trim().trim_matches('"'))
+}
+
+/// Parse a forwarded hop: an IP address, optionally with a port and IPv6 brackets
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    hop.parse()
+        .ok()
+        .or_else(|| hop.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| hop.strip_prefix('[')?.strip_suffix(']')?.parse().ok())
+}
+
 /// CORS configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct CorsConfig {
@@ -113,10 +196,108 @@ pub struct CorsConfig {
 /// File storage configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: StorageBackendKind,
     #[serde(default = "default_upload_dir")]
     pub upload_dir: String,
     #[serde(default = "default_max_file_size")]
     pub max_file_size_mb: u32,
+    /// Base URL prepended to attachment URLs (empty for relative URLs)
+    #[serde(default)]
+    pub public_url: String,
+    #[serde(default)]
+    pub s3: Option<S3Config>,
+}
+
+impl StorageConfig {
+    /// Maximum upload size in bytes
+    #[must_use]
+    pub fn max_file_size_bytes(&self) -> u64 {
+        u64::from(self.max_file_size_mb) * 1024 * 1024
+    }
+}
+
+/// Storage backend selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackendKind {
+    #[default]
+    Local,
+    S3,
+}
+
+/// S3-compatible object storage configuration (AWS S3, MinIO, ...)
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Config {
+    pub bucket: String,
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    /// Custom endpoint for S3-compatible services such as MinIO
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Allow plain HTTP endpoints (local MinIO)
+    #[serde(default)]
+    pub allow_http: bool,
+}
+
+/// Outgoing mail configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct MailConfig {
+    #[serde(default)]
+    pub backend: MailBackendKind,
+    /// Sender address, e.g. `Chat <noreply@example.com>`
+    #[serde(default = "default_mail_from")]
+    pub from: String,
+    /// Base URL of the client, used to build links in emails
+    #[serde(default = "default_mail_link_base_url")]
+    pub link_base_url: String,
+    /// Directory the file backend writes captured messages to
+    #[serde(default = "default_mail_capture_dir")]
+    pub capture_dir: String,
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// Mail backend selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MailBackendKind {
+    Smtp,
+    /// Write each message to `capture_dir` as JSON
+    #[default]
+    File,
+    /// Keep messages in memory (tests)
+    Memory,
+}
+
+/// SMTP relay configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    /// Defaults to 587 for STARTTLS, 465 for implicit TLS and 25 for plain text
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub tls: SmtpTls,
+}
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS
+    #[default]
+    StartTls,
+    /// Implicit TLS from the first byte
+    Tls,
+    /// Plain text (local relays such as MailHog)
+    None,
 }
 
 /// Snowflake ID generator configuration
@@ -175,6 +356,29 @@ fn default_max_file_size() -> u32 {
     10
 }
 
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_mail_from() -> String {
+    "Chat Server <noreply@localhost>".to_string()
+}
+
+fn default_mail_link_base_url() -> String {
+    "http://localhost:3000".to_string()
+}
+
+fn default_mail_capture_dir() -> String {
+    "./mail".to_string()
+}
+
+/// Parse a trusted proxy network; a bare address is a single-host network
+fn parse_proxy(s: &str) -> Result<IpNet, ConfigError> {
+    s.parse()
+        .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+        .map_err(|_| ConfigError::InvalidValue("TRUSTED_PROXIES", s.to_string()))
+}
+
 impl AppConfig {
     /// Load configuration from environment variables
     ///
@@ -250,6 +454,17 @@ impl AppConfig {
                     .and_then(|s| s.parse().ok())
                     .unwrap_or_else(default_burst),
             },
+            proxy: ProxyConfig {
+                trusted_proxies: match env::var("TRUSTED_PROXIES") {
+                    Ok(s) => s
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(parse_proxy)
+                        .collect::<Result<_, _>>()?,
+                    Err(_) => Vec::new(),
+                },
+            },
             cors: CorsConfig {
                 allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
                     .ok()
@@ -257,11 +472,71 @@ impl AppConfig {
                     .unwrap_or_default(),
             },
             storage: StorageConfig {
+                backend: match env::var("STORAGE_BACKEND").ok().as_deref() {
+                    None | Some("local") => StorageBackendKind::Local,
+                    Some("s3") => StorageBackendKind::S3,
+                    Some(other) => {
+                        return Err(ConfigError::InvalidValue("STORAGE_BACKEND", other.to_string()))
+                    }
+                },
                 upload_dir: env::var("UPLOAD_DIR").unwrap_or_else(|_| default_upload_dir()),
                 max_file_size_mb: env::var("MAX_FILE_SIZE_MB")
                     .ok()
                     .and_then(|s| s.parse().ok())
                     .unwrap_or_else(default_max_file_size),
+                public_url: env::var("STORAGE_PUBLIC_URL")
+                    .map(|s| s.trim_end_matches('/').to_string())
+                    .unwrap_or_default(),
+                s3: match env::var("S3_BUCKET") {
+                    Ok(bucket) => Some(S3Config {
+                        bucket,
+                        region: env::var("S3_REGION").unwrap_or_else(|_| default_s3_region()),
+                        endpoint: env::var("S3_ENDPOINT").ok(),
+                        access_key_id: env::var("S3_ACCESS_KEY_ID")
+                            .map_err(|_| ConfigError::MissingVar("S3_ACCESS_KEY_ID"))?,
+                        secret_access_key: env::var("S3_SECRET_ACCESS_KEY")
+                            .map_err(|_| ConfigError::MissingVar("S3_SECRET_ACCESS_KEY"))?,
+                        allow_http: env::var("S3_ALLOW_HTTP")
+                            .ok()
+                            .and_then(|s| s.parse().ok())
+                            .unwrap_or(false),
+                    }),
+                    Err(_) => None,
+                },
+            },
+            mail: MailConfig {
+                backend: match env::var("MAIL_BACKEND").ok().as_deref() {
+                    None | Some("file") => MailBackendKind::File,
+                    Some("smtp") => MailBackendKind::Smtp,
+                    Some("memory") => MailBackendKind::Memory,
+                    Some(other) => {
+                        return Err(ConfigError::InvalidValue("MAIL_BACKEND", other.to_string()))
+                    }
+                },
+                from: env::var("MAIL_FROM").unwrap_or_else(|_| default_mail_from()),
+                link_base_url: env::var("MAIL_LINK_BASE_URL").map_or_else(
+                    |_| default_mail_link_base_url(),
+                    |s| s.trim_end_matches('/').to_string(),
+                ),
+                capture_dir: env::var("MAIL_CAPTURE_DIR")
+                    .unwrap_or_else(|_| default_mail_capture_dir()),
+                smtp: match env::var("SMTP_HOST").ok().filter(|s| !s.is_empty()) {
+                    Some(host) => Some(SmtpConfig {
+                        host,
+                        port: env::var("SMTP_PORT").ok().and_then(|s| s.parse().ok()),
+                        username: env::var("SMTP_USERNAME").ok().filter(|s| !s.is_empty()),
+                        password: env::var("SMTP_PASSWORD").ok().filter(|s| !s.is_empty()),
+                        tls: match env::var("SMTP_TLS").ok().as_deref() {
+                            None | Some("starttls") => SmtpTls::StartTls,
+                            Some("tls") => SmtpTls::Tls,
+                            Some("none") => SmtpTls::None,
+                            Some(other) => {
+                                return Err(ConfigError::InvalidValue("SMTP_TLS", other.to_string()))
+                            }
+                        },
+                    }),
+                    None => None,
+                },
             },
             snowflake: SnowflakeConfig {
                 worker_id: env::var("WORKER_ID")
@@ -318,4 +593,68 @@ mod tests {
         assert_eq!(default_access_token_expiry(), 900);
         assert_eq!(default_refresh_token_expiry(), 604800);
     }
+
+    fn proxy_config(proxies: &[&str]) -> ProxyConfig {
+        ProxyConfig {
+            trusted_proxies: proxies.iter().map(|s| parse_proxy(s).unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_client_ip_untrusted_peer() {
+        let config = proxy_config(&["10.0.0.0/8"]);
+        let peer: IpAddr = "203.0.113.7".parse().unwrap();
+        assert_eq!(config.client_ip(peer, [], ["198.51.100.1"]), peer);
+    }
+
+    #[test]
+    fn test_client_ip_x_forwarded_for() {
+        let config = proxy_config(&["10.0.0.0/8"]);
+        let peer: IpAddr = "10.0.0.2".parse().unwrap();
+
+        // Spoofed entries left of the first untrusted hop are ignored
+        let ip = config.client_ip(peer, [], ["1.2.3.4, 198.51.100.1", "10.0.0.3"]);
+        assert_eq!(ip, "198.51.100.1".parse::<IpAddr>().unwrap());
+
+        // Without a header the peer is the client
+        assert_eq!(config.client_ip(peer, [], []), peer);
+    }
+
+    #[test]
+    fn test_client_ip_forwarded() {
+        let config = proxy_config(&["10.0.0.2"]);
+        let peer: IpAddr = "10.0.0.2".parse().unwrap();
+
+        let ip = config.client_ip(
+            peer,
+            [r#"for="[2001:db8::1]:4711";proto=https, for=_hidden"#],
+            ["198.51.100.1"],
+        );
+        assert_eq!(ip, peer);
+
+        let ip = config.client_ip(peer, [r#"For="[2001:db8::1]:4711";proto=https"#], []);
+        assert_eq!(ip, "2001:db8::1".parse::<IpAddr>().unwrap());
+
+        let ip = config.client_ip(peer, ["for=192.0.2.60:8080;by=10.0.0.2"], []);
+        assert_eq!(ip, "192.0.2.60".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_parse_proxy() {
+        assert_eq!(parse_proxy("10.0.0.1").unwrap().to_string(), "10.0.0.1/32");
+        assert_eq!(parse_proxy("fd00::/8").unwrap().to_string(), "fd00::/8");
+        assert!(parse_proxy("proxy.local").is_err());
+    }
+
+    #[test]
+    fn test_max_file_size_bytes() {
+        let config = StorageConfig {
+            backend: StorageBackendKind::Local,
+            upload_dir: default_upload_dir(),
+            max_file_size_mb: 8,
+            public_url: String::new(),
+            s3: None,
+        };
+        assert_eq!(config.max_file_size_bytes(), 8 * 1024 * 1024);
+    }
 }
@@ -4,5 +4,6 @@ mod app_config;
 
 pub use app_config::{
     AppConfig, AppSettings, ConfigError, CorsConfig, DatabaseConfig, Environment,
-    JwtConfig, RateLimitConfig, RedisConfig, ServerConfig, SnowflakeConfig, StorageConfig,
+    JwtConfig, MailBackendKind, MailConfig, ProxyConfig, RateLimitConfig, RedisConfig, S3Config,
+    ServerConfig, SmtpConfig, SmtpTls, SnowflakeConfig, StorageBackendKind, StorageConfig,
 };
@@ -10,11 +10,12 @@ pub mod telemetry;
 // Re-export commonly used types at crate root
 pub use auth::{
     hash_password, validate_password_strength, verify_password, Claims, JwtService,
-    PasswordService, TokenPair, TokenType,
+    PasswordService, TokenPair, TokenType, TotpService,
 };
 pub use config::{
     AppConfig, AppSettings, ConfigError, CorsConfig, DatabaseConfig, Environment, JwtConfig,
-    RateLimitConfig, RedisConfig, ServerConfig, SnowflakeConfig, StorageConfig,
+    MailBackendKind, MailConfig, ProxyConfig, RateLimitConfig, RedisConfig, S3Config, ServerConfig,
+    SmtpConfig, SmtpTls, SnowflakeConfig, StorageBackendKind, StorageConfig,
 };
 pub use error::{AppError, AppResult, ErrorResponse};
 pub use telemetry::{
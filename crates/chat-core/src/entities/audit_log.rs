@@ -0,0 +1,249 @@
+//! Audit log entity - record of an administrative action in a guild
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::value_objects::Snowflake;
+
+/// Audit log action type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditLogAction {
+    GuildUpdate,
+    ChannelCreate,
+    ChannelUpdate,
+    ChannelDelete,
+    RoleCreate,
+    RoleUpdate,
+    RoleDelete,
+    MemberKick,
+    MemberBan,
+    MemberUnban,
+    MemberRoleUpdate,
+    MessageDelete,
+    MessagePin,
+    MessageUnpin,
+}
+
+impl AuditLogAction {
+    /// Get the numeric action type used by the API
+    #[must_use]
+    pub const fn as_i32(self) -> i32 {
+        match self {
+            Self::GuildUpdate => 1,
+            Self::ChannelCreate => 10,
+            Self::ChannelUpdate => 11,
+            Self::ChannelDelete => 12,
+            Self::MemberKick => 20,
+            Self::MemberBan => 22,
+            Self::MemberUnban => 23,
+            Self::MemberRoleUpdate => 25,
+            Self::RoleCreate => 30,
+            Self::RoleUpdate => 31,
+            Self::RoleDelete => 32,
+            Self::MessageDelete => 72,
+            Self::MessagePin => 74,
+            Self::MessageUnpin => 75,
+        }
+    }
+
+    /// Parse from the numeric action type
+    #[must_use]
+    pub const fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(Self::GuildUpdate),
+            10 => Some(Self::ChannelCreate),
+            11 => Some(Self::ChannelUpdate),
+            12 => Some(Self::ChannelDelete),
+            20 => Some(Self::MemberKick),
+            22 => Some(Self::MemberBan),
+            23 => Some(Self::MemberUnban),
+            25 => Some(Self::MemberRoleUpdate),
+            30 => Some(Self::RoleCreate),
+            31 => Some(Self::RoleUpdate),
+            32 => Some(Self::RoleDelete),
+            72 => Some(Self::MessageDelete),
+            74 => Some(Self::MessagePin),
+            75 => Some(Self::MessageUnpin),
+            _ => None,
+        }
+    }
+}
+
+/// A single changed field in an audit log entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogChange {
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<Value>,
+}
+
+/// Audit log entry entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogEntry {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    /// User who performed the action
+    pub user_id: Snowflake,
+    pub action: AuditLogAction,
+    /// ID of the affected entity
+    pub target_id: Option<Snowflake>,
+    /// Type of the affected entity (e.g. "channel", "role", "user")
+    pub target_type: Option<String>,
+    pub changes: Vec<AuditLogChange>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    /// Create a new audit log entry
+    #[must_use]
+    pub fn new(
+        id: Snowflake,
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        action: AuditLogAction,
+    ) -> Self {
+        Self {
+            id,
+            guild_id,
+            user_id,
+            action,
+            target_id: None,
+            target_type: None,
+            changes: Vec::new(),
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Set the affected entity
+    #[must_use]
+    pub fn with_target(mut self, target_id: Snowflake, target_type: &str) -> Self {
+        self.target_id = Some(target_id);
+        self.target_type = Some(target_type.to_string());
+        self
+    }
+
+    /// Set the recorded changes
+    #[must_use]
+    pub fn with_changes(mut self, changes: Vec<AuditLogChange>) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Set the reason given for the action
+    #[must_use]
+    pub fn with_reason(mut self, reason: Option<String>) -> Self {
+        self.reason = reason;
+        self
+    }
+
+    /// Compute the changed fields between two JSON object snapshots
+    ///
+    /// `Value::Null` stands for "did not exist", so a create diffs against
+    /// `Null` and a delete diffs to `Null`. Keys are reported in sorted order.
+    #[must_use]
+    pub fn diff(before: &Value, after: &Value) -> Vec<AuditLogChange> {
+        let empty = serde_json::Map::new();
+        let before = before.as_object().unwrap_or(&empty);
+        let after = after.as_object().unwrap_or(&empty);
+
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let old_value = before.get(key).filter(|v| !v.is_null());
+                let new_value = after.get(key).filter(|v| !v.is_null());
+                if old_value == new_value {
+                    return None;
+                }
+                Some(AuditLogChange {
+                    key: key.clone(),
+                    old_value: old_value.cloned(),
+                    new_value: new_value.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_action_codes_roundtrip() {
+        let actions = [
+            AuditLogAction::GuildUpdate,
+            AuditLogAction::ChannelCreate,
+            AuditLogAction::ChannelUpdate,
+            AuditLogAction::ChannelDelete,
+            AuditLogAction::RoleCreate,
+            AuditLogAction::RoleUpdate,
+            AuditLogAction::RoleDelete,
+            AuditLogAction::MemberKick,
+            AuditLogAction::MemberBan,
+            AuditLogAction::MemberUnban,
+            AuditLogAction::MemberRoleUpdate,
+            AuditLogAction::MessageDelete,
+            AuditLogAction::MessagePin,
+            AuditLogAction::MessageUnpin,
+        ];
+        for action in actions {
+            assert_eq!(AuditLogAction::from_i32(action.as_i32()), Some(action));
+        }
+        assert_eq!(AuditLogAction::from_i32(0), None);
+    }
+
+    #[test]
+    fn test_diff_update() {
+        let before = json!({"name": "general", "topic": null, "position": 0});
+        let after = json!({"name": "chat", "topic": "hi", "position": 0});
+
+        let changes = AuditLogEntry::diff(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].key, "name");
+        assert_eq!(changes[0].old_value, Some(json!("general")));
+        assert_eq!(changes[0].new_value, Some(json!("chat")));
+        assert_eq!(changes[1].key, "topic");
+        assert_eq!(changes[1].old_value, None);
+        assert_eq!(changes[1].new_value, Some(json!("hi")));
+    }
+
+    #[test]
+    fn test_diff_create_and_delete() {
+        let snapshot = json!({"name": "mods"});
+
+        let created = AuditLogEntry::diff(&Value::Null, &snapshot);
+        assert_eq!(created.len(), 1);
+        assert!(created[0].old_value.is_none());
+
+        let deleted = AuditLogEntry::diff(&snapshot, &Value::Null);
+        assert_eq!(deleted.len(), 1);
+        assert!(deleted[0].new_value.is_none());
+    }
+
+    #[test]
+    fn test_builder() {
+        let entry = AuditLogEntry::new(
+            Snowflake::new(1),
+            Snowflake::new(2),
+            Snowflake::new(3),
+            AuditLogAction::MemberKick,
+        )
+        .with_target(Snowflake::new(4), "user")
+        .with_reason(Some("spam".to_string()));
+
+        assert_eq!(entry.target_id, Some(Snowflake::new(4)));
+        assert_eq!(entry.target_type.as_deref(), Some("user"));
+        assert_eq!(entry.reason.as_deref(), Some("spam"));
+        assert!(entry.changes.is_empty());
+    }
+}
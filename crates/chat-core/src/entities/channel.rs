@@ -1,4 +1,4 @@
-//! Channel entity - represents a text channel, DM, or category
+//! Channel entity - represents a text channel, DM, group DM, category, or thread
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -14,8 +14,12 @@ pub enum ChannelType {
     GuildText = 0,
     /// Direct message between users
     Dm = 1,
+    /// Direct message between a group of users, with an owner
+    GroupDm = 3,
     /// Guild category for organizing channels
     GuildCategory = 4,
+    /// Thread started from a message in a guild text channel
+    GuildThread = 11,
 }
 
 impl ChannelType {
@@ -31,7 +35,9 @@ impl From<i16> for ChannelType {
     fn from(value: i16) -> Self {
         match value {
             1 => Self::Dm,
+            3 => Self::GroupDm,
             4 => Self::GuildCategory,
+            11 => Self::GuildThread,
             _ => Self::GuildText, // Default for 0 and unknown values
         }
     }
@@ -53,6 +59,10 @@ pub struct Channel {
     pub topic: Option<String>,
     pub position: i32,
     pub parent_id: Option<Snowflake>,
+    /// Group DM owner
+    pub owner_id: Option<Snowflake>,
+    /// Group DM icon
+    pub icon: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -70,6 +80,8 @@ impl Channel {
             topic: None,
             position: 0,
             parent_id: None,
+            owner_id: None,
+            icon: None,
             created_at: now,
             updated_at: now,
         }
@@ -87,6 +99,27 @@ impl Channel {
             topic: None,
             position: 0,
             parent_id: None,
+            owner_id: None,
+            icon: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Create a new group DM channel owned by `owner_id`
+    #[must_use]
+    pub fn new_group_dm(id: Snowflake, owner_id: Snowflake, name: Option<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            guild_id: None,
+            name,
+            channel_type: ChannelType::GroupDm,
+            topic: None,
+            position: 0,
+            parent_id: None,
+            owner_id: Some(owner_id),
+            icon: None,
             created_at: now,
             updated_at: now,
         }
@@ -104,16 +137,43 @@ impl Channel {
             topic: None,
             position: 0,
             parent_id: None,
+            owner_id: None,
+            icon: None,
             created_at: now,
             updated_at: now,
         }
     }
 
-    /// Check if this is a text channel (guild text or DM)
+    /// Create a new thread channel under a guild text channel
+    #[must_use]
+    pub fn new_thread(id: Snowflake, guild_id: Snowflake, parent_id: Snowflake, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            guild_id: Some(guild_id),
+            name: Some(name),
+            channel_type: ChannelType::GuildThread,
+            topic: None,
+            position: 0,
+            parent_id: Some(parent_id),
+            owner_id: None,
+            icon: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check if this is a text channel (guild text, DM, group DM, or thread)
     #[inline]
     #[must_use]
     pub fn is_text(&self) -> bool {
-        matches!(self.channel_type, ChannelType::GuildText | ChannelType::Dm)
+        matches!(
+            self.channel_type,
+            ChannelType::GuildText
+                | ChannelType::Dm
+                | ChannelType::GroupDm
+                | ChannelType::GuildThread
+        )
     }
 
     /// Check if this is a category
@@ -123,6 +183,13 @@ impl Channel {
         matches!(self.channel_type, ChannelType::GuildCategory)
     }
 
+    /// Check if this is a thread
+    #[inline]
+    #[must_use]
+    pub fn is_thread(&self) -> bool {
+        matches!(self.channel_type, ChannelType::GuildThread)
+    }
+
     /// Check if this is a DM channel
     #[inline]
     #[must_use]
@@ -130,6 +197,13 @@ impl Channel {
         matches!(self.channel_type, ChannelType::Dm)
     }
 
+    /// Check if this is a group DM channel
+    #[inline]
+    #[must_use]
+    pub fn is_group_dm(&self) -> bool {
+        matches!(self.channel_type, ChannelType::GroupDm)
+    }
+
     /// Check if this is a guild channel
     #[inline]
     #[must_use]
@@ -140,7 +214,11 @@ impl Channel {
     /// Get display name (channel name or fallback for DMs)
     #[must_use]
     pub fn display_name(&self) -> &str {
-        self.name.as_deref().unwrap_or("Direct Message")
+        match (&self.name, self.channel_type) {
+            (Some(name), _) => name,
+            (None, ChannelType::GroupDm) => "Group DM",
+            (None, _) => "Direct Message",
+        }
     }
 
     /// Update channel name
@@ -161,6 +239,12 @@ impl Channel {
         self.updated_at = Utc::now();
     }
 
+    /// Hand a group DM to a new owner
+    pub fn set_owner(&mut self, owner_id: Snowflake) {
+        self.owner_id = Some(owner_id);
+        self.updated_at = Utc::now();
+    }
+
     /// Move channel to a category
     pub fn set_parent(&mut self, parent_id: Option<Snowflake>) {
         self.parent_id = parent_id;
@@ -176,7 +260,9 @@ mod tests {
     fn test_channel_type_from_i16() {
         assert_eq!(ChannelType::from(0), ChannelType::GuildText);
         assert_eq!(ChannelType::from(1), ChannelType::Dm);
+        assert_eq!(ChannelType::from(3), ChannelType::GroupDm);
         assert_eq!(ChannelType::from(4), ChannelType::GuildCategory);
+        assert_eq!(ChannelType::from(11), ChannelType::GuildThread);
         assert_eq!(ChannelType::from(99), ChannelType::GuildText); // Unknown defaults to text
     }
 
@@ -203,6 +289,22 @@ mod tests {
         assert_eq!(channel.display_name(), "Direct Message");
     }
 
+    #[test]
+    fn test_group_dm_channel() {
+        let mut channel = Channel::new_group_dm(Snowflake::new(1), Snowflake::new(10), None);
+        assert!(channel.is_text());
+        assert!(channel.is_group_dm());
+        assert!(!channel.is_dm());
+        assert!(!channel.is_guild_channel());
+        assert_eq!(channel.owner_id, Some(Snowflake::new(10)));
+        assert_eq!(channel.display_name(), "Group DM");
+
+        channel.set_owner(Snowflake::new(20));
+        channel.set_name("weekend plans".to_string());
+        assert_eq!(channel.owner_id, Some(Snowflake::new(20)));
+        assert_eq!(channel.display_name(), "weekend plans");
+    }
+
     #[test]
     fn test_category_channel() {
         let channel = Channel::new_category(
@@ -214,4 +316,18 @@ mod tests {
         assert!(!channel.is_text());
         assert!(channel.is_guild_channel());
     }
+
+    #[test]
+    fn test_thread_channel() {
+        let channel = Channel::new_thread(
+            Snowflake::new(1),
+            Snowflake::new(100),
+            Snowflake::new(10),
+            "release-discussion".to_string(),
+        );
+        assert!(channel.is_thread());
+        assert!(channel.is_text());
+        assert!(!channel.is_dm());
+        assert_eq!(channel.parent_id, Some(Snowflake::new(10)));
+    }
 }
@@ -4,6 +4,36 @@ use chrono::{DateTime, Utc};
 
 use crate::value_objects::Snowflake;
 
+/// Two-factor requirement for moderation in a guild
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum MfaLevel {
+    /// No requirement
+    #[default]
+    None = 0,
+    /// Moderators need two-factor authentication for destructive actions
+    Elevated = 1,
+}
+
+impl MfaLevel {
+    /// Get the numeric value
+    #[inline]
+    #[must_use]
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+
+    /// Parse from numeric value
+    #[must_use]
+    pub fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Elevated),
+            _ => None,
+        }
+    }
+}
+
 /// Guild (server) entity
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Guild {
@@ -12,6 +42,8 @@ pub struct Guild {
     pub icon: Option<String>,
     pub description: Option<String>,
     pub owner_id: Snowflake,
+    /// Two-factor requirement for moderators
+    pub mfa_level: MfaLevel,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -26,6 +58,7 @@ impl Guild {
             icon: None,
             description: None,
             owner_id,
+            mfa_level: MfaLevel::None,
             created_at: now,
             updated_at: now,
         }
@@ -37,6 +70,12 @@ impl Guild {
         self.owner_id == user_id
     }
 
+    /// Check if moderators need two-factor authentication for destructive actions
+    #[inline]
+    pub fn requires_mfa(&self) -> bool {
+        self.mfa_level == MfaLevel::Elevated
+    }
+
     /// Get the guild icon URL if set
     pub fn icon_url(&self) -> Option<String> {
         self.icon
@@ -111,4 +150,19 @@ mod tests {
         assert!(!guild.is_owner(Snowflake::new(100)));
         assert!(guild.is_owner(Snowflake::new(200)));
     }
+
+    #[test]
+    fn test_mfa_level() {
+        let mut guild = Guild::new(
+            Snowflake::new(1),
+            "Test".to_string(),
+            Snowflake::new(100),
+        );
+        assert!(!guild.requires_mfa());
+
+        guild.mfa_level = MfaLevel::from_i16(1).unwrap();
+        assert!(guild.requires_mfa());
+        assert_eq!(guild.mfa_level.as_i16(), 1);
+        assert!(MfaLevel::from_i16(2).is_none());
+    }
 }
@@ -2,7 +2,34 @@
 
 use chrono::{DateTime, Utc};
 
-use crate::value_objects::Snowflake;
+use crate::value_objects::{MessageMentions, Snowflake};
+
+/// Message type enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum MessageType {
+    /// Regular user message
+    #[default]
+    Default = 0,
+    /// System notice that a message was pinned
+    ChannelPinnedMessage = 6,
+}
+
+impl MessageType {
+    /// Get the numeric value
+    #[inline]
+    #[must_use]
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+
+    /// Check if this is a system message type
+    #[inline]
+    #[must_use]
+    pub fn is_system(self) -> bool {
+        self != Self::Default
+    }
+}
 
 /// Message entity
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,9 +38,12 @@ pub struct Message {
     pub channel_id: Snowflake,
     pub author_id: Snowflake,
     pub content: String,
+    pub message_type: MessageType,
     pub created_at: DateTime<Utc>,
     pub edited_at: Option<DateTime<Utc>>,
     pub reference_id: Option<Snowflake>,
+    /// Mentions resolved when the message was sent or last edited
+    pub mentions: MessageMentions,
 }
 
 impl Message {
@@ -29,9 +59,11 @@ impl Message {
             channel_id,
             author_id,
             content,
+            message_type: MessageType::Default,
             created_at: Utc::now(),
             edited_at: None,
             reference_id: None,
+            mentions: MessageMentions::default(),
         }
     }
 
@@ -48,12 +80,40 @@ impl Message {
             channel_id,
             author_id,
             content,
+            message_type: MessageType::Default,
             created_at: Utc::now(),
             edited_at: None,
             reference_id: Some(reference_id),
+            mentions: MessageMentions::default(),
         }
     }
 
+    /// Create a system notice that a message was pinned
+    pub fn new_pin_notice(
+        id: Snowflake,
+        channel_id: Snowflake,
+        author_id: Snowflake,
+        pinned_message_id: Snowflake,
+    ) -> Self {
+        Self {
+            id,
+            channel_id,
+            author_id,
+            content: String::new(),
+            message_type: MessageType::ChannelPinnedMessage,
+            created_at: Utc::now(),
+            edited_at: None,
+            reference_id: Some(pinned_message_id),
+            mentions: MessageMentions::default(),
+        }
+    }
+
+    /// Check if message was generated by the system
+    #[inline]
+    pub fn is_system(&self) -> bool {
+        self.message_type.is_system()
+    }
+
     /// Check if message has been edited
     #[inline]
     pub fn is_edited(&self) -> bool {
@@ -104,6 +164,8 @@ pub struct Attachment {
     pub proxy_url: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    /// Whether the file contents were identified as a raster image
+    pub verified_image: bool,
 }
 
 impl Attachment {
@@ -126,6 +188,7 @@ impl Attachment {
             proxy_url: None,
             width: None,
             height: None,
+            verified_image: false,
         }
     }
 
@@ -181,6 +244,19 @@ mod tests {
         assert_eq!(msg.reference_id, Some(Snowflake::new(1)));
     }
 
+    #[test]
+    fn test_pin_notice() {
+        let msg = Message::new_pin_notice(
+            Snowflake::new(3),
+            Snowflake::new(100),
+            Snowflake::new(200),
+            Snowflake::new(1),
+        );
+        assert!(msg.is_system());
+        assert_eq!(msg.message_type.as_i16(), 6);
+        assert_eq!(msg.reference_id, Some(Snowflake::new(1)));
+    }
+
     #[test]
     fn test_message_edit() {
         let mut msg = Message::new(
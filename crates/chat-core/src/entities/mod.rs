@@ -1,19 +1,29 @@
 //! Domain entities - core business objects
 
+mod audit_log;
 mod channel;
 mod guild;
 mod invite;
 mod member;
 mod message;
+mod permission_overwrite;
 mod reaction;
+mod relationship;
 mod role;
+mod thread;
 mod user;
 
+pub use audit_log::{AuditLogAction, AuditLogChange, AuditLogEntry};
 pub use channel::{Channel, ChannelType};
-pub use guild::Guild;
+pub use guild::{Guild, MfaLevel};
 pub use invite::{generate_invite_code, Invite};
 pub use member::GuildMember;
-pub use message::{Attachment, Message};
+pub use message::{Attachment, Message, MessageType};
+pub use permission_overwrite::{OverwriteType, PermissionOverwrite};
 pub use reaction::{Reaction, ReactionCount};
+pub use relationship::{Relationship, RelationshipType};
 pub use role::Role;
-pub use user::User;
+pub use thread::{
+    Thread, ThreadMember, ThreadMetadata, AUTO_ARCHIVE_DURATIONS, DEFAULT_AUTO_ARCHIVE_DURATION,
+};
+pub use user::{DmPrivacy, User};
@@ -0,0 +1,297 @@
+//! Permission overwrite entity - per-channel allow/deny rules for a role or member
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::value_objects::{Permissions, Snowflake};
+
+/// Permission overwrite target type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum OverwriteType {
+    /// Overwrite applies to everyone holding a role
+    Role = 0,
+    /// Overwrite applies to a single member
+    Member = 1,
+}
+
+impl OverwriteType {
+    /// Get the numeric value
+    #[inline]
+    #[must_use]
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+
+    /// Parse from numeric value
+    #[must_use]
+    pub fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            0 => Some(Self::Role),
+            1 => Some(Self::Member),
+            _ => None,
+        }
+    }
+}
+
+/// Permission overwrite entity
+///
+/// Overwrites are keyed by `(channel_id, target_id)`. For role overwrites the
+/// target is a role ID (including the guild's @everyone role), for
+/// member overwrites it is a user ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionOverwrite {
+    pub channel_id: Snowflake,
+    pub target_id: Snowflake,
+    pub overwrite_type: OverwriteType,
+    pub allow: Permissions,
+    pub deny: Permissions,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PermissionOverwrite {
+    /// Create a new permission overwrite
+    #[must_use]
+    pub fn new(
+        channel_id: Snowflake,
+        target_id: Snowflake,
+        overwrite_type: OverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            channel_id,
+            target_id,
+            overwrite_type,
+            allow,
+            deny,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check if this overwrite targets a role
+    #[inline]
+    #[must_use]
+    pub fn is_role(&self) -> bool {
+        self.overwrite_type == OverwriteType::Role
+    }
+
+    /// Check if this overwrite targets a member
+    #[inline]
+    #[must_use]
+    pub fn is_member(&self) -> bool {
+        self.overwrite_type == OverwriteType::Member
+    }
+
+    /// Update allowed and denied permissions
+    pub fn set_permissions(&mut self, allow: Permissions, deny: Permissions) {
+        self.allow = allow;
+        self.deny = deny;
+        self.updated_at = Utc::now();
+    }
+
+    /// Apply this overwrite on top of a permission set
+    #[inline]
+    #[must_use]
+    pub fn apply(&self, permissions: Permissions) -> Permissions {
+        (permissions & !self.deny) | self.allow
+    }
+
+    /// Resolve effective channel permissions from guild-level permissions
+    ///
+    /// Resolution order:
+    /// 1. Administrators keep all permissions
+    /// 2. The overwrite for the guild's @everyone role (`everyone_id`) is applied
+    /// 3. All role overwrites for the member's roles are merged, then applied
+    /// 4. The member-specific overwrite is applied
+    ///
+    /// A channel that cannot be viewed grants nothing else.
+    #[must_use]
+    pub fn resolve(
+        base: Permissions,
+        overwrites: &[PermissionOverwrite],
+        everyone_id: Option<Snowflake>,
+        role_ids: &[Snowflake],
+        user_id: Snowflake,
+    ) -> Permissions {
+        if base.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::ALL;
+        }
+
+        let mut permissions = base;
+
+        if let Some(everyone) = overwrites
+            .iter()
+            .find(|o| o.is_role() && Some(o.target_id) == everyone_id)
+        {
+            permissions = everyone.apply(permissions);
+        }
+
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|o| {
+                o.is_role() && Some(o.target_id) != everyone_id && role_ids.contains(&o.target_id)
+            })
+            .fold(
+                (Permissions::empty(), Permissions::empty()),
+                |(allow, deny), o| (allow | o.allow, deny | o.deny),
+            );
+        permissions = (permissions & !deny) | allow;
+
+        if let Some(member) = overwrites
+            .iter()
+            .find(|o| o.is_member() && o.target_id == user_id)
+        {
+            permissions = member.apply(permissions);
+        }
+
+        if !permissions.contains(Permissions::VIEW_CHANNEL) {
+            return Permissions::empty();
+        }
+
+        permissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVERYONE: Snowflake = Snowflake::new(100);
+    const USER: Snowflake = Snowflake::new(200);
+    const ROLE: Snowflake = Snowflake::new(300);
+
+    fn overwrite(
+        target: Snowflake,
+        kind: OverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite::new(Snowflake::new(1), target, kind, allow, deny)
+    }
+
+    #[test]
+    fn test_overwrite_type_from_i16() {
+        assert_eq!(OverwriteType::from_i16(0), Some(OverwriteType::Role));
+        assert_eq!(OverwriteType::from_i16(1), Some(OverwriteType::Member));
+        assert_eq!(OverwriteType::from_i16(2), None);
+    }
+
+    #[test]
+    fn test_no_overwrites_keeps_base() {
+        let perms =
+            PermissionOverwrite::resolve(Permissions::DEFAULT, &[], Some(EVERYONE), &[ROLE], USER);
+        assert_eq!(perms, Permissions::DEFAULT);
+    }
+
+    #[test]
+    fn test_everyone_deny_hides_channel() {
+        let overwrites = [overwrite(
+            EVERYONE,
+            OverwriteType::Role,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        let perms = PermissionOverwrite::resolve(
+            Permissions::DEFAULT,
+            &overwrites,
+            Some(EVERYONE),
+            &[],
+            USER,
+        );
+        assert!(perms.is_empty());
+    }
+
+    #[test]
+    fn test_role_allow_overrides_everyone_deny() {
+        let overwrites = [
+            overwrite(
+                EVERYONE,
+                OverwriteType::Role,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            ),
+            overwrite(
+                ROLE,
+                OverwriteType::Role,
+                Permissions::VIEW_CHANNEL,
+                Permissions::empty(),
+            ),
+        ];
+        let perms = PermissionOverwrite::resolve(
+            Permissions::DEFAULT,
+            &overwrites,
+            Some(EVERYONE),
+            &[ROLE],
+            USER,
+        );
+        assert!(perms.has(Permissions::VIEW_CHANNEL));
+        assert!(perms.has(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_read_only_channel() {
+        let overwrites = [overwrite(
+            EVERYONE,
+            OverwriteType::Role,
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        )];
+        let perms = PermissionOverwrite::resolve(
+            Permissions::DEFAULT,
+            &overwrites,
+            Some(EVERYONE),
+            &[],
+            USER,
+        );
+        assert!(perms.has(Permissions::VIEW_CHANNEL));
+        assert!(!perms.has(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_member_overwrite_applied_last() {
+        let overwrites = [
+            overwrite(
+                ROLE,
+                OverwriteType::Role,
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                USER,
+                OverwriteType::Member,
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            ),
+        ];
+        let perms = PermissionOverwrite::resolve(
+            Permissions::DEFAULT,
+            &overwrites,
+            Some(EVERYONE),
+            &[ROLE],
+            USER,
+        );
+        assert!(perms.has(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_administrator_ignores_overwrites() {
+        let overwrites = [overwrite(
+            USER,
+            OverwriteType::Member,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        let perms = PermissionOverwrite::resolve(
+            Permissions::ADMINISTRATOR,
+            &overwrites,
+            Some(EVERYONE),
+            &[],
+            USER,
+        );
+        assert!(perms.has(Permissions::VIEW_CHANNEL));
+    }
+}
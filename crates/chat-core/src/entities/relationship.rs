@@ -0,0 +1,125 @@
+//! Relationship entity - a user's friendship, pending friend request, or block
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::value_objects::Snowflake;
+
+/// Relationship type, from the owning user's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum RelationshipType {
+    /// Friend request accepted by both users
+    Friend = 1,
+    /// Target is blocked by the user
+    Blocked = 2,
+    /// Friend request received from the target
+    IncomingRequest = 3,
+    /// Friend request sent to the target
+    OutgoingRequest = 4,
+}
+
+impl RelationshipType {
+    /// Get the numeric value
+    #[inline]
+    #[must_use]
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+
+    /// Parse from numeric value
+    #[must_use]
+    pub fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            1 => Some(Self::Friend),
+            2 => Some(Self::Blocked),
+            3 => Some(Self::IncomingRequest),
+            4 => Some(Self::OutgoingRequest),
+            _ => None,
+        }
+    }
+}
+
+/// Relationship entity
+///
+/// Relationships are keyed by `(user_id, target_id)` and stored once per
+/// side: a friendship or pending request has a row for each user, while a
+/// block only has the blocking user's row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relationship {
+    pub user_id: Snowflake,
+    pub target_id: Snowflake,
+    pub relationship_type: RelationshipType,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Relationship {
+    /// Create a new relationship
+    #[must_use]
+    pub fn new(user_id: Snowflake, target_id: Snowflake, relationship_type: RelationshipType) -> Self {
+        Self {
+            user_id,
+            target_id,
+            relationship_type,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Check if the users are friends
+    #[inline]
+    #[must_use]
+    pub fn is_friend(&self) -> bool {
+        self.relationship_type == RelationshipType::Friend
+    }
+
+    /// Check if the user blocked the target
+    #[inline]
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.relationship_type == RelationshipType::Blocked
+    }
+
+    /// Check if this is a pending friend request in either direction
+    #[inline]
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self.relationship_type,
+            RelationshipType::IncomingRequest | RelationshipType::OutgoingRequest
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relationship_type_from_i16() {
+        assert_eq!(RelationshipType::from_i16(1), Some(RelationshipType::Friend));
+        assert_eq!(RelationshipType::from_i16(2), Some(RelationshipType::Blocked));
+        assert_eq!(RelationshipType::from_i16(3), Some(RelationshipType::IncomingRequest));
+        assert_eq!(RelationshipType::from_i16(4), Some(RelationshipType::OutgoingRequest));
+        assert_eq!(RelationshipType::from_i16(0), None);
+        assert_eq!(RelationshipType::OutgoingRequest.as_i16(), 4);
+    }
+
+    #[test]
+    fn test_relationship_predicates() {
+        let friend = Relationship::new(Snowflake::new(1), Snowflake::new(2), RelationshipType::Friend);
+        assert!(friend.is_friend());
+        assert!(!friend.is_blocked());
+        assert!(!friend.is_pending());
+
+        let request = Relationship::new(
+            Snowflake::new(1),
+            Snowflake::new(2),
+            RelationshipType::IncomingRequest,
+        );
+        assert!(request.is_pending());
+
+        let block = Relationship::new(Snowflake::new(1), Snowflake::new(2), RelationshipType::Blocked);
+        assert!(block.is_blocked());
+        assert!(!block.is_pending());
+    }
+}
@@ -0,0 +1,193 @@
+//! Thread entity - a channel branching from a message in a guild text channel
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::channel::Channel;
+use crate::value_objects::Snowflake;
+
+/// Allowed auto-archive durations in minutes (1 hour, 1 day, 3 days, 1 week)
+pub const AUTO_ARCHIVE_DURATIONS: [i32; 4] = [60, 1440, 4320, 10080];
+
+/// Default auto-archive duration in minutes
+pub const DEFAULT_AUTO_ARCHIVE_DURATION: i32 = 1440;
+
+/// Thread-specific state stored alongside the thread channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadMetadata {
+    /// User who started the thread
+    pub owner_id: Snowflake,
+    /// Message the thread was started from
+    pub message_id: Snowflake,
+    pub archived: bool,
+    /// Locked threads can only be unarchived by moderators
+    pub locked: bool,
+    /// Minutes of inactivity before the thread is archived
+    pub auto_archive_duration: i32,
+    /// When the archived state last changed
+    pub archive_timestamp: Option<DateTime<Utc>>,
+    /// Time of the last message (or creation)
+    pub last_activity_at: DateTime<Utc>,
+}
+
+impl ThreadMetadata {
+    /// Check if an auto-archive duration is one of the allowed values
+    #[inline]
+    #[must_use]
+    pub fn is_valid_auto_archive_duration(minutes: i32) -> bool {
+        AUTO_ARCHIVE_DURATIONS.contains(&minutes)
+    }
+
+    /// Check if the thread has been inactive longer than its auto-archive duration
+    #[must_use]
+    pub fn is_inactive(&self, now: DateTime<Utc>) -> bool {
+        !self.archived
+            && now - self.last_activity_at >= Duration::minutes(i64::from(self.auto_archive_duration))
+    }
+
+    /// Archive or unarchive the thread
+    pub fn set_archived(&mut self, archived: bool) {
+        if self.archived != archived {
+            self.archived = archived;
+            self.archive_timestamp = Some(Utc::now());
+        }
+    }
+
+    /// Record activity, resetting the auto-archive timer
+    pub fn touch(&mut self) {
+        self.last_activity_at = Utc::now();
+    }
+}
+
+/// Thread entity (thread channel plus its metadata)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thread {
+    pub channel: Channel,
+    pub metadata: ThreadMetadata,
+    /// Number of users who joined the thread
+    pub member_count: i32,
+}
+
+impl Thread {
+    /// Create a new thread started from a message
+    #[must_use]
+    pub fn new(
+        id: Snowflake,
+        guild_id: Snowflake,
+        parent_id: Snowflake,
+        message_id: Snowflake,
+        owner_id: Snowflake,
+        name: String,
+        auto_archive_duration: i32,
+    ) -> Self {
+        let channel = Channel::new_thread(id, guild_id, parent_id, name);
+        let last_activity_at = channel.created_at;
+        Self {
+            channel,
+            metadata: ThreadMetadata {
+                owner_id,
+                message_id,
+                archived: false,
+                locked: false,
+                auto_archive_duration,
+                archive_timestamp: None,
+                last_activity_at,
+            },
+            member_count: 0,
+        }
+    }
+
+    /// Thread (channel) ID
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> Snowflake {
+        self.channel.id
+    }
+
+    /// Parent text channel ID
+    #[inline]
+    #[must_use]
+    pub fn parent_id(&self) -> Option<Snowflake> {
+        self.channel.parent_id
+    }
+
+    /// Check if a user started this thread
+    #[inline]
+    #[must_use]
+    pub fn is_owner(&self, user_id: Snowflake) -> bool {
+        self.metadata.owner_id == user_id
+    }
+}
+
+/// A user who joined a thread
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadMember {
+    pub thread_id: Snowflake,
+    pub user_id: Snowflake,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl ThreadMember {
+    /// Create a new thread member
+    #[must_use]
+    pub fn new(thread_id: Snowflake, user_id: Snowflake) -> Self {
+        Self {
+            thread_id,
+            user_id,
+            joined_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread() -> Thread {
+        Thread::new(
+            Snowflake::new(1),
+            Snowflake::new(100),
+            Snowflake::new(10),
+            Snowflake::new(5),
+            Snowflake::new(42),
+            "release".to_string(),
+            60,
+        )
+    }
+
+    #[test]
+    fn test_new_thread() {
+        let thread = thread();
+        assert!(thread.channel.is_thread());
+        assert_eq!(thread.parent_id(), Some(Snowflake::new(10)));
+        assert!(thread.is_owner(Snowflake::new(42)));
+        assert!(!thread.metadata.archived);
+        assert!(!thread.metadata.locked);
+    }
+
+    #[test]
+    fn test_auto_archive_duration_validation() {
+        assert!(ThreadMetadata::is_valid_auto_archive_duration(60));
+        assert!(ThreadMetadata::is_valid_auto_archive_duration(10080));
+        assert!(!ThreadMetadata::is_valid_auto_archive_duration(0));
+        assert!(!ThreadMetadata::is_valid_auto_archive_duration(30));
+    }
+
+    #[test]
+    fn test_is_inactive() {
+        let mut thread = thread();
+        let now = thread.metadata.last_activity_at;
+        assert!(!thread.metadata.is_inactive(now + Duration::minutes(59)));
+        assert!(thread.metadata.is_inactive(now + Duration::minutes(60)));
+
+        thread.metadata.set_archived(true);
+        assert!(!thread.metadata.is_inactive(now + Duration::minutes(60)));
+    }
+
+    #[test]
+    fn test_set_archived() {
+        let mut thread = thread();
+        thread.metadata.set_archived(true);
+        assert!(thread.metadata.archived);
+        assert!(thread.metadata.archive_timestamp.is_some());
+    }
+}
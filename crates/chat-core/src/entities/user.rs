@@ -4,6 +4,39 @@ use chrono::{DateTime, Utc};
 
 use crate::value_objects::Snowflake;
 
+/// Who may open a DM with the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum DmPrivacy {
+    /// Anyone who is not blocked
+    #[default]
+    Everyone = 0,
+    /// Friends and members of a shared guild
+    FriendsAndGuildMates = 1,
+    /// Friends only
+    Friends = 2,
+}
+
+impl DmPrivacy {
+    /// Get the numeric value
+    #[inline]
+    #[must_use]
+    pub fn as_i16(self) -> i16 {
+        self as i16
+    }
+
+    /// Parse from numeric value
+    #[must_use]
+    pub fn from_i16(value: i16) -> Option<Self> {
+        match value {
+            0 => Some(Self::Everyone),
+            1 => Some(Self::FriendsAndGuildMates),
+            2 => Some(Self::Friends),
+            _ => None,
+        }
+    }
+}
+
 /// User entity representing a Discord-like user account
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct User {
@@ -14,6 +47,14 @@ pub struct User {
     pub avatar: Option<String>,
     pub bot: bool,
     pub system: bool,
+    /// Bot opted in to privileged gateway intents (members, presences)
+    pub privileged_intents: bool,
+    /// Who may open a DM with the user
+    pub dm_privacy: DmPrivacy,
+    /// User confirmed ownership of `email`
+    pub verified: bool,
+    /// User enrolled in TOTP two-factor authentication
+    pub mfa_enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -35,6 +76,10 @@ impl User {
             avatar: None,
             bot: false,
             system: false,
+            privileged_intents: false,
+            dm_privacy: DmPrivacy::Everyone,
+            verified: false,
+            mfa_enabled: false,
             created_at: now,
             updated_at: now,
         }
@@ -70,6 +115,14 @@ impl User {
         self.system
     }
 
+    /// Check if the account may request privileged gateway intents
+    ///
+    /// Human accounts always may; bot accounts need the opt-in flag.
+    #[inline]
+    pub fn can_use_privileged_intents(&self) -> bool {
+        !self.bot || self.privileged_intents
+    }
+
     /// Update the username
     pub fn set_username(&mut self, username: String) {
         self.username = username;
@@ -81,12 +134,27 @@ impl User {
         self.avatar = avatar;
         self.updated_at = Utc::now();
     }
+
+    /// Update the DM privacy setting
+    pub fn set_dm_privacy(&mut self, dm_privacy: DmPrivacy) {
+        self.dm_privacy = dm_privacy;
+        self.updated_at = Utc::now();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dm_privacy_from_i16() {
+        assert_eq!(DmPrivacy::from_i16(0), Some(DmPrivacy::Everyone));
+        assert_eq!(DmPrivacy::from_i16(1), Some(DmPrivacy::FriendsAndGuildMates));
+        assert_eq!(DmPrivacy::from_i16(2), Some(DmPrivacy::Friends));
+        assert_eq!(DmPrivacy::from_i16(3), None);
+        assert_eq!(DmPrivacy::default(), DmPrivacy::Everyone);
+    }
+
     #[test]
     fn test_user_tag() {
         let user = User::new(
@@ -134,4 +202,21 @@ mod tests {
             assert_eq!(user.default_avatar_index(), i as u8);
         }
     }
+
+    #[test]
+    fn test_can_use_privileged_intents() {
+        let mut user = User::new(
+            Snowflake::new(1),
+            "test".to_string(),
+            "0001".to_string(),
+            "test@example.com".to_string(),
+        );
+        assert!(user.can_use_privileged_intents());
+
+        user.bot = true;
+        assert!(!user.can_use_privileged_intents());
+
+        user.privileged_intents = true;
+        assert!(user.can_use_privileged_intents());
+    }
 }
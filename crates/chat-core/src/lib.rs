@@ -11,14 +11,21 @@ pub mod value_objects;
 
 // Re-export commonly used types at crate root
 pub use entities::{
-    Attachment, Channel, ChannelType, Guild, GuildMember, Invite, Message, Reaction,
-    ReactionCount, Role, User, generate_invite_code,
+    Attachment, AuditLogAction, AuditLogChange, AuditLogEntry, Channel, ChannelType, DmPrivacy,
+    Guild, GuildMember, Invite, Message, MessageType, MfaLevel, OverwriteType, PermissionOverwrite,
+    Reaction, ReactionCount, Relationship, RelationshipType, Role, Thread, ThreadMember,
+    ThreadMetadata, User, generate_invite_code,
 };
 pub use error::DomainError;
 pub use events::DomainEvent;
 pub use traits::{
-    AttachmentRepository, Ban, BanRepository, ChannelRepository, GuildRepository, InviteRepository,
-    MemberRepository, MessageQuery, MessageRepository, ReactionRepository, RepoResult,
-    RoleRepository, UserRepository,
+    AttachmentRepository, AuditLogQuery, AuditLogRepository, Ban, BanRepository,
+    ChannelRepository, GuildRepository, InviteRepository, MemberRepository, MessageQuery,
+    MessageRepository, MessageRevision, MessageRevisionRepository, MessageSearchQuery,
+    PermissionOverwriteRepository, Pin, PinRepository, ReactionRepository, ReadState,
+    ReadStateRepository, RelationshipRepository, RepoResult, RoleRepository, ThreadRepository,
+    UserRepository,
+};
+pub use value_objects::{
+    MessageMentions, Permissions, Snowflake, SnowflakeGenerator, SnowflakeParseError, MAX_MENTIONS,
 };
-pub use value_objects::{Permissions, Snowflake, SnowflakeGenerator, SnowflakeParseError};
@@ -5,9 +5,11 @@
 //! provides the implementation.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use crate::entities::{
-    Attachment, Channel, Guild, GuildMember, Invite, Message, Reaction, Role, User,
+    Attachment, AuditLogAction, AuditLogEntry, Channel, Guild, GuildMember, Invite, Message,
+    PermissionOverwrite, Reaction, Relationship, Role, Thread, ThreadMember, ThreadMetadata, User,
 };
 use crate::error::DomainError;
 use crate::value_objects::Snowflake;
@@ -24,6 +26,9 @@ pub trait UserRepository: Send + Sync {
     /// Find user by ID
     async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<User>>;
 
+    /// Find several users by ID, skipping deleted or unknown ones
+    async fn find_by_ids(&self, ids: &[Snowflake]) -> RepoResult<Vec<User>>;
+
     /// Find user by email
     async fn find_by_email(&self, email: &str) -> RepoResult<Option<User>>;
 
@@ -48,6 +53,37 @@ pub trait UserRepository: Send + Sync {
     /// Update password hash
     async fn update_password(&self, id: Snowflake, password_hash: &str) -> RepoResult<()>;
 
+    /// Mark the user's email as verified, provided it is still `email`
+    ///
+    /// Returns false if the user no longer exists or changed their email.
+    async fn mark_verified(&self, id: Snowflake, email: &str) -> RepoResult<bool>;
+
+    /// Get the TOTP secret of a user with two-factor authentication enabled
+    async fn get_totp_secret(&self, id: Snowflake) -> RepoResult<Option<String>>;
+
+    /// Enable two-factor authentication with the given secret and backup code hashes
+    async fn enable_mfa(
+        &self,
+        id: Snowflake,
+        totp_secret: &str,
+        backup_code_hashes: &[String],
+    ) -> RepoResult<()>;
+
+    /// Disable two-factor authentication and drop all backup codes
+    async fn disable_mfa(&self, id: Snowflake) -> RepoResult<()>;
+
+    /// Replace all backup codes of a user
+    async fn replace_backup_codes(
+        &self,
+        id: Snowflake,
+        backup_code_hashes: &[String],
+    ) -> RepoResult<()>;
+
+    /// Use up a backup code
+    ///
+    /// Returns false if the user has no unused backup code with this hash.
+    async fn consume_backup_code(&self, id: Snowflake, code_hash: &str) -> RepoResult<bool>;
+
     /// Generate next available discriminator for username
     async fn next_discriminator(&self, username: &str) -> RepoResult<String>;
 }
@@ -86,13 +122,13 @@ pub trait ChannelRepository: Send + Sync {
     /// Find channel by ID
     async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Channel>>;
 
-    /// List all channels in a guild
+    /// List all channels in a guild (excluding threads)
     async fn find_by_guild(&self, guild_id: Snowflake) -> RepoResult<Vec<Channel>>;
 
     /// Find DM channel between two users
     async fn find_dm(&self, user1_id: Snowflake, user2_id: Snowflake) -> RepoResult<Option<Channel>>;
 
-    /// List all DM channels for a user
+    /// List all DM and group DM channels for a user
     async fn find_dms_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<Channel>>;
 
     /// Create a new channel
@@ -107,10 +143,108 @@ pub trait ChannelRepository: Send + Sync {
     /// Add user to a DM channel
     async fn add_dm_recipient(&self, channel_id: Snowflake, user_id: Snowflake) -> RepoResult<()>;
 
-    /// Get DM recipients
+    /// Remove user from a DM channel
+    async fn remove_dm_recipient(&self, channel_id: Snowflake, user_id: Snowflake) -> RepoResult<()>;
+
+    /// Get DM recipients in the order they joined
     async fn get_dm_recipients(&self, channel_id: Snowflake) -> RepoResult<Vec<Snowflake>>;
 }
 
+// ============================================================================
+// Thread Repository
+// ============================================================================
+
+#[async_trait]
+pub trait ThreadRepository: Send + Sync {
+    /// Find thread by ID
+    async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Thread>>;
+
+    /// Find the thread started from a message
+    async fn find_by_message(&self, message_id: Snowflake) -> RepoResult<Option<Thread>>;
+
+    /// List unarchived threads in a channel, newest first
+    async fn find_active_by_parent(&self, parent_id: Snowflake) -> RepoResult<Vec<Thread>>;
+
+    /// List archived threads in a channel, most recently archived first
+    async fn find_archived_by_parent(
+        &self,
+        parent_id: Snowflake,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> RepoResult<Vec<Thread>>;
+
+    /// Create the thread channel and its metadata
+    async fn create(&self, thread: &Thread) -> RepoResult<()>;
+
+    /// Update thread metadata (archive state, lock, activity)
+    async fn update_metadata(&self, id: Snowflake, metadata: &ThreadMetadata) -> RepoResult<()>;
+
+    /// Archive active threads in a channel whose auto-archive duration has elapsed
+    ///
+    /// Returns the archived threads.
+    async fn archive_inactive(&self, parent_id: Snowflake) -> RepoResult<Vec<Thread>>;
+
+    /// List members of a thread
+    async fn find_members(&self, thread_id: Snowflake) -> RepoResult<Vec<ThreadMember>>;
+
+    /// Check if user joined a thread
+    async fn is_member(&self, thread_id: Snowflake, user_id: Snowflake) -> RepoResult<bool>;
+
+    /// Add a member to a thread, returning false if already a member
+    async fn add_member(&self, member: &ThreadMember) -> RepoResult<bool>;
+
+    /// Remove a member from a thread, returning false if not a member
+    async fn remove_member(&self, thread_id: Snowflake, user_id: Snowflake) -> RepoResult<bool>;
+}
+
+// ============================================================================
+// Permission Overwrite Repository
+// ============================================================================
+
+#[async_trait]
+pub trait PermissionOverwriteRepository: Send + Sync {
+    /// List all overwrites for a channel
+    async fn find_by_channel(&self, channel_id: Snowflake) -> RepoResult<Vec<PermissionOverwrite>>;
+
+    /// List all overwrites for several channels
+    async fn find_by_channels(
+        &self,
+        channel_ids: &[Snowflake],
+    ) -> RepoResult<Vec<PermissionOverwrite>>;
+
+    /// Find the overwrite for a role or member in a channel
+    async fn find(
+        &self,
+        channel_id: Snowflake,
+        target_id: Snowflake,
+    ) -> RepoResult<Option<PermissionOverwrite>>;
+
+    /// Create or replace an overwrite
+    async fn upsert(&self, overwrite: &PermissionOverwrite) -> RepoResult<()>;
+
+    /// Remove an overwrite
+    async fn delete(&self, channel_id: Snowflake, target_id: Snowflake) -> RepoResult<()>;
+}
+
+// ============================================================================
+// Relationship Repository
+// ============================================================================
+
+#[async_trait]
+pub trait RelationshipRepository: Send + Sync {
+    /// Find the user's relationship with a target
+    async fn find(&self, user_id: Snowflake, target_id: Snowflake) -> RepoResult<Option<Relationship>>;
+
+    /// List all of a user's relationships, oldest first
+    async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<Relationship>>;
+
+    /// Create or replace a relationship
+    async fn upsert(&self, relationship: &Relationship) -> RepoResult<()>;
+
+    /// Remove a relationship, returning false if there was none
+    async fn delete(&self, user_id: Snowflake, target_id: Snowflake) -> RepoResult<bool>;
+}
+
 // ============================================================================
 // Message Repository
 // ============================================================================
@@ -123,6 +257,26 @@ pub struct MessageQuery {
     pub limit: i64,
 }
 
+/// Filters for full-text message search
+#[derive(Debug, Clone, Default)]
+pub struct MessageSearchQuery {
+    /// Full-text query (web search syntax: quoted phrases, `or`, `-term`)
+    pub content: Option<String>,
+    /// Only messages by this author
+    pub author_id: Option<Snowflake>,
+    /// Only messages mentioning this user
+    pub mentions: Option<Snowflake>,
+    /// Only messages with (`true`) or without (`false`) attachments
+    pub has_attachment: Option<bool>,
+    /// Only messages created at or after this time
+    pub min_created_at: Option<DateTime<Utc>>,
+    /// Only messages created before this time
+    pub max_created_at: Option<DateTime<Utc>>,
+    /// Number of matching messages to skip
+    pub offset: i64,
+    pub limit: i64,
+}
+
 #[async_trait]
 pub trait MessageRepository: Send + Sync {
     /// Find message by ID
@@ -136,18 +290,146 @@ pub trait MessageRepository: Send + Sync {
     async fn create(&self, message: &Message) -> RepoResult<()>;
 
     /// Update message content (edit)
-    async fn update(&self, message: &Message) -> RepoResult<()>;
+    ///
+    /// Saves the message's `edited_at` as given. The replaced content, if any,
+    /// is stored as a revision in the same transaction.
+    async fn update(&self, message: &Message, revision: Option<&MessageRevision>) -> RepoResult<()>;
 
-    /// Soft delete a message
+    /// Soft delete a message, purging its revisions
     async fn delete(&self, id: Snowflake) -> RepoResult<()>;
 
-    /// Bulk delete messages
+    /// Bulk delete messages, purging their revisions
     async fn bulk_delete(&self, channel_id: Snowflake, message_ids: &[Snowflake]) -> RepoResult<u64>;
 
+    /// Soft delete all messages by an author in a guild's channels created since a time,
+    /// purging their revisions
+    ///
+    /// Returns the deleted messages.
+    async fn delete_by_author_in_guild(
+        &self,
+        guild_id: Snowflake,
+        author_id: Snowflake,
+        since: DateTime<Utc>,
+    ) -> RepoResult<Vec<Message>>;
+
+    /// Search messages in the given channels, newest first
+    ///
+    /// Returns the total number of matches along with the requested page.
+    async fn search(
+        &self,
+        channel_ids: &[Snowflake],
+        query: &MessageSearchQuery,
+    ) -> RepoResult<(i64, Vec<Message>)>;
+
+    /// Load up to `limit` messages on each side of every anchor message
+    ///
+    /// Returns one `(before, after)` pair per anchor, in anchor order, with
+    /// both sides sorted oldest first.
+    async fn find_context(
+        &self,
+        anchors: &[Message],
+        limit: i64,
+    ) -> RepoResult<Vec<(Vec<Message>, Vec<Message>)>>;
+
     /// Get message with attachments
     async fn find_with_attachments(&self, id: Snowflake) -> RepoResult<Option<(Message, Vec<Attachment>)>>;
 }
 
+// ============================================================================
+// Message Revision Repository
+// ============================================================================
+
+/// Earlier version of an edited message
+#[derive(Debug, Clone)]
+pub struct MessageRevision {
+    pub id: Snowflake,
+    pub message_id: Snowflake,
+    /// Content before the edit
+    pub content: String,
+    /// When this content was written by an edit (None for the original content)
+    pub edited_at: Option<DateTime<Utc>>,
+    /// When this content was replaced
+    pub replaced_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait MessageRevisionRepository: Send + Sync {
+    /// List revisions of a message, oldest first
+    async fn find_by_message(&self, message_id: Snowflake) -> RepoResult<Vec<MessageRevision>>;
+}
+
+// ============================================================================
+// Pin Repository
+// ============================================================================
+
+/// Pinned message record
+#[derive(Debug, Clone)]
+pub struct Pin {
+    pub channel_id: Snowflake,
+    pub message_id: Snowflake,
+    /// User who pinned the message
+    pub pinned_by: Snowflake,
+    pub pinned_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait PinRepository: Send + Sync {
+    /// List pins in a channel, most recently pinned first
+    async fn find_by_channel(&self, channel_id: Snowflake) -> RepoResult<Vec<Pin>>;
+
+    /// Check if a message is pinned in a channel
+    async fn is_pinned(&self, channel_id: Snowflake, message_id: Snowflake) -> RepoResult<bool>;
+
+    /// Count pinned messages in a channel
+    async fn count(&self, channel_id: Snowflake) -> RepoResult<i64>;
+
+    /// Pin a message unless the channel already has `max_pins` pins
+    ///
+    /// Returns false if the message was already pinned. The cap is checked
+    /// while holding a lock on the channel, so concurrent pins cannot exceed it.
+    async fn create(&self, pin: &Pin, max_pins: i64) -> RepoResult<bool>;
+
+    /// Unpin a message
+    async fn delete(&self, channel_id: Snowflake, message_id: Snowflake) -> RepoResult<()>;
+
+    /// Time of the most recent pin in a channel
+    async fn last_pin_timestamp(&self, channel_id: Snowflake) -> RepoResult<Option<DateTime<Utc>>>;
+}
+
+// ============================================================================
+// Read State Repository
+// ============================================================================
+
+/// A user's read position in a channel
+#[derive(Debug, Clone)]
+pub struct ReadState {
+    pub user_id: Snowflake,
+    pub channel_id: Snowflake,
+    /// Last acknowledged message
+    pub last_message_id: Option<Snowflake>,
+    /// Mentions of the user since the last acknowledged message
+    pub mention_count: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait ReadStateRepository: Send + Sync {
+    /// List all read states of a user
+    async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<ReadState>>;
+
+    /// Mark a channel as read up to a message, clearing its mention count.
+    /// The read position never moves backwards. Returns the stored state.
+    async fn ack(
+        &self,
+        user_id: Snowflake,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+    ) -> RepoResult<ReadState>;
+
+    /// Add one mention to each user's read state in a channel
+    async fn increment_mentions(&self, channel_id: Snowflake, user_ids: &[Snowflake]) -> RepoResult<()>;
+}
+
 // ============================================================================
 // Attachment Repository
 // ============================================================================
@@ -207,12 +489,21 @@ pub trait MemberRepository: Send + Sync {
     /// List all members in a guild
     async fn find_by_guild(&self, guild_id: Snowflake, limit: i64, after: Option<Snowflake>) -> RepoResult<Vec<GuildMember>>;
 
+    /// Search members whose username or nickname starts with `query` (case-insensitive)
+    async fn search(&self, guild_id: Snowflake, query: &str, limit: i64) -> RepoResult<Vec<GuildMember>>;
+
+    /// Find the members among a list of user IDs
+    async fn find_many(&self, guild_id: Snowflake, user_ids: &[Snowflake]) -> RepoResult<Vec<GuildMember>>;
+
     /// List all guilds a user is a member of (as member records)
     async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<GuildMember>>;
 
     /// Check if user is a member of guild
     async fn is_member(&self, guild_id: Snowflake, user_id: Snowflake) -> RepoResult<bool>;
 
+    /// Check if two users are members of at least one common guild
+    async fn shares_guild(&self, user_id: Snowflake, other_id: Snowflake) -> RepoResult<bool>;
+
     /// Add member to guild
     async fn create(&self, member: &GuildMember) -> RepoResult<()>;
 
@@ -323,3 +614,29 @@ pub trait BanRepository: Send + Sync {
     /// Remove a ban
     async fn delete(&self, guild_id: Snowflake, user_id: Snowflake) -> RepoResult<()>;
 }
+
+// ============================================================================
+// Audit Log Repository
+// ============================================================================
+
+/// Filter and pagination options for audit log queries
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogQuery {
+    /// Only entries created by this user
+    pub user_id: Option<Snowflake>,
+    /// Only entries of this action type
+    pub action: Option<AuditLogAction>,
+    /// Only entries older than this ID
+    pub before: Option<Snowflake>,
+    pub limit: i64,
+}
+
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Record an audit log entry
+    async fn create(&self, entry: &AuditLogEntry) -> RepoResult<()>;
+
+    /// List audit log entries for a guild, newest first
+    async fn find_by_guild(&self, guild_id: Snowflake, query: AuditLogQuery)
+        -> RepoResult<Vec<AuditLogEntry>>;
+}
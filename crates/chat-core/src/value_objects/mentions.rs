@@ -0,0 +1,132 @@
+//! Mentions parsed from message content
+//!
+//! Recognizes `<@user_id>`, `<@!user_id>`, `<@&role_id>`, `<#channel_id>` and `@everyone`.
+
+use super::snowflake::Snowflake;
+
+/// Maximum number of distinct mentions of each kind kept from one message
+pub const MAX_MENTIONS: usize = 100;
+
+/// Literal that mentions every member who can see the channel
+const EVERYONE: &str = "@everyone";
+
+/// Mentions contained in a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageMentions {
+    /// Users mentioned with `<@id>` or `<@!id>`
+    pub users: Vec<Snowflake>,
+    /// Roles mentioned with `<@&id>`
+    pub roles: Vec<Snowflake>,
+    /// Channels mentioned with `<#id>`
+    pub channels: Vec<Snowflake>,
+    /// Whether the content mentions `@everyone`
+    pub everyone: bool,
+}
+
+impl MessageMentions {
+    /// Parse mentions from message content
+    ///
+    /// IDs are deduplicated in order of first appearance and capped at
+    /// [`MAX_MENTIONS`] per kind. Nothing is resolved; unknown IDs are kept.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let mut mentions = Self {
+            everyone: content.contains(EVERYONE),
+            ..Self::default()
+        };
+
+        let mut rest = content;
+        while let Some(start) = rest.find('<') {
+            rest = &rest[start + 1..];
+
+            let (target, body) = if let Some(body) = rest.strip_prefix("@&") {
+                (&mut mentions.roles, body)
+            } else if let Some(body) = rest.strip_prefix("@!") {
+                (&mut mentions.users, body)
+            } else if let Some(body) = rest.strip_prefix('@') {
+                (&mut mentions.users, body)
+            } else if let Some(body) = rest.strip_prefix('#') {
+                (&mut mentions.channels, body)
+            } else {
+                continue;
+            };
+
+            let Some(end) = body.find('>') else {
+                break;
+            };
+            let digits = &body[..end];
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+            let Ok(id) = digits.parse::<i64>() else {
+                continue;
+            };
+
+            let id = Snowflake::new(id);
+            if target.len() < MAX_MENTIONS && !target.contains(&id) {
+                target.push(id);
+            }
+            rest = &body[end + 1..];
+        }
+
+        mentions
+    }
+
+    /// Check if nothing is mentioned
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty() && self.roles.is_empty() && self.channels.is_empty() && !self.everyone
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[i64]) -> Vec<Snowflake> {
+        values.iter().map(|v| Snowflake::new(*v)).collect()
+    }
+
+    #[test]
+    fn test_parse_all_kinds() {
+        let mentions = MessageMentions::parse("hi <@1> and <@!2>, see <#3> @everyone <@&4>");
+        assert_eq!(mentions.users, ids(&[1, 2]));
+        assert_eq!(mentions.roles, ids(&[4]));
+        assert_eq!(mentions.channels, ids(&[3]));
+        assert!(mentions.everyone);
+    }
+
+    #[test]
+    fn test_parse_plain_text() {
+        let mentions = MessageMentions::parse("no mentions here <3 @every one");
+        assert!(mentions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_deduplicates() {
+        let mentions = MessageMentions::parse("<@5> <@!5> <@6> <@5>");
+        assert_eq!(mentions.users, ids(&[5, 6]));
+    }
+
+    #[test]
+    fn test_parse_ignores_malformed() {
+        let mentions =
+            MessageMentions::parse("<@> <@abc> <@12 3> <@99999999999999999999> <#> <@&-1> <@7");
+        assert!(mentions.is_empty());
+    }
+
+    #[test]
+    fn test_parse_nested_brackets() {
+        let mentions = MessageMentions::parse("<<@8>> <a<@9>");
+        assert_eq!(mentions.users, ids(&[8, 9]));
+    }
+
+    #[test]
+    fn test_parse_caps_mentions() {
+        let content = (1..=150).map(|i| format!("<@{i}>")).collect::<Vec<_>>().join(" ");
+        let mentions = MessageMentions::parse(&content);
+        assert_eq!(mentions.users.len(), MAX_MENTIONS);
+        assert_eq!(mentions.users[0], Snowflake::new(1));
+    }
+}
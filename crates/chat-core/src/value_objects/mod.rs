@@ -1,7 +1,9 @@
 //! Value objects - immutable types that represent domain concepts
 
+mod mentions;
 mod permissions;
 mod snowflake;
 
+pub use mentions::{MessageMentions, MAX_MENTIONS};
 pub use permissions::Permissions;
 pub use snowflake::{Snowflake, SnowflakeGenerator, SnowflakeParseError};
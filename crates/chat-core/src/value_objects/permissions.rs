@@ -1,6 +1,6 @@
 //! Permissions bitflags for Discord-like access control
 //!
-//! Defines 11 permissions stored as a 64-bit integer bitfield.
+//! Defines 14 permissions stored as a 64-bit integer bitfield.
 
 use bitflags::bitflags;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -34,6 +34,12 @@ bitflags! {
         const ATTACH_FILES     = 1 << 9;
         /// Add emoji reactions
         const ADD_REACTIONS    = 1 << 10;
+        /// View the guild audit log
+        const VIEW_AUDIT_LOG   = 1 << 11;
+        /// Archive, lock, and delete threads started by others
+        const MANAGE_THREADS   = 1 << 12;
+        /// Mention @everyone and roles that are not mentionable
+        const MENTION_EVERYONE = 1 << 13;
 
         /// Default permissions for @everyone role
         const DEFAULT = Self::VIEW_CHANNEL.bits()
@@ -137,6 +143,15 @@ impl Permissions {
         if self.contains(Self::ADD_REACTIONS) {
             result.push("ADD_REACTIONS");
         }
+        if self.contains(Self::VIEW_AUDIT_LOG) {
+            result.push("VIEW_AUDIT_LOG");
+        }
+        if self.contains(Self::MANAGE_THREADS) {
+            result.push("MANAGE_THREADS");
+        }
+        if self.contains(Self::MENTION_EVERYONE) {
+            result.push("MENTION_EVERYONE");
+        }
         result
     }
 
@@ -252,6 +267,7 @@ mod tests {
         assert!(default.contains(Permissions::ATTACH_FILES));
         assert!(!default.contains(Permissions::ADMINISTRATOR));
         assert!(!default.contains(Permissions::MANAGE_GUILD));
+        assert!(!default.contains(Permissions::MENTION_EVERYONE));
     }
 
     #[test]
@@ -54,6 +54,14 @@ impl Snowflake {
         (self.0 & 0xFFF) as u16
     }
 
+    /// Gateway shard that owns this guild ID: `(id >> 22) % num_shards`
+    ///
+    /// `num_shards` must be non-zero.
+    #[inline]
+    pub fn shard_id(&self, num_shards: u32) -> u32 {
+        ((self.0 as u64 >> 22) % u64::from(num_shards)) as u32
+    }
+
     /// Convert timestamp to DateTime<Utc>
     pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
         use chrono::{TimeZone, Utc};
@@ -379,6 +387,15 @@ mod tests {
         SnowflakeGenerator::new(1024);
     }
 
+    #[test]
+    fn test_snowflake_shard_id() {
+        let id = Snowflake::new((41 << 22) | 0x3F_FFFF);
+        assert_eq!(id.shard_id(1), 0);
+        assert_eq!(id.shard_id(2), 1);
+        assert_eq!(id.shard_id(16), 9);
+        assert_eq!(Snowflake::new(0).shard_id(4), 0);
+    }
+
     #[test]
     fn test_snowflake_timestamp_extraction() {
         let gen = SnowflakeGenerator::new(1);
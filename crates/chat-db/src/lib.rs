@@ -37,7 +37,9 @@ pub mod repositories;
 // Re-export commonly used types
 pub use pool::{create_pool, create_pool_from_env, DatabaseConfig, PgPool};
 pub use repositories::{
-    PgAttachmentRepository, PgBanRepository, PgChannelRepository, PgGuildRepository,
-    PgInviteRepository, PgMemberRepository, PgMessageRepository, PgReactionRepository,
-    PgRoleRepository, PgUserRepository,
+    PgAttachmentRepository, PgAuditLogRepository, PgBanRepository, PgChannelRepository,
+    PgGuildRepository, PgInviteRepository, PgMemberRepository, PgMessageRepository,
+    PgMessageRevisionRepository, PgPermissionOverwriteRepository, PgPinRepository,
+    PgReactionRepository, PgReadStateRepository, PgRelationshipRepository, PgRoleRepository,
+    PgThreadRepository, PgUserRepository,
 };
@@ -0,0 +1,78 @@
+//! Audit log entity <-> model mapper
+
+use chat_core::entities::{AuditLogAction, AuditLogEntry};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::AuditLogModel;
+
+/// Convert database audit action string to AuditLogAction enum
+///
+/// Returns `None` for actions this build does not know about.
+fn parse_audit_action(action_str: &str) -> Option<AuditLogAction> {
+    let action = match action_str {
+        "guild_update" => AuditLogAction::GuildUpdate,
+        "channel_create" => AuditLogAction::ChannelCreate,
+        "channel_update" => AuditLogAction::ChannelUpdate,
+        "channel_delete" => AuditLogAction::ChannelDelete,
+        "role_create" => AuditLogAction::RoleCreate,
+        "role_update" => AuditLogAction::RoleUpdate,
+        "role_delete" => AuditLogAction::RoleDelete,
+        "member_kick" => AuditLogAction::MemberKick,
+        "member_ban" => AuditLogAction::MemberBan,
+        "member_unban" => AuditLogAction::MemberUnban,
+        "member_role_update" => AuditLogAction::MemberRoleUpdate,
+        "message_delete" => AuditLogAction::MessageDelete,
+        "message_pin" => AuditLogAction::MessagePin,
+        "message_unpin" => AuditLogAction::MessageUnpin,
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Convert AuditLogAction enum to database string
+pub fn audit_action_to_str(action: AuditLogAction) -> &'static str {
+    match action {
+        AuditLogAction::GuildUpdate => "guild_update",
+        AuditLogAction::ChannelCreate => "channel_create",
+        AuditLogAction::ChannelUpdate => "channel_update",
+        AuditLogAction::ChannelDelete => "channel_delete",
+        AuditLogAction::RoleCreate => "role_create",
+        AuditLogAction::RoleUpdate => "role_update",
+        AuditLogAction::RoleDelete => "role_delete",
+        AuditLogAction::MemberKick => "member_kick",
+        AuditLogAction::MemberBan => "member_ban",
+        AuditLogAction::MemberUnban => "member_unban",
+        AuditLogAction::MemberRoleUpdate => "member_role_update",
+        AuditLogAction::MessageDelete => "message_delete",
+        AuditLogAction::MessagePin => "message_pin",
+        AuditLogAction::MessageUnpin => "message_unpin",
+    }
+}
+
+/// Convert AuditLogModel to AuditLogEntry entity
+///
+/// Fails with the stored action string when it is not a known action.
+impl TryFrom<AuditLogModel> for AuditLogEntry {
+    type Error = String;
+
+    fn try_from(model: AuditLogModel) -> Result<Self, Self::Error> {
+        let Some(action) = parse_audit_action(&model.action) else {
+            return Err(model.action);
+        };
+
+        Ok(AuditLogEntry {
+            id: Snowflake::new(model.id),
+            guild_id: Snowflake::new(model.guild_id),
+            user_id: Snowflake::new(model.user_id),
+            action,
+            target_id: model.target_id.map(Snowflake::new),
+            target_type: model.target_type,
+            changes: model
+                .changes
+                .and_then(|changes| serde_json::from_value(changes).ok())
+                .unwrap_or_default(),
+            reason: model.reason,
+            created_at: model.created_at,
+        })
+    }
+}
@@ -10,7 +10,9 @@ fn parse_channel_type(type_str: &str) -> ChannelType {
     match type_str {
         "text" => ChannelType::GuildText,
         "dm" => ChannelType::Dm,
+        "group_dm" => ChannelType::GroupDm,
         "category" => ChannelType::GuildCategory,
+        "thread" => ChannelType::GuildThread,
         _ => ChannelType::GuildText,
     }
 }
@@ -20,7 +22,9 @@ pub fn channel_type_to_str(ct: ChannelType) -> &'static str {
     match ct {
         ChannelType::GuildText => "text",
         ChannelType::Dm => "dm",
+        ChannelType::GroupDm => "group_dm",
         ChannelType::GuildCategory => "category",
+        ChannelType::GuildThread => "thread",
     }
 }
 
@@ -35,6 +39,8 @@ impl From<ChannelModel> for Channel {
             topic: model.topic,
             position: model.position,
             parent_id: model.parent_id.map(Snowflake::new),
+            owner_id: model.owner_id.map(Snowflake::new),
+            icon: model.icon,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -50,6 +56,8 @@ pub struct ChannelInsert<'a> {
     pub topic: Option<&'a str>,
     pub position: i32,
     pub parent_id: Option<i64>,
+    pub owner_id: Option<i64>,
+    pub icon: Option<&'a str>,
 }
 
 impl<'a> ChannelInsert<'a> {
@@ -62,6 +70,8 @@ impl<'a> ChannelInsert<'a> {
             topic: channel.topic.as_deref(),
             position: channel.position,
             parent_id: channel.parent_id.map(chat_core::Snowflake::into_inner),
+            owner_id: channel.owner_id.map(chat_core::Snowflake::into_inner),
+            icon: channel.icon.as_deref(),
         }
     }
 }
@@ -73,6 +83,8 @@ pub struct ChannelUpdate<'a> {
     pub topic: Option<&'a str>,
     pub position: i32,
     pub parent_id: Option<i64>,
+    pub owner_id: Option<i64>,
+    pub icon: Option<&'a str>,
 }
 
 impl<'a> ChannelUpdate<'a> {
@@ -83,6 +95,8 @@ impl<'a> ChannelUpdate<'a> {
             topic: channel.topic.as_deref(),
             position: channel.position,
             parent_id: channel.parent_id.map(chat_core::Snowflake::into_inner),
+            owner_id: channel.owner_id.map(chat_core::Snowflake::into_inner),
+            icon: channel.icon.as_deref(),
         }
     }
 }
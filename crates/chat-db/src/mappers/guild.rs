@@ -1,10 +1,26 @@
 //! Guild entity <-> model mapper
 
-use chat_core::entities::Guild;
+use chat_core::entities::{Guild, MfaLevel};
 use chat_core::value_objects::Snowflake;
 
 use crate::models::GuildModel;
 
+/// Convert database MFA level string to MfaLevel enum
+fn parse_mfa_level(level_str: &str) -> MfaLevel {
+    match level_str {
+        "elevated" => MfaLevel::Elevated,
+        _ => MfaLevel::None,
+    }
+}
+
+/// Convert MfaLevel enum to database string
+pub fn mfa_level_to_str(level: MfaLevel) -> &'static str {
+    match level {
+        MfaLevel::None => "none",
+        MfaLevel::Elevated => "elevated",
+    }
+}
+
 /// Convert GuildModel to Guild entity
 impl From<GuildModel> for Guild {
     fn from(model: GuildModel) -> Self {
@@ -14,6 +30,7 @@ impl From<GuildModel> for Guild {
             icon: model.icon,
             description: model.description,
             owner_id: Snowflake::new(model.owner_id),
+            mfa_level: parse_mfa_level(&model.mfa_level),
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -27,6 +44,7 @@ pub struct GuildInsert<'a> {
     pub icon: Option<&'a str>,
     pub description: Option<&'a str>,
     pub owner_id: i64,
+    pub mfa_level: &'static str,
 }
 
 impl<'a> GuildInsert<'a> {
@@ -37,6 +55,7 @@ impl<'a> GuildInsert<'a> {
             icon: guild.icon.as_deref(),
             description: guild.description.as_deref(),
             owner_id: guild.owner_id.into_inner(),
+            mfa_level: mfa_level_to_str(guild.mfa_level),
         }
     }
 }
@@ -48,6 +67,7 @@ pub struct GuildUpdate<'a> {
     pub icon: Option<&'a str>,
     pub description: Option<&'a str>,
     pub owner_id: i64,
+    pub mfa_level: &'static str,
 }
 
 impl<'a> GuildUpdate<'a> {
@@ -58,6 +78,7 @@ impl<'a> GuildUpdate<'a> {
             icon: guild.icon.as_deref(),
             description: guild.description.as_deref(),
             owner_id: guild.owner_id.into_inner(),
+            mfa_level: mfa_level_to_str(guild.mfa_level),
         }
     }
 }
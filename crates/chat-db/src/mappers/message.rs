@@ -1,10 +1,31 @@
 //! Message and Attachment entity <-> model mapper
 
-use chat_core::entities::{Attachment, Message};
-use chat_core::value_objects::Snowflake;
+use chat_core::entities::{Attachment, Message, MessageType};
+use chat_core::value_objects::{MessageMentions, Snowflake};
 
 use crate::models::{AttachmentModel, MessageModel};
 
+/// Convert database message type string to MessageType enum
+fn parse_message_type(type_str: &str) -> MessageType {
+    match type_str {
+        "channel_pinned_message" => MessageType::ChannelPinnedMessage,
+        _ => MessageType::Default,
+    }
+}
+
+/// Convert MessageType enum to database string
+pub fn message_type_to_str(mt: MessageType) -> &'static str {
+    match mt {
+        MessageType::Default => "default",
+        MessageType::ChannelPinnedMessage => "channel_pinned_message",
+    }
+}
+
+/// Convert Snowflakes to raw IDs for BIGINT[] columns
+pub fn snowflakes_to_i64(ids: &[Snowflake]) -> Vec<i64> {
+    ids.iter().map(|id| id.into_inner()).collect()
+}
+
 /// Convert MessageModel to Message entity
 impl From<MessageModel> for Message {
     fn from(model: MessageModel) -> Self {
@@ -13,9 +34,16 @@ impl From<MessageModel> for Message {
             channel_id: Snowflake::new(model.channel_id),
             author_id: Snowflake::new(model.author_id),
             content: model.content,
+            message_type: parse_message_type(&model.message_type),
             created_at: model.created_at,
             edited_at: model.edited_at,
             reference_id: model.reference_id.map(Snowflake::new),
+            mentions: MessageMentions {
+                users: model.mention_user_ids.into_iter().map(Snowflake::new).collect(),
+                roles: model.mention_role_ids.into_iter().map(Snowflake::new).collect(),
+                channels: model.mention_channel_ids.into_iter().map(Snowflake::new).collect(),
+                everyone: model.mention_everyone,
+            },
         }
     }
 }
@@ -33,6 +61,7 @@ impl From<AttachmentModel> for Attachment {
             proxy_url: model.proxy_url,
             width: model.width,
             height: model.height,
+            verified_image: model.verified_image,
         }
     }
 }
@@ -43,6 +72,7 @@ pub struct MessageInsert<'a> {
     pub channel_id: i64,
     pub author_id: i64,
     pub content: &'a str,
+    pub message_type: &'static str,
     pub reference_id: Option<i64>,
 }
 
@@ -53,6 +83,7 @@ impl<'a> MessageInsert<'a> {
             channel_id: message.channel_id.into_inner(),
             author_id: message.author_id.into_inner(),
             content: &message.content,
+            message_type: message_type_to_str(message.message_type),
             reference_id: message.reference_id.map(chat_core::Snowflake::into_inner),
         }
     }
@@ -69,6 +100,7 @@ pub struct AttachmentInsert<'a> {
     pub proxy_url: Option<&'a str>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub verified_image: bool,
 }
 
 impl<'a> AttachmentInsert<'a> {
@@ -83,6 +115,7 @@ impl<'a> AttachmentInsert<'a> {
             proxy_url: attachment.proxy_url.as_deref(),
             width: attachment.width,
             height: attachment.height,
+            verified_image: attachment.verified_image,
         }
     }
 }
@@ -4,20 +4,27 @@
 //! - `From<Model> for Entity`: Convert database rows to domain objects
 //! - `*Insert`/`*Update` structs: Prepare entity data for database operations
 
+mod audit_log;
 mod channel;
 mod guild;
 mod invite;
 mod member;
 mod message;
+mod permission_overwrite;
 mod reaction;
+mod relationship;
 mod role;
+mod thread;
 mod user;
 
+pub use audit_log::audit_action_to_str;
 pub use channel::{channel_type_to_str, ChannelInsert, ChannelUpdate};
-pub use guild::{GuildInsert, GuildUpdate};
+pub use guild::{mfa_level_to_str, GuildInsert, GuildUpdate};
 pub use invite::InviteInsert;
 pub use member::{member_with_roles, MemberInsert, MemberUpdate};
-pub use message::{AttachmentInsert, MessageInsert};
+pub use message::{message_type_to_str, snowflakes_to_i64, AttachmentInsert, MessageInsert};
+pub use permission_overwrite::overwrite_type_to_str;
 pub use reaction::ReactionInsert;
+pub use relationship::relationship_type_to_str;
 pub use role::{RoleInsert, RoleUpdate};
-pub use user::{UserInsert, UserUpdate};
+pub use user::{dm_privacy_to_str, UserInsert, UserUpdate};
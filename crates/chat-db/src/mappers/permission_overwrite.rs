@@ -0,0 +1,37 @@
+//! Permission overwrite entity <-> model mapper
+
+use chat_core::entities::{OverwriteType, PermissionOverwrite};
+use chat_core::value_objects::{Permissions, Snowflake};
+
+use crate::models::PermissionOverwriteModel;
+
+/// Convert database overwrite type string to OverwriteType enum
+fn parse_overwrite_type(type_str: &str) -> OverwriteType {
+    match type_str {
+        "member" => OverwriteType::Member,
+        _ => OverwriteType::Role,
+    }
+}
+
+/// Convert OverwriteType enum to database string
+pub fn overwrite_type_to_str(ot: OverwriteType) -> &'static str {
+    match ot {
+        OverwriteType::Role => "role",
+        OverwriteType::Member => "member",
+    }
+}
+
+/// Convert PermissionOverwriteModel to PermissionOverwrite entity
+impl From<PermissionOverwriteModel> for PermissionOverwrite {
+    fn from(model: PermissionOverwriteModel) -> Self {
+        PermissionOverwrite {
+            channel_id: Snowflake::new(model.channel_id),
+            target_id: Snowflake::new(model.target_id),
+            overwrite_type: parse_overwrite_type(&model.overwrite_type),
+            allow: Permissions::from_i64(model.allow),
+            deny: Permissions::from_i64(model.deny),
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Relationship entity <-> model mapper
+
+use chat_core::entities::{Relationship, RelationshipType};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::RelationshipModel;
+
+/// Convert database relationship type string to RelationshipType enum
+fn parse_relationship_type(type_str: &str) -> RelationshipType {
+    match type_str {
+        "blocked" => RelationshipType::Blocked,
+        "incoming_request" => RelationshipType::IncomingRequest,
+        "outgoing_request" => RelationshipType::OutgoingRequest,
+        _ => RelationshipType::Friend,
+    }
+}
+
+/// Convert RelationshipType enum to database string
+pub fn relationship_type_to_str(rt: RelationshipType) -> &'static str {
+    match rt {
+        RelationshipType::Friend => "friend",
+        RelationshipType::Blocked => "blocked",
+        RelationshipType::IncomingRequest => "incoming_request",
+        RelationshipType::OutgoingRequest => "outgoing_request",
+    }
+}
+
+/// Convert RelationshipModel to Relationship entity
+impl From<RelationshipModel> for Relationship {
+    fn from(model: RelationshipModel) -> Self {
+        Relationship {
+            user_id: Snowflake::new(model.user_id),
+            target_id: Snowflake::new(model.target_id),
+            relationship_type: parse_relationship_type(&model.relationship_type),
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Thread entity <-> model mapper
+
+use chat_core::entities::{Channel, ChannelType, Thread, ThreadMember, ThreadMetadata};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::{ThreadMemberModel, ThreadModel};
+
+/// Convert ThreadModel to Thread entity
+impl From<ThreadModel> for Thread {
+    fn from(model: ThreadModel) -> Self {
+        Thread {
+            channel: Channel {
+                id: Snowflake::new(model.id),
+                guild_id: model.guild_id.map(Snowflake::new),
+                name: model.name,
+                channel_type: ChannelType::GuildThread,
+                topic: None,
+                position: 0,
+                parent_id: model.parent_id.map(Snowflake::new),
+                owner_id: None,
+                icon: None,
+                created_at: model.created_at,
+                updated_at: model.updated_at,
+            },
+            metadata: ThreadMetadata {
+                owner_id: Snowflake::new(model.owner_id),
+                message_id: Snowflake::new(model.message_id),
+                archived: model.archived,
+                locked: model.locked,
+                auto_archive_duration: model.auto_archive_duration,
+                archive_timestamp: model.archive_timestamp,
+                last_activity_at: model.last_activity_at,
+            },
+            member_count: i32::try_from(model.member_count).unwrap_or(i32::MAX),
+        }
+    }
+}
+
+/// Convert ThreadMemberModel to ThreadMember entity
+impl From<ThreadMemberModel> for ThreadMember {
+    fn from(model: ThreadMemberModel) -> Self {
+        ThreadMember {
+            thread_id: Snowflake::new(model.thread_id),
+            user_id: Snowflake::new(model.user_id),
+            joined_at: model.joined_at,
+        }
+    }
+}
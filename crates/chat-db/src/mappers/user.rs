@@ -1,10 +1,28 @@
 //! User entity <-> model mapper
 
-use chat_core::entities::User;
+use chat_core::entities::{DmPrivacy, User};
 use chat_core::value_objects::Snowflake;
 
 use crate::models::UserModel;
 
+/// Convert database DM privacy string to DmPrivacy enum
+fn parse_dm_privacy(privacy_str: &str) -> DmPrivacy {
+    match privacy_str {
+        "friends_and_guild_mates" => DmPrivacy::FriendsAndGuildMates,
+        "friends" => DmPrivacy::Friends,
+        _ => DmPrivacy::Everyone,
+    }
+}
+
+/// Convert DmPrivacy enum to database string
+pub fn dm_privacy_to_str(privacy: DmPrivacy) -> &'static str {
+    match privacy {
+        DmPrivacy::Everyone => "everyone",
+        DmPrivacy::FriendsAndGuildMates => "friends_and_guild_mates",
+        DmPrivacy::Friends => "friends",
+    }
+}
+
 /// Convert UserModel to User entity
 impl From<UserModel> for User {
     fn from(model: UserModel) -> Self {
@@ -16,6 +34,10 @@ impl From<UserModel> for User {
             avatar: model.avatar,
             bot: model.bot,
             system: model.system,
+            privileged_intents: model.privileged_intents,
+            dm_privacy: parse_dm_privacy(&model.dm_privacy),
+            verified: model.verified,
+            mfa_enabled: model.mfa_enabled,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -32,6 +54,9 @@ pub struct UserInsert<'a> {
     pub avatar: Option<&'a str>,
     pub bot: bool,
     pub system: bool,
+    pub privileged_intents: bool,
+    pub dm_privacy: &'static str,
+    pub verified: bool,
 }
 
 impl<'a> UserInsert<'a> {
@@ -45,6 +70,9 @@ impl<'a> UserInsert<'a> {
             avatar: user.avatar.as_deref(),
             bot: user.bot,
             system: user.system,
+            privileged_intents: user.privileged_intents,
+            dm_privacy: dm_privacy_to_str(user.dm_privacy),
+            verified: user.verified,
         }
     }
 }
@@ -54,6 +82,7 @@ pub struct UserUpdate<'a> {
     pub id: i64,
     pub username: &'a str,
     pub avatar: Option<&'a str>,
+    pub dm_privacy: &'static str,
 }
 
 impl<'a> UserUpdate<'a> {
@@ -62,6 +91,7 @@ impl<'a> UserUpdate<'a> {
             id: user.id.into_inner(),
             username: &user.username,
             avatar: user.avatar.as_deref(),
+            dm_privacy: dm_privacy_to_str(user.dm_privacy),
         }
     }
 }
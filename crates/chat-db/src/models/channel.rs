@@ -9,12 +9,14 @@ pub struct ChannelModel {
     pub id: i64,
     pub guild_id: Option<i64>,
     pub name: Option<String>,
-    /// Channel type: 'text', 'category', 'dm' (stored as PostgreSQL enum)
+    /// Channel type: 'text', 'category', 'dm', 'group_dm', 'thread' (stored as PostgreSQL enum)
     #[sqlx(rename = "type")]
     pub channel_type: String,
     pub topic: Option<String>,
     pub position: i32,
     pub parent_id: Option<i64>,
+    pub owner_id: Option<i64>,
+    pub icon: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
@@ -33,10 +35,22 @@ impl ChannelModel {
         self.channel_type == "dm"
     }
 
+    /// Check if this is a group DM channel
+    #[inline]
+    pub fn is_group_dm(&self) -> bool {
+        self.channel_type == "group_dm"
+    }
+
     /// Check if this is a text channel
     #[inline]
     pub fn is_text(&self) -> bool {
-        self.channel_type == "text" || self.channel_type == "dm"
+        matches!(self.channel_type.as_str(), "text" | "dm" | "group_dm" | "thread")
+    }
+
+    /// Check if this is a thread
+    #[inline]
+    pub fn is_thread(&self) -> bool {
+        self.channel_type == "thread"
     }
 
     /// Check if this is a category
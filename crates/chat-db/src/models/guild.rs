@@ -11,6 +11,8 @@ pub struct GuildModel {
     pub icon: Option<String>,
     pub description: Option<String>,
     pub owner_id: i64,
+    /// Two-factor requirement: 'none', 'elevated' (stored as PostgreSQL enum)
+    pub mfa_level: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
@@ -10,10 +10,17 @@ pub struct MessageModel {
     pub channel_id: i64,
     pub author_id: i64,
     pub content: String,
+    /// Message type: 'default', 'channel_pinned_message' (stored as PostgreSQL enum)
+    #[sqlx(rename = "type")]
+    pub message_type: String,
     pub created_at: DateTime<Utc>,
     pub edited_at: Option<DateTime<Utc>>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub reference_id: Option<i64>,
+    pub mention_everyone: bool,
+    pub mention_user_ids: Vec<i64>,
+    pub mention_role_ids: Vec<i64>,
+    pub mention_channel_ids: Vec<i64>,
 }
 
 impl MessageModel {
@@ -36,6 +43,14 @@ impl MessageModel {
     }
 }
 
+/// Message row returned alongside the search hit it surrounds
+#[derive(Debug, Clone, FromRow)]
+pub struct MessageContextModel {
+    pub anchor_id: i64,
+    #[sqlx(flatten)]
+    pub message: MessageModel,
+}
+
 /// Database model for attachments table
 #[derive(Debug, Clone, FromRow)]
 pub struct AttachmentModel {
@@ -48,6 +63,7 @@ pub struct AttachmentModel {
     pub proxy_url: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub verified_image: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -0,0 +1,14 @@
+//! Message revision database model
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Database model for message_revisions table
+#[derive(Debug, Clone, FromRow)]
+pub struct MessageRevisionModel {
+    pub id: i64,
+    pub message_id: i64,
+    pub content: String,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub replaced_at: DateTime<Utc>,
+}
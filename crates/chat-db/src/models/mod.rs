@@ -7,9 +7,15 @@ mod guild;
 mod invite;
 mod member;
 mod message;
+mod message_revision;
+mod permission_overwrite;
+mod pin;
 mod reaction;
+mod read_state;
 mod refresh_token;
+mod relationship;
 mod role;
+mod thread;
 mod user;
 
 pub use audit_log::AuditLogModel;
@@ -18,8 +24,14 @@ pub use channel::{ChannelModel, DmRecipientModel};
 pub use guild::GuildModel;
 pub use invite::InviteModel;
 pub use member::{GuildMemberModel, MemberRoleModel, MemberWithRolesModel};
-pub use message::{AttachmentModel, MessageModel};
+pub use message::{AttachmentModel, MessageContextModel, MessageModel};
+pub use message_revision::MessageRevisionModel;
+pub use permission_overwrite::PermissionOverwriteModel;
+pub use pin::PinModel;
 pub use reaction::{ReactionCountModel, ReactionModel};
+pub use read_state::ReadStateModel;
 pub use refresh_token::RefreshTokenModel;
+pub use relationship::RelationshipModel;
 pub use role::RoleModel;
+pub use thread::{ThreadMemberModel, ThreadModel};
 pub use user::UserModel;
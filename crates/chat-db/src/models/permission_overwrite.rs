@@ -0,0 +1,18 @@
+//! Permission overwrite database model
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Database model for permission_overwrites table
+#[derive(Debug, Clone, FromRow)]
+pub struct PermissionOverwriteModel {
+    pub channel_id: i64,
+    pub target_id: i64,
+    /// Overwrite type: 'role', 'member' (stored as PostgreSQL enum)
+    #[sqlx(rename = "type")]
+    pub overwrite_type: String,
+    pub allow: i64,
+    pub deny: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,13 @@
+//! Pin database model
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Database model for pins table
+#[derive(Debug, Clone, FromRow)]
+pub struct PinModel {
+    pub channel_id: i64,
+    pub message_id: i64,
+    pub pinned_by: i64,
+    pub pinned_at: DateTime<Utc>,
+}
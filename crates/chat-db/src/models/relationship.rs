@@ -0,0 +1,16 @@
+//! Relationship database model
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Database model for relationships table
+#[derive(Debug, Clone, FromRow)]
+pub struct RelationshipModel {
+    pub user_id: i64,
+    pub target_id: i64,
+    /// Relationship type: 'friend', 'blocked', 'incoming_request', 'outgoing_request'
+    /// (stored as PostgreSQL enum)
+    #[sqlx(rename = "type")]
+    pub relationship_type: String,
+    pub created_at: DateTime<Utc>,
+}
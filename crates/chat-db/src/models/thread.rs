@@ -0,0 +1,31 @@
+//! Thread database models
+
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Thread channel joined with its threads row
+#[derive(Debug, Clone, FromRow)]
+pub struct ThreadModel {
+    pub id: i64,
+    pub guild_id: Option<i64>,
+    pub name: Option<String>,
+    pub parent_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub owner_id: i64,
+    pub message_id: i64,
+    pub archived: bool,
+    pub locked: bool,
+    pub auto_archive_duration: i32,
+    pub archive_timestamp: Option<DateTime<Utc>>,
+    pub last_activity_at: DateTime<Utc>,
+    pub member_count: i64,
+}
+
+/// Database model for thread_members table
+#[derive(Debug, Clone, FromRow)]
+pub struct ThreadMemberModel {
+    pub thread_id: i64,
+    pub user_id: i64,
+    pub joined_at: DateTime<Utc>,
+}
@@ -14,6 +14,11 @@ pub struct UserModel {
     pub avatar: Option<String>,
     pub bot: bool,
     pub system: bool,
+    pub privileged_intents: bool,
+    /// DM privacy: 'everyone', 'friends_and_guild_mates', 'friends' (stored as PostgreSQL enum)
+    pub dm_privacy: String,
+    pub verified: bool,
+    pub mfa_enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
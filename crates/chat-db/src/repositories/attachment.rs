@@ -31,7 +31,7 @@ impl AttachmentRepository for PgAttachmentRepository {
     async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Attachment>> {
         let result = sqlx::query_as::<_, AttachmentModel>(
             r"
-            SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, created_at
+            SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, verified_image, created_at
             FROM attachments
             WHERE id = $1
             ",
@@ -48,7 +48,7 @@ impl AttachmentRepository for PgAttachmentRepository {
     async fn find_by_message(&self, message_id: Snowflake) -> RepoResult<Vec<Attachment>> {
         let results = sqlx::query_as::<_, AttachmentModel>(
             r"
-            SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, created_at
+            SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, verified_image, created_at
             FROM attachments
             WHERE message_id = $1
             ORDER BY created_at
@@ -66,8 +66,8 @@ impl AttachmentRepository for PgAttachmentRepository {
     async fn create(&self, attachment: &Attachment) -> RepoResult<()> {
         sqlx::query(
             r"
-            INSERT INTO attachments (id, message_id, filename, content_type, size, url, proxy_url, width, height)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO attachments (id, message_id, filename, content_type, size, url, proxy_url, width, height, verified_image)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ",
         )
         .bind(attachment.id.into_inner())
@@ -79,6 +79,7 @@ impl AttachmentRepository for PgAttachmentRepository {
         .bind(&attachment.proxy_url)
         .bind(attachment.width)
         .bind(attachment.height)
+        .bind(attachment.verified_image)
         .execute(&self.pool)
         .await
         .map_err(map_db_error)?;
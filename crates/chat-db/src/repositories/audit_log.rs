@@ -0,0 +1,115 @@
+//! PostgreSQL implementation of AuditLogRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::{instrument, warn};
+
+use chat_core::entities::AuditLogEntry;
+use chat_core::traits::{AuditLogQuery, AuditLogRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::mappers::audit_action_to_str;
+use crate::models::AuditLogModel;
+
+use super::error::map_db_error;
+
+/// PostgreSQL implementation of AuditLogRepository
+#[derive(Clone)]
+pub struct PgAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PgAuditLogRepository {
+    /// Create a new PgAuditLogRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PgAuditLogRepository {
+    #[instrument(skip(self, entry), fields(guild_id = %entry.guild_id))]
+    async fn create(&self, entry: &AuditLogEntry) -> RepoResult<()> {
+        let changes = if entry.changes.is_empty() {
+            None
+        } else {
+            serde_json::to_value(&entry.changes).ok()
+        };
+
+        sqlx::query(
+            r"
+            INSERT INTO audit_logs (id, guild_id, user_id, action, target_id, target_type, changes, reason, created_at)
+            VALUES ($1, $2, $3, $4::audit_action, $5, $6, $7, $8, $9)
+            ",
+        )
+        .bind(entry.id.into_inner())
+        .bind(entry.guild_id.into_inner())
+        .bind(entry.user_id.into_inner())
+        .bind(audit_action_to_str(entry.action))
+        .bind(entry.target_id.map(Snowflake::into_inner))
+        .bind(&entry.target_type)
+        .bind(changes)
+        .bind(&entry.reason)
+        .bind(entry.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_guild(
+        &self,
+        guild_id: Snowflake,
+        query: AuditLogQuery,
+    ) -> RepoResult<Vec<AuditLogEntry>> {
+        let limit = query.limit.clamp(1, 100);
+
+        let results = sqlx::query_as::<_, AuditLogModel>(
+            r"
+            SELECT id, guild_id, user_id, action::TEXT as action, target_id, target_type, changes, reason, created_at
+            FROM audit_logs
+            WHERE guild_id = $1
+              AND ($2::BIGINT IS NULL OR user_id = $2)
+              AND ($3::TEXT IS NULL OR action = $3::audit_action)
+              AND ($4::BIGINT IS NULL OR id < $4)
+            ORDER BY id DESC
+            LIMIT $5
+            ",
+        )
+        .bind(guild_id.into_inner())
+        .bind(query.user_id.map(Snowflake::into_inner))
+        .bind(query.action.map(audit_action_to_str))
+        .bind(query.before.map(Snowflake::into_inner))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let entries = results
+            .into_iter()
+            .filter_map(|model| {
+                let id = model.id;
+                AuditLogEntry::try_from(model)
+                    .inspect_err(|action| {
+                        warn!(entry_id = id, action = %action, "Skipping audit log entry with unknown action");
+                    })
+                    .ok()
+            })
+            .collect();
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgAuditLogRepository>();
+    }
+}
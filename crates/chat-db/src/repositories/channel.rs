@@ -33,7 +33,7 @@ impl ChannelRepository for PgChannelRepository {
         let result = sqlx::query_as::<_, ChannelModel>(
             r"
             SELECT id, guild_id, name, type::TEXT as type, topic, position, parent_id,
-                   created_at, updated_at, deleted_at
+                   owner_id, icon, created_at, updated_at, deleted_at
             FROM channels
             WHERE id = $1 AND deleted_at IS NULL
             ",
@@ -51,9 +51,9 @@ impl ChannelRepository for PgChannelRepository {
         let results = sqlx::query_as::<_, ChannelModel>(
             r"
             SELECT id, guild_id, name, type::TEXT as type, topic, position, parent_id,
-                   created_at, updated_at, deleted_at
+                   owner_id, icon, created_at, updated_at, deleted_at
             FROM channels
-            WHERE guild_id = $1 AND deleted_at IS NULL
+            WHERE guild_id = $1 AND type != 'thread' AND deleted_at IS NULL
             ORDER BY COALESCE(parent_id, id), type = 'category' DESC, position
             ",
         )
@@ -70,7 +70,7 @@ impl ChannelRepository for PgChannelRepository {
         let result = sqlx::query_as::<_, ChannelModel>(
             r"
             SELECT c.id, c.guild_id, c.name, c.type::TEXT as type, c.topic, c.position, c.parent_id,
-                   c.created_at, c.updated_at, c.deleted_at
+                   c.owner_id, c.icon, c.created_at, c.updated_at, c.deleted_at
             FROM channels c
             JOIN dm_channel_recipients r1 ON r1.channel_id = c.id AND r1.user_id = $1
             JOIN dm_channel_recipients r2 ON r2.channel_id = c.id AND r2.user_id = $2
@@ -93,11 +93,11 @@ impl ChannelRepository for PgChannelRepository {
         let results = sqlx::query_as::<_, ChannelModel>(
             r"
             SELECT c.id, c.guild_id, c.name, c.type::TEXT as type, c.topic, c.position, c.parent_id,
-                   c.created_at, c.updated_at, c.deleted_at
+                   c.owner_id, c.icon, c.created_at, c.updated_at, c.deleted_at
             FROM channels c
             JOIN dm_channel_recipients r ON r.channel_id = c.id
             WHERE r.user_id = $1
-              AND c.type = 'dm'
+              AND c.type IN ('dm', 'group_dm')
               AND c.deleted_at IS NULL
             ORDER BY c.created_at DESC
             ",
@@ -114,8 +114,9 @@ impl ChannelRepository for PgChannelRepository {
     async fn create(&self, channel: &Channel) -> RepoResult<()> {
         sqlx::query(
             r"
-            INSERT INTO channels (id, guild_id, name, type, topic, position, parent_id, created_at, updated_at)
-            VALUES ($1, $2, $3, $4::channel_type, $5, $6, $7, $8, $9)
+            INSERT INTO channels (id, guild_id, name, type, topic, position, parent_id, owner_id, icon,
+                                  created_at, updated_at)
+            VALUES ($1, $2, $3, $4::channel_type, $5, $6, $7, $8, $9, $10, $11)
             ",
         )
         .bind(channel.id.into_inner())
@@ -125,6 +126,8 @@ impl ChannelRepository for PgChannelRepository {
         .bind(&channel.topic)
         .bind(channel.position)
         .bind(channel.parent_id.map(chat_core::Snowflake::into_inner))
+        .bind(channel.owner_id.map(chat_core::Snowflake::into_inner))
+        .bind(&channel.icon)
         .bind(channel.created_at)
         .bind(channel.updated_at)
         .execute(&self.pool)
@@ -139,7 +142,8 @@ impl ChannelRepository for PgChannelRepository {
         let result = sqlx::query(
             r"
             UPDATE channels
-            SET name = $2, topic = $3, position = $4, parent_id = $5, updated_at = NOW()
+            SET name = $2, topic = $3, position = $4, parent_id = $5, owner_id = $6, icon = $7,
+                updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
             ",
         )
@@ -148,6 +152,8 @@ impl ChannelRepository for PgChannelRepository {
         .bind(&channel.topic)
         .bind(channel.position)
         .bind(channel.parent_id.map(chat_core::Snowflake::into_inner))
+        .bind(channel.owner_id.map(chat_core::Snowflake::into_inner))
+        .bind(&channel.icon)
         .execute(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -198,11 +204,30 @@ impl ChannelRepository for PgChannelRepository {
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    async fn remove_dm_recipient(&self, channel_id: Snowflake, user_id: Snowflake) -> RepoResult<()> {
+        sqlx::query(
+            r"
+            DELETE FROM dm_channel_recipients
+            WHERE channel_id = $1 AND user_id = $2
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(user_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn get_dm_recipients(&self, channel_id: Snowflake) -> RepoResult<Vec<Snowflake>> {
         let results = sqlx::query_scalar::<_, i64>(
             r"
-            SELECT user_id FROM dm_channel_recipients WHERE channel_id = $1
+            SELECT user_id FROM dm_channel_recipients
+            WHERE channel_id = $1
+            ORDER BY created_at, user_id
             ",
         )
         .bind(channel_id.into_inner())
@@ -61,3 +61,18 @@ pub fn invite_not_found(code: &str) -> DomainError {
 pub fn ban_not_found() -> DomainError {
     DomainError::DatabaseError("Ban not found".to_string())
 }
+
+/// Create a "permission overwrite not found" error
+pub fn overwrite_not_found() -> DomainError {
+    DomainError::DatabaseError("Permission overwrite not found".to_string())
+}
+
+/// Create a "thread not found" error
+pub fn thread_not_found(id: Snowflake) -> DomainError {
+    DomainError::ChannelNotFound(id)
+}
+
+/// Create a "pin not found" error
+pub fn pin_not_found(message_id: Snowflake) -> DomainError {
+    DomainError::MessageNotFound(message_id)
+}
@@ -8,6 +8,7 @@ use chat_core::entities::Guild;
 use chat_core::traits::{GuildRepository, RepoResult};
 use chat_core::value_objects::Snowflake;
 
+use crate::mappers::mfa_level_to_str;
 use crate::models::GuildModel;
 
 use super::error::{guild_not_found, map_db_error};
@@ -31,7 +32,8 @@ impl GuildRepository for PgGuildRepository {
     async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Guild>> {
         let result = sqlx::query_as::<_, GuildModel>(
             r"
-            SELECT id, name, icon, description, owner_id, created_at, updated_at, deleted_at
+            SELECT id, name, icon, description, owner_id, mfa_level::TEXT as mfa_level,
+                   created_at, updated_at, deleted_at
             FROM guilds
             WHERE id = $1 AND deleted_at IS NULL
             ",
@@ -48,7 +50,8 @@ impl GuildRepository for PgGuildRepository {
     async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<Guild>> {
         let results = sqlx::query_as::<_, GuildModel>(
             r"
-            SELECT g.id, g.name, g.icon, g.description, g.owner_id, g.created_at, g.updated_at, g.deleted_at
+            SELECT g.id, g.name, g.icon, g.description, g.owner_id, g.mfa_level::TEXT as mfa_level,
+                   g.created_at, g.updated_at, g.deleted_at
             FROM guilds g
             JOIN guild_members gm ON gm.guild_id = g.id
             WHERE gm.user_id = $1 AND g.deleted_at IS NULL
@@ -67,8 +70,8 @@ impl GuildRepository for PgGuildRepository {
     async fn create(&self, guild: &Guild) -> RepoResult<()> {
         sqlx::query(
             r"
-            INSERT INTO guilds (id, name, icon, description, owner_id, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO guilds (id, name, icon, description, owner_id, mfa_level, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6::mfa_level, $7, $8)
             ",
         )
         .bind(guild.id.into_inner())
@@ -76,6 +79,7 @@ impl GuildRepository for PgGuildRepository {
         .bind(&guild.icon)
         .bind(&guild.description)
         .bind(guild.owner_id.into_inner())
+        .bind(mfa_level_to_str(guild.mfa_level))
         .bind(guild.created_at)
         .bind(guild.updated_at)
         .execute(&self.pool)
@@ -90,7 +94,8 @@ impl GuildRepository for PgGuildRepository {
         let result = sqlx::query(
             r"
             UPDATE guilds
-            SET name = $2, icon = $3, description = $4, owner_id = $5, updated_at = NOW()
+            SET name = $2, icon = $3, description = $4, owner_id = $5,
+                mfa_level = $6::mfa_level, updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
             ",
         )
@@ -99,6 +104,7 @@ impl GuildRepository for PgGuildRepository {
         .bind(&guild.icon)
         .bind(&guild.description)
         .bind(guild.owner_id.into_inner())
+        .bind(mfa_level_to_str(guild.mfa_level))
         .execute(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -1,5 +1,7 @@
 //! PostgreSQL implementation of MemberRepository
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use sqlx::PgPool;
 use tracing::instrument;
@@ -41,6 +43,43 @@ impl PgMemberRepository {
 
         Ok(role_ids)
     }
+
+    /// Load role IDs for several members of a guild in one query
+    async fn load_role_ids_many(
+        &self,
+        guild_id: i64,
+        user_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<i64>>, DomainError> {
+        let rows = sqlx::query_as::<_, (i64, i64)>(
+            r"
+            SELECT user_id, role_id FROM member_roles WHERE guild_id = $1 AND user_id = ANY($2)
+            ",
+        )
+        .bind(guild_id)
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut role_ids: HashMap<i64, Vec<i64>> = HashMap::new();
+        for (user_id, role_id) in rows {
+            role_ids.entry(user_id).or_default().push(role_id);
+        }
+
+        Ok(role_ids)
+    }
+}
+
+/// Escape `LIKE` wildcards so the query matches literally
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 #[async_trait]
@@ -123,6 +162,71 @@ impl MemberRepository for PgMemberRepository {
         Ok(members)
     }
 
+    #[instrument(skip(self))]
+    async fn search(&self, guild_id: Snowflake, query: &str, limit: i64) -> RepoResult<Vec<GuildMember>> {
+        let limit = limit.clamp(1, 1000);
+        let pattern = format!("{}%", escape_like(&query.to_lowercase()));
+
+        let results = sqlx::query_as::<_, GuildMemberModel>(
+            r"
+            SELECT gm.guild_id, gm.user_id, gm.nickname, gm.joined_at, gm.updated_at
+            FROM guild_members gm
+            JOIN users u ON u.id = gm.user_id
+            WHERE gm.guild_id = $1
+              AND (LOWER(u.username) LIKE $2 OR LOWER(gm.nickname) LIKE $2)
+            ORDER BY gm.user_id
+            LIMIT $3
+            ",
+        )
+        .bind(guild_id.into_inner())
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut members = Vec::with_capacity(results.len());
+        for model in results {
+            let role_ids = self.load_role_ids(model.guild_id, model.user_id).await?;
+            members.push(member_with_roles(model, role_ids));
+        }
+
+        Ok(members)
+    }
+
+    #[instrument(skip(self))]
+    async fn find_many(&self, guild_id: Snowflake, user_ids: &[Snowflake]) -> RepoResult<Vec<GuildMember>> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<i64> = user_ids.iter().map(|id| id.into_inner()).collect();
+        let results = sqlx::query_as::<_, GuildMemberModel>(
+            r"
+            SELECT guild_id, user_id, nickname, joined_at, updated_at
+            FROM guild_members
+            WHERE guild_id = $1 AND user_id = ANY($2)
+            ORDER BY user_id
+            ",
+        )
+        .bind(guild_id.into_inner())
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut role_ids = self.load_role_ids_many(guild_id.into_inner(), &ids).await?;
+        let members = results
+            .into_iter()
+            .map(|model| {
+                let roles = role_ids.remove(&model.user_id).unwrap_or_default();
+                member_with_roles(model, roles)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(members)
+    }
+
     #[instrument(skip(self))]
     async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<GuildMember>> {
         let results = sqlx::query_as::<_, GuildMemberModel>(
@@ -163,6 +267,27 @@ impl MemberRepository for PgMemberRepository {
         Ok(result)
     }
 
+    #[instrument(skip(self))]
+    async fn shares_guild(&self, user_id: Snowflake, other_id: Snowflake) -> RepoResult<bool> {
+        let result = sqlx::query_scalar::<_, bool>(
+            r"
+            SELECT EXISTS(
+                SELECT 1
+                FROM guild_members a
+                INNER JOIN guild_members b ON b.guild_id = a.guild_id
+                WHERE a.user_id = $1 AND b.user_id = $2
+            )
+            ",
+        )
+        .bind(user_id.into_inner())
+        .bind(other_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result)
+    }
+
     #[instrument(skip(self))]
     async fn create(&self, member: &GuildMember) -> RepoResult<()> {
         sqlx::query(
@@ -291,4 +416,10 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<PgMemberRepository>();
     }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("ali"), "ali");
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+    }
 }
@@ -1,17 +1,63 @@
 //! PostgreSQL implementation of MessageRepository
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use tracing::instrument;
 
 use chat_core::entities::{Attachment, Message};
-use chat_core::traits::{MessageQuery, MessageRepository, RepoResult};
+use chat_core::traits::{
+    MessageQuery, MessageRepository, MessageRevision, MessageSearchQuery, RepoResult,
+};
 use chat_core::value_objects::Snowflake;
 
-use crate::models::{AttachmentModel, MessageModel};
+use crate::mappers::{message_type_to_str, snowflakes_to_i64};
+use crate::models::{AttachmentModel, MessageContextModel, MessageModel};
 
 use super::error::{map_db_error, message_not_found};
 
+/// Remove the revisions of soft-deleted messages
+///
+/// Messages are never hard-deleted, so the `ON DELETE CASCADE` on
+/// `message_revisions` does not fire for them.
+async fn purge_revisions(tx: &mut Transaction<'_, Postgres>, message_ids: &[i64]) -> RepoResult<()> {
+    if message_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r"
+        DELETE FROM message_revisions WHERE message_id = ANY($1)
+        ",
+    )
+    .bind(message_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(map_db_error)?;
+
+    Ok(())
+}
+
+/// Shared FROM/WHERE clause for message search
+///
+/// The content predicate matches `idx_messages_content_search`, so it must use
+/// the same `to_tsvector('english', content)` expression.
+const SEARCH_FILTER: &str = r"
+    FROM messages
+    WHERE channel_id = ANY($1)
+      AND deleted_at IS NULL
+      AND type = 'default'
+      AND ($2::TEXT IS NULL OR to_tsvector('english', content) @@ websearch_to_tsquery('english', $2))
+      AND ($3::BIGINT IS NULL OR author_id = $3)
+      AND ($4::BIGINT IS NULL OR mention_user_ids @> ARRAY[$4::BIGINT])
+      AND ($5::BOOLEAN IS NULL
+           OR EXISTS (SELECT 1 FROM attachments WHERE attachments.message_id = messages.id) = $5)
+      AND ($6::TIMESTAMPTZ IS NULL OR created_at >= $6)
+      AND ($7::TIMESTAMPTZ IS NULL OR created_at < $7)
+";
+
 /// PostgreSQL implementation of MessageRepository
 #[derive(Clone)]
 pub struct PgMessageRepository {
@@ -31,7 +77,9 @@ impl MessageRepository for PgMessageRepository {
     async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Message>> {
         let result = sqlx::query_as::<_, MessageModel>(
             r"
-            SELECT id, channel_id, author_id, content, created_at, edited_at, deleted_at, reference_id
+            SELECT id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                   deleted_at, reference_id, mention_everyone, mention_user_ids,
+                   mention_role_ids, mention_channel_ids
             FROM messages
             WHERE id = $1 AND deleted_at IS NULL
             ",
@@ -57,7 +105,9 @@ impl MessageRepository for PgMessageRepository {
                 // Fetch messages before cursor (scrolling up)
                 sqlx::query_as::<_, MessageModel>(
                     r"
-                    SELECT id, channel_id, author_id, content, created_at, edited_at, deleted_at, reference_id
+                    SELECT id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                           deleted_at, reference_id, mention_everyone, mention_user_ids,
+                           mention_role_ids, mention_channel_ids
                     FROM messages
                     WHERE channel_id = $1 AND id < $2 AND deleted_at IS NULL
                     ORDER BY id DESC
@@ -74,7 +124,9 @@ impl MessageRepository for PgMessageRepository {
                 // Fetch messages after cursor (scrolling down)
                 sqlx::query_as::<_, MessageModel>(
                     r"
-                    SELECT id, channel_id, author_id, content, created_at, edited_at, deleted_at, reference_id
+                    SELECT id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                           deleted_at, reference_id, mention_everyone, mention_user_ids,
+                           mention_role_ids, mention_channel_ids
                     FROM messages
                     WHERE channel_id = $1 AND id > $2 AND deleted_at IS NULL
                     ORDER BY id ASC
@@ -91,7 +143,9 @@ impl MessageRepository for PgMessageRepository {
                 // Fetch latest messages (no cursor)
                 sqlx::query_as::<_, MessageModel>(
                     r"
-                    SELECT id, channel_id, author_id, content, created_at, edited_at, deleted_at, reference_id
+                    SELECT id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                           deleted_at, reference_id, mention_everyone, mention_user_ids,
+                           mention_role_ids, mention_channel_ids
                     FROM messages
                     WHERE channel_id = $1 AND deleted_at IS NULL
                     ORDER BY id DESC
@@ -113,16 +167,22 @@ impl MessageRepository for PgMessageRepository {
     async fn create(&self, message: &Message) -> RepoResult<()> {
         sqlx::query(
             r"
-            INSERT INTO messages (id, channel_id, author_id, content, created_at, reference_id)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO messages (id, channel_id, author_id, content, type, created_at, reference_id,
+                                  mention_everyone, mention_user_ids, mention_role_ids, mention_channel_ids)
+            VALUES ($1, $2, $3, $4, $5::message_type, $6, $7, $8, $9, $10, $11)
             ",
         )
         .bind(message.id.into_inner())
         .bind(message.channel_id.into_inner())
         .bind(message.author_id.into_inner())
         .bind(&message.content)
+        .bind(message_type_to_str(message.message_type))
         .bind(message.created_at)
         .bind(message.reference_id.map(chat_core::Snowflake::into_inner))
+        .bind(message.mentions.everyone)
+        .bind(snowflakes_to_i64(&message.mentions.users))
+        .bind(snowflakes_to_i64(&message.mentions.roles))
+        .bind(snowflakes_to_i64(&message.mentions.channels))
         .execute(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -130,18 +190,44 @@ impl MessageRepository for PgMessageRepository {
         Ok(())
     }
 
-    #[instrument(skip(self))]
-    async fn update(&self, message: &Message) -> RepoResult<()> {
+    #[instrument(skip(self, revision))]
+    async fn update(&self, message: &Message, revision: Option<&MessageRevision>) -> RepoResult<()> {
+        // A revision must only exist for an edit that was saved
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        if let Some(revision) = revision {
+            sqlx::query(
+                r"
+                INSERT INTO message_revisions (id, message_id, content, edited_at, replaced_at)
+                VALUES ($1, $2, $3, $4, $5)
+                ",
+            )
+            .bind(revision.id.into_inner())
+            .bind(revision.message_id.into_inner())
+            .bind(&revision.content)
+            .bind(revision.edited_at)
+            .bind(revision.replaced_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_db_error)?;
+        }
+
         let result = sqlx::query(
             r"
             UPDATE messages
-            SET content = $2, edited_at = NOW()
+            SET content = $2, edited_at = $3, mention_everyone = $4, mention_user_ids = $5,
+                mention_role_ids = $6, mention_channel_ids = $7
             WHERE id = $1 AND deleted_at IS NULL
             ",
         )
         .bind(message.id.into_inner())
         .bind(&message.content)
-        .execute(&self.pool)
+        .bind(message.edited_at)
+        .bind(message.mentions.everyone)
+        .bind(snowflakes_to_i64(&message.mentions.users))
+        .bind(snowflakes_to_i64(&message.mentions.roles))
+        .bind(snowflakes_to_i64(&message.mentions.channels))
+        .execute(&mut *tx)
         .await
         .map_err(map_db_error)?;
 
@@ -149,11 +235,15 @@ impl MessageRepository for PgMessageRepository {
             return Err(message_not_found(message.id));
         }
 
+        tx.commit().await.map_err(map_db_error)?;
+
         Ok(())
     }
 
     #[instrument(skip(self))]
     async fn delete(&self, id: Snowflake) -> RepoResult<()> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
         let result = sqlx::query(
             r"
             UPDATE messages
@@ -162,7 +252,7 @@ impl MessageRepository for PgMessageRepository {
             ",
         )
         .bind(id.into_inner())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .map_err(map_db_error)?;
 
@@ -170,6 +260,9 @@ impl MessageRepository for PgMessageRepository {
             return Err(message_not_found(id));
         }
 
+        purge_revisions(&mut tx, &[id.into_inner()]).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
         Ok(())
     }
 
@@ -181,20 +274,177 @@ impl MessageRepository for PgMessageRepository {
 
         let ids: Vec<i64> = message_ids.iter().map(|s| s.into_inner()).collect();
 
-        let result = sqlx::query(
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let deleted = sqlx::query_scalar::<_, i64>(
             r"
             UPDATE messages
             SET deleted_at = NOW()
             WHERE channel_id = $1 AND id = ANY($2) AND deleted_at IS NULL
+            RETURNING id
             ",
         )
         .bind(channel_id.into_inner())
         .bind(&ids)
-        .execute(&self.pool)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        purge_revisions(&mut tx, &deleted).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(deleted.len() as u64)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_by_author_in_guild(
+        &self,
+        guild_id: Snowflake,
+        author_id: Snowflake,
+        since: DateTime<Utc>,
+    ) -> RepoResult<Vec<Message>> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let results = sqlx::query_as::<_, MessageModel>(
+            r"
+            UPDATE messages
+            SET deleted_at = NOW()
+            WHERE author_id = $2
+              AND created_at >= $3
+              AND deleted_at IS NULL
+              AND channel_id IN (SELECT id FROM channels WHERE guild_id = $1)
+            RETURNING id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                      deleted_at, reference_id, mention_everyone, mention_user_ids,
+                      mention_role_ids, mention_channel_ids
+            ",
+        )
+        .bind(guild_id.into_inner())
+        .bind(author_id.into_inner())
+        .bind(since)
+        .fetch_all(&mut *tx)
         .await
         .map_err(map_db_error)?;
 
-        Ok(result.rows_affected())
+        let ids: Vec<i64> = results.iter().map(|m| m.id).collect();
+        purge_revisions(&mut tx, &ids).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Message::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn search(
+        &self,
+        channel_ids: &[Snowflake],
+        query: &MessageSearchQuery,
+    ) -> RepoResult<(i64, Vec<Message>)> {
+        if channel_ids.is_empty() {
+            return Ok((0, Vec::new()));
+        }
+
+        let ids: Vec<i64> = channel_ids.iter().map(|s| s.into_inner()).collect();
+        let mentions = query.mentions.map(Snowflake::into_inner);
+
+        let count_sql = format!("SELECT COUNT(*) {SEARCH_FILTER}");
+        let total: i64 = sqlx::query_scalar(&count_sql)
+            .bind(&ids)
+            .bind(&query.content)
+            .bind(query.author_id.map(Snowflake::into_inner))
+            .bind(mentions)
+            .bind(query.has_attachment)
+            .bind(query.min_created_at)
+            .bind(query.max_created_at)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        if total <= query.offset {
+            return Ok((total, Vec::new()));
+        }
+
+        let select_sql = format!(
+            r"
+            SELECT id, channel_id, author_id, content, type::TEXT as type, created_at, edited_at,
+                   deleted_at, reference_id, mention_everyone, mention_user_ids,
+                   mention_role_ids, mention_channel_ids
+            {SEARCH_FILTER}
+            ORDER BY id DESC
+            OFFSET $8
+            LIMIT $9
+            "
+        );
+        let results = sqlx::query_as::<_, MessageModel>(&select_sql)
+            .bind(&ids)
+            .bind(&query.content)
+            .bind(query.author_id.map(Snowflake::into_inner))
+            .bind(mentions)
+            .bind(query.has_attachment)
+            .bind(query.min_created_at)
+            .bind(query.max_created_at)
+            .bind(query.offset.max(0))
+            .bind(query.limit.clamp(1, 100))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok((total, results.into_iter().map(Message::from).collect()))
+    }
+
+    #[instrument(skip(self, anchors), fields(anchors = anchors.len()))]
+    async fn find_context(
+        &self,
+        anchors: &[Message],
+        limit: i64,
+    ) -> RepoResult<Vec<(Vec<Message>, Vec<Message>)>> {
+        if anchors.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let anchor_ids: Vec<i64> = anchors.iter().map(|m| m.id.into_inner()).collect();
+        let channel_ids: Vec<i64> = anchors.iter().map(|m| m.channel_id.into_inner()).collect();
+        let results = sqlx::query_as::<_, MessageContextModel>(
+            r"
+            SELECT anchor.anchor_id, m.id, m.channel_id, m.author_id, m.content, m.type::TEXT as type,
+                   m.created_at, m.edited_at, m.deleted_at, m.reference_id, m.mention_everyone,
+                   m.mention_user_ids, m.mention_role_ids, m.mention_channel_ids
+            FROM UNNEST($1::BIGINT[], $2::BIGINT[]) AS anchor(anchor_id, anchor_channel_id)
+            CROSS JOIN LATERAL (
+                (SELECT * FROM messages
+                 WHERE channel_id = anchor.anchor_channel_id AND id < anchor.anchor_id
+                   AND deleted_at IS NULL
+                 ORDER BY id DESC
+                 LIMIT $3)
+                UNION ALL
+                (SELECT * FROM messages
+                 WHERE channel_id = anchor.anchor_channel_id AND id > anchor.anchor_id
+                   AND deleted_at IS NULL
+                 ORDER BY id ASC
+                 LIMIT $3)
+            ) AS m
+            ORDER BY anchor.anchor_id, m.id
+            ",
+        )
+        .bind(&anchor_ids)
+        .bind(&channel_ids)
+        .bind(limit.clamp(1, 100))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        let mut contexts: HashMap<i64, (Vec<Message>, Vec<Message>)> = HashMap::new();
+        for row in results {
+            let (before, after) = contexts.entry(row.anchor_id).or_default();
+            if row.message.id < row.anchor_id {
+                before.push(Message::from(row.message));
+            } else {
+                after.push(Message::from(row.message));
+            }
+        }
+
+        Ok(anchor_ids
+            .iter()
+            .map(|id| contexts.remove(id).unwrap_or_default())
+            .collect())
     }
 
     #[instrument(skip(self))]
@@ -205,7 +455,7 @@ impl MessageRepository for PgMessageRepository {
             Some(msg) => {
                 let attachments = sqlx::query_as::<_, AttachmentModel>(
                     r"
-                    SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, created_at
+                    SELECT id, message_id, filename, content_type, size, url, proxy_url, width, height, verified_image, created_at
                     FROM attachments
                     WHERE message_id = $1
                     ",
@@ -0,0 +1,69 @@
+//! PostgreSQL implementation of MessageRevisionRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::traits::{MessageRevision, MessageRevisionRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::MessageRevisionModel;
+
+use super::error::map_db_error;
+
+/// PostgreSQL implementation of MessageRevisionRepository
+#[derive(Clone)]
+pub struct PgMessageRevisionRepository {
+    pool: PgPool,
+}
+
+impl PgMessageRevisionRepository {
+    /// Create a new PgMessageRevisionRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl From<MessageRevisionModel> for MessageRevision {
+    fn from(model: MessageRevisionModel) -> Self {
+        MessageRevision {
+            id: Snowflake::new(model.id),
+            message_id: Snowflake::new(model.message_id),
+            content: model.content,
+            edited_at: model.edited_at,
+            replaced_at: model.replaced_at,
+        }
+    }
+}
+
+#[async_trait]
+impl MessageRevisionRepository for PgMessageRevisionRepository {
+    #[instrument(skip(self))]
+    async fn find_by_message(&self, message_id: Snowflake) -> RepoResult<Vec<MessageRevision>> {
+        let results = sqlx::query_as::<_, MessageRevisionModel>(
+            r"
+            SELECT id, message_id, content, edited_at, replaced_at
+            FROM message_revisions
+            WHERE message_id = $1
+            ORDER BY id
+            ",
+        )
+        .bind(message_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(MessageRevision::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgMessageRevisionRepository>();
+    }
+}
@@ -4,6 +4,7 @@
 //! Each repository handles database operations for a specific domain entity.
 
 mod attachment;
+mod audit_log;
 mod ban;
 mod channel;
 mod error;
@@ -11,17 +12,30 @@ mod guild;
 mod invite;
 mod member;
 mod message;
+mod message_revision;
+mod permission_overwrite;
+mod pin;
 mod reaction;
+mod read_state;
+mod relationship;
 mod role;
+mod thread;
 mod user;
 
 pub use attachment::PgAttachmentRepository;
+pub use audit_log::PgAuditLogRepository;
 pub use ban::PgBanRepository;
 pub use channel::PgChannelRepository;
 pub use guild::PgGuildRepository;
 pub use invite::PgInviteRepository;
 pub use member::PgMemberRepository;
 pub use message::PgMessageRepository;
+pub use message_revision::PgMessageRevisionRepository;
+pub use permission_overwrite::PgPermissionOverwriteRepository;
+pub use pin::PgPinRepository;
 pub use reaction::PgReactionRepository;
+pub use read_state::PgReadStateRepository;
+pub use relationship::PgRelationshipRepository;
 pub use role::PgRoleRepository;
+pub use thread::PgThreadRepository;
 pub use user::PgUserRepository;
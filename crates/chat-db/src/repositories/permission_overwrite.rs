@@ -0,0 +1,151 @@
+//! PostgreSQL implementation of PermissionOverwriteRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::entities::PermissionOverwrite;
+use chat_core::traits::{PermissionOverwriteRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::mappers::overwrite_type_to_str;
+use crate::models::PermissionOverwriteModel;
+
+use super::error::{map_db_error, overwrite_not_found};
+
+/// PostgreSQL implementation of PermissionOverwriteRepository
+#[derive(Clone)]
+pub struct PgPermissionOverwriteRepository {
+    pool: PgPool,
+}
+
+impl PgPermissionOverwriteRepository {
+    /// Create a new PgPermissionOverwriteRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PermissionOverwriteRepository for PgPermissionOverwriteRepository {
+    #[instrument(skip(self))]
+    async fn find_by_channel(&self, channel_id: Snowflake) -> RepoResult<Vec<PermissionOverwrite>> {
+        let results = sqlx::query_as::<_, PermissionOverwriteModel>(
+            r"
+            SELECT channel_id, target_id, type::TEXT as type, allow, deny, created_at, updated_at
+            FROM permission_overwrites
+            WHERE channel_id = $1
+            ORDER BY type, target_id
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(PermissionOverwrite::from).collect())
+    }
+
+    #[instrument(skip(self, channel_ids), fields(channels = channel_ids.len()))]
+    async fn find_by_channels(
+        &self,
+        channel_ids: &[Snowflake],
+    ) -> RepoResult<Vec<PermissionOverwrite>> {
+        if channel_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<i64> = channel_ids.iter().map(|id| id.into_inner()).collect();
+        let results = sqlx::query_as::<_, PermissionOverwriteModel>(
+            r"
+            SELECT channel_id, target_id, type::TEXT as type, allow, deny, created_at, updated_at
+            FROM permission_overwrites
+            WHERE channel_id = ANY($1)
+            ORDER BY channel_id, type, target_id
+            ",
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(PermissionOverwrite::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn find(
+        &self,
+        channel_id: Snowflake,
+        target_id: Snowflake,
+    ) -> RepoResult<Option<PermissionOverwrite>> {
+        let result = sqlx::query_as::<_, PermissionOverwriteModel>(
+            r"
+            SELECT channel_id, target_id, type::TEXT as type, allow, deny, created_at, updated_at
+            FROM permission_overwrites
+            WHERE channel_id = $1 AND target_id = $2
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(target_id.into_inner())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.map(PermissionOverwrite::from))
+    }
+
+    #[instrument(skip(self))]
+    async fn upsert(&self, overwrite: &PermissionOverwrite) -> RepoResult<()> {
+        sqlx::query(
+            r"
+            INSERT INTO permission_overwrites (channel_id, target_id, type, allow, deny, created_at, updated_at)
+            VALUES ($1, $2, $3::overwrite_type, $4, $5, $6, $7)
+            ON CONFLICT (channel_id, target_id)
+            DO UPDATE SET type = $3::overwrite_type, allow = $4, deny = $5
+            ",
+        )
+        .bind(overwrite.channel_id.into_inner())
+        .bind(overwrite.target_id.into_inner())
+        .bind(overwrite_type_to_str(overwrite.overwrite_type))
+        .bind(overwrite.allow.to_i64())
+        .bind(overwrite.deny.to_i64())
+        .bind(overwrite.created_at)
+        .bind(overwrite.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, channel_id: Snowflake, target_id: Snowflake) -> RepoResult<()> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM permission_overwrites WHERE channel_id = $1 AND target_id = $2
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(target_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(overwrite_not_found());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgPermissionOverwriteRepository>();
+    }
+}
@@ -0,0 +1,202 @@
+//! PostgreSQL implementation of PinRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::error::DomainError;
+use chat_core::traits::{Pin, PinRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::PinModel;
+
+use super::error::{channel_not_found, map_db_error, pin_not_found};
+
+/// PostgreSQL implementation of PinRepository
+#[derive(Clone)]
+pub struct PgPinRepository {
+    pool: PgPool,
+}
+
+impl PgPinRepository {
+    /// Create a new PgPinRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl From<PinModel> for Pin {
+    fn from(model: PinModel) -> Self {
+        Pin {
+            channel_id: Snowflake::new(model.channel_id),
+            message_id: Snowflake::new(model.message_id),
+            pinned_by: Snowflake::new(model.pinned_by),
+            pinned_at: model.pinned_at,
+        }
+    }
+}
+
+#[async_trait]
+impl PinRepository for PgPinRepository {
+    #[instrument(skip(self))]
+    async fn find_by_channel(&self, channel_id: Snowflake) -> RepoResult<Vec<Pin>> {
+        // Pins of deleted messages are hidden rather than removed
+        let results = sqlx::query_as::<_, PinModel>(
+            r"
+            SELECT p.channel_id, p.message_id, p.pinned_by, p.pinned_at
+            FROM pins p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.channel_id = $1 AND m.deleted_at IS NULL
+            ORDER BY p.pinned_at DESC
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Pin::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn is_pinned(&self, channel_id: Snowflake, message_id: Snowflake) -> RepoResult<bool> {
+        let result = sqlx::query_scalar::<_, bool>(
+            r"
+            SELECT EXISTS(SELECT 1 FROM pins WHERE channel_id = $1 AND message_id = $2)
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(message_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    async fn count(&self, channel_id: Snowflake) -> RepoResult<i64> {
+        let result = sqlx::query_scalar::<_, i64>(
+            r"
+            SELECT COUNT(*)
+            FROM pins p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.channel_id = $1 AND m.deleted_at IS NULL
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    async fn create(&self, pin: &Pin, max_pins: i64) -> RepoResult<bool> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        // Serialize pins per channel so the count below stays accurate
+        let channel = sqlx::query_scalar::<_, i64>(
+            r"
+            SELECT id FROM channels WHERE id = $1 FOR UPDATE
+            ",
+        )
+        .bind(pin.channel_id.into_inner())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if channel.is_none() {
+            return Err(channel_not_found(pin.channel_id));
+        }
+
+        let count = sqlx::query_scalar::<_, i64>(
+            r"
+            SELECT COUNT(*)
+            FROM pins p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.channel_id = $1 AND m.deleted_at IS NULL
+            ",
+        )
+        .bind(pin.channel_id.into_inner())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if count >= max_pins {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot pin more than {max_pins} messages in a channel"
+            )));
+        }
+
+        let result = sqlx::query(
+            r"
+            INSERT INTO pins (channel_id, message_id, pinned_by, pinned_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (channel_id, message_id) DO NOTHING
+            ",
+        )
+        .bind(pin.channel_id.into_inner())
+        .bind(pin.message_id.into_inner())
+        .bind(pin.pinned_by.into_inner())
+        .bind(pin.pinned_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, channel_id: Snowflake, message_id: Snowflake) -> RepoResult<()> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM pins WHERE channel_id = $1 AND message_id = $2
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(message_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(pin_not_found(message_id));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn last_pin_timestamp(&self, channel_id: Snowflake) -> RepoResult<Option<DateTime<Utc>>> {
+        let result = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            r"
+            SELECT MAX(p.pinned_at)
+            FROM pins p
+            JOIN messages m ON m.id = p.message_id
+            WHERE p.channel_id = $1 AND m.deleted_at IS NULL
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgPinRepository>();
+    }
+}
@@ -0,0 +1,127 @@
+//! PostgreSQL implementation of ReadStateRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::traits::{ReadState, ReadStateRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::ReadStateModel;
+
+use super::error::map_db_error;
+
+/// PostgreSQL implementation of ReadStateRepository
+#[derive(Clone)]
+pub struct PgReadStateRepository {
+    pool: PgPool,
+}
+
+impl PgReadStateRepository {
+    /// Create a new PgReadStateRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl From<ReadStateModel> for ReadState {
+    fn from(model: ReadStateModel) -> Self {
+        ReadState {
+            user_id: Snowflake::new(model.user_id),
+            channel_id: Snowflake::new(model.channel_id),
+            last_message_id: model.last_message_id.map(Snowflake::new),
+            mention_count: model.mention_count,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+#[async_trait]
+impl ReadStateRepository for PgReadStateRepository {
+    #[instrument(skip(self))]
+    async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<ReadState>> {
+        let results = sqlx::query_as::<_, ReadStateModel>(
+            r"
+            SELECT r.user_id, r.channel_id, r.last_message_id, r.mention_count, r.updated_at
+            FROM read_states r
+            JOIN channels c ON c.id = r.channel_id
+            WHERE r.user_id = $1 AND c.deleted_at IS NULL
+            ORDER BY r.channel_id
+            ",
+        )
+        .bind(user_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(ReadState::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn ack(
+        &self,
+        user_id: Snowflake,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+    ) -> RepoResult<ReadState> {
+        // Acking an older message keeps the newer position and its mention count
+        let result = sqlx::query_as::<_, ReadStateModel>(
+            r"
+            INSERT INTO read_states (user_id, channel_id, last_message_id, mention_count, updated_at)
+            VALUES ($1, $2, $3, 0, NOW())
+            ON CONFLICT (user_id, channel_id) DO UPDATE
+            SET last_message_id = GREATEST(read_states.last_message_id, EXCLUDED.last_message_id),
+                mention_count = CASE
+                    WHEN read_states.last_message_id > EXCLUDED.last_message_id
+                        THEN read_states.mention_count
+                    ELSE 0
+                END,
+                updated_at = NOW()
+            RETURNING user_id, channel_id, last_message_id, mention_count, updated_at
+            ",
+        )
+        .bind(user_id.into_inner())
+        .bind(channel_id.into_inner())
+        .bind(message_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(ReadState::from(result))
+    }
+
+    #[instrument(skip(self, user_ids), fields(count = user_ids.len()))]
+    async fn increment_mentions(&self, channel_id: Snowflake, user_ids: &[Snowflake]) -> RepoResult<()> {
+        if user_ids.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<i64> = user_ids.iter().map(|id| id.into_inner()).collect();
+        sqlx::query(
+            r"
+            INSERT INTO read_states (user_id, channel_id, last_message_id, mention_count, updated_at)
+            SELECT user_id, $1, NULL, 1, NOW() FROM UNNEST($2::BIGINT[]) AS user_id
+            ON CONFLICT (user_id, channel_id) DO UPDATE
+            SET mention_count = read_states.mention_count + 1, updated_at = NOW()
+            ",
+        )
+        .bind(channel_id.into_inner())
+        .bind(&ids)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgReadStateRepository>();
+    }
+}
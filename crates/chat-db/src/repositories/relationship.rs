@@ -0,0 +1,118 @@
+//! PostgreSQL implementation of RelationshipRepository
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::entities::Relationship;
+use chat_core::traits::{RelationshipRepository, RepoResult};
+use chat_core::value_objects::Snowflake;
+
+use crate::mappers::relationship_type_to_str;
+use crate::models::RelationshipModel;
+
+use super::error::map_db_error;
+
+/// PostgreSQL implementation of RelationshipRepository
+#[derive(Clone)]
+pub struct PgRelationshipRepository {
+    pool: PgPool,
+}
+
+impl PgRelationshipRepository {
+    /// Create a new PgRelationshipRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RelationshipRepository for PgRelationshipRepository {
+    #[instrument(skip(self))]
+    async fn find(
+        &self,
+        user_id: Snowflake,
+        target_id: Snowflake,
+    ) -> RepoResult<Option<Relationship>> {
+        let result = sqlx::query_as::<_, RelationshipModel>(
+            r"
+            SELECT user_id, target_id, type::TEXT as type, created_at
+            FROM relationships
+            WHERE user_id = $1 AND target_id = $2
+            ",
+        )
+        .bind(user_id.into_inner())
+        .bind(target_id.into_inner())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.map(Relationship::from))
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_user(&self, user_id: Snowflake) -> RepoResult<Vec<Relationship>> {
+        let results = sqlx::query_as::<_, RelationshipModel>(
+            r"
+            SELECT user_id, target_id, type::TEXT as type, created_at
+            FROM relationships
+            WHERE user_id = $1
+            ORDER BY created_at, target_id
+            ",
+        )
+        .bind(user_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Relationship::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn upsert(&self, relationship: &Relationship) -> RepoResult<()> {
+        sqlx::query(
+            r"
+            INSERT INTO relationships (user_id, target_id, type, created_at)
+            VALUES ($1, $2, $3::relationship_type, $4)
+            ON CONFLICT (user_id, target_id)
+            DO UPDATE SET type = $3::relationship_type, created_at = $4
+            ",
+        )
+        .bind(relationship.user_id.into_inner())
+        .bind(relationship.target_id.into_inner())
+        .bind(relationship_type_to_str(relationship.relationship_type))
+        .bind(relationship.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, user_id: Snowflake, target_id: Snowflake) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM relationships WHERE user_id = $1 AND target_id = $2
+            ",
+        )
+        .bind(user_id.into_inner())
+        .bind(target_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgRelationshipRepository>();
+    }
+}
@@ -0,0 +1,298 @@
+//! PostgreSQL implementation of ThreadRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chat_core::entities::{Thread, ThreadMember, ThreadMetadata};
+use chat_core::traits::{RepoResult, ThreadRepository};
+use chat_core::value_objects::Snowflake;
+
+use crate::models::{ThreadMemberModel, ThreadModel};
+
+use super::error::{map_db_error, thread_not_found};
+
+/// Shared SELECT/FROM clause for thread queries
+const THREAD_SELECT: &str = r"
+    SELECT c.id, c.guild_id, c.name, c.parent_id, c.created_at, c.updated_at,
+           t.owner_id, t.message_id, t.archived, t.locked, t.auto_archive_duration,
+           t.archive_timestamp, t.last_activity_at,
+           (SELECT COUNT(*) FROM thread_members m WHERE m.thread_id = c.id) AS member_count
+    FROM threads t
+    JOIN channels c ON c.id = t.channel_id
+";
+
+/// PostgreSQL implementation of ThreadRepository
+#[derive(Clone)]
+pub struct PgThreadRepository {
+    pool: PgPool,
+}
+
+impl PgThreadRepository {
+    /// Create a new PgThreadRepository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ThreadRepository for PgThreadRepository {
+    #[instrument(skip(self))]
+    async fn find_by_id(&self, id: Snowflake) -> RepoResult<Option<Thread>> {
+        let sql = format!("{THREAD_SELECT} WHERE c.id = $1 AND c.deleted_at IS NULL");
+        let result = sqlx::query_as::<_, ThreadModel>(&sql)
+            .bind(id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.map(Thread::from))
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_message(&self, message_id: Snowflake) -> RepoResult<Option<Thread>> {
+        let sql = format!("{THREAD_SELECT} WHERE t.message_id = $1 AND c.deleted_at IS NULL");
+        let result = sqlx::query_as::<_, ThreadModel>(&sql)
+            .bind(message_id.into_inner())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(result.map(Thread::from))
+    }
+
+    #[instrument(skip(self))]
+    async fn find_active_by_parent(&self, parent_id: Snowflake) -> RepoResult<Vec<Thread>> {
+        let sql = format!(
+            r"
+            {THREAD_SELECT}
+            WHERE c.parent_id = $1 AND c.deleted_at IS NULL AND t.archived = FALSE
+            ORDER BY c.id DESC
+            "
+        );
+        let results = sqlx::query_as::<_, ThreadModel>(&sql)
+            .bind(parent_id.into_inner())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Thread::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_archived_by_parent(
+        &self,
+        parent_id: Snowflake,
+        before: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> RepoResult<Vec<Thread>> {
+        let sql = format!(
+            r"
+            {THREAD_SELECT}
+            WHERE c.parent_id = $1 AND c.deleted_at IS NULL AND t.archived = TRUE
+              AND ($2::TIMESTAMPTZ IS NULL OR t.archive_timestamp < $2)
+            ORDER BY t.archive_timestamp DESC, c.id DESC
+            LIMIT $3
+            "
+        );
+        let results = sqlx::query_as::<_, ThreadModel>(&sql)
+            .bind(parent_id.into_inner())
+            .bind(before)
+            .bind(limit.clamp(1, 100))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Thread::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn create(&self, thread: &Thread) -> RepoResult<()> {
+        let channel = &thread.channel;
+        let metadata = &thread.metadata;
+
+        // The channel row and its metadata must exist together
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        sqlx::query(
+            r"
+            INSERT INTO channels (id, guild_id, name, type, topic, position, parent_id, created_at, updated_at)
+            VALUES ($1, $2, $3, 'thread', NULL, 0, $4, $5, $6)
+            ",
+        )
+        .bind(channel.id.into_inner())
+        .bind(channel.guild_id.map(Snowflake::into_inner))
+        .bind(&channel.name)
+        .bind(channel.parent_id.map(Snowflake::into_inner))
+        .bind(channel.created_at)
+        .bind(channel.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        sqlx::query(
+            r"
+            INSERT INTO threads (channel_id, owner_id, message_id, archived, locked,
+                                 auto_archive_duration, archive_timestamp, last_activity_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ",
+        )
+        .bind(channel.id.into_inner())
+        .bind(metadata.owner_id.into_inner())
+        .bind(metadata.message_id.into_inner())
+        .bind(metadata.archived)
+        .bind(metadata.locked)
+        .bind(metadata.auto_archive_duration)
+        .bind(metadata.archive_timestamp)
+        .bind(metadata.last_activity_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_metadata(&self, id: Snowflake, metadata: &ThreadMetadata) -> RepoResult<()> {
+        let result = sqlx::query(
+            r"
+            UPDATE threads
+            SET archived = $2, locked = $3, auto_archive_duration = $4,
+                archive_timestamp = $5, last_activity_at = $6
+            WHERE channel_id = $1
+            ",
+        )
+        .bind(id.into_inner())
+        .bind(metadata.archived)
+        .bind(metadata.locked)
+        .bind(metadata.auto_archive_duration)
+        .bind(metadata.archive_timestamp)
+        .bind(metadata.last_activity_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(thread_not_found(id));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn archive_inactive(&self, parent_id: Snowflake) -> RepoResult<Vec<Thread>> {
+        let archived_ids = sqlx::query_scalar::<_, i64>(
+            r"
+            UPDATE threads t
+            SET archived = TRUE, archive_timestamp = NOW()
+            FROM channels c
+            WHERE c.id = t.channel_id
+              AND c.parent_id = $1
+              AND c.deleted_at IS NULL
+              AND t.archived = FALSE
+              AND t.last_activity_at + make_interval(mins => t.auto_archive_duration) <= NOW()
+            RETURNING t.channel_id
+            ",
+        )
+        .bind(parent_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        if archived_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!("{THREAD_SELECT} WHERE c.id = ANY($1) ORDER BY c.id DESC");
+        let results = sqlx::query_as::<_, ThreadModel>(&sql)
+            .bind(&archived_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(Thread::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_members(&self, thread_id: Snowflake) -> RepoResult<Vec<ThreadMember>> {
+        let results = sqlx::query_as::<_, ThreadMemberModel>(
+            r"
+            SELECT thread_id, user_id, joined_at
+            FROM thread_members
+            WHERE thread_id = $1
+            ORDER BY joined_at
+            ",
+        )
+        .bind(thread_id.into_inner())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(ThreadMember::from).collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn is_member(&self, thread_id: Snowflake, user_id: Snowflake) -> RepoResult<bool> {
+        let result = sqlx::query_scalar::<_, bool>(
+            r"
+            SELECT EXISTS(SELECT 1 FROM thread_members WHERE thread_id = $1 AND user_id = $2)
+            ",
+        )
+        .bind(thread_id.into_inner())
+        .bind(user_id.into_inner())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    async fn add_member(&self, member: &ThreadMember) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r"
+            INSERT INTO thread_members (thread_id, user_id, joined_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (thread_id, user_id) DO NOTHING
+            ",
+        )
+        .bind(member.thread_id.into_inner())
+        .bind(member.user_id.into_inner())
+        .bind(member.joined_at)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn remove_member(&self, thread_id: Snowflake, user_id: Snowflake) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM thread_members WHERE thread_id = $1 AND user_id = $2
+            ",
+        )
+        .bind(thread_id.into_inner())
+        .bind(user_id.into_inner())
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PgThreadRepository>();
+    }
+}
@@ -1,7 +1,7 @@
 //! PostgreSQL implementation of UserRepository
 
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use tracing::instrument;
 
 use chat_core::entities::User;
@@ -9,6 +9,7 @@ use chat_core::error::DomainError;
 use chat_core::traits::{RepoResult, UserRepository};
 use chat_core::value_objects::Snowflake;
 
+use crate::mappers::dm_privacy_to_str;
 use crate::models::UserModel;
 
 use super::error::{map_db_error, map_unique_violation, user_not_found};
@@ -24,6 +25,33 @@ impl PgUserRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Replace a user's backup codes on an open transaction
+    async fn write_backup_codes(
+        conn: &mut PgConnection,
+        id: Snowflake,
+        backup_code_hashes: &[String],
+    ) -> RepoResult<()> {
+        sqlx::query("DELETE FROM user_backup_codes WHERE user_id = $1")
+            .bind(id.into_inner())
+            .execute(&mut *conn)
+            .await
+            .map_err(map_db_error)?;
+
+        sqlx::query(
+            r"
+            INSERT INTO user_backup_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::TEXT[]) AS code_hash
+            ",
+        )
+        .bind(id.into_inner())
+        .bind(backup_code_hashes)
+        .execute(&mut *conn)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -33,6 +61,7 @@ impl UserRepository for PgUserRepository {
         let result = sqlx::query_as::<_, UserModel>(
             r"
             SELECT id, username, discriminator, email, password_hash, avatar, bot, system,
+                   privileged_intents, dm_privacy::TEXT as dm_privacy, verified, mfa_enabled,
                    created_at, updated_at, deleted_at
             FROM users
             WHERE id = $1 AND deleted_at IS NULL
@@ -46,11 +75,36 @@ impl UserRepository for PgUserRepository {
         Ok(result.map(User::from))
     }
 
+    #[instrument(skip(self, ids), fields(count = ids.len()))]
+    async fn find_by_ids(&self, ids: &[Snowflake]) -> RepoResult<Vec<User>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<i64> = ids.iter().map(|id| id.into_inner()).collect();
+        let results = sqlx::query_as::<_, UserModel>(
+            r"
+            SELECT id, username, discriminator, email, password_hash, avatar, bot, system,
+                   privileged_intents, dm_privacy::TEXT as dm_privacy, verified, mfa_enabled,
+                   created_at, updated_at, deleted_at
+            FROM users
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            ",
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(results.into_iter().map(User::from).collect())
+    }
+
     #[instrument(skip(self))]
     async fn find_by_email(&self, email: &str) -> RepoResult<Option<User>> {
         let result = sqlx::query_as::<_, UserModel>(
             r"
             SELECT id, username, discriminator, email, password_hash, avatar, bot, system,
+                   privileged_intents, dm_privacy::TEXT as dm_privacy, verified, mfa_enabled,
                    created_at, updated_at, deleted_at
             FROM users
             WHERE email = $1 AND deleted_at IS NULL
@@ -69,6 +123,7 @@ impl UserRepository for PgUserRepository {
         let result = sqlx::query_as::<_, UserModel>(
             r"
             SELECT id, username, discriminator, email, password_hash, avatar, bot, system,
+                   privileged_intents, dm_privacy::TEXT as dm_privacy, verified, mfa_enabled,
                    created_at, updated_at, deleted_at
             FROM users
             WHERE username = $1 AND discriminator = $2 AND deleted_at IS NULL
@@ -102,8 +157,9 @@ impl UserRepository for PgUserRepository {
     async fn create(&self, user: &User, password_hash: &str) -> RepoResult<()> {
         sqlx::query(
             r"
-            INSERT INTO users (id, username, discriminator, email, password_hash, avatar, bot, system, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            INSERT INTO users (id, username, discriminator, email, password_hash, avatar, bot, system,
+                               privileged_intents, dm_privacy, verified, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10::dm_privacy, $11, $12, $13)
             ",
         )
         .bind(user.id.into_inner())
@@ -114,6 +170,9 @@ impl UserRepository for PgUserRepository {
         .bind(&user.avatar)
         .bind(user.bot)
         .bind(user.system)
+        .bind(user.privileged_intents)
+        .bind(dm_privacy_to_str(user.dm_privacy))
+        .bind(user.verified)
         .bind(user.created_at)
         .bind(user.updated_at)
         .execute(&self.pool)
@@ -128,13 +187,14 @@ impl UserRepository for PgUserRepository {
         let result = sqlx::query(
             r"
             UPDATE users
-            SET username = $2, avatar = $3, updated_at = NOW()
+            SET username = $2, avatar = $3, dm_privacy = $4::dm_privacy, updated_at = NOW()
             WHERE id = $1 AND deleted_at IS NULL
             ",
         )
         .bind(user.id.into_inner())
         .bind(&user.username)
         .bind(&user.avatar)
+        .bind(dm_privacy_to_str(user.dm_privacy))
         .execute(&self.pool)
         .await
         .map_err(map_db_error)?;
@@ -204,6 +264,127 @@ impl UserRepository for PgUserRepository {
         Ok(())
     }
 
+    #[instrument(skip(self))]
+    async fn mark_verified(&self, id: Snowflake, email: &str) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r"
+            UPDATE users
+            SET verified = TRUE, updated_at = NOW()
+            WHERE id = $1 AND email = $2 AND deleted_at IS NULL
+            ",
+        )
+        .bind(id.into_inner())
+        .bind(email)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_totp_secret(&self, id: Snowflake) -> RepoResult<Option<String>> {
+        let result = sqlx::query_scalar::<_, Option<String>>(
+            r"
+            SELECT totp_secret FROM users WHERE id = $1 AND mfa_enabled AND deleted_at IS NULL
+            ",
+        )
+        .bind(id.into_inner())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.flatten())
+    }
+
+    #[instrument(skip(self, totp_secret, backup_code_hashes))]
+    async fn enable_mfa(
+        &self,
+        id: Snowflake,
+        totp_secret: &str,
+        backup_code_hashes: &[String],
+    ) -> RepoResult<()> {
+        // The secret and its backup codes must be stored together
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let result = sqlx::query(
+            r"
+            UPDATE users
+            SET mfa_enabled = TRUE, totp_secret = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            ",
+        )
+        .bind(id.into_inner())
+        .bind(totp_secret)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(user_not_found(id));
+        }
+
+        Self::write_backup_codes(&mut tx, id, backup_code_hashes).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn disable_mfa(&self, id: Snowflake) -> RepoResult<()> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+
+        let result = sqlx::query(
+            r"
+            UPDATE users
+            SET mfa_enabled = FALSE, totp_secret = NULL, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            ",
+        )
+        .bind(id.into_inner())
+        .execute(&mut *tx)
+        .await
+        .map_err(map_db_error)?;
+
+        if result.rows_affected() == 0 {
+            return Err(user_not_found(id));
+        }
+
+        Self::write_backup_codes(&mut tx, id, &[]).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, backup_code_hashes))]
+    async fn replace_backup_codes(
+        &self,
+        id: Snowflake,
+        backup_code_hashes: &[String],
+    ) -> RepoResult<()> {
+        let mut tx = self.pool.begin().await.map_err(map_db_error)?;
+        Self::write_backup_codes(&mut tx, id, backup_code_hashes).await?;
+        tx.commit().await.map_err(map_db_error)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, code_hash))]
+    async fn consume_backup_code(&self, id: Snowflake, code_hash: &str) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r"
+            DELETE FROM user_backup_codes WHERE user_id = $1 AND code_hash = $2
+            ",
+        )
+        .bind(id.into_inner())
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(map_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     #[instrument(skip(self))]
     async fn next_discriminator(&self, username: &str) -> RepoResult<String> {
         // Find next available discriminator for the username
@@ -8,20 +8,23 @@
 //! cargo test -p chat-db --test integration_tests
 //! ```
 
-use chrono::Utc;
+use chrono::{SubsecRound, Utc};
 use sqlx::PgPool;
 
 use chat_core::entities::{
-    Channel, ChannelType, Guild, GuildMember, Invite, Message, Reaction, Role, User,
+    Channel, ChannelType, DmPrivacy, Guild, GuildMember, Invite, Message, MessageType, MfaLevel,
+    Reaction, Relationship, RelationshipType, Role, User,
 };
 use chat_core::traits::{
     ChannelRepository, GuildRepository, InviteRepository, MemberRepository, MessageQuery,
-    MessageRepository, ReactionRepository, RoleRepository, UserRepository,
+    MessageRepository, MessageRevision, MessageRevisionRepository, Pin, PinRepository,
+    ReactionRepository, RelationshipRepository, RoleRepository, UserRepository,
 };
-use chat_core::value_objects::{Permissions, Snowflake};
+use chat_core::value_objects::{MessageMentions, Permissions, Snowflake};
 use chat_db::{
     PgChannelRepository, PgGuildRepository, PgInviteRepository, PgMemberRepository,
-    PgMessageRepository, PgReactionRepository, PgRoleRepository, PgUserRepository,
+    PgMessageRepository, PgMessageRevisionRepository, PgPinRepository, PgReactionRepository,
+    PgRelationshipRepository, PgRoleRepository, PgUserRepository,
 };
 
 /// Helper to create a test database pool
@@ -48,6 +51,10 @@ fn create_test_user() -> User {
         avatar: None,
         bot: false,
         system: false,
+        privileged_intents: false,
+        dm_privacy: DmPrivacy::Everyone,
+        verified: false,
+        mfa_enabled: false,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -62,6 +69,7 @@ fn create_test_guild(owner_id: Snowflake) -> Guild {
         icon: None,
         description: Some("A test guild".to_string()),
         owner_id,
+        mfa_level: MfaLevel::None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -78,6 +86,8 @@ fn create_test_channel(guild_id: Snowflake) -> Channel {
         topic: Some("Test topic".to_string()),
         position: 0,
         parent_id: None,
+        owner_id: None,
+        icon: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     }
@@ -113,9 +123,11 @@ fn create_test_message(channel_id: Snowflake, author_id: Snowflake) -> Message {
         channel_id,
         author_id,
         content: format!("Test message {}", id.into_inner()),
+        message_type: MessageType::Default,
         created_at: Utc::now(),
         edited_at: None,
         reference_id: None,
+        mentions: MessageMentions::default(),
     }
 }
 
@@ -181,6 +193,91 @@ async fn test_user_email_exists() {
     repo.delete(user.id).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_user_find_by_ids() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let repo = PgUserRepository::new(pool);
+    let user = create_test_user();
+    let deleted = create_test_user();
+    repo.create(&user, "password").await.unwrap();
+    repo.create(&deleted, "password").await.unwrap();
+    repo.delete(deleted.id).await.unwrap();
+
+    // Deleted and unknown users are skipped
+    let found = repo
+        .find_by_ids(&[user.id, deleted.id, test_snowflake()])
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, user.id);
+
+    assert!(repo.find_by_ids(&[]).await.unwrap().is_empty());
+
+    // Clean up
+    repo.delete(user.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_user_mark_verified() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let repo = PgUserRepository::new(pool);
+    let user = create_test_user();
+    repo.create(&user, "password").await.unwrap();
+    assert!(!repo.find_by_id(user.id).await.unwrap().unwrap().verified);
+
+    // A stale email does not verify the account
+    assert!(!repo.mark_verified(user.id, "old@example.com").await.unwrap());
+    assert!(!repo.find_by_id(user.id).await.unwrap().unwrap().verified);
+
+    assert!(repo.mark_verified(user.id, &user.email).await.unwrap());
+    assert!(repo.find_by_id(user.id).await.unwrap().unwrap().verified);
+
+    // Clean up
+    repo.delete(user.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_user_mfa() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let repo = PgUserRepository::new(pool);
+    let user = create_test_user();
+    repo.create(&user, "password").await.unwrap();
+    assert!(repo.get_totp_secret(user.id).await.unwrap().is_none());
+
+    let codes = vec!["hash1".to_string(), "hash2".to_string()];
+    repo.enable_mfa(user.id, "SECRET", &codes).await.unwrap();
+    assert!(repo.find_by_id(user.id).await.unwrap().unwrap().mfa_enabled);
+    assert_eq!(repo.get_totp_secret(user.id).await.unwrap().as_deref(), Some("SECRET"));
+
+    // Backup codes work once
+    assert!(repo.consume_backup_code(user.id, "hash1").await.unwrap());
+    assert!(!repo.consume_backup_code(user.id, "hash1").await.unwrap());
+
+    // Replacing drops the old codes
+    repo.replace_backup_codes(user.id, &["hash3".to_string()]).await.unwrap();
+    assert!(!repo.consume_backup_code(user.id, "hash2").await.unwrap());
+
+    repo.disable_mfa(user.id).await.unwrap();
+    assert!(!repo.find_by_id(user.id).await.unwrap().unwrap().mfa_enabled);
+    assert!(repo.get_totp_secret(user.id).await.unwrap().is_none());
+    assert!(!repo.consume_backup_code(user.id, "hash3").await.unwrap());
+
+    // Clean up
+    repo.delete(user.id).await.unwrap();
+}
+
 // ============================================================================
 // Guild Repository Tests
 // ============================================================================
@@ -260,6 +357,92 @@ async fn test_channel_create_and_find() {
     user_repo.delete(owner.id).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_group_dm_recipients() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let channel_repo = PgChannelRepository::new(pool);
+
+    // Setup
+    let owner = create_test_user();
+    let friend = create_test_user();
+    user_repo.create(&owner, "password").await.unwrap();
+    user_repo.create(&friend, "password").await.unwrap();
+
+    // Create group DM
+    let channel = Channel::new_group_dm(test_snowflake(), owner.id, Some("plans".to_string()));
+    channel_repo.create(&channel).await.unwrap();
+    channel_repo.add_dm_recipient(channel.id, owner.id).await.unwrap();
+    channel_repo.add_dm_recipient(channel.id, friend.id).await.unwrap();
+
+    let found = channel_repo.find_by_id(channel.id).await.unwrap().unwrap();
+    assert_eq!(found.channel_type, ChannelType::GroupDm);
+    assert_eq!(found.owner_id, Some(owner.id));
+
+    // Listed with the user's DMs, recipients in join order
+    let dms = channel_repo.find_dms_by_user(friend.id).await.unwrap();
+    assert!(dms.iter().any(|c| c.id == channel.id));
+    let recipients = channel_repo.get_dm_recipients(channel.id).await.unwrap();
+    assert_eq!(recipients, vec![owner.id, friend.id]);
+
+    // Remove recipient
+    channel_repo.remove_dm_recipient(channel.id, friend.id).await.unwrap();
+    let recipients = channel_repo.get_dm_recipients(channel.id).await.unwrap();
+    assert_eq!(recipients, vec![owner.id]);
+
+    // Clean up
+    channel_repo.delete(channel.id).await.unwrap();
+    user_repo.delete(friend.id).await.unwrap();
+    user_repo.delete(owner.id).await.unwrap();
+}
+
+// ============================================================================
+// Relationship Repository Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_relationship_upsert_and_delete() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let relationship_repo = PgRelationshipRepository::new(pool);
+
+    // Setup
+    let user = create_test_user();
+    let target = create_test_user();
+    user_repo.create(&user, "password").await.unwrap();
+    user_repo.create(&target, "password").await.unwrap();
+
+    // Pending request
+    let outgoing = Relationship::new(user.id, target.id, RelationshipType::OutgoingRequest);
+    relationship_repo.upsert(&outgoing).await.unwrap();
+    let found = relationship_repo.find(user.id, target.id).await.unwrap().unwrap();
+    assert_eq!(found.relationship_type, RelationshipType::OutgoingRequest);
+    assert!(relationship_repo.find(target.id, user.id).await.unwrap().is_none());
+
+    // Accepting replaces the type
+    let friend = Relationship::new(user.id, target.id, RelationshipType::Friend);
+    relationship_repo.upsert(&friend).await.unwrap();
+    let relationships = relationship_repo.find_by_user(user.id).await.unwrap();
+    assert_eq!(relationships.len(), 1);
+    assert!(relationships[0].is_friend());
+
+    // Delete
+    assert!(relationship_repo.delete(user.id, target.id).await.unwrap());
+    assert!(!relationship_repo.delete(user.id, target.id).await.unwrap());
+
+    // Clean up
+    user_repo.delete(target.id).await.unwrap();
+    user_repo.delete(user.id).await.unwrap();
+}
+
 // ============================================================================
 // Message Repository Tests
 // ============================================================================
@@ -314,6 +497,248 @@ async fn test_message_create_and_find() {
     user_repo.delete(owner.id).await.unwrap();
 }
 
+#[tokio::test]
+async fn test_message_revisions_saved_and_purged() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let guild_repo = PgGuildRepository::new(pool.clone());
+    let channel_repo = PgChannelRepository::new(pool.clone());
+    let message_repo = PgMessageRepository::new(pool.clone());
+    let revision_repo = PgMessageRevisionRepository::new(pool);
+
+    // Setup
+    let owner = create_test_user();
+    user_repo.create(&owner, "password").await.unwrap();
+
+    let guild = create_test_guild(owner.id);
+    guild_repo.create(&guild).await.unwrap();
+
+    let channel = create_test_channel(guild.id);
+    channel_repo.create(&channel).await.unwrap();
+
+    let mut message = create_test_message(channel.id, owner.id);
+    message_repo.create(&message).await.unwrap();
+
+    // Edit, keeping the original content as a revision
+    let revision = MessageRevision {
+        id: test_snowflake(),
+        message_id: message.id,
+        content: std::mem::replace(&mut message.content, "Edited".to_string()),
+        edited_at: None,
+        replaced_at: Utc::now(),
+    };
+    message_repo.update(&message, Some(&revision)).await.unwrap();
+
+    let revisions = revision_repo.find_by_message(message.id).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].content, revision.content);
+
+    // A failed edit leaves no revision behind
+    let missing = create_test_message(channel.id, owner.id);
+    let orphan = MessageRevision {
+        id: test_snowflake(),
+        message_id: message.id,
+        content: "Never replaced".to_string(),
+        edited_at: None,
+        replaced_at: Utc::now(),
+    };
+    assert!(message_repo.update(&missing, Some(&orphan)).await.is_err());
+    assert_eq!(revision_repo.find_by_message(message.id).await.unwrap().len(), 1);
+
+    // Soft-deleting the message purges its revisions
+    message_repo.delete(message.id).await.unwrap();
+    assert!(revision_repo.find_by_message(message.id).await.unwrap().is_empty());
+
+    // Clean up
+    channel_repo.delete(channel.id).await.unwrap();
+    guild_repo.delete(guild.id).await.unwrap();
+    user_repo.delete(owner.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_message_update_saves_edited_at() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let guild_repo = PgGuildRepository::new(pool.clone());
+    let channel_repo = PgChannelRepository::new(pool.clone());
+    let message_repo = PgMessageRepository::new(pool.clone());
+    let revision_repo = PgMessageRevisionRepository::new(pool);
+
+    // Setup
+    let owner = create_test_user();
+    user_repo.create(&owner, "password").await.unwrap();
+
+    let guild = create_test_guild(owner.id);
+    guild_repo.create(&guild).await.unwrap();
+
+    let channel = create_test_channel(guild.id);
+    channel_repo.create(&channel).await.unwrap();
+
+    let mut message = create_test_message(channel.id, owner.id);
+    message_repo.create(&message).await.unwrap();
+
+    // First edit stores the timestamp it was given
+    let first_edit = Utc::now().trunc_subsecs(6) - chrono::Duration::minutes(5);
+    message.content = "Second draft".to_string();
+    message.edited_at = Some(first_edit);
+    message_repo.update(&message, None).await.unwrap();
+
+    let found = message_repo.find_by_id(message.id).await.unwrap().unwrap();
+    assert_eq!(found.edited_at, Some(first_edit));
+
+    // Second edit records the first edit's timestamp on the revision
+    let second_edit = Utc::now().trunc_subsecs(6);
+    let revision = MessageRevision {
+        id: test_snowflake(),
+        message_id: message.id,
+        content: std::mem::replace(&mut message.content, "Final".to_string()),
+        edited_at: found.edited_at,
+        replaced_at: second_edit,
+    };
+    message.edited_at = Some(second_edit);
+    message_repo.update(&message, Some(&revision)).await.unwrap();
+
+    let found = message_repo.find_by_id(message.id).await.unwrap().unwrap();
+    assert_eq!(found.edited_at, Some(second_edit));
+    let revisions = revision_repo.find_by_message(message.id).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].edited_at, Some(first_edit));
+
+    // Clean up
+    message_repo.delete(message.id).await.unwrap();
+    channel_repo.delete(channel.id).await.unwrap();
+    guild_repo.delete(guild.id).await.unwrap();
+    user_repo.delete(owner.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_message_find_context() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let guild_repo = PgGuildRepository::new(pool.clone());
+    let channel_repo = PgChannelRepository::new(pool.clone());
+    let message_repo = PgMessageRepository::new(pool);
+
+    // Setup
+    let owner = create_test_user();
+    user_repo.create(&owner, "password").await.unwrap();
+
+    let guild = create_test_guild(owner.id);
+    guild_repo.create(&guild).await.unwrap();
+
+    let channel = create_test_channel(guild.id);
+    channel_repo.create(&channel).await.unwrap();
+
+    let mut messages = Vec::new();
+    for _ in 0..6 {
+        let message = create_test_message(channel.id, owner.id);
+        message_repo.create(&message).await.unwrap();
+        messages.push(message);
+    }
+    message_repo.delete(messages[1].id).await.unwrap();
+
+    // Both anchors are resolved in one call, skipping deleted messages
+    let anchors = [messages[2].clone(), messages[5].clone()];
+    let contexts = message_repo.find_context(&anchors, 2).await.unwrap();
+    assert_eq!(contexts.len(), 2);
+
+    let ids = |side: &[Message]| side.iter().map(|m| m.id).collect::<Vec<_>>();
+    assert_eq!(ids(&contexts[0].0), vec![messages[0].id]);
+    assert_eq!(ids(&contexts[0].1), vec![messages[3].id, messages[4].id]);
+    assert_eq!(ids(&contexts[1].0), vec![messages[3].id, messages[4].id]);
+    assert!(contexts[1].1.is_empty());
+
+    // Clean up
+    for message in &messages {
+        message_repo.delete(message.id).await.ok();
+    }
+    channel_repo.delete(channel.id).await.unwrap();
+    guild_repo.delete(guild.id).await.unwrap();
+    user_repo.delete(owner.id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pin_cap_holds_under_concurrent_pins() {
+    let Some(pool) = get_test_pool().await else {
+        eprintln!("Skipping test: DATABASE_URL not set");
+        return;
+    };
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let guild_repo = PgGuildRepository::new(pool.clone());
+    let channel_repo = PgChannelRepository::new(pool.clone());
+    let message_repo = PgMessageRepository::new(pool.clone());
+    let pin_repo = PgPinRepository::new(pool);
+
+    // Setup
+    let owner = create_test_user();
+    user_repo.create(&owner, "password").await.unwrap();
+
+    let guild = create_test_guild(owner.id);
+    guild_repo.create(&guild).await.unwrap();
+
+    let channel = create_test_channel(guild.id);
+    channel_repo.create(&channel).await.unwrap();
+
+    let mut pins = Vec::new();
+    for _ in 0..6 {
+        let message = create_test_message(channel.id, owner.id);
+        message_repo.create(&message).await.unwrap();
+        pins.push(Pin {
+            channel_id: channel.id,
+            message_id: message.id,
+            pinned_by: owner.id,
+            pinned_at: Utc::now(),
+        });
+    }
+
+    // All pins race for the last three slots
+    let tasks: Vec<_> = pins
+        .iter()
+        .cloned()
+        .map(|pin| {
+            let pin_repo = pin_repo.clone();
+            tokio::spawn(async move { pin_repo.create(&pin, 3).await })
+        })
+        .collect();
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+    assert_eq!(results.iter().filter(|r| matches!(r, Ok(true))).count(), 3);
+    assert_eq!(results.iter().filter(|r| r.is_err()).count(), 3);
+    assert_eq!(pin_repo.count(channel.id).await.unwrap(), 3);
+
+    // Pinning an already pinned message is a no-op
+    let pinned = pins
+        .iter()
+        .zip(&results)
+        .find(|(_, r)| matches!(r, Ok(true)))
+        .map(|(pin, _)| pin)
+        .unwrap();
+    assert!(!pin_repo.create(pinned, 4).await.unwrap());
+
+    // Clean up
+    for pin in &pins {
+        message_repo.delete(pin.message_id).await.ok();
+    }
+    channel_repo.delete(channel.id).await.unwrap();
+    guild_repo.delete(guild.id).await.unwrap();
+    user_repo.delete(owner.id).await.unwrap();
+}
+
 // ============================================================================
 // Role Repository Tests
 // ============================================================================
@@ -410,6 +835,25 @@ async fn test_member_create_and_find() {
     // Check is_member
     assert!(member_repo.is_member(guild.id, owner.id).await.unwrap());
 
+    // A guild is only shared once both users are members
+    let stranger = create_test_user();
+    user_repo.create(&stranger, "password").await.unwrap();
+    assert!(!member_repo.shares_guild(owner.id, stranger.id).await.unwrap());
+    member_repo
+        .create(&GuildMember {
+            guild_id: guild.id,
+            user_id: stranger.id,
+            nickname: None,
+            role_ids: vec![],
+            joined_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    assert!(member_repo.shares_guild(owner.id, stranger.id).await.unwrap());
+    member_repo.delete(guild.id, stranger.id).await.unwrap();
+    user_repo.delete(stranger.id).await.unwrap();
+
     // Clean up
     member_repo.delete(guild.id, owner.id).await.unwrap();
     guild_repo.delete(guild.id).await.unwrap();
@@ -3,10 +3,15 @@
 //! Receives events from Redis Pub/Sub and dispatches them to WebSocket connections.
 
 use crate::connection::ConnectionManager;
-use crate::protocol::GatewayMessage;
-use chat_cache::{PubSubChannel, ReceivedMessage, Subscriber, SubscriberBuilder};
-use chat_core::Snowflake;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::protocol::{CloseCode, GatewayMessage, Shard};
+use chat_cache::{
+    PubSubChannel, ReceivedMessage, Subscriber, SubscriberBuilder, SESSION_REVOKED_EVENT,
+    SESSION_TAKEOVER_EVENT,
+};
+use chat_core::{DomainError, Permissions, Snowflake};
+use chat_service::{PermissionService, ServiceContext};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
@@ -35,12 +40,12 @@ impl Default for EventDispatcherConfig {
 pub struct EventDispatcher {
     /// Connection manager for sending messages
     connection_manager: Arc<ConnectionManager>,
+    /// Service context for permission checks
+    service_context: Arc<ServiceContext>,
     /// Redis subscriber
     subscriber: Subscriber,
     /// Whether the dispatcher is running
     running: Arc<AtomicBool>,
-    /// Sequence number for events
-    sequence: Arc<AtomicU64>,
 }
 
 impl EventDispatcher {
@@ -48,6 +53,7 @@ impl EventDispatcher {
     pub async fn new(
         config: EventDispatcherConfig,
         connection_manager: Arc<ConnectionManager>,
+        service_context: Arc<ServiceContext>,
     ) -> Result<Self, chat_cache::SubscriberError> {
         let subscriber = SubscriberBuilder::new()
             .redis_url(&config.redis_url)
@@ -60,9 +66,9 @@ impl EventDispatcher {
 
         Ok(Self {
             connection_manager,
+            service_context,
             subscriber,
             running: Arc::new(AtomicBool::new(false)),
-            sequence: Arc::new(AtomicU64::new(0)),
         })
     }
 
@@ -106,29 +112,126 @@ impl EventDispatcher {
             .await
     }
 
-    /// Subscribe to a user's events
-    pub async fn subscribe_user(
-        &self,
-        user_id: Snowflake,
-    ) -> Result<(), chat_cache::SubscriberError> {
+    /// Subscribe to the events routed to a gateway node
+    ///
+    /// User-targeted events and session takeovers arrive on the channel of
+    /// the node holding the session.
+    pub async fn subscribe_node(&self, node_id: &str) -> Result<(), chat_cache::SubscriberError> {
         self.subscriber
-            .subscribe(&[PubSubChannel::user(user_id)])
+            .subscribe(&[PubSubChannel::node(node_id)])
             .await
     }
 
-    /// Unsubscribe from a user's events
-    pub async fn unsubscribe_user(
+    /// Subscribe a session to every event source its user receives
+    ///
+    /// Indexes the session under its guilds, their channels and active
+    /// threads, and the user's DM channels, then subscribes to the matching
+    /// Pub/Sub channels. Sharded sessions only get the guilds of their shard,
+    /// and DM channels only on shard 0. Failures are logged and the session
+    /// keeps whatever could be subscribed. User-targeted events arrive on the
+    /// node channel and need no subscription.
+    pub async fn subscribe_session(
         &self,
+        session_id: &str,
         user_id: Snowflake,
-    ) -> Result<(), chat_cache::SubscriberError> {
-        self.subscriber
-            .unsubscribe(&[PubSubChannel::user(user_id)])
-            .await
+        guild_ids: &[Snowflake],
+    ) {
+        let shard = self
+            .connection_manager
+            .get_connection(session_id)
+            .and_then(|conn| conn.shard());
+        let guild_ids: Vec<Snowflake> = guild_ids
+            .iter()
+            .copied()
+            .filter(|id| shard.is_none_or(|shard| shard.owns_guild(*id)))
+            .collect();
+
+        let mut channel_ids = Vec::new();
+        for guild_id in &guild_ids {
+            self.connection_manager
+                .subscribe_to_guild(session_id, *guild_id)
+                .await;
+
+            match self.guild_channel_ids(*guild_id).await {
+                Ok(ids) => channel_ids.extend(ids),
+                Err(e) => {
+                    tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load guild channels");
+                }
+            }
+        }
+
+        if shard.is_none_or(Shard::receives_dms) {
+            match self.service_context.channel_repo().find_dms_by_user(user_id).await {
+                Ok(dms) => channel_ids.extend(dms.iter().map(|c| c.id)),
+                Err(e) => {
+                    tracing::warn!(user_id = %user_id, error = %e, "Failed to load DM channels");
+                }
+            }
+        }
+
+        for channel_id in &channel_ids {
+            self.connection_manager
+                .subscribe_to_channel(session_id, *channel_id)
+                .await;
+        }
+
+        let mut pubsub_channels: Vec<PubSubChannel> =
+            guild_ids.iter().map(|id| PubSubChannel::guild(*id)).collect();
+        pubsub_channels.extend(channel_ids.iter().map(|id| PubSubChannel::channel(*id)));
+
+        if let Err(e) = self.subscriber.subscribe(&pubsub_channels).await {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to subscribe session");
+        }
+    }
+
+    /// Remove a session, dropping Pub/Sub subscriptions no other session needs
+    pub async fn remove_session(&self, session_id: &str) {
+        let Some(connection) = self.connection_manager.get_connection(session_id) else {
+            return;
+        };
+        let guild_ids = connection.guilds().await;
+        let channel_ids = connection.channels().await;
+
+        self.connection_manager.remove_connection(session_id).await;
+
+        let manager = &self.connection_manager;
+        let mut unused: Vec<PubSubChannel> = guild_ids
+            .into_iter()
+            .filter(|id| manager.get_guild_connections(*id).is_empty())
+            .map(PubSubChannel::guild)
+            .collect();
+        unused.extend(
+            channel_ids
+                .into_iter()
+                .filter(|id| manager.get_channel_connections(*id).is_empty())
+                .map(PubSubChannel::channel),
+        );
+
+        if !unused.is_empty() {
+            if let Err(e) = self.subscriber.unsubscribe(&unused).await {
+                tracing::debug!(session_id = %session_id, error = %e, "Failed to unsubscribe");
+            }
+        }
     }
 
-    /// Get the next sequence number
-    fn next_sequence(&self) -> u64 {
-        self.sequence.fetch_add(1, Ordering::SeqCst) + 1
+    /// IDs of a guild's channels and their active threads
+    async fn guild_channel_ids(&self, guild_id: Snowflake) -> Result<Vec<Snowflake>, DomainError> {
+        let channels = self.service_context.channel_repo().find_by_guild(guild_id).await?;
+
+        let mut ids = Vec::with_capacity(channels.len());
+        for channel in channels {
+            if channel.is_text() {
+                let threads = self
+                    .service_context
+                    .thread_repo()
+                    .find_active_by_parent(channel.id)
+                    .await?;
+                ids.extend(threads.iter().map(chat_core::Thread::id));
+            }
+            ids.push(channel.id);
+        }
+
+        Ok(ids)
     }
 
     /// Start the event dispatcher
@@ -199,24 +302,56 @@ impl EventDispatcher {
             "Dispatching event"
         );
 
-        // Create the gateway message
-        let seq = self.next_sequence();
-        let gateway_msg = GatewayMessage::dispatch(event_type, seq, data.clone());
+        // Create the gateway message; each connection sequences it on send
+        let gateway_msg = GatewayMessage::event(event_type, data.clone());
+
+        let exclude_users: Vec<Snowflake> = event
+            .target
+            .as_ref()
+            .map(|t| {
+                t.exclude_users
+                    .iter()
+                    .filter_map(|u| u.parse::<Snowflake>().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Events routed to this node carry their recipient
+        let source = match &msg.channel {
+            PubSubChannel::Node(node_id) => {
+                if event_type == SESSION_TAKEOVER_EVENT {
+                    self.take_over_session(data);
+                    return;
+                }
+                if event_type == SESSION_REVOKED_EVENT {
+                    self.close_revoked_sessions(data);
+                    return;
+                }
+                let Some(user_id) = event
+                    .target
+                    .as_ref()
+                    .and_then(|t| t.user_id.as_deref())
+                    .and_then(|id| id.parse::<Snowflake>().ok())
+                else {
+                    tracing::debug!(
+                        node_id = %node_id,
+                        event_type = %event_type,
+                        "Received node event without recipient, ignoring"
+                    );
+                    return;
+                };
+                PubSubChannel::User(user_id)
+            }
+            channel => channel.clone(),
+        };
 
         // Route based on channel type
-        match &msg.channel {
+        match &source {
             PubSubChannel::Guild(guild_id) => {
                 // Send to all connections subscribed to this guild
-                let exclude_user = event.target.as_ref().and_then(|t| {
-                    t.exclude_users
-                        .first()
-                        .and_then(|u| u.parse::<i64>().ok())
-                        .map(Snowflake::from)
-                });
-
                 let sent = self
                     .connection_manager
-                    .send_to_guild(*guild_id, gateway_msg, exclude_user)
+                    .send_to_guild(*guild_id, gateway_msg, &exclude_users)
                     .await;
 
                 tracing::trace!(
@@ -227,21 +362,27 @@ impl EventDispatcher {
                 );
             }
             PubSubChannel::Channel(channel_id) => {
-                // For channel-specific events, we need to find which guild this belongs to
-                // For now, we'll broadcast to all connections (can be optimized later)
-                // In production, you'd look up the guild from the channel
+                // Send to sessions subscribed to the channel that can view it
+                let sent = self
+                    .send_to_channel(*channel_id, gateway_msg, &exclude_users)
+                    .await;
+
                 tracing::trace!(
                     channel_id = %channel_id,
                     event_type = %event_type,
-                    "Channel event received (routing via guild)"
+                    sent = sent,
+                    "Event dispatched to channel"
                 );
             }
             PubSubChannel::User(user_id) => {
                 // Send to all connections of this user
-                let sent = self
-                    .connection_manager
-                    .send_to_user(*user_id, gateway_msg)
-                    .await;
+                let sent = if exclude_users.contains(user_id) {
+                    0
+                } else {
+                    self.connection_manager
+                        .send_to_user(*user_id, gateway_msg, snowflake_field(data, "guild_id"))
+                        .await
+                };
 
                 tracing::trace!(
                     user_id = %user_id,
@@ -252,7 +393,10 @@ impl EventDispatcher {
             }
             PubSubChannel::Broadcast => {
                 // Send to all connections
-                let sent = self.connection_manager.broadcast(gateway_msg).await;
+                let sent = self
+                    .connection_manager
+                    .broadcast(gateway_msg, &exclude_users)
+                    .await;
 
                 tracing::trace!(
                     event_type = %event_type,
@@ -260,14 +404,168 @@ impl EventDispatcher {
                     "Event broadcast to all"
                 );
             }
-            PubSubChannel::Custom(name) => {
+            PubSubChannel::Node(_) | PubSubChannel::Custom(_) => {
                 tracing::debug!(
-                    channel = %name,
+                    channel = %source,
                     event_type = %event_type,
                     "Received event on custom channel, ignoring"
                 );
             }
         }
+
+        self.track_channel_lifecycle(&source, event_type, data)
+            .await;
+    }
+
+    /// Close a local connection whose session is being resumed on another connection
+    fn take_over_session(&self, data: &Value) {
+        let Some(session_id) = data.get("session_id").and_then(Value::as_str) else {
+            return;
+        };
+
+        if let Some(connection) = self.connection_manager.get_connection(session_id) {
+            tracing::info!(session_id = %session_id, "Session taken over, closing connection");
+            connection.close(CloseCode::SessionTimeout);
+        }
+    }
+
+    /// Close local connections whose login session was revoked
+    fn close_revoked_sessions(&self, data: &Value) {
+        let Some(session_ids) = data.get("session_ids").and_then(Value::as_array) else {
+            return;
+        };
+
+        for session_id in session_ids.iter().filter_map(Value::as_str) {
+            if let Some(connection) = self.connection_manager.get_connection(session_id) {
+                tracing::info!(session_id = %session_id, "Login session revoked, closing connection");
+                connection.close(CloseCode::AuthenticationFailed);
+            }
+        }
+    }
+
+    /// Keep channel subscriptions in step with channels created or deleted after identify
+    ///
+    /// Guild channels and threads are added for every session in the guild.
+    /// DM channels arrive as user events and only concern that user's sessions.
+    async fn track_channel_lifecycle(
+        &self,
+        source: &PubSubChannel,
+        event_type: &str,
+        data: &Value,
+    ) {
+        let subscribe = match event_type {
+            "CHANNEL_CREATE" | "THREAD_CREATE" | "THREAD_UPDATE" => true,
+            "CHANNEL_DELETE" | "THREAD_DELETE" => false,
+            _ => return,
+        };
+        let Some(channel_id) = snowflake_field(data, "id") else {
+            return;
+        };
+
+        let sessions = match (source, snowflake_field(data, "guild_id")) {
+            (_, Some(guild_id)) => self.connection_manager.get_guild_connections(guild_id),
+            (PubSubChannel::User(user_id), None) => self
+                .connection_manager
+                .get_user_connections(*user_id)
+                .into_iter()
+                .filter(|conn| conn.owns(None))
+                .collect(),
+            _ => return,
+        };
+
+        if subscribe {
+            if sessions.is_empty() {
+                return;
+            }
+            for conn in &sessions {
+                self.connection_manager
+                    .subscribe_to_channel(conn.session_id(), channel_id)
+                    .await;
+            }
+            if let Err(e) = self.subscribe_channel(channel_id).await {
+                tracing::warn!(channel_id = %channel_id, error = %e, "Failed to subscribe to channel");
+            }
+        } else {
+            for conn in &sessions {
+                self.connection_manager
+                    .unsubscribe_from_channel(conn.session_id(), channel_id)
+                    .await;
+            }
+            if self
+                .connection_manager
+                .get_channel_connections(channel_id)
+                .is_empty()
+            {
+                self.unsubscribe_channel(channel_id).await.ok();
+            }
+        }
+    }
+
+    /// Send a message to channel connections, limited to users who can view
+    /// the channel and requested the event's intent when it belongs to a guild
+    async fn send_to_channel(
+        &self,
+        channel_id: Snowflake,
+        message: GatewayMessage,
+        exclude_users: &[Snowflake],
+    ) -> usize {
+        let channel = match self.service_context.channel_repo().find_by_id(channel_id).await {
+            Ok(Some(channel)) => channel,
+            Ok(None) => return 0,
+            Err(e) => {
+                tracing::warn!(channel_id = %channel_id, error = %e, "Failed to load channel");
+                return 0;
+            }
+        };
+
+        // DM recipients are exactly the subscribed sessions
+        if channel.guild_id.is_none() {
+            return self
+                .connection_manager
+                .send_to_channel(channel_id, message, exclude_users)
+                .await;
+        }
+
+        let mut recipients = Vec::new();
+        for conn in self.connection_manager.get_channel_connections(channel_id) {
+            let Some(user_id) = conn.user_id().await else {
+                continue;
+            };
+            if exclude_users.contains(&user_id) || !conn.accepts(&message, true) {
+                continue;
+            }
+            recipients.push((conn, user_id));
+        }
+        if recipients.is_empty() {
+            return 0;
+        }
+
+        let mut user_ids: Vec<Snowflake> = recipients.iter().map(|(_, id)| *id).collect();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+
+        let permissions = match PermissionService::new(&self.service_context)
+            .compute_channel_permissions_many(&channel, &user_ids)
+            .await
+        {
+            Ok(permissions) => permissions,
+            Err(e) => {
+                tracing::warn!(channel_id = %channel_id, error = %e, "Failed to compute channel permissions");
+                return 0;
+            }
+        };
+
+        let mut sent = 0;
+        for (conn, user_id) in recipients {
+            let visible = permissions
+                .get(&user_id)
+                .is_some_and(|p| p.has(Permissions::VIEW_CHANNEL));
+            if visible && self.connection_manager.deliver(&conn, message.clone()) {
+                sent += 1;
+            }
+        }
+
+        sent
     }
 
     /// Check if the dispatcher is running
@@ -276,6 +574,11 @@ impl EventDispatcher {
     }
 }
 
+/// Parse a Snowflake string field from an event payload
+fn snowflake_field(data: &Value, field: &str) -> Option<Snowflake> {
+    data.get(field)?.as_str()?.parse().ok()
+}
+
 impl Drop for EventDispatcher {
     fn drop(&mut self) {
         self.running.store(false, Ordering::SeqCst);
@@ -293,4 +596,13 @@ mod tests {
         assert_eq!(config.broadcast_buffer, 1024);
         assert_eq!(config.reconnect_delay_ms, 1000);
     }
+
+    #[test]
+    fn test_snowflake_field() {
+        let data = serde_json::json!({"id": "123", "guild_id": null, "count": 5});
+        assert_eq!(snowflake_field(&data, "id"), Some(Snowflake::from(123i64)));
+        assert_eq!(snowflake_field(&data, "guild_id"), None);
+        assert_eq!(snowflake_field(&data, "count"), None);
+        assert_eq!(snowflake_field(&data, "missing"), None);
+    }
 }
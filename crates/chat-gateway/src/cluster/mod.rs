@@ -0,0 +1,9 @@
+//! Multi-node gateway support
+//!
+//! Node leases and session ownership in Redis, so several gateway processes
+//! can share sessions: user events are routed to the node holding the
+//! user's sessions, and a session can be resumed on any node.
+
+mod node;
+
+pub use node::{GatewayNode, NODE_HEARTBEAT_INTERVAL};
@@ -0,0 +1,170 @@
+//! Gateway node membership
+//!
+//! Registers this gateway process in Redis, keeps its lease alive and
+//! records which sessions it holds. Each heartbeat also releases the sessions
+//! of nodes whose lease expired, so their clients can resume elsewhere.
+
+use chat_cache::{
+    GatewayNodeData, GatewayNodeStore, RedisResult, WebSocketSessionStore, NODE_LEASE_TTL,
+};
+use chat_core::Snowflake;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Interval between lease renewals (a third of the lease)
+pub const NODE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(NODE_LEASE_TTL / 3);
+
+/// This gateway node
+pub struct GatewayNode {
+    /// Node data stored in the lease
+    data: GatewayNodeData,
+    /// Node registry
+    nodes: GatewayNodeStore,
+    /// Session store, for releasing dead nodes' sessions
+    sessions: WebSocketSessionStore,
+    /// Whether the heartbeat task is running
+    running: AtomicBool,
+    /// Signalled to stop the heartbeat task
+    stopped: Notify,
+}
+
+impl GatewayNode {
+    /// Create a node with a new ID
+    pub fn new(
+        nodes: GatewayNodeStore,
+        sessions: WebSocketSessionStore,
+        address: impl Into<String>,
+    ) -> Self {
+        Self {
+            data: GatewayNodeData::new(uuid::Uuid::new_v4().to_string(), address),
+            nodes,
+            sessions,
+            running: AtomicBool::new(false),
+            stopped: Notify::new(),
+        }
+    }
+
+    /// Get the node ID
+    pub fn id(&self) -> &str {
+        &self.data.node_id
+    }
+
+    /// Get the address the node listens on
+    pub fn address(&self) -> &str {
+        &self.data.address
+    }
+
+    /// Register the node or renew its lease
+    pub async fn register(&self) -> RedisResult<()> {
+        self.nodes.register(&self.data).await
+    }
+
+    /// Start renewing the lease in the background
+    pub fn start(self: Arc<Self>) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            tracing::warn!(node_id = %self.id(), "Gateway node heartbeat is already running");
+            return;
+        }
+
+        let node = self.clone();
+        tokio::spawn(async move {
+            node.run().await;
+        });
+
+        tracing::info!(node_id = %self.id(), "Gateway node heartbeat started");
+    }
+
+    /// Stop renewing the lease and remove the node from the registry
+    pub async fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.stopped.notify_one();
+
+        if let Err(e) = self.nodes.deregister(self.id()).await {
+            tracing::warn!(node_id = %self.id(), error = %e, "Failed to deregister gateway node");
+        }
+        tracing::info!(node_id = %self.id(), "Gateway node stopped");
+    }
+
+    /// Heartbeat loop
+    async fn run(&self) {
+        let mut interval = tokio::time::interval(NODE_HEARTBEAT_INTERVAL);
+
+        while self.running.load(Ordering::SeqCst) {
+            tokio::select! {
+                _ = interval.tick() => {}
+                () = self.stopped.notified() => break,
+            }
+
+            if let Err(e) = self.register().await {
+                tracing::warn!(node_id = %self.id(), error = %e, "Failed to renew gateway node lease");
+                continue;
+            }
+
+            match self.reap().await {
+                Ok(0) => {}
+                Ok(released) => {
+                    tracing::info!(released = released, "Released sessions of dead gateway nodes");
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to release sessions of dead gateway nodes");
+                }
+            }
+        }
+    }
+
+    /// Release the sessions of nodes whose lease expired
+    ///
+    /// Their sessions are marked disconnected, which starts the resume window,
+    /// and the nodes are removed from the registry and from their users'
+    /// event routes. Returns the number of sessions released.
+    pub async fn reap(&self) -> RedisResult<usize> {
+        let mut released = 0;
+
+        for node_id in self.nodes.nodes().await? {
+            if node_id == self.id() || self.nodes.is_alive(&node_id).await? {
+                continue;
+            }
+
+            for session_id in self.nodes.node_sessions(&node_id).await? {
+                if self.sessions.mark_disconnected(&session_id).await? {
+                    released += 1;
+                }
+            }
+            self.nodes.deregister(&node_id).await?;
+
+            tracing::info!(node_id = %node_id, "Gateway node lease expired");
+        }
+
+        Ok(released)
+    }
+
+    /// Check if another node's lease is current
+    pub async fn is_alive(&self, node_id: &str) -> RedisResult<bool> {
+        self.nodes.is_alive(node_id).await
+    }
+
+    /// Record a session held by this node
+    pub async fn add_session(&self, session_id: &str, user_id: Snowflake) -> RedisResult<()> {
+        self.nodes.add_session(self.id(), session_id, user_id).await
+    }
+
+    /// Forget a session held by this node
+    ///
+    /// The user's events stop being routed here with their last session.
+    pub async fn remove_session(&self, session_id: &str, user_id: Snowflake) -> RedisResult<()> {
+        self.nodes
+            .remove_session(self.id(), session_id, user_id)
+            .await
+    }
+}
+
+impl std::fmt::Debug for GatewayNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GatewayNode")
+            .field("id", &self.data.node_id)
+            .field("address", &self.data.address)
+            .finish()
+    }
+}
@@ -2,14 +2,19 @@
 //!
 //! Represents a single WebSocket connection and its state.
 
-use crate::protocol::GatewayMessage;
+use super::queue::{CoalesceKey, Delivery, OVERFLOW_LIMIT};
+use crate::events::GatewayEventType;
+use crate::protocol::{CloseCode, GatewayMessage, Intents, OpCode, Shard};
+use crate::rate_limit::ConnectionRateLimiter;
 use chat_core::Snowflake;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, Notify, RwLock};
 
 /// Connection state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,9 +43,39 @@ pub struct Connection {
     /// Channel to send messages to the WebSocket
     sender: mpsc::Sender<GatewayMessage>,
 
+    /// Latest version of each coalescable event still in the queue
+    pending: Mutex<HashMap<CoalesceKey, GatewayMessage>>,
+
+    /// Events that arrived after the queue filled up (None while keeping up)
+    overflow: Mutex<Option<Vec<GatewayMessage>>>,
+
+    /// Whether events were lost after the overflow filled up too
+    events_lost: AtomicBool,
+
+    /// Whether the overflow was drained for good, so later events are lost
+    overflow_sealed: AtomicBool,
+
+    /// Signalled when the queue first fills up
+    lagged: Notify,
+
+    /// Close code requested from outside the connection's tasks
+    close_code: Mutex<Option<CloseCode>>,
+
+    /// Signalled when a close is requested
+    close_requested: Notify,
+
     /// Last sequence number sent
     sequence: AtomicU64,
 
+    /// Intents requested in Identify (bits of [`Intents`])
+    intents: AtomicU64,
+
+    /// Whether payloads are zlib-compressed individually (Identify `compress`)
+    compress_payloads: AtomicBool,
+
+    /// Shard sent in Identify (None for unsharded connections)
+    shard: OnceLock<Shard>,
+
     /// Last heartbeat received
     last_heartbeat: RwLock<Instant>,
 
@@ -50,6 +85,12 @@ pub struct Connection {
     /// Guilds this connection is subscribed to
     guilds: RwLock<HashSet<Snowflake>>,
 
+    /// Channels (guild channels, threads and DMs) this connection is subscribed to
+    channels: RwLock<HashSet<Snowflake>>,
+
+    /// Limits on payloads received from the client
+    rate_limiter: ConnectionRateLimiter,
+
     /// Connection creation time
     created_at: Instant,
 }
@@ -62,10 +103,22 @@ impl Connection {
             user_id: RwLock::new(None),
             state: RwLock::new(ConnectionState::Connecting),
             sender,
+            pending: Mutex::new(HashMap::new()),
+            overflow: Mutex::new(None),
+            events_lost: AtomicBool::new(false),
+            overflow_sealed: AtomicBool::new(false),
+            lagged: Notify::new(),
+            close_code: Mutex::new(None),
+            close_requested: Notify::new(),
             sequence: AtomicU64::new(0),
+            intents: AtomicU64::new(Intents::all().bits()),
+            compress_payloads: AtomicBool::new(false),
+            shard: OnceLock::new(),
             last_heartbeat: RwLock::new(Instant::now()),
             heartbeat_acked: RwLock::new(true),
             guilds: RwLock::new(HashSet::new()),
+            channels: RwLock::new(HashSet::new()),
+            rate_limiter: ConnectionRateLimiter::new(),
             created_at: Instant::now(),
         })
     }
@@ -115,6 +168,75 @@ impl Connection {
         self.sequence.store(seq, Ordering::SeqCst);
     }
 
+    /// Assign the next sequence number to an unsequenced Dispatch message
+    ///
+    /// Called by the send loop so sequence numbers follow delivery order.
+    /// Messages that already carry a sequence (replayed events) keep it.
+    pub fn assign_sequence(&self, message: &mut GatewayMessage) {
+        if message.op == OpCode::Dispatch && message.s.is_none() {
+            message.s = Some(self.next_sequence());
+        }
+    }
+
+    /// Get the intents this session receives
+    pub fn intents(&self) -> Intents {
+        Intents::from_bits_truncate(self.intents.load(Ordering::SeqCst))
+    }
+
+    /// Set the intents (on Identify or Resume)
+    pub fn set_intents(&self, intents: Intents) {
+        self.intents.store(intents.bits(), Ordering::SeqCst);
+    }
+
+    /// Check if payloads are compressed individually
+    pub fn compresses_payloads(&self) -> bool {
+        self.compress_payloads.load(Ordering::SeqCst)
+    }
+
+    /// Enable or disable payload compression (on Identify)
+    pub fn set_compress_payloads(&self, enabled: bool) {
+        self.compress_payloads.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Get the shard sent in Identify
+    pub fn shard(&self) -> Option<Shard> {
+        self.shard.get().copied()
+    }
+
+    /// Assign the connection's shard (on Identify or Resume; set at most once)
+    pub fn set_shard(&self, shard: Shard) {
+        self.shard.set(shard).ok();
+    }
+
+    /// Check if this connection's shard receives events for `guild_id`
+    ///
+    /// Events without a guild (DMs, user events) only go to shard 0.
+    /// Unsharded connections receive everything.
+    pub fn owns(&self, guild_id: Option<Snowflake>) -> bool {
+        self.shard().is_none_or(|shard| match guild_id {
+            Some(guild_id) => shard.owns_guild(guild_id),
+            None => shard.receives_dms(),
+        })
+    }
+
+    /// Check if the session requested the intent a Dispatch message needs
+    ///
+    /// `in_guild` tells guild events apart from DM events. Non-dispatch
+    /// messages and unknown event types are always accepted.
+    pub fn accepts(&self, message: &GatewayMessage, in_guild: bool) -> bool {
+        let required = message
+            .t
+            .as_deref()
+            .and_then(GatewayEventType::from_str)
+            .and_then(|event_type| event_type.required_intent(in_guild));
+        self.intents().allows(required)
+    }
+
+    /// Get the limits on payloads received from the client
+    pub fn rate_limiter(&self) -> &ConnectionRateLimiter {
+        &self.rate_limiter
+    }
+
     /// Record a heartbeat received
     pub async fn record_heartbeat(&self) {
         *self.last_heartbeat.write().await = Instant::now();
@@ -165,6 +287,29 @@ impl Connection {
         *self.guilds.write().await = guilds.into_iter().collect();
     }
 
+    /// Add a channel subscription
+    pub async fn subscribe_channel(&self, channel_id: Snowflake) {
+        self.channels.write().await.insert(channel_id);
+    }
+
+    /// Remove a channel subscription
+    pub async fn unsubscribe_channel(&self, channel_id: Snowflake) {
+        self.channels.write().await.remove(&channel_id);
+    }
+
+    /// Get all subscribed channels
+    pub async fn channels(&self) -> Vec<Snowflake> {
+        self.channels.read().await.iter().copied().collect()
+    }
+
+    /// Check if the connection belongs to one of the given users
+    pub async fn is_any_user(&self, user_ids: &[Snowflake]) -> bool {
+        match self.user_id().await {
+            Some(user_id) => user_ids.contains(&user_id),
+            None => false,
+        }
+    }
+
     /// Get connection age
     pub fn age(&self) -> std::time::Duration {
         self.created_at.elapsed()
@@ -175,6 +320,134 @@ impl Connection {
         self.sender.send(message).await
     }
 
+    /// Hand an event to this connection without waiting
+    ///
+    /// Coalescable events replace a queued one they supersede. When the queue
+    /// is full the connection starts lagging: this and later events are kept
+    /// for the resume queue (up to [`OVERFLOW_LIMIT`]) and the send loop is
+    /// woken to tell the client to reconnect.
+    pub fn dispatch(&self, message: GatewayMessage) -> Delivery {
+        if let Some(overflow) = self.overflow.lock().as_mut() {
+            return self.defer(overflow, message);
+        }
+
+        let key = CoalesceKey::of(&message);
+        if let Some(key) = key {
+            let mut pending = self.pending.lock();
+            if let Some(queued) = pending.get_mut(&key) {
+                *queued = message;
+                return Delivery::Coalesced;
+            }
+            pending.insert(key, message.clone());
+        }
+
+        match self.sender.try_send(message) {
+            Ok(()) => Delivery::Queued,
+            Err(TrySendError::Closed(_)) => {
+                if let Some(key) = key {
+                    self.pending.lock().remove(&key);
+                }
+                Delivery::Closed
+            }
+            Err(TrySendError::Full(message)) => {
+                // Keep the newest version if another event coalesced into it meanwhile
+                let message = key
+                    .and_then(|key| self.pending.lock().remove(&key))
+                    .unwrap_or(message);
+
+                let mut overflow = self.overflow.lock();
+                if let Some(overflow) = overflow.as_mut() {
+                    return self.defer(overflow, message);
+                }
+                *overflow = Some(vec![message]);
+                self.lagged.notify_one();
+                Delivery::Overflowed
+            }
+        }
+    }
+
+    /// Keep an event for the resume queue of a lagging connection
+    fn defer(&self, overflow: &mut Vec<GatewayMessage>, message: GatewayMessage) -> Delivery {
+        if overflow.len() >= OVERFLOW_LIMIT || self.overflow_sealed.load(Ordering::SeqCst) {
+            self.events_lost.store(true, Ordering::SeqCst);
+            return Delivery::Dropped;
+        }
+        overflow.push(message);
+        Delivery::Deferred
+    }
+
+    /// Swap a dequeued event for the latest version coalesced into it
+    pub fn take_latest(&self, message: GatewayMessage) -> GatewayMessage {
+        match CoalesceKey::of(&message) {
+            Some(key) => self.pending.lock().remove(&key).unwrap_or(message),
+            None => message,
+        }
+    }
+
+    /// Check if the queue has filled up
+    pub fn is_lagging(&self) -> bool {
+        self.overflow.lock().is_some()
+    }
+
+    /// Wait until the queue fills up
+    pub async fn lagged(&self) {
+        if !self.is_lagging() {
+            self.lagged.notified().await;
+        }
+    }
+
+    /// Ask the connection to close with `code`
+    ///
+    /// Used when the session is taken over by a resume on another connection.
+    pub fn close(&self, code: CloseCode) {
+        self.close_code.lock().get_or_insert(code);
+        self.close_requested.notify_one();
+    }
+
+    /// Wait until a close is requested, returning its close code
+    pub async fn close_requested(&self) -> CloseCode {
+        loop {
+            if let Some(code) = *self.close_code.lock() {
+                return code;
+            }
+            self.close_requested.notified().await;
+        }
+    }
+
+    /// Take the events kept after the queue filled up
+    pub fn take_overflow(&self) -> Vec<GatewayMessage> {
+        self.overflow
+            .lock()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Take the events kept after the queue filled up for the last time
+    ///
+    /// Called once the connection no longer receives events; any event
+    /// deferred afterwards is counted as lost.
+    pub fn seal_overflow(&self) -> Vec<GatewayMessage> {
+        let mut overflow = self.overflow.lock();
+        self.overflow_sealed.store(true, Ordering::SeqCst);
+        overflow.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Check if events were lost while lagging, so the session cannot be resumed
+    pub fn lost_events(&self) -> bool {
+        self.events_lost.load(Ordering::SeqCst)
+    }
+
+    /// Get the number of events waiting in the queue
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Get the queue capacity
+    pub fn queue_capacity(&self) -> usize {
+        self.sender.max_capacity()
+    }
+
     /// Try to send a message (non-blocking)
     pub fn try_send(&self, message: GatewayMessage) -> Result<(), mpsc::error::TrySendError<GatewayMessage>> {
         self.sender.try_send(message)
@@ -244,6 +517,152 @@ mod tests {
         assert_eq!(conn.current_sequence(), 100);
     }
 
+    #[tokio::test]
+    async fn test_connection_assign_sequence() {
+        let (tx, _rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+
+        let mut event = GatewayMessage::event("MESSAGE_CREATE", serde_json::json!({}));
+        conn.assign_sequence(&mut event);
+        assert_eq!(event.s, Some(1));
+
+        // Replayed events keep their original sequence
+        let mut replayed = GatewayMessage::dispatch("MESSAGE_CREATE", 7, serde_json::json!({}));
+        conn.assign_sequence(&mut replayed);
+        assert_eq!(replayed.s, Some(7));
+
+        let mut ack = GatewayMessage::heartbeat_ack();
+        conn.assign_sequence(&mut ack);
+        assert_eq!(ack.s, None);
+        assert_eq!(conn.current_sequence(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_intents() {
+        let (tx, _rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+        conn.set_intents(Intents::GUILDS | Intents::DIRECT_MESSAGES);
+
+        let message = GatewayMessage::event("MESSAGE_CREATE", serde_json::json!({}));
+        assert!(!conn.accepts(&message, true));
+        assert!(conn.accepts(&message, false));
+
+        let ack = GatewayMessage::event("MESSAGE_ACK", serde_json::json!({}));
+        assert!(conn.accepts(&ack, true));
+        assert!(conn.accepts(&GatewayMessage::heartbeat_ack(), true));
+    }
+
+    #[tokio::test]
+    async fn test_connection_shard() {
+        let (tx, _rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+        let guild_id = Snowflake::from(1i64 << 22);
+        assert!(conn.owns(Some(guild_id)));
+        assert!(conn.owns(None));
+
+        conn.set_shard(Shard { id: 1, count: 2 });
+        assert!(conn.owns(Some(guild_id)));
+        assert!(!conn.owns(Some(Snowflake::from(2i64 << 22))));
+        assert!(!conn.owns(None));
+
+        conn.set_shard(Shard { id: 0, count: 2 });
+        assert_eq!(conn.shard(), Some(Shard { id: 1, count: 2 }));
+    }
+
+    #[tokio::test]
+    async fn test_connection_dispatch_coalesces() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+        let typing = |ts: i64| {
+            GatewayMessage::event(
+                "TYPING_START",
+                serde_json::json!({"channel_id": "5", "user_id": "1", "timestamp": ts}),
+            )
+        };
+
+        assert_eq!(conn.dispatch(typing(1)), Delivery::Queued);
+        assert_eq!(conn.dispatch(typing(2)), Delivery::Coalesced);
+        assert_eq!(conn.queue_depth(), 1);
+
+        // The send loop gets the latest version
+        let queued = rx.try_recv().unwrap();
+        let sent = conn.take_latest(queued);
+        assert_eq!(sent.d.unwrap()["timestamp"], 2);
+
+        // Once sent, a new event is queued again
+        assert_eq!(conn.dispatch(typing(3)), Delivery::Queued);
+    }
+
+    #[tokio::test]
+    async fn test_connection_dispatch_overflow() {
+        let (tx, mut rx) = mpsc::channel(2);
+        let conn = Connection::new("session123".to_string(), tx);
+        let event = || GatewayMessage::event("MESSAGE_CREATE", serde_json::json!({}));
+
+        assert_eq!(conn.dispatch(event()), Delivery::Queued);
+        assert_eq!(conn.dispatch(event()), Delivery::Queued);
+        assert!(!conn.is_lagging());
+        assert_eq!(conn.queue_depth(), 2);
+
+        assert_eq!(conn.dispatch(event()), Delivery::Overflowed);
+        assert!(conn.is_lagging());
+        conn.lagged().await;
+
+        // Later events are kept for resume even if the queue drains
+        rx.try_recv().unwrap();
+        assert_eq!(conn.dispatch(event()), Delivery::Deferred);
+        assert_eq!(conn.take_overflow().len(), 2);
+
+        for _ in 0..OVERFLOW_LIMIT {
+            conn.dispatch(event());
+        }
+        assert!(!conn.lost_events());
+        assert_eq!(conn.dispatch(event()), Delivery::Dropped);
+        assert!(conn.lost_events());
+    }
+
+    #[tokio::test]
+    async fn test_connection_overflow_after_lag_flush() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let conn = Connection::new("session123".to_string(), tx);
+        let event = || GatewayMessage::event("MESSAGE_CREATE", serde_json::json!({}));
+
+        assert_eq!(conn.dispatch(event()), Delivery::Queued);
+        assert_eq!(conn.dispatch(event()), Delivery::Overflowed);
+
+        // The send loop flushes the queue and overflow for resume
+        let mut flushed = vec![rx.try_recv().unwrap()];
+        flushed.extend(conn.take_overflow());
+        for msg in &mut flushed {
+            conn.assign_sequence(msg);
+        }
+        assert_eq!(flushed.last().unwrap().s, Some(2));
+
+        // An event arriving before cleanup is kept and sequenced after them
+        assert_eq!(conn.dispatch(event()), Delivery::Deferred);
+        let mut remaining = conn.seal_overflow();
+        assert_eq!(remaining.len(), 1);
+        conn.assign_sequence(&mut remaining[0]);
+        assert_eq!(remaining[0].s, Some(3));
+        assert!(!conn.lost_events());
+
+        // Once sealed, later events can no longer be replayed
+        assert_eq!(conn.dispatch(event()), Delivery::Dropped);
+        assert!(conn.lost_events());
+    }
+
+    #[tokio::test]
+    async fn test_connection_close_requested() {
+        let (tx, _rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+
+        conn.close(CloseCode::SessionTimeout);
+        conn.close(CloseCode::UnknownError);
+
+        // The first requested code wins
+        assert_eq!(conn.close_requested().await, CloseCode::SessionTimeout);
+    }
+
     #[tokio::test]
     async fn test_connection_guilds() {
         let (tx, _rx) = mpsc::channel(10);
@@ -264,6 +683,20 @@ mod tests {
         assert!(conn.is_subscribed_to(guild2).await);
     }
 
+    #[tokio::test]
+    async fn test_connection_channels() {
+        let (tx, _rx) = mpsc::channel(10);
+        let conn = Connection::new("session123".to_string(), tx);
+
+        let channel_id = Snowflake::from(3i64);
+        conn.subscribe_channel(channel_id).await;
+        conn.subscribe_channel(channel_id).await;
+        assert_eq!(conn.channels().await, vec![channel_id]);
+
+        conn.unsubscribe_channel(channel_id).await;
+        assert!(conn.channels().await.is_empty());
+    }
+
     #[tokio::test]
     async fn test_connection_heartbeat() {
         let (tx, _rx) = mpsc::channel(10);
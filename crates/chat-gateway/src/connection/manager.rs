@@ -2,7 +2,7 @@
 //!
 //! Manages all active WebSocket connections using DashMap for thread-safe access.
 
-use super::{Connection, ConnectionState};
+use super::{Connection, ConnectionState, Delivery, LaggingConnection, QueueMetrics, QueueSnapshot};
 use crate::protocol::GatewayMessage;
 use chat_core::Snowflake;
 use dashmap::DashMap;
@@ -22,6 +22,12 @@ pub struct ConnectionManager {
 
     /// Guild ID to session IDs mapping
     guild_connections: DashMap<Snowflake, HashSet<String>>,
+
+    /// Channel ID to session IDs mapping
+    channel_connections: DashMap<Snowflake, HashSet<String>>,
+
+    /// Outbound queue counters
+    queue_metrics: QueueMetrics,
 }
 
 impl ConnectionManager {
@@ -32,6 +38,8 @@ impl ConnectionManager {
             connections: DashMap::new(),
             user_connections: DashMap::new(),
             guild_connections: DashMap::new(),
+            channel_connections: DashMap::new(),
+            queue_metrics: QueueMetrics::default(),
         }
     }
 
@@ -84,6 +92,15 @@ impl ConnectionManager {
             // Clean up all empty guild entries atomically
             self.guild_connections.retain(|_, sessions| !sessions.is_empty());
 
+            // Remove from channel mappings
+            for channel_id in connection.channels().await {
+                self.channel_connections.alter(&channel_id, |_, mut sessions| {
+                    sessions.remove(session_id);
+                    sessions
+                });
+            }
+            self.channel_connections.retain(|_, sessions| !sessions.is_empty());
+
             tracing::debug!(session_id = %session_id, "Connection removed");
         }
     }
@@ -167,6 +184,51 @@ impl ConnectionManager {
         }
     }
 
+    /// Subscribe a connection to a channel
+    pub async fn subscribe_to_channel(&self, session_id: &str, channel_id: Snowflake) -> bool {
+        if let Some(connection) = self.connections.get(session_id) {
+            connection.subscribe_channel(channel_id).await;
+
+            self.channel_connections
+                .entry(channel_id)
+                .or_default()
+                .insert(session_id.to_string());
+
+            tracing::trace!(
+                session_id = %session_id,
+                channel_id = %channel_id,
+                "Connection subscribed to channel"
+            );
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Unsubscribe a connection from a channel
+    pub async fn unsubscribe_from_channel(&self, session_id: &str, channel_id: Snowflake) -> bool {
+        if let Some(connection) = self.connections.get(session_id) {
+            connection.unsubscribe_channel(channel_id).await;
+
+            self.channel_connections.alter(&channel_id, |_, mut sessions| {
+                sessions.remove(session_id);
+                sessions
+            });
+            self.channel_connections.retain(|_, sessions| !sessions.is_empty());
+
+            tracing::trace!(
+                session_id = %session_id,
+                channel_id = %channel_id,
+                "Connection unsubscribed from channel"
+            );
+
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get all connections for a user
     pub fn get_user_connections(&self, user_id: Snowflake) -> Vec<Arc<Connection>> {
         self.user_connections
@@ -193,13 +255,57 @@ impl ConnectionManager {
             .unwrap_or_default()
     }
 
+    /// Get all connections subscribed to a channel
+    pub fn get_channel_connections(&self, channel_id: Snowflake) -> Vec<Arc<Connection>> {
+        self.channel_connections
+            .get(&channel_id)
+            .map(|sessions| {
+                sessions
+                    .iter()
+                    .filter_map(|sid| self.connections.get(sid).map(|c| c.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Hand a message to a connection without waiting and count the outcome
+    ///
+    /// Returns true if the client will receive it. Fan-out goes through here
+    /// so one slow connection cannot hold up the others.
+    pub fn deliver(&self, connection: &Connection, message: GatewayMessage) -> bool {
+        let delivery = connection.dispatch(message);
+        self.queue_metrics.record(delivery);
+
+        match delivery {
+            Delivery::Overflowed => tracing::warn!(
+                session_id = %connection.session_id(),
+                "Outbound queue full, dropping slow connection"
+            ),
+            Delivery::Dropped => tracing::debug!(
+                session_id = %connection.session_id(),
+                "Event dropped for lagging connection"
+            ),
+            _ => {}
+        }
+
+        delivery.is_accepted()
+    }
+
     /// Send a message to all connections of a user
-    pub async fn send_to_user(&self, user_id: Snowflake, message: GatewayMessage) -> usize {
+    ///
+    /// Sharded connections only receive it on the shard owning `guild_id`,
+    /// or on shard 0 when the event has no guild.
+    pub async fn send_to_user(
+        &self,
+        user_id: Snowflake,
+        message: GatewayMessage,
+        guild_id: Option<Snowflake>,
+    ) -> usize {
         let connections = self.get_user_connections(user_id);
         let mut sent = 0;
 
         for conn in connections {
-            if conn.send(message.clone()).await.is_ok() {
+            if conn.owns(guild_id) && self.deliver(&conn, message.clone()) {
                 sent += 1;
             }
         }
@@ -218,20 +324,18 @@ impl ConnectionManager {
         &self,
         guild_id: Snowflake,
         message: GatewayMessage,
-        exclude_user: Option<Snowflake>,
+        exclude_users: &[Snowflake],
     ) -> usize {
         let connections = self.get_guild_connections(guild_id);
         let mut sent = 0;
 
         for conn in connections {
-            // Skip excluded user
-            if let Some(exclude) = exclude_user {
-                if conn.user_id().await == Some(exclude) {
-                    continue;
-                }
+            // Skip excluded users and sessions without the event's intent
+            if !conn.accepts(&message, true) || conn.is_any_user(exclude_users).await {
+                continue;
             }
 
-            if conn.send(message.clone()).await.is_ok() {
+            if self.deliver(&conn, message.clone()) {
                 sent += 1;
             }
         }
@@ -245,12 +349,50 @@ impl ConnectionManager {
         sent
     }
 
+    /// Send a message to all connections subscribed to a DM channel
+    ///
+    /// Every subscribed session belongs to a recipient, so only DM intents
+    /// and exclusions are checked.
+    pub async fn send_to_channel(
+        &self,
+        channel_id: Snowflake,
+        message: GatewayMessage,
+        exclude_users: &[Snowflake],
+    ) -> usize {
+        let connections = self.get_channel_connections(channel_id);
+        let mut sent = 0;
+
+        for conn in connections {
+            if !conn.accepts(&message, false) || conn.is_any_user(exclude_users).await {
+                continue;
+            }
+
+            if self.deliver(&conn, message.clone()) {
+                sent += 1;
+            }
+        }
+
+        tracing::trace!(
+            channel_id = %channel_id,
+            sent = sent,
+            "Message sent to channel connections"
+        );
+
+        sent
+    }
+
     /// Broadcast a message to all connections
-    pub async fn broadcast(&self, message: GatewayMessage) -> usize {
+    pub async fn broadcast(&self, message: GatewayMessage, exclude_users: &[Snowflake]) -> usize {
         let mut sent = 0;
 
-        for entry in &self.connections {
-            if entry.send(message.clone()).await.is_ok() {
+        let connections: Vec<Arc<Connection>> =
+            self.connections.iter().map(|r| r.value().clone()).collect();
+        for conn in connections {
+            if conn.is_any_user(exclude_users).await {
+                continue;
+            }
+
+            if self.deliver(&conn, message.clone()) {
                 sent += 1;
             }
         }
@@ -275,6 +417,38 @@ impl ConnectionManager {
         self.guild_connections.len()
     }
 
+    /// Get the number of channels with active connections
+    pub fn channel_count(&self) -> usize {
+        self.channel_connections.len()
+    }
+
+    /// Get the outbound queue counters
+    pub fn queue_metrics(&self) -> QueueSnapshot {
+        self.queue_metrics.snapshot()
+    }
+
+    /// Get connections whose queue is at least half full, deepest first
+    pub async fn lagging_connections(&self) -> Vec<LaggingConnection> {
+        let connections: Vec<Arc<Connection>> = self
+            .connections
+            .iter()
+            .filter(|r| r.queue_depth() * 2 >= r.queue_capacity())
+            .map(|r| r.value().clone())
+            .collect();
+
+        let mut lagging = Vec::with_capacity(connections.len());
+        for conn in connections {
+            lagging.push(LaggingConnection {
+                session_id: conn.session_id().to_string(),
+                user_id: conn.user_id().await,
+                depth: conn.queue_depth(),
+                capacity: conn.queue_capacity(),
+            });
+        }
+        lagging.sort_by_key(|c| std::cmp::Reverse(c.depth));
+        lagging
+    }
+
     /// Get all session IDs
     pub fn all_sessions(&self) -> Vec<String> {
         self.connections.iter().map(|r| r.key().clone()).collect()
@@ -320,6 +494,7 @@ impl std::fmt::Debug for ConnectionManager {
             .field("connections", &self.connections.len())
             .field("users", &self.user_connections.len())
             .field("guilds", &self.guild_connections.len())
+            .field("channels", &self.channel_connections.len())
             .finish()
     }
 }
@@ -327,6 +502,7 @@ impl std::fmt::Debug for ConnectionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::protocol::Intents;
 
     #[tokio::test]
     async fn test_connection_manager_creation() {
@@ -385,6 +561,111 @@ mod tests {
         assert_eq!(connections.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_channel_subscriptions() {
+        let manager = ConnectionManager::new();
+        let (tx, _rx) = mpsc::channel(10);
+
+        manager.add_connection("session1".to_string(), tx);
+
+        let channel_id = Snowflake::from(24680i64);
+        assert!(manager.subscribe_to_channel("session1", channel_id).await);
+        assert_eq!(manager.channel_count(), 1);
+        assert_eq!(manager.get_channel_connections(channel_id).len(), 1);
+
+        assert!(manager.unsubscribe_from_channel("session1", channel_id).await);
+        assert_eq!(manager.channel_count(), 0);
+
+        // Removing a connection drops its channel subscriptions
+        manager.subscribe_to_channel("session1", channel_id).await;
+        manager.remove_connection("session1").await;
+        assert_eq!(manager.channel_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_to_channel_excludes_users() {
+        let manager = ConnectionManager::new();
+        let (tx1, mut rx1) = mpsc::channel(10);
+        let (tx2, mut rx2) = mpsc::channel(10);
+        let (tx3, mut rx3) = mpsc::channel(10);
+
+        manager.add_connection("session1".to_string(), tx1);
+        manager.add_connection("session2".to_string(), tx2);
+        manager.add_connection("session3".to_string(), tx3);
+
+        let users = [Snowflake::from(1i64), Snowflake::from(2i64), Snowflake::from(3i64)];
+        let channel_id = Snowflake::from(100i64);
+        for (i, user_id) in users.iter().enumerate() {
+            let session_id = format!("session{}", i + 1);
+            manager.authenticate_connection(&session_id, *user_id).await;
+            manager.subscribe_to_channel(&session_id, channel_id).await;
+        }
+
+        let message = GatewayMessage::event("TYPING_START", serde_json::json!({}));
+        let sent = manager
+            .send_to_channel(channel_id, message, &users[..2])
+            .await;
+
+        assert_eq!(sent, 1);
+        assert!(rx1.try_recv().is_err());
+        assert!(rx2.try_recv().is_err());
+        assert!(rx3.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_send_to_guild_filters_intents() {
+        let manager = ConnectionManager::new();
+        let (tx1, mut rx1) = mpsc::channel(10);
+        let (tx2, mut rx2) = mpsc::channel(10);
+
+        let conn1 = manager.add_connection("session1".to_string(), tx1);
+        manager.add_connection("session2".to_string(), tx2);
+        conn1.set_intents(Intents::GUILDS);
+
+        let guild_id = Snowflake::from(100i64);
+        manager.subscribe_to_guild("session1", guild_id).await;
+        manager.subscribe_to_guild("session2", guild_id).await;
+
+        let message = GatewayMessage::event("GUILD_MEMBER_ADD", serde_json::json!({}));
+        assert_eq!(manager.send_to_guild(guild_id, message, &[]).await, 1);
+        assert!(rx1.try_recv().is_err());
+        assert!(rx2.try_recv().is_ok());
+
+        let message = GatewayMessage::event("GUILD_UPDATE", serde_json::json!({}));
+        assert_eq!(manager.send_to_guild(guild_id, message, &[]).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_to_guild_skips_slow_connection() {
+        let manager = ConnectionManager::new();
+        let (tx1, _rx1) = mpsc::channel(1);
+        let (tx2, mut rx2) = mpsc::channel(10);
+
+        manager.add_connection("slow".to_string(), tx1);
+        manager.add_connection("fast".to_string(), tx2);
+
+        let guild_id = Snowflake::from(100i64);
+        manager.subscribe_to_guild("slow", guild_id).await;
+        manager.subscribe_to_guild("fast", guild_id).await;
+
+        for _ in 0..3 {
+            let message = GatewayMessage::event("GUILD_UPDATE", serde_json::json!({}));
+            manager.send_to_guild(guild_id, message, &[]).await;
+        }
+
+        // The full queue neither blocks nor starves the other connection
+        for _ in 0..3 {
+            assert!(rx2.try_recv().is_ok());
+        }
+        assert!(manager.get_connection("slow").unwrap().is_lagging());
+        assert_eq!(manager.queue_metrics().slow_consumers, 1);
+
+        let lagging = manager.lagging_connections().await;
+        assert_eq!(lagging.len(), 1);
+        assert_eq!(lagging[0].session_id, "slow");
+        assert_eq!(lagging[0].depth, 1);
+    }
+
     #[tokio::test]
     async fn test_multiple_user_connections() {
         let manager = ConnectionManager::new();
@@ -4,8 +4,12 @@
 
 mod connection;
 mod manager;
+mod queue;
+mod resume_queue;
 mod session;
 
 pub use connection::{Connection, ConnectionState};
 pub use manager::ConnectionManager;
+pub use queue::{CoalesceKey, Delivery, LaggingConnection, QueueMetrics, QueueSnapshot, OVERFLOW_LIMIT};
+pub use resume_queue::ResumeQueue;
 pub use session::Session;
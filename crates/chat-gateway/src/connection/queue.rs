@@ -0,0 +1,208 @@
+//! Outbound queue policy
+//!
+//! Fan-out never waits on a connection's queue. Superseded events (typing,
+//! presence) are coalesced while still queued, and a connection whose queue
+//! fills up is sent Reconnect and closed so the client resumes.
+
+use crate::events::GatewayEventType;
+use crate::protocol::{GatewayMessage, OpCode};
+use chat_core::Snowflake;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Events kept for the resume queue after a connection's queue fills up
+pub const OVERFLOW_LIMIT: usize = 100;
+
+/// Identifies events that supersede each other while queued
+///
+/// A newer PRESENCE_UPDATE replaces a queued one for the same user and guild,
+/// and a newer TYPING_START one for the same user and channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoalesceKey {
+    event: GatewayEventType,
+    /// Guild (presence) or channel (typing)
+    scope: Snowflake,
+    user_id: Snowflake,
+}
+
+impl CoalesceKey {
+    /// Get the key of a coalescable Dispatch message
+    ///
+    /// Returns None for other events and for replayed events, which already
+    /// carry a sequence number.
+    pub fn of(message: &GatewayMessage) -> Option<Self> {
+        if message.op != OpCode::Dispatch || message.s.is_some() {
+            return None;
+        }
+
+        let event = GatewayEventType::from_str(message.t.as_deref()?)?;
+        let data = message.d.as_ref()?;
+        let (scope, user_id) = match event {
+            GatewayEventType::PresenceUpdate => (
+                snowflake_field(data, "guild_id")?,
+                snowflake_field(data.get("user")?, "id")?,
+            ),
+            GatewayEventType::TypingStart => {
+                (snowflake_field(data, "channel_id")?, snowflake_field(data, "user_id")?)
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            event,
+            scope,
+            user_id,
+        })
+    }
+}
+
+/// Parse a Snowflake string field from an event payload
+fn snowflake_field(data: &Value, field: &str) -> Option<Snowflake> {
+    data.get(field)?.as_str()?.parse().ok()
+}
+
+/// Result of handing an event to a connection without waiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Added to the queue
+    Queued,
+    /// Replaced a queued event it supersedes
+    Coalesced,
+    /// Did not fit; the connection is now being dropped and the event kept for resume
+    Overflowed,
+    /// Kept for resume on a connection that is being dropped
+    Deferred,
+    /// Lost: the connection is being dropped and its overflow is full
+    Dropped,
+    /// The connection is closed
+    Closed,
+}
+
+impl Delivery {
+    /// Check if the client will receive the event (now or on resume)
+    #[must_use]
+    pub const fn is_accepted(self) -> bool {
+        matches!(self, Self::Queued | Self::Coalesced | Self::Overflowed | Self::Deferred)
+    }
+}
+
+/// Outbound queue counters since the gateway started
+#[derive(Debug, Default)]
+pub struct QueueMetrics {
+    coalesced: AtomicU64,
+    slow_consumers: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl QueueMetrics {
+    /// Count the outcome of one delivery
+    pub fn record(&self, delivery: Delivery) {
+        let counter = match delivery {
+            Delivery::Coalesced => &self.coalesced,
+            Delivery::Overflowed => &self.slow_consumers,
+            Delivery::Dropped => &self.dropped,
+            Delivery::Queued | Delivery::Deferred | Delivery::Closed => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Read the current counts
+    pub fn snapshot(&self) -> QueueSnapshot {
+        QueueSnapshot {
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            slow_consumers: self.slow_consumers.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Point-in-time copy of [`QueueMetrics`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct QueueSnapshot {
+    /// Queued events replaced by a newer one
+    pub coalesced: u64,
+    /// Connections dropped because their queue filled up
+    pub slow_consumers: u64,
+    /// Events lost because a dropped connection's overflow was full
+    pub dropped: u64,
+}
+
+/// Queue depth of a connection that is falling behind
+#[derive(Debug, Clone, Serialize)]
+pub struct LaggingConnection {
+    /// Session ID of the connection
+    pub session_id: String,
+    /// Authenticated user, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Snowflake>,
+    /// Events waiting in the queue
+    pub depth: usize,
+    /// Queue capacity
+    pub capacity: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_coalesce_key_presence() {
+        let a = GatewayMessage::event(
+            "PRESENCE_UPDATE",
+            json!({"user": {"id": "1"}, "guild_id": "10", "status": "idle"}),
+        );
+        let b = GatewayMessage::event(
+            "PRESENCE_UPDATE",
+            json!({"user": {"id": "1"}, "guild_id": "10", "status": "online"}),
+        );
+        let other_guild = GatewayMessage::event(
+            "PRESENCE_UPDATE",
+            json!({"user": {"id": "1"}, "guild_id": "11", "status": "online"}),
+        );
+
+        assert!(CoalesceKey::of(&a).is_some());
+        assert_eq!(CoalesceKey::of(&a), CoalesceKey::of(&b));
+        assert_ne!(CoalesceKey::of(&a), CoalesceKey::of(&other_guild));
+    }
+
+    #[test]
+    fn test_coalesce_key_typing() {
+        let a = GatewayMessage::event("TYPING_START", json!({"channel_id": "5", "user_id": "1"}));
+        let b = GatewayMessage::event("TYPING_START", json!({"channel_id": "5", "user_id": "2"}));
+
+        assert!(CoalesceKey::of(&a).is_some());
+        assert_ne!(CoalesceKey::of(&a), CoalesceKey::of(&b));
+    }
+
+    #[test]
+    fn test_coalesce_key_other_events() {
+        let message = GatewayMessage::event("MESSAGE_CREATE", json!({"channel_id": "5"}));
+        assert_eq!(CoalesceKey::of(&message), None);
+
+        let replayed =
+            GatewayMessage::dispatch("TYPING_START", 3, json!({"channel_id": "5", "user_id": "1"}));
+        assert_eq!(CoalesceKey::of(&replayed), None);
+        assert_eq!(CoalesceKey::of(&GatewayMessage::heartbeat_ack()), None);
+    }
+
+    #[test]
+    fn test_queue_metrics() {
+        let metrics = QueueMetrics::default();
+        metrics.record(Delivery::Queued);
+        metrics.record(Delivery::Coalesced);
+        metrics.record(Delivery::Overflowed);
+        metrics.record(Delivery::Dropped);
+        metrics.record(Delivery::Dropped);
+
+        assert_eq!(
+            metrics.snapshot(),
+            QueueSnapshot {
+                coalesced: 1,
+                slow_consumers: 1,
+                dropped: 2,
+            }
+        );
+    }
+}
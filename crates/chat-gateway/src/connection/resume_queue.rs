@@ -0,0 +1,102 @@
+//! Resume queue writer
+//!
+//! Sent Dispatch events are written to the session's resume queue by a
+//! background task, so the send loop never waits on Redis. Events sent while
+//! a write is in flight go out together in the next one.
+
+use super::Session;
+use crate::protocol::{GatewayMessage, OpCode};
+use chat_cache::{SessionEvent, WebSocketSessionStore};
+use tokio::sync::{mpsc, oneshot};
+
+/// Maximum events written to Redis at once
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Request handled by the writer task
+enum Command {
+    /// Append a sent event
+    Event(SessionEvent),
+    /// Report back once every event pushed before is written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Handle to a connection's resume queue writer
+#[derive(Clone)]
+pub struct ResumeQueue {
+    tx: mpsc::UnboundedSender<Command>,
+}
+
+impl ResumeQueue {
+    /// Start the writer for a session
+    ///
+    /// The writer stops once every handle is dropped.
+    #[must_use]
+    pub fn spawn(store: WebSocketSessionStore, session_id: String) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(store, session_id, rx));
+        Self { tx }
+    }
+
+    /// Queue a sent Dispatch event; other messages are ignored
+    pub fn push(&self, message: &GatewayMessage) {
+        let (OpCode::Dispatch, Some(event_type), Some(sequence)) =
+            (message.op, &message.t, message.s)
+        else {
+            return;
+        };
+
+        let event = SessionEvent {
+            sequence,
+            event_type: event_type.clone(),
+            data: message.d.clone().unwrap_or_default(),
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        self.tx.send(Command::Event(event)).ok();
+    }
+
+    /// Wait until every event pushed so far is written
+    pub async fn flush(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        if self.tx.send(Command::Flush(done_tx)).is_ok() {
+            done_rx.await.ok();
+        }
+    }
+}
+
+/// Write queued events in batches until every handle is dropped
+async fn run(
+    store: WebSocketSessionStore,
+    session_id: String,
+    mut rx: mpsc::UnboundedReceiver<Command>,
+) {
+    let mut events = Vec::new();
+    let mut waiters = Vec::new();
+
+    while let Some(command) = rx.recv().await {
+        let mut next = Some(command);
+        while let Some(command) = next {
+            match command {
+                Command::Event(event) => events.push(event),
+                Command::Flush(done) => waiters.push(done),
+            }
+            if events.len() >= MAX_BATCH_SIZE {
+                break;
+            }
+            next = rx.try_recv().ok();
+        }
+
+        if let Err(e) = Session::queue_events(&store, &session_id, &events).await {
+            tracing::debug!(
+                session_id = %session_id,
+                events = events.len(),
+                error = %e,
+                "Failed to queue events for resume"
+            );
+        }
+        events.clear();
+
+        for done in waiters.drain(..) {
+            done.send(()).ok();
+        }
+    }
+}
@@ -15,29 +15,47 @@ impl Session {
         uuid::Uuid::new_v4().to_string()
     }
 
-    /// Create a new session in the store
+    /// Create a new session in the store, held by the gateway node `node_id`
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         store: &WebSocketSessionStore,
+        node_id: &str,
         session_id: &str,
         user_id: Snowflake,
         properties: Option<ClientProperties>,
         resume_url: Option<String>,
+        intents: Option<u64>,
+        shard: Option<[u32; 2]>,
+        auth_session_id: Option<String>,
     ) -> Result<WebSocketSessionData, chat_cache::RedisPoolError> {
-        let mut session = WebSocketSessionData::new(session_id.to_string(), user_id);
+        let mut session = WebSocketSessionData::new(session_id.to_string(), user_id).with_node(node_id);
 
         if let Some(props) = properties {
             session = session.with_properties(props);
         }
 
+        if let Some(intents) = intents {
+            session = session.with_intents(intents);
+        }
+
+        if let Some(shard) = shard {
+            session = session.with_shard(shard);
+        }
+
         if let Some(url) = resume_url {
             session = session.with_resume_url(url);
         }
 
+        if let Some(auth_session_id) = auth_session_id {
+            session = session.with_auth_session(auth_session_id);
+        }
+
         store.create(&session).await?;
 
         tracing::info!(
             session_id = %session_id,
             user_id = %user_id,
+            node_id = %node_id,
             "Created new WebSocket session"
         );
 
@@ -63,7 +81,8 @@ impl Session {
 
     /// Attempt to resume a session
     ///
-    /// Returns the session if valid and resumable, None otherwise.
+    /// Returns the session and the events after `last_sequence` if the session
+    /// is resumable and those events can all be replayed, None otherwise.
     pub async fn resume(
         store: &WebSocketSessionStore,
         session_id: &str,
@@ -81,7 +100,20 @@ impl Session {
         };
 
         // Get missed events
-        let events = store.get_events_since(session_id, last_sequence).await?;
+        let events = if let Some(events) = store
+            .get_events_since(session_id, last_sequence, session.sequence)
+            .await?
+        {
+            events
+        } else {
+            tracing::debug!(
+                session_id = %session_id,
+                last_sequence = last_sequence,
+                session_sequence = session.sequence,
+                "Missed events are no longer available for replay"
+            );
+            return Ok(None);
+        };
 
         // Mark session as connected again
         store.mark_connected(session_id).await?;
@@ -108,22 +140,13 @@ impl Session {
         Ok(())
     }
 
-    /// Queue an event for potential resume
-    pub async fn queue_event(
+    /// Queue sent events for potential resume, oldest first
+    pub async fn queue_events(
         store: &WebSocketSessionStore,
         session_id: &str,
-        sequence: u64,
-        event_type: &str,
-        data: serde_json::Value,
+        events: &[SessionEvent],
     ) -> Result<(), chat_cache::RedisPoolError> {
-        let event = SessionEvent {
-            sequence,
-            event_type: event_type.to_string(),
-            data,
-            timestamp: chrono::Utc::now().timestamp(),
-        };
-
-        store.queue_event(session_id, &event).await?;
+        store.queue_events(session_id, events).await?;
 
         Ok(())
     }
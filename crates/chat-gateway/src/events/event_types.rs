@@ -2,6 +2,7 @@
 //!
 //! Defines all event type names for dispatch messages.
 
+use crate::protocol::Intents;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -32,6 +33,22 @@ pub enum GatewayEventType {
     ChannelUpdate,
     /// Channel deleted
     ChannelDelete,
+    /// Message pinned or unpinned
+    ChannelPinsUpdate,
+    /// User added to a group DM (sent to every recipient)
+    ChannelRecipientAdd,
+    /// User removed from a group DM (sent to every recipient)
+    ChannelRecipientRemove,
+
+    // Thread events
+    /// Thread started from a message
+    ThreadCreate,
+    /// Thread renamed, archived, unarchived, or locked
+    ThreadUpdate,
+    /// Thread deleted
+    ThreadDelete,
+    /// Users joined or left a thread
+    ThreadMembersUpdate,
 
     // Message events
     /// New message
@@ -40,6 +57,12 @@ pub enum GatewayEventType {
     MessageUpdate,
     /// Message deleted
     MessageDelete,
+    /// Multiple messages deleted at once
+    MessageDeleteBulk,
+    /// Channel marked read by the current user (sent to all their sessions)
+    MessageAck,
+    /// Current user mentioned in a new message (sent to all their sessions)
+    MessageMention,
 
     // Reaction events
     /// Reaction added
@@ -54,6 +77,14 @@ pub enum GatewayEventType {
     GuildMemberUpdate,
     /// User left guild
     GuildMemberRemove,
+    /// Members returned for a Request Guild Members op
+    GuildMembersChunk,
+
+    // Ban events
+    /// User banned from guild
+    GuildBanAdd,
+    /// User unbanned from guild
+    GuildBanRemove,
 
     // Presence events
     /// User status changed
@@ -64,9 +95,51 @@ pub enum GatewayEventType {
     // User events
     /// Current user updated
     UserUpdate,
+    /// Friend, friend request or block added
+    RelationshipAdd,
+    /// Friend, friend request or block removed
+    RelationshipRemove,
 }
 
 impl GatewayEventType {
+    /// Every event type
+    pub const ALL: [Self; 34] = [
+        Self::Ready,
+        Self::Resumed,
+        Self::GuildCreate,
+        Self::GuildUpdate,
+        Self::GuildDelete,
+        Self::ChannelCreate,
+        Self::ChannelUpdate,
+        Self::ChannelDelete,
+        Self::ChannelPinsUpdate,
+        Self::ChannelRecipientAdd,
+        Self::ChannelRecipientRemove,
+        Self::ThreadCreate,
+        Self::ThreadUpdate,
+        Self::ThreadDelete,
+        Self::ThreadMembersUpdate,
+        Self::MessageCreate,
+        Self::MessageUpdate,
+        Self::MessageDelete,
+        Self::MessageDeleteBulk,
+        Self::MessageAck,
+        Self::MessageMention,
+        Self::MessageReactionAdd,
+        Self::MessageReactionRemove,
+        Self::GuildMemberAdd,
+        Self::GuildMemberUpdate,
+        Self::GuildMemberRemove,
+        Self::GuildMembersChunk,
+        Self::GuildBanAdd,
+        Self::GuildBanRemove,
+        Self::PresenceUpdate,
+        Self::TypingStart,
+        Self::UserUpdate,
+        Self::RelationshipAdd,
+        Self::RelationshipRemove,
+    ];
+
     /// Get the string representation of the event type
     #[must_use]
     pub const fn as_str(self) -> &'static str {
@@ -79,17 +152,90 @@ impl GatewayEventType {
             Self::ChannelCreate => "CHANNEL_CREATE",
             Self::ChannelUpdate => "CHANNEL_UPDATE",
             Self::ChannelDelete => "CHANNEL_DELETE",
+            Self::ChannelPinsUpdate => "CHANNEL_PINS_UPDATE",
+            Self::ChannelRecipientAdd => "CHANNEL_RECIPIENT_ADD",
+            Self::ChannelRecipientRemove => "CHANNEL_RECIPIENT_REMOVE",
+            Self::ThreadCreate => "THREAD_CREATE",
+            Self::ThreadUpdate => "THREAD_UPDATE",
+            Self::ThreadDelete => "THREAD_DELETE",
+            Self::ThreadMembersUpdate => "THREAD_MEMBERS_UPDATE",
             Self::MessageCreate => "MESSAGE_CREATE",
             Self::MessageUpdate => "MESSAGE_UPDATE",
             Self::MessageDelete => "MESSAGE_DELETE",
+            Self::MessageDeleteBulk => "MESSAGE_DELETE_BULK",
+            Self::MessageAck => "MESSAGE_ACK",
+            Self::MessageMention => "MESSAGE_MENTION",
             Self::MessageReactionAdd => "MESSAGE_REACTION_ADD",
             Self::MessageReactionRemove => "MESSAGE_REACTION_REMOVE",
             Self::GuildMemberAdd => "GUILD_MEMBER_ADD",
             Self::GuildMemberUpdate => "GUILD_MEMBER_UPDATE",
             Self::GuildMemberRemove => "GUILD_MEMBER_REMOVE",
+            Self::GuildMembersChunk => "GUILD_MEMBERS_CHUNK",
+            Self::GuildBanAdd => "GUILD_BAN_ADD",
+            Self::GuildBanRemove => "GUILD_BAN_REMOVE",
             Self::PresenceUpdate => "PRESENCE_UPDATE",
             Self::TypingStart => "TYPING_START",
             Self::UserUpdate => "USER_UPDATE",
+            Self::RelationshipAdd => "RELATIONSHIP_ADD",
+            Self::RelationshipRemove => "RELATIONSHIP_REMOVE",
+        }
+    }
+
+    /// Get the intent a session needs to receive this event
+    ///
+    /// `in_guild` selects between the guild and DM variant of message,
+    /// reaction and typing intents. None means the event is always delivered.
+    #[must_use]
+    pub const fn required_intent(self, in_guild: bool) -> Option<Intents> {
+        match self {
+            Self::GuildCreate
+            | Self::GuildUpdate
+            | Self::GuildDelete
+            | Self::ThreadCreate
+            | Self::ThreadUpdate
+            | Self::ThreadDelete => Some(Intents::GUILDS),
+            Self::ChannelCreate | Self::ChannelUpdate | Self::ChannelDelete if in_guild => {
+                Some(Intents::GUILDS)
+            }
+            Self::ChannelPinsUpdate if in_guild => Some(Intents::GUILDS),
+            Self::ChannelPinsUpdate => Some(Intents::DIRECT_MESSAGES),
+            Self::GuildMemberAdd
+            | Self::GuildMemberUpdate
+            | Self::GuildMemberRemove
+            | Self::ThreadMembersUpdate => Some(Intents::GUILD_MEMBERS),
+            Self::GuildBanAdd | Self::GuildBanRemove => Some(Intents::GUILD_MODERATION),
+            Self::PresenceUpdate => Some(Intents::GUILD_PRESENCES),
+            Self::MessageCreate
+            | Self::MessageUpdate
+            | Self::MessageDelete
+            | Self::MessageDeleteBulk => Some(if in_guild {
+                Intents::GUILD_MESSAGES
+            } else {
+                Intents::DIRECT_MESSAGES
+            }),
+            Self::MessageReactionAdd | Self::MessageReactionRemove => Some(if in_guild {
+                Intents::GUILD_MESSAGE_REACTIONS
+            } else {
+                Intents::DIRECT_MESSAGE_REACTIONS
+            }),
+            Self::TypingStart => Some(if in_guild {
+                Intents::GUILD_MESSAGE_TYPING
+            } else {
+                Intents::DIRECT_MESSAGE_TYPING
+            }),
+            Self::Ready
+            | Self::Resumed
+            | Self::ChannelCreate
+            | Self::ChannelUpdate
+            | Self::ChannelDelete
+            | Self::ChannelRecipientAdd
+            | Self::ChannelRecipientRemove
+            | Self::MessageAck
+            | Self::MessageMention
+            | Self::GuildMembersChunk
+            | Self::UserUpdate
+            | Self::RelationshipAdd
+            | Self::RelationshipRemove => None,
         }
     }
 
@@ -105,17 +251,32 @@ impl GatewayEventType {
             "CHANNEL_CREATE" => Some(Self::ChannelCreate),
             "CHANNEL_UPDATE" => Some(Self::ChannelUpdate),
             "CHANNEL_DELETE" => Some(Self::ChannelDelete),
+            "CHANNEL_PINS_UPDATE" => Some(Self::ChannelPinsUpdate),
+            "CHANNEL_RECIPIENT_ADD" => Some(Self::ChannelRecipientAdd),
+            "CHANNEL_RECIPIENT_REMOVE" => Some(Self::ChannelRecipientRemove),
+            "THREAD_CREATE" => Some(Self::ThreadCreate),
+            "THREAD_UPDATE" => Some(Self::ThreadUpdate),
+            "THREAD_DELETE" => Some(Self::ThreadDelete),
+            "THREAD_MEMBERS_UPDATE" => Some(Self::ThreadMembersUpdate),
             "MESSAGE_CREATE" => Some(Self::MessageCreate),
             "MESSAGE_UPDATE" => Some(Self::MessageUpdate),
             "MESSAGE_DELETE" => Some(Self::MessageDelete),
+            "MESSAGE_DELETE_BULK" => Some(Self::MessageDeleteBulk),
+            "MESSAGE_ACK" => Some(Self::MessageAck),
+            "MESSAGE_MENTION" => Some(Self::MessageMention),
             "MESSAGE_REACTION_ADD" => Some(Self::MessageReactionAdd),
             "MESSAGE_REACTION_REMOVE" => Some(Self::MessageReactionRemove),
             "GUILD_MEMBER_ADD" => Some(Self::GuildMemberAdd),
             "GUILD_MEMBER_UPDATE" => Some(Self::GuildMemberUpdate),
             "GUILD_MEMBER_REMOVE" => Some(Self::GuildMemberRemove),
+            "GUILD_MEMBERS_CHUNK" => Some(Self::GuildMembersChunk),
+            "GUILD_BAN_ADD" => Some(Self::GuildBanAdd),
+            "GUILD_BAN_REMOVE" => Some(Self::GuildBanRemove),
             "PRESENCE_UPDATE" => Some(Self::PresenceUpdate),
             "TYPING_START" => Some(Self::TypingStart),
             "USER_UPDATE" => Some(Self::UserUpdate),
+            "RELATIONSHIP_ADD" => Some(Self::RelationshipAdd),
+            "RELATIONSHIP_REMOVE" => Some(Self::RelationshipRemove),
             _ => None,
         }
     }
@@ -151,9 +312,70 @@ mod tests {
             GatewayEventType::from_str("MESSAGE_CREATE"),
             Some(GatewayEventType::MessageCreate)
         );
+        assert_eq!(
+            GatewayEventType::from_str("GUILD_BAN_ADD"),
+            Some(GatewayEventType::GuildBanAdd)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("THREAD_MEMBERS_UPDATE"),
+            Some(GatewayEventType::ThreadMembersUpdate)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("CHANNEL_PINS_UPDATE"),
+            Some(GatewayEventType::ChannelPinsUpdate)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("CHANNEL_RECIPIENT_ADD"),
+            Some(GatewayEventType::ChannelRecipientAdd)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("RELATIONSHIP_ADD"),
+            Some(GatewayEventType::RelationshipAdd)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("MESSAGE_ACK"),
+            Some(GatewayEventType::MessageAck)
+        );
+        assert_eq!(
+            GatewayEventType::from_str("MESSAGE_MENTION"),
+            Some(GatewayEventType::MessageMention)
+        );
         assert_eq!(GatewayEventType::from_str("INVALID"), None);
     }
 
+    #[test]
+    fn test_all_event_types_round_trip() {
+        for event_type in GatewayEventType::ALL {
+            assert_eq!(GatewayEventType::from_str(event_type.as_str()), Some(event_type));
+        }
+    }
+
+    #[test]
+    fn test_required_intent() {
+        assert_eq!(
+            GatewayEventType::MessageCreate.required_intent(true),
+            Some(Intents::GUILD_MESSAGES)
+        );
+        assert_eq!(
+            GatewayEventType::MessageCreate.required_intent(false),
+            Some(Intents::DIRECT_MESSAGES)
+        );
+        assert_eq!(
+            GatewayEventType::TypingStart.required_intent(false),
+            Some(Intents::DIRECT_MESSAGE_TYPING)
+        );
+        assert_eq!(
+            GatewayEventType::GuildMemberAdd.required_intent(true),
+            Some(Intents::GUILD_MEMBERS)
+        );
+        assert_eq!(GatewayEventType::ChannelCreate.required_intent(true), Some(Intents::GUILDS));
+        assert_eq!(GatewayEventType::ChannelCreate.required_intent(false), None);
+        assert_eq!(GatewayEventType::Ready.required_intent(true), None);
+        assert_eq!(GatewayEventType::MessageAck.required_intent(true), None);
+        assert_eq!(GatewayEventType::GuildMembersChunk.required_intent(true), None);
+        assert_eq!(GatewayEventType::RelationshipAdd.required_intent(false), None);
+    }
+
     #[test]
     fn test_event_type_serialization() {
         let event = GatewayEventType::MessageCreate;
@@ -7,9 +7,11 @@ mod payloads;
 
 pub use event_types::GatewayEventType;
 pub use payloads::{
-    ChannelDeleteEvent, ChannelEvent, ChannelPayload, GuildCreateEvent, GuildDeleteEvent,
-    GuildEvent, GuildMemberAddEvent, GuildMemberRemoveEvent, GuildMemberUpdateEvent, MemberEvent,
-    MemberPayload, MessageCreateEvent, MessageDeleteEvent, MessageEvent, MessageReactionEvent,
-    PresenceEvent, ReadyEvent, ResumedEvent, RolePayload, TypingStartEvent, UnavailableGuild,
+    ChannelDeleteEvent, ChannelEvent, ChannelPayload, ChannelPinsUpdateEvent, GuildBanEvent,
+    GuildCreateEvent, GuildDeleteEvent, GuildEvent, GuildMemberAddEvent, GuildMemberRemoveEvent,
+    GuildMemberUpdateEvent, GuildMembersChunkEvent, MemberEvent, MemberPayload, MemberPresencePayload, MessageAckEvent, MessageCreateEvent,
+    MessageDeleteBulkEvent, MessageDeleteEvent, MessageEvent, MessageReactionEvent, PresenceEvent,
+    ReadStatePayload, ReadyEvent, ResumedEvent, RolePayload, ThreadDeleteEvent, ThreadEvent, ThreadMemberPayload,
+    ThreadMembersUpdateEvent, ThreadMetadataPayload, TypingStartEvent, UnavailableGuild,
     UserEvent, UserIdPayload, UserPayload,
 };
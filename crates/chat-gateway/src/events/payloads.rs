@@ -24,16 +24,27 @@ pub struct ReadyEvent {
     /// Session ID for resuming
     pub session_id: String,
 
+    /// Read position and mention count per channel
+    #[serde(default)]
+    pub read_state: Vec<ReadStatePayload>,
+
     /// Gateway URL for resuming (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resume_gateway_url: Option<String>,
+
+    /// `[shard_id, num_shards]` when the session is sharded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u32; 2]>,
 }
 
 /// RESUMED event payload
 ///
-/// Sent after successful Resume.
+/// Sent after successful Resume, once the missed events were replayed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ResumedEvent {}
+pub struct ResumedEvent {
+    /// Session ID to use for the next Resume
+    pub session_id: String,
+}
 
 /// Unavailable guild in READY event
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +63,16 @@ impl UnavailableGuild {
     }
 }
 
+/// Read state entry in READY event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadStatePayload {
+    pub channel_id: Snowflake,
+    /// Last acknowledged message
+    pub last_message_id: Option<Snowflake>,
+    /// Mentions since the last acknowledged message
+    pub mention_count: i32,
+}
+
 // === User Payload ===
 
 /// User data included in events
@@ -84,9 +105,13 @@ pub struct GuildCreateEvent {
     pub channels: Vec<ChannelPayload>,
     #[serde(default)]
     pub roles: Vec<RolePayload>,
+    /// All members for small guilds; only the current user's member for large ones
     #[serde(default)]
     pub members: Vec<MemberPayload>,
     pub member_count: i32,
+    /// Member list is partial; request the rest with Request Guild Members
+    #[serde(default)]
+    pub large: bool,
     pub created_at: String,
 }
 
@@ -159,6 +184,76 @@ pub struct ChannelDeleteEvent {
     pub channel_type: i32,
 }
 
+/// CHANNEL_PINS_UPDATE event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPinsUpdateEvent {
+    pub channel_id: Snowflake,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Snowflake>,
+    /// Time of the most recent remaining pin, if any
+    pub last_pin_timestamp: Option<String>,
+}
+
+// === Thread Events ===
+
+/// Thread-specific state included in thread events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMetadataPayload {
+    pub archived: bool,
+    pub locked: bool,
+    pub auto_archive_duration: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_timestamp: Option<String>,
+    pub last_activity_at: String,
+}
+
+/// THREAD_CREATE/THREAD_UPDATE event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadEvent {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    pub parent_id: Snowflake,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub channel_type: i32,
+    pub owner_id: Snowflake,
+    pub message_id: Snowflake,
+    pub member_count: i32,
+    pub thread_metadata: ThreadMetadataPayload,
+}
+
+/// THREAD_DELETE event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadDeleteEvent {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    pub parent_id: Snowflake,
+    #[serde(rename = "type")]
+    pub channel_type: i32,
+}
+
+/// Thread member data included in events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMemberPayload {
+    /// Thread ID
+    pub id: Snowflake,
+    pub user_id: Snowflake,
+    pub join_timestamp: String,
+}
+
+/// THREAD_MEMBERS_UPDATE event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMembersUpdateEvent {
+    /// Thread ID
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    pub member_count: i32,
+    #[serde(default)]
+    pub added_members: Vec<ThreadMemberPayload>,
+    #[serde(default)]
+    pub removed_member_ids: Vec<Snowflake>,
+}
+
 // === Role Events ===
 
 /// Role data included in events
@@ -183,6 +278,8 @@ pub struct MessageCreateEvent {
     pub guild_id: Option<Snowflake>,
     pub author: UserPayload,
     pub content: String,
+    #[serde(rename = "type", default)]
+    pub message_type: i32,
     pub timestamp: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_timestamp: Option<String>,
@@ -194,6 +291,15 @@ pub struct MessageCreateEvent {
     pub message_reference: Option<MessageReferencePayload>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub referenced_message: Option<Box<MessageCreateEvent>>,
+    /// Users mentioned in the content
+    #[serde(default)]
+    pub mentions: Vec<UserPayload>,
+    #[serde(default)]
+    pub mention_roles: Vec<Snowflake>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mention_channels: Vec<Snowflake>,
+    #[serde(default)]
+    pub mention_everyone: bool,
 }
 
 /// MESSAGE_UPDATE event payload (partial update)
@@ -207,6 +313,18 @@ pub struct MessageEvent {
     pub content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_timestamp: Option<String>,
+    /// Edit timestamp before this update (null if this is the first edit)
+    #[serde(default)]
+    pub previous_edited_timestamp: Option<String>,
+    /// Mentions re-resolved from the edited content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mentions: Option<Vec<UserPayload>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention_roles: Option<Vec<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention_channels: Option<Vec<Snowflake>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention_everyone: Option<bool>,
 }
 
 /// MESSAGE_DELETE event payload
@@ -218,6 +336,25 @@ pub struct MessageDeleteEvent {
     pub guild_id: Option<Snowflake>,
 }
 
+/// MESSAGE_DELETE_BULK event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageDeleteBulkEvent {
+    pub ids: Vec<Snowflake>,
+    pub channel_id: Snowflake,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<Snowflake>,
+}
+
+/// MESSAGE_ACK event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageAckEvent {
+    pub channel_id: Snowflake,
+    /// Last acknowledged message
+    pub message_id: Option<Snowflake>,
+    /// Mentions remaining after the ack
+    pub mention_count: i32,
+}
+
 /// Attachment data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttachmentPayload {
@@ -227,6 +364,12 @@ pub struct AttachmentPayload {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
 }
 
 /// Reaction data
@@ -303,6 +446,33 @@ pub struct GuildMemberRemoveEvent {
     pub user: UserPayload,
 }
 
+/// GUILD_MEMBERS_CHUNK event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildMembersChunkEvent {
+    pub guild_id: Snowflake,
+    #[serde(default)]
+    pub members: Vec<MemberPayload>,
+    /// Zero-based index of this chunk
+    pub chunk_index: u32,
+    /// Total chunks answering the request
+    pub chunk_count: u32,
+    /// Requested user IDs that are not members (first chunk only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<Snowflake>,
+    /// Presences of this chunk's members, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presences: Option<Vec<MemberPresencePayload>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+/// Presence included in a member chunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberPresencePayload {
+    pub user: UserIdPayload,
+    pub status: String,
+}
+
 /// Partial user with just ID
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserIdPayload {
@@ -312,6 +482,15 @@ pub struct UserIdPayload {
 /// Convenience type for member events
 pub type MemberEvent = GuildMemberUpdateEvent;
 
+// === Ban Events ===
+
+/// GUILD_BAN_ADD / GUILD_BAN_REMOVE event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildBanEvent {
+    pub guild_id: Snowflake,
+    pub user: UserPayload,
+}
+
 // === Presence Events ===
 
 /// PRESENCE_UPDATE event payload
@@ -371,12 +550,19 @@ mod tests {
             },
             guilds: vec![UnavailableGuild::new(Snowflake::from(67890i64))],
             session_id: "session123".to_string(),
+            read_state: vec![ReadStatePayload {
+                channel_id: Snowflake::from(111i64),
+                last_message_id: Some(Snowflake::from(222i64)),
+                mention_count: 2,
+            }],
             resume_gateway_url: Some("wss://gateway.example.com".to_string()),
+            shard: None,
         };
 
         let json = serde_json::to_string(&ready).unwrap();
         assert!(json.contains("testuser"));
         assert!(json.contains("session123"));
+        assert!(json.contains("\"read_state\":[{\"channel_id\":\"111\""));
     }
 
     #[test]
@@ -393,16 +579,40 @@ mod tests {
                 bot: false,
             },
             content: "Hello!".to_string(),
+            message_type: 0,
             timestamp: "2024-01-01T00:00:00Z".to_string(),
             edited_timestamp: None,
             attachments: vec![],
             reactions: vec![],
             message_reference: None,
             referenced_message: None,
+            mentions: vec![],
+            mention_roles: vec![],
+            mention_channels: vec![],
+            mention_everyone: false,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("Hello!"));
+        assert!(!json.contains("mention_channels"));
+    }
+
+    #[test]
+    fn test_guild_members_chunk_event() {
+        let chunk = GuildMembersChunkEvent {
+            guild_id: Snowflake::from(1i64),
+            members: vec![],
+            chunk_index: 0,
+            chunk_count: 1,
+            not_found: vec![Snowflake::from(2i64)],
+            presences: None,
+            nonce: Some("abc".to_string()),
+        };
+
+        let json = serde_json::to_string(&chunk).unwrap();
+        assert!(json.contains("\"not_found\":[\"2\"]"));
+        assert!(json.contains("\"nonce\":\"abc\""));
+        assert!(!json.contains("presences"));
     }
 
     #[test]
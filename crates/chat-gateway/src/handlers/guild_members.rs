@@ -0,0 +1,199 @@
+//! Request Guild Members handler (op 8)
+
+use super::{HandlerError, HandlerResult};
+use crate::connection::Connection;
+use crate::events::{
+    GatewayEventType, GuildMembersChunkEvent, MemberPayload, MemberPresencePayload, UserIdPayload,
+    UserPayload,
+};
+use crate::protocol::{CloseCode, GatewayMessage, Intents, RequestGuildMembersPayload};
+use crate::server::GatewayState;
+use chat_core::entities::{GuildMember, User};
+use chat_core::Snowflake;
+use std::sync::Arc;
+
+/// Maximum members sent in one GUILD_MEMBERS_CHUNK
+const CHUNK_SIZE: usize = 1000;
+
+/// Handles Request Guild Members messages
+pub struct RequestGuildMembersHandler;
+
+impl RequestGuildMembersHandler {
+    /// Handle a Request Guild Members message
+    pub async fn handle(
+        state: &GatewayState,
+        connection: &Arc<Connection>,
+        payload: RequestGuildMembersPayload,
+    ) -> HandlerResult<Option<CloseCode>> {
+        // Must be authenticated
+        let user_id = if let Some(id) = connection.user_id().await { id } else {
+            tracing::warn!(
+                session_id = %connection.session_id(),
+                "Request Guild Members from unauthenticated client"
+            );
+            return Ok(Some(CloseCode::NotAuthenticated));
+        };
+
+        payload
+            .validate()
+            .map_err(|reason| HandlerError::InvalidPayload(reason.to_string()))?;
+
+        // The full member list and presences are gated by their privileged intents
+        let intents = connection.intents();
+        if (payload.requests_all() && !intents.contains(Intents::GUILD_MEMBERS))
+            || (payload.presences && !intents.contains(Intents::GUILD_PRESENCES))
+        {
+            tracing::debug!(
+                session_id = %connection.session_id(),
+                guild_id = %payload.guild_id,
+                "Request Guild Members without the required intents"
+            );
+            return Ok(Some(CloseCode::DisallowedIntents));
+        }
+
+        // Requests for guilds the user is not in are ignored
+        let member_repo = state.service_context().member_repo();
+        if !member_repo.is_member(payload.guild_id, user_id).await? {
+            tracing::debug!(
+                session_id = %connection.session_id(),
+                guild_id = %payload.guild_id,
+                "Request Guild Members for a guild the user is not in"
+            );
+            return Ok(None);
+        }
+
+        if payload.requests_all() {
+            return Self::send_all(state, connection, &payload).await;
+        }
+
+        let (members, not_found) = match (&payload.query, &payload.user_ids) {
+            (_, Some(user_ids)) => {
+                let members = member_repo.find_many(payload.guild_id, user_ids).await?;
+                let not_found = user_ids
+                    .iter()
+                    .filter(|id| !members.iter().any(|m| m.user_id == **id))
+                    .copied()
+                    .collect();
+                (members, not_found)
+            }
+            (Some(query), None) => {
+                let members = member_repo
+                    .search(payload.guild_id, query, i64::from(payload.limit))
+                    .await?;
+                (members, Vec::new())
+            }
+            (None, None) => (Vec::new(), Vec::new()), // Rejected by validate()
+        };
+
+        Self::send_chunk(state, connection, &payload, members, 0, 1, not_found).await?;
+        Ok(None)
+    }
+
+    /// Page through every member of the guild, one chunk per page
+    async fn send_all(
+        state: &GatewayState,
+        connection: &Arc<Connection>,
+        payload: &RequestGuildMembersPayload,
+    ) -> HandlerResult<Option<CloseCode>> {
+        let member_count = state
+            .service_context()
+            .guild_repo()
+            .member_count(payload.guild_id)
+            .await?;
+        let chunk_count = u32::try_from(usize::try_from(member_count).unwrap_or(0).div_ceil(CHUNK_SIZE))
+            .unwrap_or(u32::MAX)
+            .max(1);
+
+        // Members joining mid-request may be left out; chunk_count is fixed up front
+        let mut after = None;
+        for chunk_index in 0..chunk_count {
+            let members = state
+                .service_context()
+                .member_repo()
+                .find_by_guild(payload.guild_id, CHUNK_SIZE as i64, after)
+                .await?;
+            after = members.last().map(|m| m.user_id).or(after);
+
+            Self::send_chunk(state, connection, payload, members, chunk_index, chunk_count, Vec::new())
+                .await?;
+        }
+
+        tracing::debug!(
+            session_id = %connection.session_id(),
+            guild_id = %payload.guild_id,
+            chunks = chunk_count,
+            "Sent all guild members"
+        );
+
+        Ok(None)
+    }
+
+    /// Send one GUILD_MEMBERS_CHUNK dispatch
+    async fn send_chunk(
+        state: &GatewayState,
+        connection: &Arc<Connection>,
+        payload: &RequestGuildMembersPayload,
+        members: Vec<GuildMember>,
+        chunk_index: u32,
+        chunk_count: u32,
+        not_found: Vec<Snowflake>,
+    ) -> HandlerResult<()> {
+        let mut payloads = Vec::with_capacity(members.len());
+        for member in members {
+            if let Some(user) = state.service_context().user_repo().find_by_id(member.user_id).await? {
+                payloads.push(member_payload(member, &user));
+            }
+        }
+
+        let presences = if payload.presences {
+            let user_ids: Vec<Snowflake> = payloads.iter().map(|m| m.user.id).collect();
+            let presences = state
+                .service_context()
+                .presence_store()
+                .get_presences(&user_ids)
+                .await?
+                .into_iter()
+                .filter(|p| p.status.is_visible())
+                .map(|p| MemberPresencePayload {
+                    user: UserIdPayload { id: p.user_id },
+                    status: p.status.to_string(),
+                })
+                .collect();
+            Some(presences)
+        } else {
+            None
+        };
+
+        let chunk = GuildMembersChunkEvent {
+            guild_id: payload.guild_id,
+            members: payloads,
+            chunk_index,
+            chunk_count,
+            not_found,
+            presences,
+            nonce: payload.nonce.clone(),
+        };
+        let chunk_data = serde_json::to_value(&chunk).unwrap_or_default();
+
+        connection
+            .send(GatewayMessage::event(GatewayEventType::GuildMembersChunk.as_str(), chunk_data))
+            .await
+            .map_err(|e| HandlerError::Internal(format!("Failed to send GUILD_MEMBERS_CHUNK: {e}")))
+    }
+}
+
+/// Build the member payload sent in GUILD_CREATE and GUILD_MEMBERS_CHUNK
+pub(super) fn member_payload(member: GuildMember, user: &User) -> MemberPayload {
+    MemberPayload {
+        user: UserPayload {
+            id: user.id,
+            username: user.username.clone(),
+            discriminator: user.discriminator.clone(),
+            avatar: user.avatar.clone(),
+            bot: user.bot,
+        },
+        nickname: member.nickname,
+        roles: member.role_ids,
+        joined_at: member.joined_at.to_rfc3339(),
+    }
+}
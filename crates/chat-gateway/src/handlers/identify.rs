@@ -1,14 +1,22 @@
 //! Identify handler (op 2)
 
+use super::guild_members::member_payload;
 use super::{HandlerError, HandlerResult};
 use crate::connection::{Connection, Session};
-use crate::events::{GatewayEventType, GuildCreateEvent, ReadyEvent, UnavailableGuild, UserPayload};
-use crate::protocol::{CloseCode, GatewayMessage, IdentifyPayload};
+use crate::events::{
+    GatewayEventType, GuildCreateEvent, ReadStatePayload, ReadyEvent, UnavailableGuild, UserPayload,
+};
+use crate::protocol::{CloseCode, GatewayMessage, IdentifyPayload, Intents, Shard};
+use crate::rate_limit::RateLimitKind;
 use crate::server::GatewayState;
-use chat_cache::ClientProperties;
+use chat_cache::{ClientProperties, SESSION_START_LIMIT};
 use chat_core::Snowflake;
+use chat_service::ReadStateService;
 use std::sync::Arc;
 
+/// Guilds with more members than this send a partial member list in GUILD_CREATE
+const LARGE_THRESHOLD: i64 = 250;
+
 /// Handles Identify messages
 pub struct IdentifyHandler;
 
@@ -45,6 +53,18 @@ impl IdentifyHandler {
             .user_id()
             .map_err(|e| HandlerError::AuthenticationFailed(e.to_string()))?;
 
+        // Access tokens of a revoked login cannot open new connections
+        if super::login_revoked(state, &claims).await? {
+            tracing::debug!(user_id = %user_id, "Identify rejected: login session revoked");
+            return Err(HandlerError::AuthenticationFailed("Session revoked".to_string()));
+        }
+
+        // One Identify per user every few seconds
+        if !state.rate_limiter().check_identify(user_id) {
+            tracing::debug!(user_id = %user_id, "Identify rejected: identify rate limit reached");
+            return Ok(Some(CloseCode::RateLimited));
+        }
+
         // Get user from database
         let user = state
             .service_context()
@@ -53,12 +73,53 @@ impl IdentifyHandler {
             .await?
             .ok_or_else(|| HandlerError::AuthenticationFailed("User not found".to_string()))?;
 
-        // Get user's guilds
-        let guilds = state
+        // Resolve requested intents against the account's privileged opt-in
+        let intents = match Intents::resolve(payload.intents, user.can_use_privileged_intents()) {
+            Ok(intents) => intents,
+            Err(code) => {
+                tracing::debug!(
+                    user_id = %user_id,
+                    intents = ?payload.intents,
+                    "Identify rejected: {}",
+                    code.description()
+                );
+                return Ok(Some(code));
+            }
+        };
+
+        let shard = match Shard::resolve(payload.shard) {
+            Ok(shard) => shard,
+            Err(code) => {
+                tracing::debug!(user_id = %user_id, shard = ?payload.shard, "Identify rejected: invalid shard");
+                return Ok(Some(code));
+            }
+        };
+
+        // Get user's guilds, keeping only those on this shard
+        let mut guilds = state
             .service_context()
             .guild_repo()
             .find_by_user(user_id)
             .await?;
+        if let Some(shard) = shard {
+            guilds.retain(|g| shard.owns_guild(g.id));
+        }
+        if guilds.len() > Shard::MAX_GUILDS {
+            tracing::debug!(user_id = %user_id, guilds = guilds.len(), "Identify rejected: sharding required");
+            return Ok(Some(CloseCode::ShardingRequired));
+        }
+
+        // Count the session start against the daily limit
+        let session_starts = state
+            .service_context()
+            .session_store()
+            .record_session_start(user_id)
+            .await?;
+        if session_starts > SESSION_START_LIMIT {
+            state.rate_limiter().record(RateLimitKind::SessionStart);
+            tracing::debug!(user_id = %user_id, "Identify rejected: session start limit reached");
+            return Ok(Some(CloseCode::RateLimited));
+        }
 
         // Convert client properties
         let client_props = payload.properties.map(ClientProperties::from);
@@ -69,32 +130,62 @@ impl IdentifyHandler {
 
         Session::create(
             state.service_context().session_store(),
+            state.node().id(),
             &session_id,
             user_id,
             client_props,
             Some(format!("ws://{resume_url}/gateway")),
+            Some(intents.bits()),
+            shard.map(Shard::to_array),
+            claims.session_id.clone(),
         )
         .await
         .map_err(HandlerError::CacheError)?;
 
+        // Route the user's events to this node
+        state
+            .node()
+            .add_session(&session_id, user_id)
+            .await
+            .map_err(HandlerError::CacheError)?;
+
         // Authenticate the connection
         state
             .connection_manager()
             .authenticate_connection(&session_id, user_id)
             .await;
+        connection.set_intents(intents);
+        if let Some(shard) = shard {
+            connection.set_shard(shard);
+        }
+        connection.set_compress_payloads(payload.compress.unwrap_or(false));
 
         // Subscribe to guilds
         let guild_ids: Vec<Snowflake> = guilds.iter().map(|g| g.id).collect();
         for guild_id in &guild_ids {
-            state
-                .connection_manager()
-                .subscribe_to_guild(&session_id, *guild_id)
-                .await;
             Session::subscribe_guild(state.service_context().session_store(), &session_id, *guild_id)
                 .await
                 .ok(); // Ignore errors for now
         }
 
+        // Receive guild, channel, DM and user-targeted events
+        state
+            .event_dispatcher()
+            .subscribe_session(&session_id, user_id, &guild_ids)
+            .await;
+
+        // Load read states for unread badges
+        let read_state = ReadStateService::new(state.service_context())
+            .get_read_states(user_id)
+            .await?
+            .into_iter()
+            .map(|s| ReadStatePayload {
+                channel_id: s.channel_id,
+                last_message_id: s.last_message_id,
+                mention_count: s.mention_count,
+            })
+            .collect();
+
         // Build READY event
         let ready = ReadyEvent {
             v: 1,
@@ -107,33 +198,19 @@ impl IdentifyHandler {
             },
             guilds: guild_ids.iter().map(|id| UnavailableGuild::new(*id)).collect(),
             session_id: session_id.clone(),
+            read_state,
             resume_gateway_url: Some(format!("ws://{resume_url}/gateway")),
+            shard: shard.map(Shard::to_array),
         };
 
         // Send READY event
         let ready_data = serde_json::to_value(&ready).unwrap_or_default();
-        let seq = connection.next_sequence();
 
         connection
-            .send(GatewayMessage::dispatch(
-                GatewayEventType::Ready.as_str(),
-                seq,
-                ready_data.clone(),
-            ))
+            .send(GatewayMessage::event(GatewayEventType::Ready.as_str(), ready_data))
             .await
             .map_err(|e| HandlerError::Internal(format!("Failed to send READY: {e}")))?;
 
-        // Queue event for potential resume
-        Session::queue_event(
-            state.service_context().session_store(),
-            &session_id,
-            seq,
-            GatewayEventType::Ready.as_str(),
-            ready_data,
-        )
-        .await
-        .ok();
-
         tracing::info!(
             session_id = %session_id,
             user_id = %user_id,
@@ -144,29 +221,16 @@ impl IdentifyHandler {
 
         // Send GUILD_CREATE for each guild
         for guild in guilds {
-            let guild_create = Self::build_guild_create(state, &guild).await?;
+            let guild_create = Self::build_guild_create(state, &guild, &user).await?;
             let guild_data = serde_json::to_value(&guild_create).unwrap_or_default();
-            let seq = connection.next_sequence();
 
             connection
-                .send(GatewayMessage::dispatch(
+                .send(GatewayMessage::event(
                     GatewayEventType::GuildCreate.as_str(),
-                    seq,
-                    guild_data.clone(),
+                    guild_data,
                 ))
                 .await
                 .map_err(|e| HandlerError::Internal(format!("Failed to send GUILD_CREATE: {e}")))?;
-
-            // Queue event for potential resume
-            Session::queue_event(
-                state.service_context().session_store(),
-                &session_id,
-                seq,
-                GatewayEventType::GuildCreate.as_str(),
-                guild_data,
-            )
-            .await
-            .ok();
         }
 
         // Set user presence to online
@@ -182,11 +246,15 @@ impl IdentifyHandler {
     }
 
     /// Build a GUILD_CREATE event for a guild
+    ///
+    /// Small guilds include every member; large guilds only include the
+    /// identifying user, and clients fetch the rest with Request Guild Members.
     async fn build_guild_create(
         state: &GatewayState,
         guild: &chat_core::Guild,
+        user: &chat_core::User,
     ) -> HandlerResult<GuildCreateEvent> {
-        use crate::events::{ChannelPayload, MemberPayload, RolePayload};
+        use crate::events::{ChannelPayload, RolePayload};
 
         // Get channels
         let channels = state
@@ -209,9 +277,24 @@ impl IdentifyHandler {
             .member_count(guild.id)
             .await?;
 
-        // We won't load all members here - just get basic info
-        // In a full implementation, you'd paginate or limit this
-        let members: Vec<MemberPayload> = vec![];
+        let large = member_count > LARGE_THRESHOLD;
+        let member_repo = state.service_context().member_repo();
+        let mut members = Vec::new();
+        if large {
+            if let Some(member) = member_repo.find(guild.id, user.id).await? {
+                members.push(member_payload(member, user));
+            }
+        } else {
+            for member in member_repo.find_by_guild(guild.id, LARGE_THRESHOLD, None).await? {
+                if member.user_id == user.id {
+                    members.push(member_payload(member, user));
+                } else if let Some(member_user) =
+                    state.service_context().user_repo().find_by_id(member.user_id).await?
+                {
+                    members.push(member_payload(member, &member_user));
+                }
+            }
+        }
 
         Ok(GuildCreateEvent {
             id: guild.id,
@@ -243,6 +326,7 @@ impl IdentifyHandler {
                 .collect(),
             members,
             member_count: member_count as i32,
+            large,
             created_at: guild.created_at.to_rfc3339(),
         })
     }
@@ -3,12 +3,14 @@
 //! Handles incoming WebSocket messages based on their operation code.
 
 mod error;
+mod guild_members;
 mod heartbeat;
 mod identify;
 mod presence;
 mod resume;
 
 pub use error::{HandlerError, HandlerResult};
+pub use guild_members::RequestGuildMembersHandler;
 pub use heartbeat::HeartbeatHandler;
 pub use identify::IdentifyHandler;
 pub use presence::PresenceHandler;
@@ -17,8 +19,25 @@ pub use resume::ResumeHandler;
 use crate::connection::Connection;
 use crate::protocol::{CloseCode, GatewayMessage, OpCode};
 use crate::server::GatewayState;
+use chat_common::auth::Claims;
 use std::sync::Arc;
 
+/// Whether the login session of an access token was revoked
+///
+/// Access tokens without a session ID predate session tracking and pass.
+async fn login_revoked(state: &GatewayState, claims: &Claims) -> HandlerResult<bool> {
+    let Some(auth_session_id) = claims.session_id.as_deref() else {
+        return Ok(false);
+    };
+
+    let active = state
+        .service_context()
+        .refresh_token_store()
+        .is_session_active(auth_session_id)
+        .await?;
+    Ok(!active)
+}
+
 /// Dispatch incoming client messages to appropriate handlers
 pub struct MessageDispatcher;
 
@@ -39,6 +58,18 @@ impl MessageDispatcher {
             return Ok(Some(CloseCode::UnknownOpcode));
         }
 
+        // Count the payload against the connection's rate limits
+        if let Err(kind) = connection.rate_limiter().check(message.op) {
+            state.rate_limiter().record(kind);
+            tracing::warn!(
+                session_id = %connection.session_id(),
+                op = %message.op,
+                limit = kind.as_str(),
+                "Client exceeded rate limit"
+            );
+            return Ok(Some(CloseCode::RateLimited));
+        }
+
         match message.op {
             OpCode::Identify => {
                 let payload = message.as_identify().ok_or_else(|| {
@@ -68,6 +99,13 @@ impl MessageDispatcher {
 
                 PresenceHandler::handle(state, connection, payload).await
             }
+            OpCode::RequestGuildMembers => {
+                let payload = message.as_request_guild_members().ok_or_else(|| {
+                    HandlerError::InvalidPayload("Invalid RequestGuildMembers payload".to_string())
+                })?;
+
+                RequestGuildMembersHandler::handle(state, connection, payload).await
+            }
             // These ops should never reach here due to is_client_op check
             _ => {
                 tracing::error!(op = %message.op, "Unhandled client op code");
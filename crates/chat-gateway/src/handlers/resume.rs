@@ -3,9 +3,18 @@
 use super::{HandlerError, HandlerResult};
 use crate::connection::{Connection, Session};
 use crate::events::{GatewayEventType, ResumedEvent};
-use crate::protocol::{CloseCode, GatewayMessage, ResumePayload};
+use crate::protocol::{CloseCode, GatewayMessage, Intents, ResumePayload, Shard};
 use crate::server::GatewayState;
+use chat_cache::SessionState;
+use chat_core::Snowflake;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Time allowed for the connection still holding a session to let go of it
+const TAKEOVER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Interval between checks of a session being taken over
+const TAKEOVER_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Handles Resume messages
 pub struct ResumeHandler;
@@ -52,6 +61,25 @@ impl ResumeHandler {
             return Ok(None);
         };
 
+        // Access tokens of a revoked login cannot resume either
+        if super::login_revoked(state, &claims).await? {
+            tracing::debug!(user_id = %user_id, "Resume rejected: login session revoked");
+            connection
+                .send(GatewayMessage::invalid_session(false))
+                .await
+                .ok();
+            return Ok(None);
+        }
+
+        // A session still open on another connection (or node) is taken over
+        if let Err(e) = Self::release_session(state, &payload.session_id, user_id).await {
+            tracing::warn!(
+                session_id = %payload.session_id,
+                error = %e,
+                "Failed to release session for resume"
+            );
+        }
+
         // Attempt to resume the session
         let resume_result = Session::resume(
             state.service_context().session_store(),
@@ -72,25 +100,59 @@ impl ResumeHandler {
                     "Session resumed"
                 );
 
+                let store = state.service_context().session_store();
+
+                // Delete the old session (we're using a new session ID now)
+                Session::delete(store, &payload.session_id).await.ok();
+
+                // Create new session in Redis for this connection
+                Session::create(
+                    store,
+                    state.node().id(),
+                    connection.session_id(),
+                    user_id,
+                    session.properties.clone(),
+                    Some(format!("ws://{}/gateway", state.config().gateway.address())),
+                    session.intents,
+                    session.shard,
+                    claims.session_id.clone().or(session.auth_session_id.clone()),
+                )
+                .await
+                .map_err(HandlerError::CacheError)?;
+
+                // Route the user's events to this node
+                state
+                    .node()
+                    .add_session(connection.session_id(), user_id)
+                    .await
+                    .map_err(HandlerError::CacheError)?;
+
                 // Authenticate the new connection
                 state
                     .connection_manager()
                     .authenticate_connection(connection.session_id(), user_id)
                     .await;
+                connection.set_intents(
+                    session.intents.map_or(Intents::all(), Intents::from_bits_truncate),
+                );
+                if let Some(shard) = session.shard.and_then(|s| Shard::resolve(Some(s)).ok().flatten()) {
+                    connection.set_shard(shard);
+                }
 
-                // Restore sequence number
-                connection.set_sequence(session.sequence);
+                // Continue numbering after the last event of the old session
+                let last_sequence = missed_events
+                    .last()
+                    .map_or(session.sequence, |e| e.sequence.max(session.sequence));
+                connection.set_sequence(last_sequence);
 
-                // Restore guild subscriptions
+                // Restore the session's guild list
                 for guild_id in &session.guilds {
-                    state
-                        .connection_manager()
-                        .subscribe_to_guild(connection.session_id(), *guild_id)
-                        .await;
+                    Session::subscribe_guild(store, connection.session_id(), *guild_id)
+                        .await
+                        .ok();
                 }
-                connection.set_guilds(session.guilds).await;
 
-                // Replay missed events
+                // Replay missed events with their original sequence numbers
                 for event in missed_events {
                     let msg = GatewayMessage::dispatch(event.event_type, event.sequence, event.data);
                     if connection.send(msg).await.is_err() {
@@ -102,36 +164,26 @@ impl ResumeHandler {
                     }
                 }
 
+                // Live events are routed only after the replay so they follow it
+                state
+                    .event_dispatcher()
+                    .subscribe_session(connection.session_id(), user_id, &session.guilds)
+                    .await;
+
                 // Send RESUMED event
-                let resumed = ResumedEvent {};
-                let seq = connection.next_sequence();
+                let resumed = ResumedEvent {
+                    session_id: connection.session_id().to_string(),
+                };
                 let resumed_data = serde_json::to_value(&resumed).unwrap_or_default();
 
                 connection
-                    .send(GatewayMessage::dispatch(
+                    .send(GatewayMessage::event(
                         GatewayEventType::Resumed.as_str(),
-                        seq,
                         resumed_data,
                     ))
                     .await
                     .map_err(|e| HandlerError::Internal(format!("Failed to send RESUMED: {e}")))?;
 
-                // Delete the old session (we're using a new session ID now)
-                Session::delete(state.service_context().session_store(), &payload.session_id)
-                    .await
-                    .ok();
-
-                // Create new session in Redis for this connection
-                Session::create(
-                    state.service_context().session_store(),
-                    connection.session_id(),
-                    user_id,
-                    None,
-                    Some(format!("ws://{}/gateway", state.config().gateway.address())),
-                )
-                .await
-                .ok();
-
                 // Set user presence to online
                 let presence_data =
                     chat_cache::PresenceData::new(user_id, chat_cache::UserStatus::Online);
@@ -177,4 +229,67 @@ impl ResumeHandler {
             }
         }
     }
+
+    /// Make a session that is still connected resumable
+    ///
+    /// The connection holding it is closed, directly when it is on this node
+    /// or with a takeover event to its node, and the session becomes
+    /// resumable once that connection is cleaned up. Sessions held by a node
+    /// whose lease expired are released right away.
+    async fn release_session(
+        state: &GatewayState,
+        session_id: &str,
+        user_id: Snowflake,
+    ) -> Result<(), chat_cache::RedisPoolError> {
+        let store = state.service_context().session_store();
+        let Some(session) = store.get(session_id).await? else {
+            return Ok(());
+        };
+        if session.user_id != user_id || session.state != SessionState::Connected {
+            return Ok(());
+        }
+
+        let node = state.node();
+        let held = match session.node_id.as_deref() {
+            Some(node_id) if node_id == node.id() => {
+                match state.connection_manager().get_connection(session_id) {
+                    Some(connection) => {
+                        connection.close(CloseCode::SessionTimeout);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Some(node_id) if node.is_alive(node_id).await? => {
+                tracing::info!(
+                    session_id = %session_id,
+                    node_id = %node_id,
+                    "Taking over session from another gateway node"
+                );
+                state
+                    .service_context()
+                    .publisher()
+                    .publish_session_takeover(node_id, session_id)
+                    .await?
+                    > 0
+            }
+            _ => false,
+        };
+
+        if !held {
+            // Nothing holds the connection any more
+            Session::disconnect(store, session_id).await?;
+            return Ok(());
+        }
+
+        let deadline = Instant::now() + TAKEOVER_TIMEOUT;
+        while Instant::now() < deadline {
+            if Session::get_state(store, session_id).await? != Some(SessionState::Connected) {
+                break;
+            }
+            tokio::time::sleep(TAKEOVER_POLL_INTERVAL).await;
+        }
+
+        Ok(())
+    }
 }
@@ -12,6 +12,8 @@
 //! - **Session Resume**: 2-minute window for reconnecting without losing events
 //! - **Heartbeat System**: Keep-alive mechanism with automatic zombie detection
 //! - **Event Distribution**: Redis Pub/Sub integration for cross-instance events
+//! - **Rate Limiting**: Token buckets per connection, user and IP address
+//! - **Multi-Node**: Node leases in Redis, node-routed user events and cross-node resume
 //!
 //! ## Op Codes
 //!
@@ -42,10 +44,12 @@
 //! ```
 
 pub mod broadcast;
+pub mod cluster;
 pub mod connection;
 pub mod events;
 pub mod handlers;
 pub mod protocol;
+pub mod rate_limit;
 pub mod server;
 
 // Re-export main server function
@@ -53,8 +57,10 @@ pub use server::run;
 
 // Re-export commonly used types
 pub use broadcast::{EventDispatcher, EventDispatcherConfig};
+pub use cluster::GatewayNode;
 pub use connection::{Connection, ConnectionManager, ConnectionState, Session};
 pub use events::{GatewayEventType, ReadyEvent, UnavailableGuild};
 pub use handlers::{HandlerError, HandlerResult, MessageDispatcher};
-pub use protocol::{CloseCode, GatewayMessage, HelloPayload, IdentifyPayload, OpCode};
+pub use protocol::{CloseCode, GatewayMessage, HelloPayload, IdentifyPayload, Intents, OpCode};
+pub use rate_limit::{GatewayRateLimiter, RateLimitSnapshot};
 pub use server::{create_app, create_gateway_state, GatewayState};
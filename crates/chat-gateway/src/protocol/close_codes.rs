@@ -34,6 +34,10 @@ pub enum CloseCode {
     ShardingRequired = 4011,
     /// Invalid/outdated API version
     InvalidApiVersion = 4012,
+    /// Intents contain unknown bits
+    InvalidIntents = 4013,
+    /// Privileged intents requested without the account opt-in
+    DisallowedIntents = 4014,
 }
 
 impl CloseCode {
@@ -53,6 +57,8 @@ impl CloseCode {
             4010 => Some(Self::InvalidShard),
             4011 => Some(Self::ShardingRequired),
             4012 => Some(Self::InvalidApiVersion),
+            4013 => Some(Self::InvalidIntents),
+            4014 => Some(Self::DisallowedIntents),
             _ => None,
         }
     }
@@ -94,6 +100,8 @@ impl CloseCode {
             Self::InvalidShard => "Invalid shard configuration",
             Self::ShardingRequired => "Sharding required",
             Self::InvalidApiVersion => "Invalid API version",
+            Self::InvalidIntents => "Invalid intents",
+            Self::DisallowedIntents => "Disallowed intents",
         }
     }
 
@@ -113,6 +121,8 @@ impl CloseCode {
             Self::InvalidShard => "InvalidShard",
             Self::ShardingRequired => "ShardingRequired",
             Self::InvalidApiVersion => "InvalidApiVersion",
+            Self::InvalidIntents => "InvalidIntents",
+            Self::DisallowedIntents => "DisallowedIntents",
         }
     }
 }
@@ -138,6 +148,7 @@ mod tests {
         assert_eq!(CloseCode::from_u16(4000), Some(CloseCode::UnknownError));
         assert_eq!(CloseCode::from_u16(4004), Some(CloseCode::AuthenticationFailed));
         assert_eq!(CloseCode::from_u16(4012), Some(CloseCode::InvalidApiVersion));
+        assert_eq!(CloseCode::from_u16(4014), Some(CloseCode::DisallowedIntents));
         assert_eq!(CloseCode::from_u16(1000), None);
         assert_eq!(CloseCode::from_u16(4006), None); // 4006 is not defined
     }
@@ -166,6 +177,8 @@ mod tests {
         assert!(!CloseCode::InvalidShard.should_reconnect());
         assert!(!CloseCode::ShardingRequired.should_reconnect());
         assert!(!CloseCode::InvalidApiVersion.should_reconnect());
+        assert!(!CloseCode::InvalidIntents.should_reconnect());
+        assert!(!CloseCode::DisallowedIntents.should_reconnect());
     }
 
     #[test]
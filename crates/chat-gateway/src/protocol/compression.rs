@@ -0,0 +1,172 @@
+//! Gateway compression
+//!
+//! Transport compression (`compress` query parameter) keeps one compression
+//! context for the whole connection and flushes it after every message, so
+//! clients feed each binary frame into a single long-lived decompressor.
+//! Payload compression (`compress` in Identify) zlib-compresses each message
+//! on its own.
+
+use flate2::write::ZlibEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+/// zstd compression level for transport compression
+const ZSTD_LEVEL: i32 = 3;
+
+/// Transport compression requested with the `compress` query parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransportCompression {
+    /// zlib stream; every frame ends with a sync flush (`00 00 FF FF`)
+    #[serde(rename = "zlib-stream")]
+    ZlibStream,
+    /// zstd stream; every frame ends with a flushed block
+    #[serde(rename = "zstd-stream")]
+    ZstdStream,
+}
+
+/// Per-connection compression context for transport compression
+pub struct TransportCompressor {
+    encoder: Encoder,
+}
+
+enum Encoder {
+    Zlib(ZlibEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+}
+
+impl TransportCompressor {
+    /// Create a compression context for a connection
+    pub fn new(compression: TransportCompression) -> io::Result<Self> {
+        let encoder = match compression {
+            TransportCompression::ZlibStream => {
+                Encoder::Zlib(ZlibEncoder::new(Vec::new(), flate2::Compression::default()))
+            }
+            TransportCompression::ZstdStream => {
+                Encoder::Zstd(zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL)?)
+            }
+        };
+        Ok(Self { encoder })
+    }
+
+    /// Compress one message into a frame
+    ///
+    /// The context is flushed so the frame can be decoded as soon as it
+    /// arrives; earlier frames stay in the window and improve the ratio.
+    pub fn compress(&mut self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match &mut self.encoder {
+            Encoder::Zlib(encoder) => {
+                encoder.write_all(data)?;
+                encoder.flush()?;
+                Ok(std::mem::take(encoder.get_mut()))
+            }
+            Encoder::Zstd(encoder) => {
+                encoder.write_all(data)?;
+                encoder.flush()?;
+                Ok(std::mem::take(encoder.get_mut()))
+            }
+        }
+    }
+}
+
+/// zlib-compress a single message for payload compression
+pub fn compress_payload(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Decompress, FlushDecompress};
+    use std::io::Read;
+
+    const ZLIB_SUFFIX: [u8; 4] = [0x00, 0x00, 0xFF, 0xFF];
+
+    fn messages() -> Vec<String> {
+        vec![
+            r#"{"op":10,"d":{"heartbeat_interval":45000}}"#.to_string(),
+            r#"{"op":0,"t":"READY","s":1,"d":{"v":1}}"#.to_string(),
+            format!(
+                r#"{{"op":0,"t":"GUILD_CREATE","s":2,"d":{{"channels":[{}]}}}}"#,
+                vec![r#"{"name":"general","type":0}"#; 200].join(",")
+            ),
+        ]
+    }
+
+    /// Feed one frame into a long-lived zlib decompressor
+    fn inflate_frame(decompress: &mut Decompress, frame: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(frame.len() * 8);
+        let mut input = frame;
+        loop {
+            if output.capacity() == output.len() {
+                output.reserve(output.len().max(1024));
+            }
+            let before = decompress.total_in();
+            decompress
+                .decompress_vec(input, &mut output, FlushDecompress::Sync)
+                .unwrap();
+            let consumed = usize::try_from(decompress.total_in() - before).unwrap();
+            input = &input[consumed..];
+            if input.is_empty() && output.len() < output.capacity() {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_zlib_stream_round_trip() {
+        let mut compressor = TransportCompressor::new(TransportCompression::ZlibStream).unwrap();
+        let mut decompress = Decompress::new(true);
+
+        for message in messages() {
+            let frame = compressor.compress(message.as_bytes()).unwrap();
+            assert!(frame.ends_with(&ZLIB_SUFFIX));
+            assert_eq!(inflate_frame(&mut decompress, &frame), message.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_zlib_stream_shares_context() {
+        let mut compressor = TransportCompressor::new(TransportCompression::ZlibStream).unwrap();
+        let message = messages().pop().unwrap();
+
+        let first = compressor.compress(message.as_bytes()).unwrap();
+        let second = compressor.compress(message.as_bytes()).unwrap();
+        assert!(second.len() < first.len());
+    }
+
+    #[test]
+    fn test_zstd_stream_round_trip() {
+        let mut compressor = TransportCompressor::new(TransportCompression::ZstdStream).unwrap();
+        let mut decoder = zstd::stream::write::Decoder::new(Vec::new()).unwrap();
+
+        for message in messages() {
+            let frame = compressor.compress(message.as_bytes()).unwrap();
+            decoder.write_all(&frame).unwrap();
+            decoder.flush().unwrap();
+            assert_eq!(std::mem::take(decoder.get_mut()), message.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_compress_payload_round_trip() {
+        for message in messages() {
+            let payload = compress_payload(message.as_bytes()).unwrap();
+            let mut decoded = String::new();
+            flate2::read::ZlibDecoder::new(payload.as_slice())
+                .read_to_string(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn test_transport_compression_query_values() {
+        let parsed: TransportCompression = serde_json::from_str("\"zlib-stream\"").unwrap();
+        assert_eq!(parsed, TransportCompression::ZlibStream);
+        let parsed: TransportCompression = serde_json::from_str("\"zstd-stream\"").unwrap();
+        assert_eq!(parsed, TransportCompression::ZstdStream);
+        assert!(serde_json::from_str::<TransportCompression>("\"gzip\"").is_err());
+    }
+}
@@ -0,0 +1,440 @@
+//! Gateway payload encodings
+//!
+//! Messages are JSON by default. Clients may pick MessagePack or Erlang
+//! External Term Format with the `encoding` query parameter; both are sent in
+//! binary frames and carry Snowflake IDs as integers instead of strings.
+
+use super::GatewayMessage;
+use eetf::{Atom, BigInteger, Binary, FixInteger, Float, List, Map, Term};
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Keys holding arrays of Snowflake IDs without an `_ids` suffix
+const SNOWFLAKE_ARRAY_KEYS: [&str; 3] = ["roles", "mention_roles", "mention_channels"];
+
+/// Errors encoding or decoding a gateway payload
+#[derive(Debug, Error)]
+pub enum EncodingError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("MessagePack encode error: {0}")]
+    MsgpackEncode(#[from] rmp_serde::encode::Error),
+
+    #[error("MessagePack decode error: {0}")]
+    MsgpackDecode(#[from] rmp_serde::decode::Error),
+
+    #[error("ETF encode error: {0}")]
+    EtfEncode(#[from] eetf::EncodeError),
+
+    #[error("ETF decode error: {0}")]
+    EtfDecode(#[from] eetf::DecodeError),
+
+    #[error("Unsupported ETF term: {0}")]
+    UnsupportedTerm(String),
+}
+
+/// Wire encoding requested with the `encoding` query parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    /// JSON text frames
+    #[default]
+    Json,
+    /// MessagePack binary frames
+    Msgpack,
+    /// Erlang External Term Format binary frames
+    Etf,
+}
+
+impl Encoding {
+    /// Check if messages are sent in binary frames
+    #[must_use]
+    pub const fn is_binary(self) -> bool {
+        !matches!(self, Self::Json)
+    }
+
+    /// Encode a message
+    pub fn encode(self, message: &GatewayMessage) -> Result<Vec<u8>, EncodingError> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(message)?),
+            Self::Msgpack => Ok(rmp_serde::to_vec_named(&binary_value(message)?)?),
+            Self::Etf => {
+                let mut bytes = Vec::new();
+                value_to_term(&binary_value(message)?)?.encode(&mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Decode a message
+    pub fn decode(self, data: &[u8]) -> Result<GatewayMessage, EncodingError> {
+        let mut value = match self {
+            Self::Json => return Ok(serde_json::from_slice(data)?),
+            Self::Msgpack => rmp_serde::from_slice::<Value>(data)?,
+            Self::Etf => term_to_value(Term::decode(data)?)?,
+        };
+        snowflakes_to_strings(&mut value, false);
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// Convert a message to the value tree sent in binary encodings
+fn binary_value(message: &GatewayMessage) -> Result<Value, EncodingError> {
+    let mut value = serde_json::to_value(message)?;
+    snowflakes_to_integers(&mut value, false);
+    Ok(value)
+}
+
+/// Check if values under a key hold Snowflake IDs
+fn is_snowflake_key(key: &str) -> bool {
+    key == "id"
+        || key == "ids"
+        || key.ends_with("_id")
+        || key.ends_with("_ids")
+        || SNOWFLAKE_ARRAY_KEYS.contains(&key)
+}
+
+/// Parse a canonical Snowflake string (digits only, no leading zeros)
+fn parse_snowflake(s: &str) -> Option<i64> {
+    let id = s.parse::<i64>().ok()?;
+    (id >= 0 && id.to_string() == s).then_some(id)
+}
+
+/// Replace Snowflake strings with integers under keys that hold IDs
+fn snowflakes_to_integers(value: &mut Value, is_id: bool) {
+    match value {
+        Value::String(s) if is_id => {
+            if let Some(id) = parse_snowflake(s) {
+                *value = Value::from(id);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                snowflakes_to_integers(item, is_id);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                snowflakes_to_integers(item, is_snowflake_key(key));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace integer Snowflakes with strings, the inverse of [`snowflakes_to_integers`]
+fn snowflakes_to_strings(value: &mut Value, is_id: bool) {
+    match value {
+        Value::Number(n) if is_id => {
+            if let Some(id) = n.as_i64().filter(|id| *id >= 0) {
+                *value = Value::String(id.to_string());
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                snowflakes_to_strings(item, is_id);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                snowflakes_to_strings(item, is_snowflake_key(key));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Convert a JSON value to an ETF term
+///
+/// Strings become binaries, null and booleans become atoms, and map keys are binaries.
+fn value_to_term(value: &Value) -> Result<Term, EncodingError> {
+    let term = match value {
+        Value::Null => Atom::from("nil").into(),
+        Value::Bool(b) => Atom::from(if *b { "true" } else { "false" }).into(),
+        Value::Number(n) => number_to_term(n)?,
+        Value::String(s) => Binary::from(s.as_bytes()).into(),
+        Value::Array(items) => List::from(
+            items
+                .iter()
+                .map(value_to_term)
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .into(),
+        Value::Object(map) => Map::from(
+            map.iter()
+                .map(|(k, v)| Ok((Binary::from(k.as_bytes()).into(), value_to_term(v)?)))
+                .collect::<Result<HashMap<Term, Term>, EncodingError>>()?,
+        )
+        .into(),
+    };
+    Ok(term)
+}
+
+/// Convert a JSON number to the smallest ETF integer or a float
+fn number_to_term(n: &Number) -> Result<Term, EncodingError> {
+    if let Some(i) = n.as_i64() {
+        return Ok(match i32::try_from(i) {
+            Ok(small) => FixInteger::from(small).into(),
+            Err(_) => BigInteger::from(i).into(),
+        });
+    }
+    if let Some(u) = n.as_u64() {
+        return Ok(BigInteger::from(u).into());
+    }
+    let f = n.as_f64().unwrap_or_default();
+    Float::try_from(f)
+        .map(Term::from)
+        .map_err(|_| EncodingError::UnsupportedTerm(f.to_string()))
+}
+
+/// Convert an ETF term to a JSON value
+///
+/// Accepts binaries, byte lists and atoms as strings and map keys.
+fn term_to_value(term: Term) -> Result<Value, EncodingError> {
+    let value = match term {
+        Term::Atom(atom) => match atom.name.as_str() {
+            "nil" | "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => Value::String(atom.name),
+        },
+        Term::FixInteger(i) => Value::from(i.value),
+        Term::BigInteger(big) => {
+            if let Ok(i) = i64::try_from(&big.value) {
+                Value::from(i)
+            } else if let Ok(u) = u64::try_from(&big.value) {
+                Value::from(u)
+            } else {
+                return Err(EncodingError::UnsupportedTerm(big.to_string()));
+            }
+        }
+        Term::Float(f) => Number::from_f64(f.value)
+            .map(Value::Number)
+            .ok_or_else(|| EncodingError::UnsupportedTerm(f.to_string()))?,
+        Term::Binary(_) | Term::ByteList(_) => Value::String(term_to_string(term)?),
+        Term::List(list) => Value::Array(
+            list.elements
+                .into_iter()
+                .map(term_to_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        Term::Map(map) => Value::Object(
+            map.map
+                .into_iter()
+                .map(|(k, v)| Ok((term_to_string(k)?, term_to_value(v)?)))
+                .collect::<Result<_, EncodingError>>()?,
+        ),
+        other => return Err(EncodingError::UnsupportedTerm(other.to_string())),
+    };
+    Ok(value)
+}
+
+/// Read a UTF-8 string from a binary, byte list or atom term
+fn term_to_string(term: Term) -> Result<String, EncodingError> {
+    let bytes = match term {
+        Term::Binary(binary) => binary.bytes,
+        Term::ByteList(list) => list.bytes,
+        Term::Atom(atom) => return Ok(atom.name),
+        other => return Err(EncodingError::UnsupportedTerm(other.to_string())),
+    };
+    String::from_utf8(bytes).map_err(|e| EncodingError::UnsupportedTerm(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::GatewayEventType;
+    use crate::protocol::OpCode;
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    const ENCODINGS: [Encoding; 3] = [Encoding::Json, Encoding::Msgpack, Encoding::Etf];
+
+    const KEYS: [&str; 12] = [
+        "id", "guild_id", "channel_id", "ids", "roles", "mention_roles", "name", "content",
+        "count", "tts", "nonce", "session_id",
+    ];
+
+    fn op_codes() -> Vec<OpCode> {
+        (0..=u8::MAX).filter_map(OpCode::from_u8).collect()
+    }
+
+    fn snowflake() -> impl Strategy<Value = Value> {
+        (0..=i64::MAX).prop_map(|id| Value::String(id.to_string()))
+    }
+
+    fn leaf() -> impl Strategy<Value = Value> {
+        prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::from),
+            any::<u64>().prop_map(Value::from),
+            // Dyadic fractions survive JSON's float parsing exactly
+            (-1_000_000i32..1_000_000).prop_map(|n| Value::from(f64::from(n) / 4.0)),
+            ".{0,12}".prop_map(Value::String),
+            "[0-9]{1,20}".prop_map(Value::String),
+        ]
+    }
+
+    /// JSON payloads shaped like server events: ID keys always hold Snowflake strings
+    fn payload() -> impl Strategy<Value = Value> {
+        leaf().prop_recursive(4, 48, 6, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array),
+                prop::collection::vec(
+                    (
+                        prop::sample::select(KEYS.to_vec()),
+                        inner,
+                        snowflake(),
+                        prop::collection::vec(snowflake(), 0..4),
+                    ),
+                    0..6,
+                )
+                .prop_map(|fields| {
+                    let map = fields
+                        .into_iter()
+                        .map(|(key, value, id, ids)| {
+                            let value = if key.ends_with("_id") || key == "id" {
+                                id
+                            } else if key == "ids" || key.ends_with("roles") {
+                                Value::Array(ids)
+                            } else {
+                                value
+                            };
+                            (key.to_string(), value)
+                        })
+                        .collect();
+                    Value::Object(map)
+                }),
+            ]
+        })
+    }
+
+    fn message() -> impl Strategy<Value = GatewayMessage> {
+        (
+            prop::sample::select(op_codes()),
+            prop::option::of(prop::sample::select(GatewayEventType::ALL.to_vec())),
+            prop::option::of(any::<u64>()),
+            // `"d": null` reads back as no payload in every encoding
+            prop::option::of(payload().prop_filter("non-null payload", |d| !d.is_null())),
+        )
+            .prop_map(|(op, t, s, d)| GatewayMessage {
+                op,
+                t: t.map(|t| t.as_str().to_string()),
+                s,
+                d,
+            })
+    }
+
+    fn assert_same(a: &GatewayMessage, b: &GatewayMessage) {
+        assert_eq!(a.op, b.op);
+        assert_eq!(a.t, b.t);
+        assert_eq!(a.s, b.s);
+        assert_eq!(a.d, b.d);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip(message in message()) {
+            for encoding in ENCODINGS {
+                let bytes = encoding.encode(&message).unwrap();
+                let decoded = encoding.decode(&bytes).unwrap();
+                assert_same(&decoded, &message);
+            }
+        }
+
+        #[test]
+        fn prop_decode_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            for encoding in ENCODINGS {
+                let _ = encoding.decode(&bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_op_and_event_round_trips() {
+        let data = json!({"id": "175928847299117063", "guild_id": null, "content": "hi"});
+        for op in op_codes() {
+            for event_type in GatewayEventType::ALL {
+                let message = GatewayMessage {
+                    op,
+                    t: Some(event_type.as_str().to_string()),
+                    s: Some(42),
+                    d: Some(data.clone()),
+                };
+                for encoding in ENCODINGS {
+                    let bytes = encoding.encode(&message).unwrap();
+                    assert_same(&encoding.decode(&bytes).unwrap(), &message);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_encodings_send_snowflakes_as_integers() {
+        let message = GatewayMessage::event(
+            "MESSAGE_CREATE",
+            json!({
+                "id": "175928847299117063",
+                "mention_roles": ["41771983423143936"],
+                "session_id": "0c0d9a6e-1b5c",
+                "nonce": "12345"
+            }),
+        );
+
+        let bytes = Encoding::Msgpack.encode(&message).unwrap();
+        let value: Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(value["d"]["id"], json!(175_928_847_299_117_063_i64));
+        assert_eq!(value["d"]["mention_roles"], json!([41_771_983_423_143_936_i64]));
+        assert_eq!(value["d"]["session_id"], json!("0c0d9a6e-1b5c"));
+        assert_eq!(value["d"]["nonce"], json!("12345"));
+
+        let bytes = Encoding::Etf.encode(&message).unwrap();
+        let value = term_to_value(Term::decode(bytes.as_slice()).unwrap()).unwrap();
+        assert_eq!(value["d"]["id"], json!(175_928_847_299_117_063_i64));
+    }
+
+    #[test]
+    fn test_decode_etf_client_payload() {
+        let identify = Term::from(Map::from([
+            (Term::from(Atom::from("op")), Term::from(FixInteger::from(2))),
+            (
+                Term::from(Atom::from("d")),
+                Term::from(Map::from([(
+                    Term::from(Binary::from("token".as_bytes())),
+                    Term::from(Binary::from("abc".as_bytes())),
+                )])),
+            ),
+        ]));
+        let mut bytes = Vec::new();
+        identify.encode(&mut bytes).unwrap();
+
+        let message = Encoding::Etf.decode(&bytes).unwrap();
+        assert_eq!(message.op, OpCode::Identify);
+        assert_eq!(message.d, Some(json!({"token": "abc"})));
+    }
+
+    #[test]
+    fn test_decode_invalid_payloads() {
+        assert!(Encoding::Json.decode(b"{not json").is_err());
+        assert!(Encoding::Msgpack.decode(&[0xc1]).is_err());
+        assert!(Encoding::Etf.decode(&[131, 255]).is_err());
+        // Valid encoding, invalid message
+        let bytes = rmp_serde::to_vec_named(&json!({"op": 99})).unwrap();
+        assert!(Encoding::Msgpack.decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_encoding_query_values() {
+        let parsed: Encoding = serde_json::from_str("\"etf\"").unwrap();
+        assert_eq!(parsed, Encoding::Etf);
+        let parsed: Encoding = serde_json::from_str("\"msgpack\"").unwrap();
+        assert_eq!(parsed, Encoding::Msgpack);
+        assert!(serde_json::from_str::<Encoding>("\"xml\"").is_err());
+        assert!(!Encoding::default().is_binary());
+    }
+}
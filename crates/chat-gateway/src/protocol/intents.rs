@@ -0,0 +1,124 @@
+//! Gateway intents
+//!
+//! Bitfield sent in Identify to choose which groups of events a session receives.
+
+use super::CloseCode;
+use bitflags::bitflags;
+
+bitflags! {
+    /// Gateway intent flags
+    ///
+    /// Bit positions follow Discord's numbering so existing clients can reuse their values.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Intents: u64 {
+        /// Guild, channel and thread lifecycle events
+        const GUILDS                   = 1 << 0;
+        /// Member add, update and remove events (privileged)
+        const GUILD_MEMBERS            = 1 << 1;
+        /// Ban add and remove events
+        const GUILD_MODERATION         = 1 << 2;
+        /// Presence updates (privileged)
+        const GUILD_PRESENCES          = 1 << 8;
+        /// Messages in guild channels
+        const GUILD_MESSAGES           = 1 << 9;
+        /// Reactions in guild channels
+        const GUILD_MESSAGE_REACTIONS  = 1 << 10;
+        /// Typing indicators in guild channels
+        const GUILD_MESSAGE_TYPING     = 1 << 11;
+        /// Messages and pins in DM channels
+        const DIRECT_MESSAGES          = 1 << 12;
+        /// Reactions in DM channels
+        const DIRECT_MESSAGE_REACTIONS = 1 << 13;
+        /// Typing indicators in DM channels
+        const DIRECT_MESSAGE_TYPING    = 1 << 14;
+    }
+}
+
+impl Intents {
+    /// Intents that require the account's privileged intents opt-in
+    pub const PRIVILEGED: Self = Self::GUILD_MEMBERS.union(Self::GUILD_PRESENCES);
+
+    /// Intents used when Identify omits the field
+    ///
+    /// Every intent the account is allowed to request.
+    #[must_use]
+    pub fn default_for(allow_privileged: bool) -> Self {
+        if allow_privileged {
+            Self::all()
+        } else {
+            Self::all().difference(Self::PRIVILEGED)
+        }
+    }
+
+    /// Resolve the intents requested in Identify
+    ///
+    /// Unknown bits close with [`CloseCode::InvalidIntents`]; privileged intents
+    /// the account has not opted in to close with [`CloseCode::DisallowedIntents`].
+    pub fn resolve(requested: Option<u64>, allow_privileged: bool) -> Result<Self, CloseCode> {
+        let Some(bits) = requested else {
+            return Ok(Self::default_for(allow_privileged));
+        };
+
+        let intents = Self::from_bits(bits).ok_or(CloseCode::InvalidIntents)?;
+        if !allow_privileged && intents.intersects(Self::PRIVILEGED) {
+            return Err(CloseCode::DisallowedIntents);
+        }
+
+        Ok(intents)
+    }
+
+    /// Check if an event requiring `required` should be delivered
+    ///
+    /// Events without an intent are always delivered.
+    #[inline]
+    #[must_use]
+    pub fn allows(self, required: Option<Self>) -> bool {
+        required.is_none_or(|required| self.contains(required))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_for() {
+        assert_eq!(Intents::default_for(true), Intents::all());
+        let default = Intents::default_for(false);
+        assert!(default.contains(Intents::GUILD_MESSAGES));
+        assert!(!default.intersects(Intents::PRIVILEGED));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let bits = (Intents::GUILDS | Intents::GUILD_MESSAGES).bits();
+        assert_eq!(
+            Intents::resolve(Some(bits), false),
+            Ok(Intents::GUILDS | Intents::GUILD_MESSAGES)
+        );
+        assert_eq!(Intents::resolve(Some(0), false), Ok(Intents::empty()));
+        assert_eq!(Intents::resolve(None, false), Ok(Intents::default_for(false)));
+    }
+
+    #[test]
+    fn test_resolve_invalid_bits() {
+        assert_eq!(Intents::resolve(Some(1 << 3), true), Err(CloseCode::InvalidIntents));
+        assert_eq!(Intents::resolve(Some(1 << 40), true), Err(CloseCode::InvalidIntents));
+    }
+
+    #[test]
+    fn test_resolve_privileged() {
+        let bits = (Intents::GUILDS | Intents::GUILD_PRESENCES).bits();
+        assert_eq!(Intents::resolve(Some(bits), false), Err(CloseCode::DisallowedIntents));
+        assert!(Intents::resolve(Some(bits), true).is_ok());
+    }
+
+    #[test]
+    fn test_allows() {
+        let intents = Intents::GUILDS | Intents::DIRECT_MESSAGES;
+        assert!(intents.allows(None));
+        assert!(intents.allows(Some(Intents::GUILDS)));
+        assert!(!intents.allows(Some(Intents::GUILD_MESSAGES)));
+        assert!(Intents::empty().allows(None));
+    }
+}
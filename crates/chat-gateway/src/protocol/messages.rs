@@ -2,7 +2,10 @@
 //!
 //! Defines the structure for all WebSocket messages.
 
-use super::{CloseCode, HelloPayload, IdentifyPayload, OpCode, PresenceUpdatePayload, ResumePayload};
+use super::{
+    CloseCode, HelloPayload, IdentifyPayload, OpCode, PresenceUpdatePayload, RequestGuildMembersPayload,
+    ResumePayload,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -41,6 +44,19 @@ impl GatewayMessage {
         }
     }
 
+    /// Create a Dispatch message (op=0) without a sequence number
+    ///
+    /// The connection assigns the sequence when the message is sent.
+    #[must_use]
+    pub fn event(event_type: impl Into<String>, data: Value) -> Self {
+        Self {
+            op: OpCode::Dispatch,
+            t: Some(event_type.into()),
+            s: None,
+            d: Some(data),
+        }
+    }
+
     /// Create a Hello message (op=10)
     #[must_use]
     pub fn hello(payload: HelloPayload) -> Self {
@@ -130,6 +146,14 @@ impl GatewayMessage {
         self.d.as_ref().and_then(|d| serde_json::from_value(d.clone()).ok())
     }
 
+    /// Try to parse as a Request Guild Members payload (op=8)
+    pub fn as_request_guild_members(&self) -> Option<RequestGuildMembersPayload> {
+        if self.op != OpCode::RequestGuildMembers {
+            return None;
+        }
+        self.d.as_ref().and_then(|d| serde_json::from_value(d.clone()).ok())
+    }
+
     /// Try to parse the heartbeat sequence number (op=1)
     pub fn as_heartbeat_seq(&self) -> Option<Option<u64>> {
         if self.op != OpCode::Heartbeat {
@@ -239,6 +263,26 @@ mod tests {
         assert!(identify.properties.is_some());
     }
 
+    #[test]
+    fn test_parse_request_guild_members() {
+        let msg = GatewayMessage {
+            op: OpCode::RequestGuildMembers,
+            t: None,
+            s: None,
+            d: Some(serde_json::json!({
+                "guild_id": "123",
+                "query": "",
+                "limit": 0,
+                "nonce": "abc"
+            })),
+        };
+
+        let request = msg.as_request_guild_members().unwrap();
+        assert!(request.requests_all());
+        assert_eq!(request.nonce.as_deref(), Some("abc"));
+        assert!(msg.as_identify().is_none());
+    }
+
     #[test]
     fn test_parse_heartbeat() {
         let msg = GatewayMessage {
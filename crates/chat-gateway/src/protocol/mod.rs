@@ -1,15 +1,24 @@
 //! Gateway protocol definitions
 //!
-//! Defines the WebSocket protocol including op codes, message formats, and close codes.
+//! Defines the WebSocket protocol including op codes, message formats, encodings, compression, intents, sharding, and close codes.
 
 mod close_codes;
+mod compression;
+mod encoding;
+mod intents;
 mod messages;
 mod opcodes;
 mod payloads;
+mod shard;
 
 pub use close_codes::CloseCode;
+pub use compression::{compress_payload, TransportCompression, TransportCompressor};
+pub use encoding::{Encoding, EncodingError};
+pub use intents::Intents;
 pub use messages::GatewayMessage;
 pub use opcodes::OpCode;
 pub use payloads::{
-    HelloPayload, IdentifyPayload, IdentifyProperties, PresenceUpdatePayload, ResumePayload,
+    HelloPayload, IdentifyPayload, IdentifyProperties, PresenceUpdatePayload,
+    RequestGuildMembersPayload, ResumePayload,
 };
+pub use shard::Shard;
@@ -24,6 +24,8 @@ pub enum OpCode {
     Reconnect = 5,
     /// Invalid Session - session is invalid (server only)
     InvalidSession = 7,
+    /// Request Guild Members - request member chunks for a guild (client only)
+    RequestGuildMembers = 8,
     /// Hello - sent on connect (server only)
     Hello = 10,
     /// Heartbeat ACK - heartbeat acknowledged (server only)
@@ -42,6 +44,7 @@ impl OpCode {
             4 => Some(Self::Resume),
             5 => Some(Self::Reconnect),
             7 => Some(Self::InvalidSession),
+            8 => Some(Self::RequestGuildMembers),
             10 => Some(Self::Hello),
             11 => Some(Self::HeartbeatAck),
             _ => None,
@@ -59,7 +62,11 @@ impl OpCode {
     pub const fn is_client_op(self) -> bool {
         matches!(
             self,
-            Self::Heartbeat | Self::Identify | Self::PresenceUpdate | Self::Resume
+            Self::Heartbeat
+                | Self::Identify
+                | Self::PresenceUpdate
+                | Self::Resume
+                | Self::RequestGuildMembers
         )
     }
 
@@ -88,6 +95,7 @@ impl OpCode {
             Self::Resume => "Resume",
             Self::Reconnect => "Reconnect",
             Self::InvalidSession => "InvalidSession",
+            Self::RequestGuildMembers => "RequestGuildMembers",
             Self::Hello => "Hello",
             Self::HeartbeatAck => "HeartbeatAck",
         }
@@ -132,6 +140,7 @@ mod tests {
         assert_eq!(OpCode::from_u8(4), Some(OpCode::Resume));
         assert_eq!(OpCode::from_u8(5), Some(OpCode::Reconnect));
         assert_eq!(OpCode::from_u8(7), Some(OpCode::InvalidSession));
+        assert_eq!(OpCode::from_u8(8), Some(OpCode::RequestGuildMembers));
         assert_eq!(OpCode::from_u8(10), Some(OpCode::Hello));
         assert_eq!(OpCode::from_u8(11), Some(OpCode::HeartbeatAck));
         assert_eq!(OpCode::from_u8(6), None);
@@ -151,6 +160,7 @@ mod tests {
         assert!(OpCode::Identify.is_client_op());
         assert!(OpCode::PresenceUpdate.is_client_op());
         assert!(OpCode::Resume.is_client_op());
+        assert!(OpCode::RequestGuildMembers.is_client_op());
         assert!(!OpCode::Dispatch.is_client_op());
         assert!(!OpCode::Hello.is_client_op());
     }
@@ -165,6 +175,7 @@ mod tests {
         assert!(OpCode::HeartbeatAck.is_server_op());
         assert!(!OpCode::Identify.is_server_op());
         assert!(!OpCode::Resume.is_server_op());
+        assert!(!OpCode::RequestGuildMembers.is_server_op());
     }
 
     #[test]
@@ -2,6 +2,7 @@
 //!
 //! Defines the payload structures for client-to-server messages.
 
+use chat_core::Snowflake;
 use serde::{Deserialize, Serialize};
 
 /// Payload for op 10 (Hello)
@@ -49,6 +50,18 @@ pub struct IdentifyPayload {
     /// Optional client properties
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<IdentifyProperties>,
+
+    /// Intents bitfield (defaults to every intent the account may request)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intents: Option<u64>,
+
+    /// zlib-compress each payload sent after Identify (ignored with transport compression)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+
+    /// `[shard_id, num_shards]` to receive only a subset of guilds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u32; 2]>,
 }
 
 /// Client connection properties
@@ -151,6 +164,76 @@ pub struct ResumePayload {
     pub seq: u64,
 }
 
+/// Payload for op 8 (Request Guild Members)
+///
+/// Exactly one of `query` or `user_ids` must be set. An empty `query` with a
+/// `limit` of 0 requests every member. Answered with `GUILD_MEMBERS_CHUNK` dispatches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestGuildMembersPayload {
+    /// Guild to list members of
+    pub guild_id: Snowflake,
+
+    /// Username or nickname prefix to match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+
+    /// Maximum members to return for a query (0 with an empty query for all)
+    #[serde(default)]
+    pub limit: u32,
+
+    /// Include presences of the returned members
+    #[serde(default)]
+    pub presences: bool,
+
+    /// Specific members to fetch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_ids: Option<Vec<Snowflake>>,
+
+    /// Echoed back in every chunk to match responses to requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+impl RequestGuildMembersPayload {
+    /// Maximum members returned for a prefix query
+    pub const MAX_QUERY_LIMIT: u32 = 100;
+
+    /// Maximum user IDs per request
+    pub const MAX_USER_IDS: usize = 100;
+
+    /// Maximum nonce length in bytes
+    pub const MAX_NONCE_LEN: usize = 32;
+
+    /// Check if every member of the guild is requested
+    #[must_use]
+    pub fn requests_all(&self) -> bool {
+        self.user_ids.is_none() && self.query.as_deref() == Some("") && self.limit == 0
+    }
+
+    /// Validate the request, returning the reason it is malformed
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match (&self.query, &self.user_ids) {
+            (Some(_), Some(_)) => return Err("query and user_ids are mutually exclusive"),
+            (None, None) => return Err("one of query or user_ids is required"),
+            (Some(_), None)
+                if !self.requests_all() && !(1..=Self::MAX_QUERY_LIMIT).contains(&self.limit) =>
+            {
+                return Err("limit must be between 1 and 100 for a query");
+            }
+            (None, Some(user_ids)) if user_ids.len() > Self::MAX_USER_IDS => {
+                return Err("at most 100 user_ids may be requested");
+            }
+            _ => {}
+        }
+
+        if self.nonce.as_ref().is_some_and(|n| n.len() > Self::MAX_NONCE_LEN) {
+            return Err("nonce must be at most 32 bytes");
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,11 +273,22 @@ mod tests {
         let payload = IdentifyPayload {
             token: "Bearer token123".to_string(),
             properties: Some(IdentifyProperties::new().with_os("linux")),
+            intents: Some(513),
+            compress: None,
+            shard: Some([1, 4]),
         };
 
         let json = serde_json::to_string(&payload).unwrap();
         assert!(json.contains("token123"));
         assert!(json.contains("linux"));
+        assert!(json.contains("513"));
+        assert!(json.contains("\"shard\":[1,4]"));
+    }
+
+    #[test]
+    fn test_identify_payload_without_intents() {
+        let payload: IdentifyPayload = serde_json::from_str(r#"{"token": "abc"}"#).unwrap();
+        assert!(payload.intents.is_none());
     }
 
     #[test]
@@ -209,4 +303,34 @@ mod tests {
         assert!(json.contains("session456"));
         assert!(json.contains("42"));
     }
+
+    #[test]
+    fn test_request_guild_members_validation() {
+        let parse = |json: &str| serde_json::from_str::<RequestGuildMembersPayload>(json).unwrap();
+
+        let all = parse(r#"{"guild_id": "1", "query": "", "limit": 0}"#);
+        assert!(all.requests_all());
+        assert!(all.validate().is_ok());
+
+        let query = parse(r#"{"guild_id": "1", "query": "ali", "limit": 10, "nonce": "n1"}"#);
+        assert!(!query.requests_all());
+        assert!(query.validate().is_ok());
+
+        let ids = parse(r#"{"guild_id": 1, "user_ids": ["2", 3], "presences": true}"#);
+        assert_eq!(ids.user_ids.as_deref().map(<[_]>::len), Some(2));
+        assert!(ids.validate().is_ok());
+
+        assert!(parse(r#"{"guild_id": "1"}"#).validate().is_err());
+        assert!(parse(r#"{"guild_id": "1", "query": "a", "user_ids": []}"#).validate().is_err());
+        assert!(parse(r#"{"guild_id": "1", "query": "a", "limit": 0}"#).validate().is_err());
+        assert!(parse(r#"{"guild_id": "1", "query": "a", "limit": 101}"#).validate().is_err());
+
+        let nonce = "x".repeat(33);
+        let long_nonce = parse(&format!(r#"{{"guild_id": "1", "query": "", "nonce": "{nonce}"}}"#));
+        assert!(long_nonce.validate().is_err());
+
+        let too_many = (0..101).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+        let too_many = parse(&format!(r#"{{"guild_id": "1", "user_ids": [{too_many}]}}"#));
+        assert!(too_many.validate().is_err());
+    }
 }
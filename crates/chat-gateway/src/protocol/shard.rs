@@ -0,0 +1,97 @@
+//! Gateway sharding
+//!
+//! Bots split their guilds across connections by sending `shard: [shard_id, num_shards]`
+//! in Identify. Guild events go to shard `(guild_id >> 22) % num_shards`; DMs and
+//! other events without a guild go to shard 0.
+
+use super::CloseCode;
+use chat_core::Snowflake;
+
+/// Shard assignment of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    /// Index of this shard
+    pub id: u32,
+    /// Total number of shards
+    pub count: u32,
+}
+
+impl Shard {
+    /// Maximum number of shards a bot may use
+    pub const MAX_SHARDS: u32 = 4096;
+
+    /// Maximum guilds one connection may receive before sharding is required
+    pub const MAX_GUILDS: usize = 2500;
+
+    /// Resolve the shard sent in Identify
+    ///
+    /// Closes with [`CloseCode::InvalidShard`] unless `0 <= shard_id < num_shards <= MAX_SHARDS`.
+    pub fn resolve(requested: Option<[u32; 2]>) -> Result<Option<Self>, CloseCode> {
+        let Some([id, count]) = requested else {
+            return Ok(None);
+        };
+
+        if count == 0 || count > Self::MAX_SHARDS || id >= count {
+            return Err(CloseCode::InvalidShard);
+        }
+
+        Ok(Some(Self { id, count }))
+    }
+
+    /// Check if this shard receives a guild's events
+    #[inline]
+    #[must_use]
+    pub fn owns_guild(self, guild_id: Snowflake) -> bool {
+        guild_id.shard_id(self.count) == self.id
+    }
+
+    /// Check if this shard receives DMs and events outside guilds
+    #[inline]
+    #[must_use]
+    pub const fn receives_dms(self) -> bool {
+        self.id == 0
+    }
+
+    /// The `[shard_id, num_shards]` pair sent in Identify
+    #[inline]
+    #[must_use]
+    pub const fn to_array(self) -> [u32; 2] {
+        [self.id, self.count]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(Shard::resolve(None), Ok(None));
+        assert_eq!(Shard::resolve(Some([0, 1])), Ok(Some(Shard { id: 0, count: 1 })));
+        assert_eq!(Shard::resolve(Some([3, 4])), Ok(Some(Shard { id: 3, count: 4 })));
+    }
+
+    #[test]
+    fn test_resolve_invalid() {
+        assert_eq!(Shard::resolve(Some([0, 0])), Err(CloseCode::InvalidShard));
+        assert_eq!(Shard::resolve(Some([4, 4])), Err(CloseCode::InvalidShard));
+        assert_eq!(
+            Shard::resolve(Some([0, Shard::MAX_SHARDS + 1])),
+            Err(CloseCode::InvalidShard)
+        );
+    }
+
+    #[test]
+    fn test_owns_guild() {
+        let guild_id = Snowflake::new(5 << 22);
+        let shards: Vec<Shard> = (0..4).map(|id| Shard { id, count: 4 }).collect();
+        let owners: Vec<u32> = shards.iter().filter(|s| s.owns_guild(guild_id)).map(|s| s.id).collect();
+        assert_eq!(owners, vec![1]);
+    }
+
+    #[test]
+    fn test_receives_dms() {
+        assert!(Shard { id: 0, count: 2 }.receives_dms());
+        assert!(!Shard { id: 1, count: 2 }.receives_dms());
+    }
+}
@@ -0,0 +1,192 @@
+//! Token buckets
+//!
+//! A bucket holds up to `capacity` tokens and earns one back every
+//! `period / capacity`. Each request spends a token; an empty bucket rejects it.
+
+use dashmap::DashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Buckets tracked by a [`KeyedRateLimiter`] before full ones are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// A single token bucket
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    /// Maximum tokens (burst size)
+    capacity: u32,
+    /// Tokens currently available
+    tokens: u32,
+    /// Time to earn one token
+    interval: Duration,
+    /// When the last earned token was added
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket allowing `capacity` requests per `period`
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        Self::new_at(capacity, period, Instant::now())
+    }
+
+    /// Create a full bucket as of `now`
+    pub fn new_at(capacity: u32, period: Duration, now: Instant) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            tokens: capacity,
+            interval: period / capacity,
+            refilled_at: now,
+        }
+    }
+
+    /// Spend a token, returning false if the bucket is empty
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_at(Instant::now())
+    }
+
+    /// Spend a token as of `now`
+    pub fn try_acquire_at(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens == 0 {
+            return false;
+        }
+        self.tokens -= 1;
+        true
+    }
+
+    /// Check if the bucket has refilled completely as of `now`
+    pub fn is_full_at(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens == self.capacity
+    }
+
+    /// Add the tokens earned since the last refill
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        let earned = elapsed.as_nanos() / self.interval.as_nanos().max(1);
+        if earned == 0 {
+            return;
+        }
+
+        let tokens = u128::from(self.tokens) + earned;
+        if tokens >= u128::from(self.capacity) {
+            self.tokens = self.capacity;
+            self.refilled_at = now;
+        } else {
+            // Below capacity, so both fit in u32; keep the partial interval
+            self.tokens = tokens as u32;
+            self.refilled_at += self.interval * earned as u32;
+        }
+    }
+}
+
+/// One token bucket per key (IP address, user ID)
+#[derive(Debug)]
+pub struct KeyedRateLimiter<K: Hash + Eq> {
+    buckets: DashMap<K, TokenBucket>,
+    capacity: u32,
+    period: Duration,
+}
+
+impl<K: Hash + Eq> KeyedRateLimiter<K> {
+    /// Create a limiter allowing `capacity` requests per `period` for each key
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        Self {
+            buckets: DashMap::new(),
+            capacity,
+            period,
+        }
+    }
+
+    /// Spend a token from the key's bucket, returning false if it is empty
+    pub fn check(&self, key: K) -> bool {
+        self.check_at(key, Instant::now())
+    }
+
+    /// Spend a token from the key's bucket as of `now`
+    pub fn check_at(&self, key: K, now: Instant) -> bool {
+        if self.buckets.len() >= PRUNE_THRESHOLD {
+            self.prune(now);
+        }
+
+        self.buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new_at(self.capacity, self.period, now))
+            .try_acquire_at(now)
+    }
+
+    /// Drop buckets that have refilled; they behave the same as new ones
+    pub fn prune(&self, now: Instant) {
+        self.buckets.retain(|_, bucket| !bucket.is_full_at(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_burst_then_reject() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new_at(5, Duration::from_secs(60), now);
+
+        for _ in 0..5 {
+            assert!(bucket.try_acquire_at(now));
+        }
+        assert!(!bucket.try_acquire_at(now));
+    }
+
+    #[test]
+    fn test_bucket_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new_at(5, Duration::from_secs(60), now);
+        for _ in 0..5 {
+            bucket.try_acquire_at(now);
+        }
+
+        // One token every 12 seconds
+        assert!(!bucket.try_acquire_at(now + Duration::from_secs(11)));
+        assert!(bucket.try_acquire_at(now + Duration::from_secs(12)));
+        assert!(!bucket.try_acquire_at(now + Duration::from_secs(12)));
+
+        // Partial intervals carry over
+        assert!(bucket.try_acquire_at(now + Duration::from_secs(24)));
+    }
+
+    #[test]
+    fn test_bucket_refill_caps_at_capacity() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new_at(2, Duration::from_secs(10), now);
+        bucket.try_acquire_at(now);
+
+        let later = now + Duration::from_secs(3600);
+        assert!(bucket.is_full_at(later));
+        assert!(bucket.try_acquire_at(later));
+        assert!(bucket.try_acquire_at(later));
+        assert!(!bucket.try_acquire_at(later));
+    }
+
+    #[test]
+    fn test_keyed_limiter() {
+        let now = Instant::now();
+        let limiter = KeyedRateLimiter::new(1, Duration::from_secs(5));
+
+        assert!(limiter.check_at("a", now));
+        assert!(!limiter.check_at("a", now));
+        assert!(limiter.check_at("b", now));
+        assert!(limiter.check_at("a", now + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_keyed_limiter_prune() {
+        let now = Instant::now();
+        let limiter = KeyedRateLimiter::new(1, Duration::from_secs(5));
+        limiter.check_at("a", now);
+        limiter.check_at("b", now + Duration::from_secs(3));
+
+        limiter.prune(now + Duration::from_secs(6));
+        assert!(!limiter.buckets.contains_key("a"));
+        assert!(limiter.buckets.contains_key("b"));
+    }
+}
@@ -0,0 +1,79 @@
+//! Rate limit counters
+//!
+//! Counts rate limit violations by kind for monitoring.
+
+use super::RateLimitKind;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Violation counters since the gateway started
+#[derive(Debug, Default)]
+pub struct RateLimitMetrics {
+    payloads: AtomicU64,
+    presence_updates: AtomicU64,
+    identifies: AtomicU64,
+    session_starts: AtomicU64,
+    connections: AtomicU64,
+}
+
+impl RateLimitMetrics {
+    /// Count one violation
+    pub fn record(&self, kind: RateLimitKind) {
+        let counter = match kind {
+            RateLimitKind::Payload => &self.payloads,
+            RateLimitKind::PresenceUpdate => &self.presence_updates,
+            RateLimitKind::Identify => &self.identifies,
+            RateLimitKind::SessionStart => &self.session_starts,
+            RateLimitKind::Connection => &self.connections,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Read the current counts
+    pub fn snapshot(&self) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            payloads: self.payloads.load(Ordering::Relaxed),
+            presence_updates: self.presence_updates.load(Ordering::Relaxed),
+            identifies: self.identifies.load(Ordering::Relaxed),
+            session_starts: self.session_starts.load(Ordering::Relaxed),
+            connections: self.connections.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Point-in-time copy of [`RateLimitMetrics`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RateLimitSnapshot {
+    /// Connections closed for exceeding the general payload limit
+    pub payloads: u64,
+    /// Connections closed for sending too many presence updates
+    pub presence_updates: u64,
+    /// Identify attempts rejected by the per-user limit
+    pub identifies: u64,
+    /// Identify attempts rejected by the daily session start limit
+    pub session_starts: u64,
+    /// Connections rejected by the per-IP limit at upgrade
+    pub connections: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let metrics = RateLimitMetrics::default();
+        metrics.record(RateLimitKind::Payload);
+        metrics.record(RateLimitKind::Payload);
+        metrics.record(RateLimitKind::Connection);
+
+        assert_eq!(
+            metrics.snapshot(),
+            RateLimitSnapshot {
+                payloads: 2,
+                connections: 1,
+                ..Default::default()
+            }
+        );
+    }
+}
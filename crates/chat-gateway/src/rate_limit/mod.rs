@@ -0,0 +1,213 @@
+//! Gateway rate limiting
+//!
+//! Token buckets for inbound payloads (per connection), Identify (per user)
+//! and new connections (per IP), plus violation counters for monitoring.
+//! Violations close the connection with [`CloseCode::RateLimited`](crate::protocol::CloseCode::RateLimited).
+
+mod bucket;
+mod metrics;
+
+pub use bucket::{KeyedRateLimiter, TokenBucket};
+pub use metrics::{RateLimitMetrics, RateLimitSnapshot};
+
+use crate::protocol::OpCode;
+use chat_core::Snowflake;
+use parking_lot::Mutex;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// Payloads of any kind a connection may send per [`PAYLOAD_PERIOD`]
+pub const PAYLOAD_LIMIT: u32 = 120;
+/// Window for [`PAYLOAD_LIMIT`]
+pub const PAYLOAD_PERIOD: Duration = Duration::from_secs(60);
+
+/// Presence updates a connection may send per [`PRESENCE_UPDATE_PERIOD`]
+pub const PRESENCE_UPDATE_LIMIT: u32 = 5;
+/// Window for [`PRESENCE_UPDATE_LIMIT`]
+pub const PRESENCE_UPDATE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Identify attempts a user may make per [`IDENTIFY_PERIOD`]
+pub const IDENTIFY_LIMIT: u32 = 1;
+/// Window for [`IDENTIFY_LIMIT`]
+pub const IDENTIFY_PERIOD: Duration = Duration::from_secs(5);
+
+/// New connections an IP address may open per [`CONNECTION_PERIOD`]
+pub const CONNECTION_LIMIT: u32 = 60;
+/// Window for [`CONNECTION_LIMIT`]
+pub const CONNECTION_PERIOD: Duration = Duration::from_secs(60);
+
+/// Which limit was exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKind {
+    /// General payload limit of a connection
+    Payload,
+    /// Presence update limit of a connection
+    PresenceUpdate,
+    /// Per-user Identify limit
+    Identify,
+    /// Per-user daily session start limit
+    SessionStart,
+    /// Per-IP connection limit
+    Connection,
+}
+
+impl RateLimitKind {
+    /// Get the name used in logs
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Payload => "payload",
+            Self::PresenceUpdate => "presence_update",
+            Self::Identify => "identify",
+            Self::SessionStart => "session_start",
+            Self::Connection => "connection",
+        }
+    }
+}
+
+/// Limits on the payloads one connection sends
+#[derive(Debug)]
+pub struct ConnectionRateLimiter {
+    payloads: Mutex<TokenBucket>,
+    presence_updates: Mutex<TokenBucket>,
+}
+
+impl ConnectionRateLimiter {
+    /// Create full buckets for a new connection
+    pub fn new() -> Self {
+        Self {
+            payloads: Mutex::new(TokenBucket::new(PAYLOAD_LIMIT, PAYLOAD_PERIOD)),
+            presence_updates: Mutex::new(TokenBucket::new(PRESENCE_UPDATE_LIMIT, PRESENCE_UPDATE_PERIOD)),
+        }
+    }
+
+    /// Count a received payload against the connection's limits
+    pub fn check(&self, op: OpCode) -> Result<(), RateLimitKind> {
+        self.check_at(op, Instant::now())
+    }
+
+    /// Count a received payload as of `now`
+    ///
+    /// Every payload counts against the general limit; presence updates also
+    /// count against their own.
+    pub fn check_at(&self, op: OpCode, now: Instant) -> Result<(), RateLimitKind> {
+        if !self.payloads.lock().try_acquire_at(now) {
+            return Err(RateLimitKind::Payload);
+        }
+        if op == OpCode::PresenceUpdate && !self.presence_updates.lock().try_acquire_at(now) {
+            return Err(RateLimitKind::PresenceUpdate);
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConnectionRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Limits shared by all connections on this gateway
+#[derive(Debug)]
+pub struct GatewayRateLimiter {
+    connections: KeyedRateLimiter<IpAddr>,
+    identifies: KeyedRateLimiter<Snowflake>,
+    metrics: RateLimitMetrics,
+}
+
+impl GatewayRateLimiter {
+    /// Create a limiter with the default limits
+    pub fn new() -> Self {
+        Self {
+            connections: KeyedRateLimiter::new(CONNECTION_LIMIT, CONNECTION_PERIOD),
+            identifies: KeyedRateLimiter::new(IDENTIFY_LIMIT, IDENTIFY_PERIOD),
+            metrics: RateLimitMetrics::default(),
+        }
+    }
+
+    /// Count a new connection from `ip`, returning false if it is over the limit
+    pub fn check_connection(&self, ip: IpAddr) -> bool {
+        let allowed = self.connections.check(ip);
+        if !allowed {
+            self.record(RateLimitKind::Connection);
+        }
+        allowed
+    }
+
+    /// Count an Identify attempt by `user_id`, returning false if it is over the limit
+    pub fn check_identify(&self, user_id: Snowflake) -> bool {
+        let allowed = self.identifies.check(user_id);
+        if !allowed {
+            self.record(RateLimitKind::Identify);
+        }
+        allowed
+    }
+
+    /// Count a violation detected elsewhere (per-connection or session start limits)
+    pub fn record(&self, kind: RateLimitKind) {
+        self.metrics.record(kind);
+    }
+
+    /// Get the violation counters
+    pub fn metrics(&self) -> RateLimitSnapshot {
+        self.metrics.snapshot()
+    }
+}
+
+impl Default for GatewayRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_presence_limit() {
+        let now = Instant::now();
+        let limiter = ConnectionRateLimiter::new();
+
+        for _ in 0..PRESENCE_UPDATE_LIMIT {
+            assert_eq!(limiter.check_at(OpCode::PresenceUpdate, now), Ok(()));
+        }
+        assert_eq!(
+            limiter.check_at(OpCode::PresenceUpdate, now),
+            Err(RateLimitKind::PresenceUpdate)
+        );
+
+        // Other payloads are still allowed
+        assert_eq!(limiter.check_at(OpCode::Heartbeat, now), Ok(()));
+    }
+
+    #[test]
+    fn test_connection_payload_limit() {
+        let now = Instant::now();
+        let limiter = ConnectionRateLimiter::new();
+
+        for _ in 0..PAYLOAD_LIMIT {
+            assert_eq!(limiter.check_at(OpCode::Heartbeat, now), Ok(()));
+        }
+        assert_eq!(limiter.check_at(OpCode::Heartbeat, now), Err(RateLimitKind::Payload));
+        assert_eq!(
+            limiter.check_at(OpCode::Heartbeat, now + PAYLOAD_PERIOD),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_gateway_limiter_records_violations() {
+        let limiter = GatewayRateLimiter::new();
+        let user_id = Snowflake::new(1);
+
+        assert!(limiter.check_identify(user_id));
+        assert!(!limiter.check_identify(user_id));
+        limiter.record(RateLimitKind::PresenceUpdate);
+
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.identifies, 1);
+        assert_eq!(metrics.presence_updates, 1);
+        assert_eq!(metrics.connections, 0);
+    }
+}
@@ -2,18 +2,26 @@
 //!
 //! Handles WebSocket connections and message processing.
 
-use crate::connection::{Connection, ConnectionState, Session};
+use crate::connection::{Connection, ConnectionState, ResumeQueue, Session};
 use crate::handlers::MessageDispatcher;
-use crate::protocol::{CloseCode, GatewayMessage, HelloPayload};
+use crate::protocol::{
+    compress_payload, CloseCode, Encoding, GatewayMessage, HelloPayload, TransportCompression, TransportCompressor,
+};
 use crate::server::GatewayState;
 use axum::{
-    extract::{ws::Message, State, WebSocketUpgrade},
+    extract::{
+        ws::{CloseFrame, Message, WebSocket},
+        ConnectInfo, Query, State, WebSocketUpgrade,
+    },
+    http::{header::FORWARDED, HeaderMap},
     response::IntoResponse,
 };
 use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::interval;
 
 /// Default heartbeat interval in milliseconds
@@ -25,18 +33,59 @@ const HEARTBEAT_TIMEOUT_MS: u64 = 90_000;
 /// Channel buffer size for outgoing messages
 const MESSAGE_BUFFER_SIZE: usize = 100;
 
+/// Time allowed for the close frame to be sent before the connection is dropped
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Gateway URL query parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct GatewayParams {
+    /// Payload encoding (`json`, `msgpack` or `etf`)
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Transport compression (`zlib-stream` or `zstd-stream`)
+    pub compress: Option<TransportCompression>,
+}
+
 /// WebSocket gateway handler
 pub async fn gateway_handler(
     State(state): State<GatewayState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Query(params): Query<GatewayParams>,
+    headers: HeaderMap,
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(state, socket))
+    // Behind a trusted proxy the limit applies to the forwarded client address
+    let ip = state.config().proxy.client_ip(
+        addr.ip(),
+        headers.get_all(FORWARDED).iter().filter_map(|v| v.to_str().ok()),
+        headers.get_all("x-forwarded-for").iter().filter_map(|v| v.to_str().ok()),
+    );
+
+    // Clients opening connections too quickly are closed right after the upgrade
+    if !state.rate_limiter().check_connection(ip) {
+        tracing::warn!(ip = %ip, "Connection rejected: connection rate limit reached");
+        return ws.on_upgrade(|mut socket| async move {
+            let _ = socket.send(close_message(CloseCode::RateLimited)).await;
+        });
+    }
+
+    ws.on_upgrade(move |socket| handle_socket(state, socket, params))
 }
 
 /// Handle an upgraded WebSocket connection
-async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket) {
+async fn handle_socket(state: GatewayState, socket: WebSocket, params: GatewayParams) {
     // Generate session ID
     let session_id = Session::generate_id();
+    let encoding = params.encoding;
+
+    // One compression context for the lifetime of the connection
+    let mut compressor = match params.compress.map(TransportCompressor::new).transpose() {
+        Ok(compressor) => compressor,
+        Err(e) => {
+            tracing::warn!(session_id = %session_id, error = %e, "Failed to create compressor");
+            return;
+        }
+    };
 
     // Create message channel for outgoing messages
     let (tx, mut rx) = mpsc::channel::<GatewayMessage>(MESSAGE_BUFFER_SIZE);
@@ -48,17 +97,25 @@ async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket
 
     tracing::info!(session_id = %session_id, "WebSocket connection established");
 
+    // Sent events are kept for resume off the send path
+    let resume_queue = ResumeQueue::spawn(
+        state.service_context().session_store().clone(),
+        session_id.clone(),
+    );
+
     // Split the WebSocket
     let (mut ws_sink, mut ws_stream) = socket.split();
 
     // Send Hello message immediately
     let hello = GatewayMessage::hello(HelloPayload::with_interval(HEARTBEAT_INTERVAL_MS));
-    if let Ok(json) = hello.to_json() {
-        if ws_sink.send(Message::Text(json)).await.is_err() {
-            tracing::warn!(session_id = %session_id, "Failed to send Hello message");
-            cleanup_connection(&state, &session_id, &connection).await;
-            return;
-        }
+    let sent = match encode_frame(&hello, encoding, compressor.as_mut(), false) {
+        Some(frame) => ws_sink.send(frame).await.is_ok(),
+        None => false,
+    };
+    if !sent {
+        tracing::warn!(session_id = %session_id, "Failed to send Hello message");
+        cleanup_connection(&state, &session_id, &connection, &resume_queue).await;
+        return;
     }
 
     // Clone state for tasks
@@ -69,32 +126,26 @@ async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket
     // Spawn task to receive messages from WebSocket
     let recv_task = tokio::spawn(async move {
         while let Some(msg) = ws_stream.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    if let Err(close_code) =
-                        handle_text_message(&state_recv, &connection_recv, &text).await
-                    {
-                        tracing::debug!(
-                            session_id = %session_id_recv,
-                            close_code = ?close_code,
-                            "Closing connection due to error"
-                        );
-                        return Some(close_code);
-                    }
-                }
-                Ok(Message::Binary(_)) => {
+            // Payload frames must match the connection encoding
+            let payload = match msg {
+                Ok(Message::Text(text)) if !encoding.is_binary() => text.into_bytes(),
+                Ok(Message::Binary(data)) if encoding.is_binary() => data,
+                Ok(Message::Text(_) | Message::Binary(_)) => {
                     tracing::debug!(
                         session_id = %session_id_recv,
-                        "Binary messages not supported"
+                        encoding = ?encoding,
+                        "Frame type does not match the connection encoding"
                     );
                     return Some(CloseCode::DecodeError);
                 }
                 Ok(Message::Ping(_)) => {
                     tracing::trace!(session_id = %session_id_recv, "Ping received");
                     // Pong is handled automatically by axum
+                    continue;
                 }
                 Ok(Message::Pong(_)) => {
                     tracing::trace!(session_id = %session_id_recv, "Pong received");
+                    continue;
                 }
                 Ok(Message::Close(_)) => {
                     tracing::info!(session_id = %session_id_recv, "Client closed connection");
@@ -108,6 +159,17 @@ async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket
                     );
                     return Some(CloseCode::UnknownError);
                 }
+            };
+
+            if let Err(close_code) =
+                handle_payload(&state_recv, &connection_recv, encoding, &payload).await
+            {
+                tracing::debug!(
+                    session_id = %session_id_recv,
+                    close_code = ?close_code,
+                    "Closing connection due to error"
+                );
+                return Some(close_code);
             }
         }
         None
@@ -115,23 +177,81 @@ async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket
 
     // Clone for send task
     let session_id_send = session_id.clone();
+    let connection_send = connection.clone();
+    let resume_queue_send = resume_queue.clone();
+
+    // Close code for the send task to deliver when the receive task ends with one
+    let (close_tx, mut close_rx) = oneshot::channel::<CloseCode>();
 
     // Spawn task to send messages to WebSocket
-    let send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if let Ok(json) = msg.to_json() {
-                if ws_sink.send(Message::Text(json)).await.is_err() {
-                    tracing::warn!(
-                        session_id = %session_id_send,
-                        "Failed to send message to WebSocket"
-                    );
+    let mut send_task = tokio::spawn(async move {
+        let mut lagged = false;
+        loop {
+            let msg = tokio::select! {
+                biased;
+                close_code = &mut close_rx => {
+                    if let Ok(close_code) = close_code {
+                        let _ = ws_sink.send(close_message(close_code)).await;
+                    }
                     break;
                 }
+                () = connection_send.lagged() => {
+                    lagged = true;
+                    break;
+                }
+                msg = rx.recv() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+            };
+
+            // Sequence in delivery order and keep the event for resume
+            let mut msg = connection_send.take_latest(msg);
+            connection_send.assign_sequence(&mut msg);
+            resume_queue_send.push(&msg);
+
+            let compress = connection_send.compresses_payloads();
+            let Some(frame) = encode_frame(&msg, encoding, compressor.as_mut(), compress) else {
+                tracing::warn!(session_id = %session_id_send, "Failed to encode message");
+                break;
+            };
+
+            // A write blocked on a slow reader gives way once the queue fills up;
+            // the frame is already in the resume queue
+            let sent = tokio::select! {
+                biased;
+                () = connection_send.lagged() => {
+                    lagged = true;
+                    break;
+                }
+                result = ws_sink.send(frame) => result.is_ok(),
+            };
+            if !sent {
+                tracing::warn!(
+                    session_id = %session_id_send,
+                    "Failed to send message to WebSocket"
+                );
+                break;
+            }
+        }
+
+        if lagged {
+            // Keep everything still queued for resume, then ask the client to resume
+            flush_for_resume(&session_id_send, &connection_send, &resume_queue_send, &mut rx).await;
+
+            let reply = if connection_send.lost_events() {
+                GatewayMessage::invalid_session(false)
+            } else {
+                GatewayMessage::reconnect()
+            };
+            let compress = connection_send.compresses_payloads();
+            if let Some(frame) = encode_frame(&reply, encoding, compressor.as_mut(), compress) {
+                tokio::time::timeout(CLOSE_TIMEOUT, ws_sink.send(frame)).await.ok();
             }
         }
 
         // Close the WebSocket when channel is closed
-        let _ = ws_sink.close().await;
+        tokio::time::timeout(CLOSE_TIMEOUT, ws_sink.close()).await.ok();
     });
 
     // Clone for heartbeat task
@@ -178,28 +298,45 @@ async fn handle_socket(state: GatewayState, socket: axum::extract::ws::WebSocket
                     close_code = ?close_code,
                     "Receive task ended with close code"
                 );
+
+                // Let the send task deliver the close frame
+                if close_tx.send(close_code).is_ok() {
+                    tokio::time::timeout(CLOSE_TIMEOUT, &mut send_task).await.ok();
+                }
             }
         }
-        _ = send_task => {
+        _ = &mut send_task => {
             tracing::debug!(session_id = %session_id, "Send task ended");
         }
+        close_code = connection.close_requested() => {
+            tracing::debug!(
+                session_id = %session_id,
+                close_code = ?close_code,
+                "Connection close requested"
+            );
+
+            if close_tx.send(close_code).is_ok() {
+                tokio::time::timeout(CLOSE_TIMEOUT, &mut send_task).await.ok();
+            }
+        }
         _ = heartbeat_task => {
             tracing::debug!(session_id = %session_id, "Heartbeat task ended");
         }
     }
 
     // Clean up
-    cleanup_connection(&state, &session_id, &connection).await;
+    cleanup_connection(&state, &session_id, &connection, &resume_queue).await;
 }
 
-/// Handle a text message from the client
-async fn handle_text_message(
+/// Decode and handle a message from the client
+async fn handle_payload(
     state: &GatewayState,
     connection: &Arc<Connection>,
-    text: &str,
+    encoding: Encoding,
+    data: &[u8],
 ) -> Result<(), CloseCode> {
     // Parse the message
-    let message = match GatewayMessage::from_json(text) {
+    let message = match encoding.decode(data) {
         Ok(m) => m,
         Err(e) => {
             tracing::debug!(
@@ -232,27 +369,140 @@ async fn handle_text_message(
     }
 }
 
+/// Build the close frame for a gateway close code
+fn close_message(close_code: CloseCode) -> Message {
+    Message::Close(Some(CloseFrame {
+        code: close_code.as_u16(),
+        reason: close_code.description().into(),
+    }))
+}
+
+/// Encode a message as a WebSocket frame
+///
+/// Transport compression takes precedence over payload compression; without
+/// either, JSON is sent as a text frame and binary encodings as binary frames.
+/// Returns None if encoding fails, which leaves a transport compression
+/// context unusable.
+fn encode_frame(
+    message: &GatewayMessage,
+    encoding: Encoding,
+    compressor: Option<&mut TransportCompressor>,
+    compress: bool,
+) -> Option<Message> {
+    let payload = encoding.encode(message).ok()?;
+
+    if let Some(compressor) = compressor {
+        return compressor.compress(&payload).ok().map(Message::Binary);
+    }
+    if compress {
+        return compress_payload(&payload).ok().map(Message::Binary);
+    }
+    if encoding.is_binary() {
+        Some(Message::Binary(payload))
+    } else {
+        String::from_utf8(payload).ok().map(Message::Text)
+    }
+}
+
+/// Move a lagging connection's queued and overflowed events to its resume queue
+async fn flush_for_resume(
+    session_id: &str,
+    connection: &Connection,
+    resume_queue: &ResumeQueue,
+    rx: &mut mpsc::Receiver<GatewayMessage>,
+) {
+    let mut flushed = 0;
+    while let Ok(msg) = rx.try_recv() {
+        let mut msg = connection.take_latest(msg);
+        connection.assign_sequence(&mut msg);
+        resume_queue.push(&msg);
+        flushed += 1;
+    }
+    for mut msg in connection.take_overflow() {
+        connection.assign_sequence(&mut msg);
+        resume_queue.push(&msg);
+        flushed += 1;
+    }
+
+    // The client resumes right after Reconnect, so the events must be written first
+    tokio::time::timeout(CLOSE_TIMEOUT, resume_queue.flush()).await.ok();
+
+    tracing::info!(
+        session_id = %session_id,
+        flushed = flushed,
+        lost_events = connection.lost_events(),
+        "Slow connection dropped, pending events kept for resume"
+    );
+}
+
 /// Clean up a connection on disconnect
-async fn cleanup_connection(state: &GatewayState, session_id: &str, connection: &Arc<Connection>) {
+async fn cleanup_connection(
+    state: &GatewayState,
+    session_id: &str,
+    connection: &Arc<Connection>,
+    resume_queue: &ResumeQueue,
+) {
     tracing::info!(session_id = %session_id, "Cleaning up connection");
 
     // Set connection state
     connection.set_state(ConnectionState::Disconnected).await;
 
+    // Remove from connection manager and drop unused Pub/Sub subscriptions
+    state.event_dispatcher().remove_session(session_id).await;
+
+    // A lagging connection keeps deferring events until it is removed
+    for mut msg in connection.seal_overflow() {
+        connection.assign_sequence(&mut msg);
+        resume_queue.push(&msg);
+    }
+
+    // Everything sent must be in the resume queue before the session becomes resumable
+    tokio::time::timeout(CLOSE_TIMEOUT, resume_queue.flush())
+        .await
+        .ok();
+
     // Mark session as disconnected in Redis (starts 2-minute resume window)
     if connection.is_authenticated().await {
-        Session::disconnect(state.service_context().session_store(), session_id)
+        let user_id = connection.user_id().await;
+
+        // Check if user has other active connections
+        let has_other_connections = user_id.is_some_and(|user_id| {
+            state
+                .connection_manager()
+                .get_user_connections(user_id)
+                .iter()
+                .any(|c| c.session_id() != session_id)
+        });
+
+        // Release the session from this node before it becomes resumable elsewhere
+        if let Some(user_id) = user_id {
+            if let Err(e) = state.node().remove_session(session_id, user_id).await {
+                tracing::debug!(session_id = %session_id, error = %e, "Failed to release session from node");
+            }
+        }
+
+        if connection.lost_events() {
+            // Missed events cannot be replayed, so the session must not be resumed
+            Session::delete(state.service_context().session_store(), session_id)
+                .await
+                .ok();
+        } else {
+            // Record the last sequence sent so resume can tell which events were missed
+            Session::update_sequence(
+                state.service_context().session_store(),
+                session_id,
+                connection.current_sequence(),
+            )
             .await
             .ok();
 
-        // Update presence to offline
-        if let Some(user_id) = connection.user_id().await {
-            // Check if user has other active connections
-            let other_connections = state.connection_manager().get_user_connections(user_id);
-            let has_other_connections = other_connections
-                .iter()
-                .any(|c| c.session_id() != session_id);
+            Session::disconnect(state.service_context().session_store(), session_id)
+                .await
+                .ok();
+        }
 
+        // Update presence to offline
+        if let Some(user_id) = user_id {
             if !has_other_connections {
                 // No other connections, set user to offline
                 let presence_data =
@@ -271,7 +521,4 @@ async fn cleanup_connection(state: &GatewayState, session_id: &str, connection:
             }
         }
     }
-
-    // Remove from connection manager
-    state.connection_manager().remove_connection(session_id).await;
 }
@@ -5,15 +5,18 @@
 mod handler;
 mod state;
 
-pub use handler::gateway_handler;
+pub use handler::{gateway_handler, GatewayParams};
 pub use state::GatewayState;
 
 use crate::broadcast::{EventDispatcher, EventDispatcherConfig};
-use crate::connection::ConnectionManager;
-use axum::{routing::get, Router};
+use crate::cluster::GatewayNode;
+use crate::connection::{ConnectionManager, LaggingConnection, QueueSnapshot};
+use crate::rate_limit::RateLimitSnapshot;
+use axum::{extract::State, routing::get, Json, Router};
 use chat_cache::{RedisPool, RedisPoolConfig};
 use chat_common::{AppConfig, AppError};
 use chat_service::ServiceContextBuilder;
+use serde::Serialize;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -24,6 +27,7 @@ pub fn create_router() -> Router<GatewayState> {
     Router::new()
         .route("/gateway", get(gateway_handler))
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics))
 }
 
 /// Health check endpoint
@@ -31,6 +35,35 @@ async fn health_check() -> &'static str {
     "OK"
 }
 
+/// Gateway counters for monitoring
+#[derive(Debug, Serialize)]
+pub struct GatewayMetrics {
+    /// ID of this gateway node
+    pub node_id: String,
+    /// Open WebSocket connections
+    pub connections: usize,
+    /// Distinct authenticated users
+    pub users: usize,
+    /// Rate limit violations since startup
+    pub rate_limited: RateLimitSnapshot,
+    /// Outbound queue counters since startup
+    pub queues: QueueSnapshot,
+    /// Connections whose outbound queue is at least half full
+    pub lagging: Vec<LaggingConnection>,
+}
+
+/// Metrics endpoint
+async fn metrics(State(state): State<GatewayState>) -> Json<GatewayMetrics> {
+    Json(GatewayMetrics {
+        node_id: state.node().id().to_string(),
+        connections: state.connection_manager().connection_count(),
+        users: state.connection_manager().user_count(),
+        rate_limited: state.rate_limiter().metrics(),
+        queues: state.connection_manager().queue_metrics(),
+        lagging: state.connection_manager().lagging_connections().await,
+    })
+}
+
 /// Build the complete application
 pub fn create_app(state: GatewayState) -> Router {
     create_router()
@@ -67,6 +100,9 @@ pub async fn create_gateway_state(config: AppConfig) -> Result<GatewayState, App
         config.jwt.refresh_token_expiry,
     ));
 
+    // Create TOTP service; authenticator apps show the app name as issuer
+    let totp_service = Arc::new(chat_common::TotpService::new(&config.app.name));
+
     // Create Snowflake generator
     let snowflake_generator = Arc::new(chat_core::SnowflakeGenerator::new(config.snowflake.worker_id));
 
@@ -81,6 +117,24 @@ pub async fn create_gateway_state(config: AppConfig) -> Result<GatewayState, App
     let invite_repo = Arc::new(chat_db::PgInviteRepository::new(pool.clone()));
     let ban_repo = Arc::new(chat_db::PgBanRepository::new(pool.clone()));
     let attachment_repo = Arc::new(chat_db::PgAttachmentRepository::new(pool.clone()));
+    let permission_overwrite_repo =
+        Arc::new(chat_db::PgPermissionOverwriteRepository::new(pool.clone()));
+    let audit_log_repo = Arc::new(chat_db::PgAuditLogRepository::new(pool.clone()));
+    let thread_repo = Arc::new(chat_db::PgThreadRepository::new(pool.clone()));
+    let pin_repo = Arc::new(chat_db::PgPinRepository::new(pool.clone()));
+    let message_revision_repo = Arc::new(chat_db::PgMessageRevisionRepository::new(pool.clone()));
+    let read_state_repo = Arc::new(chat_db::PgReadStateRepository::new(pool.clone()));
+    let relationship_repo = Arc::new(chat_db::PgRelationshipRepository::new(pool.clone()));
+
+    // Create attachment file store
+    let file_store = Arc::new(
+        chat_storage::from_config(&config.storage).map_err(|e| AppError::Config(e.to_string()))?,
+    );
+
+    // Create outgoing mail outbox
+    let outbox = Arc::new(
+        chat_mail::from_config(&config.mail).map_err(|e| AppError::Config(e.to_string()))?,
+    );
 
     // Build service context
     let service_context = ServiceContextBuilder::new()
@@ -96,10 +150,32 @@ pub async fn create_gateway_state(config: AppConfig) -> Result<GatewayState, App
         .invite_repo(invite_repo)
         .ban_repo(ban_repo)
         .attachment_repo(attachment_repo)
+        .permission_overwrite_repo(permission_overwrite_repo)
+        .audit_log_repo(audit_log_repo)
+        .thread_repo(thread_repo)
+        .pin_repo(pin_repo)
+        .message_revision_repo(message_revision_repo)
+        .read_state_repo(read_state_repo)
+        .relationship_repo(relationship_repo)
+        .file_store(file_store)
+        .outbox(outbox)
         .jwt_service(jwt_service)
+        .totp_service(totp_service)
         .snowflake_generator(snowflake_generator)
         .build()
         .map_err(|e| AppError::Config(e.to_string()))?;
+    let service_context = Arc::new(service_context);
+
+    // Register this node so user events and resumes can find its sessions
+    let node = Arc::new(GatewayNode::new(
+        service_context.gateway_node_store().clone(),
+        service_context.session_store().clone(),
+        config.gateway.address(),
+    ));
+    node.register()
+        .await
+        .map_err(|e| AppError::Cache(format!("Failed to register gateway node: {e}")))?;
+    tracing::info!(node_id = %node.id(), "Gateway node registered");
 
     // Create connection manager
     let connection_manager = ConnectionManager::new_shared();
@@ -111,19 +187,31 @@ pub async fn create_gateway_state(config: AppConfig) -> Result<GatewayState, App
         reconnect_delay_ms: 1000,
     };
 
-    let event_dispatcher = EventDispatcher::new(dispatcher_config, connection_manager.clone())
-        .await
-        .map_err(|e| AppError::Cache(format!("Failed to create event dispatcher: {e}")))?;
+    let event_dispatcher = EventDispatcher::new(
+        dispatcher_config,
+        connection_manager.clone(),
+        service_context.clone(),
+    )
+    .await
+    .map_err(|e| AppError::Cache(format!("Failed to create event dispatcher: {e}")))?;
 
     let event_dispatcher = Arc::new(event_dispatcher);
 
-    // Start the event dispatcher
+    // Receive user events and takeovers routed to this node
+    event_dispatcher
+        .subscribe_node(node.id())
+        .await
+        .map_err(|e| AppError::Cache(format!("Failed to subscribe to node channel: {e}")))?;
+
+    // Start the event dispatcher and node heartbeat
     event_dispatcher.clone().start();
+    node.clone().start();
 
     Ok(GatewayState::new(
         service_context,
         connection_manager,
         event_dispatcher,
+        node,
         config,
     ))
 }
@@ -138,7 +226,8 @@ pub async fn run_server(app: Router, addr: SocketAddr) -> Result<(), AppError> {
 
     tracing::info!("Gateway listening on ws://{}/gateway", addr);
 
-    axum::serve(listener, app)
+    // Peer addresses feed the per-IP connection limit
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .map_err(|e| AppError::Config(format!("Server error: {e}")))?;
 
@@ -3,7 +3,9 @@
 //! Application state for the gateway server.
 
 use crate::broadcast::EventDispatcher;
+use crate::cluster::GatewayNode;
 use crate::connection::ConnectionManager;
+use crate::rate_limit::GatewayRateLimiter;
 use chat_common::AppConfig;
 use chat_service::ServiceContext;
 use std::sync::Arc;
@@ -19,6 +21,10 @@ pub struct GatewayState {
     connection_manager: Arc<ConnectionManager>,
     /// Event dispatcher for Redis Pub/Sub
     event_dispatcher: Arc<EventDispatcher>,
+    /// This node in the gateway node registry
+    node: Arc<GatewayNode>,
+    /// Connection and Identify rate limits
+    rate_limiter: Arc<GatewayRateLimiter>,
     /// Application configuration
     config: Arc<AppConfig>,
 }
@@ -26,15 +32,18 @@ pub struct GatewayState {
 impl GatewayState {
     /// Create a new gateway state
     pub fn new(
-        service_context: ServiceContext,
+        service_context: Arc<ServiceContext>,
         connection_manager: Arc<ConnectionManager>,
         event_dispatcher: Arc<EventDispatcher>,
+        node: Arc<GatewayNode>,
         config: AppConfig,
     ) -> Self {
         Self {
-            service_context: Arc::new(service_context),
+            service_context,
             connection_manager,
             event_dispatcher,
+            node,
+            rate_limiter: Arc::new(GatewayRateLimiter::new()),
             config: Arc::new(config),
         }
     }
@@ -54,6 +63,16 @@ impl GatewayState {
         &self.event_dispatcher
     }
 
+    /// Get this gateway node
+    pub fn node(&self) -> &GatewayNode {
+        &self.node
+    }
+
+    /// Get the connection and Identify rate limits
+    pub fn rate_limiter(&self) -> &GatewayRateLimiter {
+        &self.rate_limiter
+    }
+
     /// Get the application configuration
     pub fn config(&self) -> &AppConfig {
         &self.config
@@ -64,6 +83,7 @@ impl std::fmt::Debug for GatewayState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GatewayState")
             .field("connection_manager", &self.connection_manager)
+            .field("node", &self.node)
             .field("config", &"AppConfig")
             .finish()
     }
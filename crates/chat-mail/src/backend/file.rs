@@ -0,0 +1,108 @@
+//! File capture backend
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use tokio::fs;
+use tracing::{debug, instrument};
+use uuid::Uuid;
+
+use super::Mailer;
+use crate::error::MailResult;
+use crate::message::EmailMessage;
+
+/// Writes each message as a JSON file below a directory instead of delivering it
+///
+/// File names start with the send time in milliseconds, so a directory
+/// listing sorts oldest first.
+#[derive(Debug, Clone)]
+pub struct FileMailer {
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    /// Create a new FileMailer writing to `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory messages are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Read every captured message in `dir`, oldest first
+    ///
+    /// A missing directory holds no messages.
+    pub async fn read_dir(dir: impl AsRef<Path>) -> MailResult<Vec<EmailMessage>> {
+        let mut entries = match fs::read_dir(dir.as_ref()).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut paths = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut messages = Vec::with_capacity(paths.len());
+        for path in paths {
+            let contents = fs::read(&path).await?;
+            messages.push(serde_json::from_slice(&contents)?);
+        }
+
+        Ok(messages)
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    #[instrument(skip(self, message), fields(to = %message.to))]
+    async fn send(&self, message: &EmailMessage) -> MailResult<()> {
+        fs::create_dir_all(&self.dir).await?;
+
+        let name = format!(
+            "{:013}-{}",
+            message.created_at.timestamp_millis(),
+            Uuid::new_v4().simple()
+        );
+        let path = self.dir.join(format!("{name}.json"));
+
+        // Write to a temporary file first so readers never see partial messages
+        let tmp_path = self.dir.join(format!("{name}.part"));
+        fs::write(&tmp_path, serde_json::to_vec_pretty(message)?).await?;
+        fs::rename(&tmp_path, &path).await?;
+
+        debug!(path = %path.display(), "Captured email");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_mailer_round_trip() {
+        let dir = std::env::temp_dir().join(format!("chat-mail-test-{}", Uuid::new_v4()));
+        let mailer = FileMailer::new(&dir);
+
+        assert!(FileMailer::read_dir(&dir).await.unwrap().is_empty());
+
+        let first = EmailMessage::new("a@example.com", "b@example.com", "One", "1");
+        let mut second = EmailMessage::new("a@example.com", "b@example.com", "Two", "2");
+        second.created_at = first.created_at + chrono::Duration::milliseconds(1);
+        mailer.send(&second).await.unwrap();
+        mailer.send(&first).await.unwrap();
+
+        let messages = FileMailer::read_dir(&dir).await.unwrap();
+        assert_eq!(messages, vec![first, second]);
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+//! In-memory capture backend
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use super::Mailer;
+use crate::error::MailResult;
+use crate::message::EmailMessage;
+
+/// Keeps sent messages in memory instead of delivering them
+///
+/// Clones share the same mailbox, so a test can keep one handle and give
+/// another to the code under test.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryMailer {
+    messages: Arc<Mutex<Vec<EmailMessage>>>,
+}
+
+impl MemoryMailer {
+    /// Create an empty MemoryMailer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All messages sent so far, oldest first
+    pub fn messages(&self) -> Vec<EmailMessage> {
+        self.messages.lock().clone()
+    }
+
+    /// The most recent message sent to `to`
+    pub fn last_to(&self, to: &str) -> Option<EmailMessage> {
+        self.messages
+            .lock()
+            .iter()
+            .rev()
+            .find(|m| m.to.eq_ignore_ascii_case(to))
+            .cloned()
+    }
+
+    /// Forget all captured messages
+    pub fn clear(&self) {
+        self.messages.lock().clear();
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, message: &EmailMessage) -> MailResult<()> {
+        self.messages.lock().push(message.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_mailer_captures_messages() {
+        let mailer = MemoryMailer::new();
+        let handle = mailer.clone();
+
+        mailer
+            .send(&EmailMessage::new("a@example.com", "b@example.com", "One", "1"))
+            .await
+            .unwrap();
+        mailer
+            .send(&EmailMessage::new("a@example.com", "b@example.com", "Two", "2"))
+            .await
+            .unwrap();
+
+        assert_eq!(handle.messages().len(), 2);
+        assert_eq!(handle.last_to("B@example.com").unwrap().subject, "Two");
+        assert!(handle.last_to("c@example.com").is_none());
+
+        handle.clear();
+        assert!(mailer.messages().is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+//! Mail backends
+//!
+//! A backend delivers a finished [`EmailMessage`]. Choosing the sender and
+//! building links lives in [`crate::Outbox`].
+
+mod file;
+mod memory;
+mod smtp;
+
+pub use file::FileMailer;
+pub use memory::MemoryMailer;
+pub use smtp::SmtpMailer;
+
+use async_trait::async_trait;
+
+use crate::error::MailResult;
+use crate::message::EmailMessage;
+
+/// Pluggable mail delivery backend
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Deliver `message`
+    ///
+    /// Returning `Ok` means the backend accepted the message, not that it
+    /// reached the recipient's inbox.
+    async fn send(&self, message: &EmailMessage) -> MailResult<()>;
+}
@@ -0,0 +1,77 @@
+//! SMTP delivery backend
+
+use async_trait::async_trait;
+use chat_common::{SmtpConfig, SmtpTls};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use tracing::{debug, instrument};
+
+use super::Mailer;
+use crate::error::MailResult;
+use crate::message::EmailMessage;
+
+/// Delivers messages through an SMTP relay
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailer {
+    /// Create a new SmtpMailer from configuration
+    ///
+    /// No connection is made until the first message is sent.
+    pub fn new(config: &SmtpConfig) -> MailResult<Self> {
+        let (builder, default_port) = match config.tls {
+            SmtpTls::StartTls => (
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?,
+                587,
+            ),
+            SmtpTls::Tls => (
+                AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+                465,
+            ),
+            SmtpTls::None => (
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+                25,
+            ),
+        };
+
+        let mut builder = builder.port(config.port.unwrap_or(default_port));
+        if let Some(username) = &config.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                config.password.clone().unwrap_or_default(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+        })
+    }
+}
+
+impl std::fmt::Debug for SmtpMailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpMailer").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    #[instrument(skip(self, message), fields(to = %message.to))]
+    async fn send(&self, message: &EmailMessage) -> MailResult<()> {
+        let email = Message::builder()
+            .from(message.from.parse::<Mailbox>()?)
+            .to(message.to.parse::<Mailbox>()?)
+            .subject(&message.subject)
+            .date(message.created_at.into())
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.clone())?;
+
+        let response = self.transport.send(email).await?;
+        debug!(code = %response.code(), "Email accepted by relay");
+        Ok(())
+    }
+}
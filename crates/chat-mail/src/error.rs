@@ -0,0 +1,46 @@
+//! Mail error types
+
+use std::io;
+
+/// Error type for mail operations
+#[derive(Debug, thiserror::Error)]
+pub enum MailError {
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid email message: {0}")]
+    InvalidMessage(String),
+
+    #[error("Mail transport error: {0}")]
+    Transport(String),
+
+    #[error("Mail I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Mail serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Mail configuration error: {0}")]
+    Config(String),
+}
+
+impl From<lettre::address::AddressError> for MailError {
+    fn from(err: lettre::address::AddressError) -> Self {
+        Self::InvalidAddress(err.to_string())
+    }
+}
+
+impl From<lettre::error::Error> for MailError {
+    fn from(err: lettre::error::Error) -> Self {
+        Self::InvalidMessage(err.to_string())
+    }
+}
+
+impl From<lettre::transport::smtp::Error> for MailError {
+    fn from(err: lettre::transport::smtp::Error) -> Self {
+        Self::Transport(err.to_string())
+    }
+}
+
+/// Result type for mail operations
+pub type MailResult<T> = Result<T, MailError>;
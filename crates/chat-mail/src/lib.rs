@@ -0,0 +1,58 @@
+//! # chat-mail
+//!
+//! Outgoing email for account flows such as password reset and email
+//! verification.
+//!
+//! ## Features
+//!
+//! - **Pluggable Backends**: [`Mailer`] trait with SMTP delivery and file or
+//!   in-memory capture implementations
+//! - **Outbox**: [`Outbox`] fills in the sender and builds client links
+//!
+//! ## Example
+//!
+//! ```ignore
+//! let outbox = chat_mail::from_config(&config.mail)?;
+//! let link = outbox.link("/verify-email", &token);
+//! outbox.send(&user.email, "Verify your email", format!("Open {link}")).await?;
+//! ```
+
+pub mod backend;
+pub mod error;
+pub mod message;
+pub mod outbox;
+
+use std::sync::Arc;
+
+use chat_common::{MailBackendKind, MailConfig};
+use lettre::message::Mailbox;
+
+pub use backend::{FileMailer, Mailer, MemoryMailer, SmtpMailer};
+pub use error::{MailError, MailResult};
+pub use message::EmailMessage;
+pub use outbox::Outbox;
+
+/// Build an [`Outbox`] from application configuration
+pub fn from_config(config: &MailConfig) -> MailResult<Outbox> {
+    config
+        .from
+        .parse::<Mailbox>()
+        .map_err(|e| MailError::Config(format!("invalid MAIL_FROM '{}': {e}", config.from)))?;
+
+    let mailer: Arc<dyn Mailer> = match config.backend {
+        MailBackendKind::Smtp => {
+            let smtp = config.smtp.as_ref().ok_or_else(|| {
+                MailError::Config("SMTP backend selected but SMTP_HOST is not set".to_string())
+            })?;
+            Arc::new(SmtpMailer::new(smtp)?)
+        }
+        MailBackendKind::File => Arc::new(FileMailer::new(&config.capture_dir)),
+        MailBackendKind::Memory => Arc::new(MemoryMailer::new()),
+    };
+
+    Ok(Outbox::new(
+        mailer,
+        config.from.clone(),
+        config.link_base_url.clone(),
+    ))
+}
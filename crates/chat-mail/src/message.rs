@@ -0,0 +1,35 @@
+//! Email message
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A plain-text email ready to hand to a [`crate::Mailer`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailMessage {
+    /// Sender mailbox, e.g. `Chat <noreply@example.com>`
+    pub from: String,
+    /// Recipient address
+    pub to: String,
+    pub subject: String,
+    /// Plain-text body
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EmailMessage {
+    /// Create a new message
+    pub fn new(
+        from: impl Into<String>,
+        to: impl Into<String>,
+        subject: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            subject: subject.into(),
+            body: body.into(),
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Outbox - application-facing mail API
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use crate::backend::Mailer;
+use crate::error::MailResult;
+use crate::message::EmailMessage;
+
+/// Sends application email through a [`Mailer`] backend
+///
+/// Holds the sender address and the client base URL used to build links,
+/// so callers only supply the recipient and content.
+#[derive(Clone)]
+pub struct Outbox {
+    mailer: Arc<dyn Mailer>,
+    from: String,
+    link_base_url: String,
+}
+
+impl Outbox {
+    /// Create a new Outbox
+    pub fn new(mailer: Arc<dyn Mailer>, from: impl Into<String>, link_base_url: impl Into<String>) -> Self {
+        Self {
+            mailer,
+            from: from.into(),
+            link_base_url: link_base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Sender address
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Build a client link, e.g. `link("/reset-password", token)`
+    pub fn link(&self, path: &str, token: &str) -> String {
+        format!("{}{path}?token={token}", self.link_base_url)
+    }
+
+    /// Send a plain-text email
+    #[instrument(skip(self, body))]
+    pub async fn send(&self, to: &str, subject: &str, body: String) -> MailResult<()> {
+        let message = EmailMessage::new(&self.from, to, subject, body);
+        self.mailer.send(&message).await
+    }
+}
+
+impl std::fmt::Debug for Outbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Outbox")
+            .field("from", &self.from)
+            .field("link_base_url", &self.link_base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryMailer;
+
+    #[tokio::test]
+    async fn test_outbox_sends_from_configured_sender() {
+        let mailer = MemoryMailer::new();
+        let outbox = Outbox::new(
+            Arc::new(mailer.clone()),
+            "Chat <noreply@example.com>",
+            "https://chat.example.com/",
+        );
+
+        let link = outbox.link("/verify-email", "abc");
+        assert_eq!(link, "https://chat.example.com/verify-email?token=abc");
+
+        outbox
+            .send("user@example.com", "Verify", format!("Open {link}"))
+            .await
+            .unwrap();
+
+        let sent = mailer.last_to("user@example.com").unwrap();
+        assert_eq!(sent.from, "Chat <noreply@example.com>");
+        assert_eq!(sent.subject, "Verify");
+        assert!(sent.body.contains(&link));
+    }
+}
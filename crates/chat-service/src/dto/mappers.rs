@@ -3,15 +3,19 @@
 //! Implements `From` conversions from domain entities to response DTOs.
 
 use chat_core::entities::{
-    Attachment, Channel, ChannelType, Guild, GuildMember, Invite, Message, Reaction, Role, User,
+    Attachment, AuditLogEntry, Channel, ChannelType, Guild, GuildMember, Invite, Message,
+    PermissionOverwrite, Reaction, Relationship, Role, Thread, ThreadMember, User,
 };
+use chat_cache::ReadStateData;
+use chat_core::traits::MessageRevision;
 use chat_core::Snowflake;
 
 use super::responses::{
-    AttachmentResponse, ChannelResponse, CurrentUserResponse, DmChannelResponse,
-    GuildPreviewResponse, GuildResponse, GuildWithCountsResponse, InviteChannelResponse,
-    InviteResponse, MemberResponse, MessageReferenceResponse, MessageResponse, PublicUserResponse,
-    ReactionResponse, RoleResponse, UserResponse,
+    AttachmentResponse, AuditLogEntryResponse, ChannelResponse, CurrentUserResponse,
+    DmChannelResponse, GuildPreviewResponse, GuildResponse, GuildWithCountsResponse,
+    InviteChannelResponse, InviteResponse, MemberResponse, MessageReferenceResponse,
+    MessageResponse, MessageRevisionResponse, PermissionOverwriteResponse, PublicUserResponse, ReactionResponse,
+    ReadStateResponse, RelationshipResponse, RoleResponse, ThreadMemberResponse, ThreadMetadataResponse, ThreadResponse, UserResponse,
 };
 
 // ============================================================================
@@ -48,6 +52,9 @@ impl From<&User> for CurrentUserResponse {
             avatar: user.avatar.clone(),
             bot: user.bot,
             system: user.system,
+            dm_privacy: user.dm_privacy.as_i16(),
+            verified: user.verified,
+            mfa_enabled: user.mfa_enabled,
             created_at: user.created_at,
         }
     }
@@ -77,6 +84,23 @@ impl From<User> for PublicUserResponse {
     }
 }
 
+/// Helper struct for creating RelationshipResponse
+pub struct RelationshipWithUser {
+    pub relationship: Relationship,
+    pub user: User,
+}
+
+impl From<RelationshipWithUser> for RelationshipResponse {
+    fn from(rwu: RelationshipWithUser) -> Self {
+        Self {
+            id: rwu.relationship.target_id.to_string(),
+            relationship_type: rwu.relationship.relationship_type.as_i16(),
+            user: PublicUserResponse::from(rwu.user),
+            since: rwu.relationship.created_at,
+        }
+    }
+}
+
 // ============================================================================
 // Guild Mappers
 // ============================================================================
@@ -89,6 +113,7 @@ impl From<&Guild> for GuildResponse {
             icon: guild.icon.clone(),
             description: guild.description.clone(),
             owner_id: guild.owner_id.to_string(),
+            mfa_level: guild.mfa_level.as_i16(),
             created_at: guild.created_at,
         }
     }
@@ -127,6 +152,7 @@ impl From<GuildWithCounts> for GuildWithCountsResponse {
             icon: gwc.guild.icon,
             description: gwc.guild.description,
             owner_id: gwc.guild.owner_id.to_string(),
+            mfa_level: gwc.guild.mfa_level.as_i16(),
             member_count: gwc.member_count,
             channel_count: gwc.channel_count,
             created_at: gwc.guild.created_at,
@@ -148,6 +174,8 @@ impl From<&Channel> for ChannelResponse {
             topic: channel.topic.clone(),
             position: channel.position,
             parent_id: channel.parent_id.map(|id| id.to_string()),
+            owner_id: channel.owner_id.map(|id| id.to_string()),
+            icon: channel.icon.clone(),
             created_at: channel.created_at,
         }
     }
@@ -181,17 +209,68 @@ impl From<DmChannelWithRecipients> for DmChannelResponse {
         Self {
             id: dm.channel.id.to_string(),
             channel_type: channel_type_to_i32(dm.channel.channel_type),
+            name: dm.channel.name,
+            icon: dm.channel.icon,
+            owner_id: dm.channel.owner_id.map(|id| id.to_string()),
             recipients: dm.recipients.into_iter().map(UserResponse::from).collect(),
             last_message_id: dm.last_message_id.map(|id| id.to_string()),
         }
     }
 }
 
+impl From<&PermissionOverwrite> for PermissionOverwriteResponse {
+    fn from(overwrite: &PermissionOverwrite) -> Self {
+        Self {
+            id: overwrite.target_id.to_string(),
+            overwrite_type: overwrite.overwrite_type.as_i16(),
+            allow: overwrite.allow.to_string(),
+            deny: overwrite.deny.to_string(),
+        }
+    }
+}
+
 fn channel_type_to_i32(channel_type: ChannelType) -> i32 {
     match channel_type {
         ChannelType::GuildText => 0,
         ChannelType::Dm => 1,
+        ChannelType::GroupDm => 3,
         ChannelType::GuildCategory => 4,
+        ChannelType::GuildThread => 11,
+    }
+}
+
+impl From<&Thread> for ThreadResponse {
+    fn from(thread: &Thread) -> Self {
+        let channel = &thread.channel;
+        let metadata = &thread.metadata;
+        Self {
+            id: channel.id.to_string(),
+            guild_id: channel.guild_id.map(|id| id.to_string()).unwrap_or_default(),
+            parent_id: channel.parent_id.map(|id| id.to_string()).unwrap_or_default(),
+            name: channel.name.clone().unwrap_or_default(),
+            channel_type: channel_type_to_i32(channel.channel_type),
+            owner_id: metadata.owner_id.to_string(),
+            message_id: metadata.message_id.to_string(),
+            member_count: thread.member_count,
+            thread_metadata: ThreadMetadataResponse {
+                archived: metadata.archived,
+                locked: metadata.locked,
+                auto_archive_duration: metadata.auto_archive_duration,
+                archive_timestamp: metadata.archive_timestamp,
+                last_activity_at: metadata.last_activity_at,
+            },
+            created_at: channel.created_at,
+        }
+    }
+}
+
+impl From<&ThreadMember> for ThreadMemberResponse {
+    fn from(member: &ThreadMember) -> Self {
+        Self {
+            id: member.thread_id.to_string(),
+            user_id: member.user_id.to_string(),
+            join_timestamp: member.joined_at,
+        }
     }
 }
 
@@ -207,6 +286,8 @@ pub struct MessageWithDetails {
     pub attachments: Vec<Attachment>,
     pub reactions: Vec<(String, i64, bool)>, // (emoji, count, me)
     pub reference: Option<MessageReference>,
+    /// Users resolved from `message.mentions.users`
+    pub mentioned_users: Vec<User>,
 }
 
 pub struct MessageReference {
@@ -217,12 +298,25 @@ pub struct MessageReference {
 
 impl From<MessageWithDetails> for MessageResponse {
     fn from(details: MessageWithDetails) -> Self {
+        // Fall back to the stored reference, which always points into the same channel
+        let reference = details.reference.or_else(|| {
+            details
+                .message
+                .reference_id
+                .map(|message_id| MessageReference {
+                    message_id,
+                    channel_id: details.message.channel_id,
+                    guild_id: details.guild_id,
+                })
+        });
+
         Self {
             id: details.message.id.to_string(),
             channel_id: details.message.channel_id.to_string(),
             guild_id: details.guild_id.map(|id| id.to_string()),
             author: UserResponse::from(details.author),
             content: details.message.content,
+            message_type: i32::from(details.message.message_type.as_i16()),
             timestamp: details.message.created_at,
             edited_timestamp: details.message.edited_at,
             attachments: details
@@ -235,11 +329,31 @@ impl From<MessageWithDetails> for MessageResponse {
                 .into_iter()
                 .map(|(emoji, count, me)| ReactionResponse { emoji, count, me })
                 .collect(),
-            message_reference: details.reference.map(|r| MessageReferenceResponse {
+            message_reference: reference.map(|r| MessageReferenceResponse {
                 message_id: r.message_id.to_string(),
                 channel_id: r.channel_id.to_string(),
                 guild_id: r.guild_id.map(|id| id.to_string()),
             }),
+            mentions: details
+                .mentioned_users
+                .into_iter()
+                .map(UserResponse::from)
+                .collect(),
+            mention_roles: details
+                .message
+                .mentions
+                .roles
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            mention_channels: details
+                .message
+                .mentions
+                .channels
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            mention_everyone: details.message.mentions.everyone,
         }
     }
 }
@@ -274,6 +388,32 @@ impl From<&Attachment> for AttachmentResponse {
     }
 }
 
+impl From<MessageRevision> for MessageRevisionResponse {
+    fn from(revision: MessageRevision) -> Self {
+        Self {
+            id: revision.id.to_string(),
+            message_id: revision.message_id.to_string(),
+            content: revision.content,
+            edited_timestamp: revision.edited_at,
+            replaced_timestamp: revision.replaced_at,
+        }
+    }
+}
+
+// ============================================================================
+// Read State Mappers
+// ============================================================================
+
+impl From<&ReadStateData> for ReadStateResponse {
+    fn from(state: &ReadStateData) -> Self {
+        Self {
+            channel_id: state.channel_id.to_string(),
+            last_message_id: state.last_message_id.map(|id| id.to_string()),
+            mention_count: state.mention_count,
+        }
+    }
+}
+
 // ============================================================================
 // Role Mappers
 // ============================================================================
@@ -319,6 +459,25 @@ impl From<MemberWithUser> for MemberResponse {
     }
 }
 
+// ============================================================================
+// Audit Log Mappers
+// ============================================================================
+
+impl From<&AuditLogEntry> for AuditLogEntryResponse {
+    fn from(entry: &AuditLogEntry) -> Self {
+        Self {
+            id: entry.id.to_string(),
+            user_id: entry.user_id.to_string(),
+            action_type: entry.action.as_i32(),
+            target_id: entry.target_id.map(|id| id.to_string()),
+            target_type: entry.target_type.clone(),
+            changes: entry.changes.clone(),
+            reason: entry.reason.clone(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
 // ============================================================================
 // Invite Mappers
 // ============================================================================
@@ -389,7 +548,7 @@ impl From<ReactionWithMeta> for ReactionResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chat_core::Permissions;
+    use chat_core::{DmPrivacy, MfaLevel, Permissions};
     use chrono::Utc;
 
     fn create_test_user() -> User {
@@ -401,6 +560,10 @@ mod tests {
             avatar: Some("avatar_hash".to_string()),
             bot: false,
             system: false,
+            privileged_intents: false,
+            dm_privacy: DmPrivacy::Everyone,
+            verified: false,
+            mfa_enabled: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -413,6 +576,7 @@ mod tests {
             icon: Some("icon_hash".to_string()),
             description: Some("A test guild".to_string()),
             owner_id: Snowflake::new(123456789),
+            mfa_level: MfaLevel::None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -452,6 +616,7 @@ mod tests {
     fn test_channel_type_mapping() {
         assert_eq!(channel_type_to_i32(ChannelType::GuildText), 0);
         assert_eq!(channel_type_to_i32(ChannelType::Dm), 1);
+        assert_eq!(channel_type_to_i32(ChannelType::GroupDm), 3);
         assert_eq!(channel_type_to_i32(ChannelType::GuildCategory), 4);
     }
 
@@ -11,26 +11,36 @@ pub mod responses;
 
 // Re-export commonly used request types
 pub use requests::{
-    AddReactionRequest, BulkDeleteMessagesRequest, CreateBanRequest, CreateChannelRequest,
+    AddReactionRequest, AllowedMentionType, AllowedMentions, BulkDeleteMessagesRequest,
+    ClientInfo, CreateBanRequest, CreateChannelRequest,
     CreateDmRequest, CreateGuildRequest, CreateInviteRequest, CreateMessageRequest,
-    CreateRoleRequest, LoginRequest, LogoutRequest, MessageReference, RefreshTokenRequest,
-    RegisterRequest, RolePosition, TypingRequest, UpdateChannelRequest, UpdateGuildRequest,
-    UpdateMemberRequest, UpdateMessageRequest, UpdatePresenceRequest, UpdateRoleRequest,
-    UpdateRolePositionsRequest, UpdateUserRequest,
+    CreateRelationshipRequest, CreateRoleRequest, EditPermissionOverwriteRequest,
+    EnableTotpRequest, ForgotPasswordRequest, LoginRequest, LogoutRequest, MessageReference,
+    MfaCodeRequest, MfaTotpRequest, RefreshTokenRequest,
+    RegisterRequest, ResetPasswordRequest, RolePosition, StartThreadRequest, TypingRequest,
+    UpdateChannelRequest, UpdateGuildRequest, UpdateMemberRequest, UpdateMessageRequest,
+    UpdatePresenceRequest, UpdateRelationshipRequest, UpdateRoleRequest,
+    UpdateRolePositionsRequest, UpdateUserRequest, VerifyEmailRequest,
 };
 
 // Re-export commonly used response types
 pub use responses::{
-    ApiResponse, AttachmentResponse, AuthResponse, BanResponse, ChannelResponse,
-    CurrentUserResponse, DmChannelResponse, GuildPreviewResponse, GuildResponse,
+    ApiResponse, AttachmentResponse, AuditLogEntryResponse, AuthResponse, BackupCodesResponse,
+    BanResponse, LoginResponse, MfaRequiredResponse, TotpEnrollmentResponse,
+    ChannelResponse, CurrentUserResponse, DmChannelResponse, GatewayBotResponse,
+    GuildPreviewResponse, GuildResponse,
     GuildWithCountsResponse, HealthChecks, HealthResponse, InviteChannelResponse, InviteMinimalResponse,
-    InviteResponse, MemberResponse, MessageReferenceResponse, MessageResponse, PaginatedResponse,
-    PaginationMeta, PresenceResponse, PublicUserResponse, ReactionResponse, ReadinessResponse,
-    RoleResponse, TypingResponse, UserResponse,
+    InviteResponse, MemberResponse, MessageReferenceResponse, MessageResponse,
+    MessageRevisionResponse, MessageSearchHitResponse, MessageSearchResponse, PaginatedResponse,
+    PaginationMeta, PermissionOverwriteResponse, PresenceResponse, PublicUserResponse,
+    ReactionResponse, ReadStateResponse, ReadinessResponse, RelationshipResponse, RoleResponse,
+    SessionResponse, SessionStartLimitResponse, ThreadListResponse,
+    ThreadMemberResponse, ThreadMetadataResponse, ThreadResponse, TypingResponse, UserResponse,
 };
 
 // Re-export mappers and helper structs
 pub use mappers::{
     DmChannelWithRecipients, GuildWithCounts, InviteWithDetails, MemberWithUser,
     MessageReference as MessageReferenceData, MessageWithDetails, ReactionWithMeta,
+    RelationshipWithUser,
 };
@@ -31,6 +31,17 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+/// Client details recorded with a new login session
+///
+/// Filled from request metadata rather than the request body.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    /// User agent of the client
+    pub user_agent: Option<String>,
+    /// IP address the request came from
+    pub ip_address: Option<String>,
+}
+
 /// Token refresh request
 #[derive(Debug, Clone, Deserialize)]
 pub struct RefreshTokenRequest {
@@ -43,6 +54,55 @@ pub struct LogoutRequest {
     pub refresh_token: Option<String>,
 }
 
+/// Request a password reset email
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ForgotPasswordRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+/// Set a new password with a reset token
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, max = 128, message = "Token is required"))]
+    pub token: String,
+
+    #[validate(length(min = 8, max = 72, message = "Password must be 8-72 characters"))]
+    pub password: String,
+}
+
+/// Confirm an email address with a verification token
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct VerifyEmailRequest {
+    #[validate(length(min = 1, max = 128, message = "Token is required"))]
+    pub token: String,
+}
+
+/// Finish a two-factor login with the ticket from `/auth/login`
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct MfaTotpRequest {
+    #[validate(length(min = 1, max = 128, message = "Ticket is required"))]
+    pub ticket: String,
+
+    /// TOTP code or unused backup code
+    #[validate(length(min = 6, max = 32, message = "Code must be 6-32 characters"))]
+    pub code: String,
+}
+
+/// Start TOTP enrollment (the password is asked again)
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct EnableTotpRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+/// Confirm an account action with a TOTP or backup code
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct MfaCodeRequest {
+    #[validate(length(min = 6, max = 32, message = "Code must be 6-32 characters"))]
+    pub code: String,
+}
+
 // ============================================================================
 // User Requests
 // ============================================================================
@@ -55,6 +115,27 @@ pub struct UpdateUserRequest {
 
     /// Avatar hash or null to remove
     pub avatar: Option<String>,
+
+    /// Who may open a DM: 0 = everyone, 1 = friends and guild mates, 2 = friends
+    pub dm_privacy: Option<i16>,
+}
+
+/// Send a friend request by user tag
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateRelationshipRequest {
+    #[validate(length(min = 2, max = 32, message = "Username must be 2-32 characters"))]
+    pub username: String,
+
+    #[validate(length(equal = 4, message = "Discriminator must be 4 digits"))]
+    pub discriminator: String,
+}
+
+/// Send or accept a friend request, or block a user
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct UpdateRelationshipRequest {
+    /// Relationship type: 1 = friend (default), 2 = blocked
+    #[serde(rename = "type")]
+    pub relationship_type: Option<i16>,
 }
 
 // ============================================================================
@@ -88,6 +169,9 @@ pub struct UpdateGuildRequest {
 
     /// Transfer ownership to another user (Snowflake ID as string)
     pub owner_id: Option<String>,
+
+    /// Two-factor requirement for moderators: 0 = none, 1 = elevated (owner only)
+    pub mfa_level: Option<i16>,
 }
 
 // ============================================================================
@@ -128,6 +212,43 @@ pub struct UpdateChannelRequest {
 
     /// Parent category ID (Snowflake as string, null to remove)
     pub parent_id: Option<String>,
+
+    /// Archive or unarchive the thread (threads only)
+    pub archived: Option<bool>,
+
+    /// Lock or unlock the thread (threads only)
+    pub locked: Option<bool>,
+
+    /// Minutes of inactivity before auto-archive: 60, 1440, 4320 or 10080 (threads only)
+    pub auto_archive_duration: Option<i32>,
+
+    /// Icon (group DMs only)
+    #[validate(length(max = 255, message = "Icon must be at most 255 characters"))]
+    pub icon: Option<String>,
+}
+
+/// Start thread from message request
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct StartThreadRequest {
+    #[validate(length(min = 1, max = 100, message = "Thread name must be 1-100 characters"))]
+    pub name: String,
+
+    /// Minutes of inactivity before auto-archive (defaults to 1440)
+    pub auto_archive_duration: Option<i32>,
+}
+
+/// Create or replace a channel permission overwrite
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct EditPermissionOverwriteRequest {
+    /// Overwrite type: 0 = role, 1 = member
+    #[serde(rename = "type")]
+    pub overwrite_type: i16,
+
+    /// Allowed permission bits (as string, defaults to 0)
+    pub allow: Option<String>,
+
+    /// Denied permission bits (as string, defaults to 0)
+    pub deny: Option<String>,
 }
 
 // ============================================================================
@@ -142,13 +263,48 @@ pub struct MessageReference {
 }
 
 /// Create message request
-#[derive(Debug, Clone, Deserialize, Validate)]
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
 pub struct CreateMessageRequest {
-    #[validate(length(min = 1, max = 2000, message = "Message must be 1-2000 characters"))]
+    /// May be empty when the message carries attachments
+    #[serde(default)]
+    #[validate(length(max = 2000, message = "Message must be at most 2000 characters"))]
     pub content: String,
 
     /// Optional reference to a message being replied to
     pub message_reference: Option<MessageReference>,
+
+    /// Limits which mentions in the content notify anyone (all by default)
+    #[validate(nested)]
+    pub allowed_mentions: Option<AllowedMentions>,
+}
+
+/// Mention type that can be allowed wholesale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AllowedMentionType {
+    Users,
+    Roles,
+    Everyone,
+}
+
+/// Controls which parsed mentions are kept on a new message
+///
+/// A mention is kept if its type is listed in `parse` or its ID in
+/// `users`/`roles`. A type cannot be both parsed and listed by ID.
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct AllowedMentions {
+    #[serde(default)]
+    pub parse: Vec<AllowedMentionType>,
+
+    /// User IDs that may be mentioned
+    #[serde(default)]
+    #[validate(length(max = 100, message = "At most 100 users can be allowed"))]
+    pub users: Vec<String>,
+
+    /// Role IDs that may be mentioned
+    #[serde(default)]
+    #[validate(length(max = 100, message = "At most 100 roles can be allowed"))]
+    pub roles: Vec<String>,
 }
 
 /// Update message request
@@ -303,10 +459,20 @@ pub struct AddReactionRequest {
 // ============================================================================
 
 /// Create DM channel request
-#[derive(Debug, Clone, Deserialize)]
+///
+/// `recipient_id` opens a one-to-one DM, `recipients` creates a group DM
+/// owned by the current user.
+#[derive(Debug, Clone, Deserialize, Validate)]
 pub struct CreateDmRequest {
     /// Recipient user ID (Snowflake as string)
-    pub recipient_id: String,
+    pub recipient_id: Option<String>,
+
+    /// Group DM recipient user IDs (Snowflakes as strings)
+    pub recipients: Option<Vec<String>>,
+
+    /// Group DM name
+    #[validate(length(min = 1, max = 100, message = "Channel name must be 1-100 characters"))]
+    pub name: Option<String>,
 }
 
 // ============================================================================
@@ -340,6 +506,7 @@ pub struct CreateBanRequest {
 
     /// Number of days of messages to delete (0-7)
     #[serde(default)]
+    #[validate(range(min = 0, max = 7, message = "delete_message_days must be 0-7"))]
     pub delete_message_days: i32,
 }
 
@@ -383,28 +550,96 @@ mod tests {
         assert!(short_password.validate().is_err());
     }
 
+    #[test]
+    fn test_reset_password_validation() {
+        let valid = ResetPasswordRequest {
+            token: "abc123".to_string(),
+            password: "securepassword123".to_string(),
+        };
+        assert!(valid.validate().is_ok());
+
+        let missing_token = ResetPasswordRequest {
+            token: String::new(),
+            password: "securepassword123".to_string(),
+        };
+        assert!(missing_token.validate().is_err());
+
+        let short_password = ResetPasswordRequest {
+            token: "abc123".to_string(),
+            password: "short".to_string(),
+        };
+        assert!(short_password.validate().is_err());
+    }
+
+    #[test]
+    fn test_mfa_totp_validation() {
+        let valid = MfaTotpRequest {
+            ticket: "ticket".to_string(),
+            code: "123456".to_string(),
+        };
+        assert!(valid.validate().is_ok());
+
+        let short_code = MfaTotpRequest {
+            ticket: "ticket".to_string(),
+            code: "123".to_string(),
+        };
+        assert!(short_code.validate().is_err());
+
+        let missing_ticket = MfaTotpRequest {
+            ticket: String::new(),
+            code: "123456".to_string(),
+        };
+        assert!(missing_ticket.validate().is_err());
+    }
+
     #[test]
     fn test_create_message_validation() {
         // Valid message
         let valid = CreateMessageRequest {
             content: "Hello, world!".to_string(),
             message_reference: None,
+            allowed_mentions: None,
         };
         assert!(valid.validate().is_ok());
 
-        // Invalid - empty message
+        // Empty content is allowed here; the service requires attachments then
         let empty = CreateMessageRequest {
-            content: "".to_string(),
+            content: String::new(),
             message_reference: None,
+            allowed_mentions: None,
         };
-        assert!(empty.validate().is_err());
+        assert!(empty.validate().is_ok());
 
         // Invalid - message too long
         let too_long = CreateMessageRequest {
             content: "a".repeat(2001),
             message_reference: None,
+            allowed_mentions: None,
         };
         assert!(too_long.validate().is_err());
+
+        // Invalid - too many allowed users
+        let too_many_allowed = CreateMessageRequest {
+            content: "hi".to_string(),
+            message_reference: None,
+            allowed_mentions: Some(AllowedMentions {
+                users: vec!["1".to_string(); 101],
+                ..AllowedMentions::default()
+            }),
+        };
+        assert!(too_many_allowed.validate().is_err());
+    }
+
+    #[test]
+    fn test_allowed_mentions_deserialize() {
+        let allowed: AllowedMentions =
+            serde_json::from_str(r#"{"parse": ["users", "everyone"], "roles": ["5"]}"#).unwrap();
+        assert_eq!(
+            allowed.parse,
+            vec![AllowedMentionType::Users, AllowedMentionType::Everyone]
+        );
+        assert!(allowed.users.is_empty());
+        assert_eq!(allowed.roles, vec!["5".to_string()]);
     }
 
     #[test]
@@ -417,7 +652,7 @@ mod tests {
         assert!(valid.validate().is_ok());
 
         let empty_name = CreateGuildRequest {
-            name: "".to_string(),
+            name: String::new(),
             icon: None,
             description: None,
         };
@@ -438,4 +673,19 @@ mod tests {
         };
         assert!(too_few.validate().is_err());
     }
+
+    #[test]
+    fn test_create_ban_validation() {
+        let valid = CreateBanRequest {
+            reason: Some("Spam".to_string()),
+            delete_message_days: 7,
+        };
+        assert!(valid.validate().is_ok());
+
+        let too_many_days = CreateBanRequest {
+            reason: None,
+            delete_message_days: 8,
+        };
+        assert!(too_many_days.validate().is_err());
+    }
 }
@@ -3,6 +3,7 @@
 //! All response DTOs implement `Serialize` for JSON output.
 //! Snowflake IDs are serialized as strings for JavaScript compatibility.
 
+use chat_core::entities::AuditLogChange;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
@@ -89,6 +90,51 @@ impl AuthResponse {
     }
 }
 
+/// Login response: tokens, or a ticket when a second factor is needed
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    /// Password was enough; the user is logged in
+    Tokens(AuthResponse),
+    /// Two-factor authentication is enabled; exchange the ticket at `/auth/mfa/totp`
+    MfaRequired(MfaRequiredResponse),
+}
+
+/// Second factor needed to finish logging in
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaRequiredResponse {
+    /// Always true
+    pub mfa: bool,
+    pub ticket: String,
+    /// Seconds until the ticket expires
+    pub expires_in: u64,
+}
+
+impl MfaRequiredResponse {
+    pub fn new(ticket: String, expires_in: u64) -> Self {
+        Self {
+            mfa: true,
+            ticket,
+            expires_in,
+        }
+    }
+}
+
+/// Pending TOTP enrollment
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpEnrollmentResponse {
+    /// Base32 secret, for entering into an authenticator app by hand
+    pub secret: String,
+    /// `otpauth://` URI, usually shown as a QR code
+    pub uri: String,
+}
+
+/// Freshly issued backup codes; they are shown only once
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupCodesResponse {
+    pub backup_codes: Vec<String>,
+}
+
 // ============================================================================
 // User Responses
 // ============================================================================
@@ -117,6 +163,12 @@ pub struct CurrentUserResponse {
     pub avatar: Option<String>,
     pub bot: bool,
     pub system: bool,
+    /// Who may open a DM: 0 = everyone, 1 = friends and guild mates, 2 = friends
+    pub dm_privacy: i16,
+    /// Whether the email address has been verified
+    pub verified: bool,
+    /// Whether TOTP two-factor authentication is enabled
+    pub mfa_enabled: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -131,6 +183,31 @@ pub struct PublicUserResponse {
     pub bot: bool,
 }
 
+/// Relationship response, keyed by the other user's ID
+#[derive(Debug, Clone, Serialize)]
+pub struct RelationshipResponse {
+    pub id: String,
+    /// 1 = friend, 2 = blocked, 3 = incoming request, 4 = outgoing request
+    #[serde(rename = "type")]
+    pub relationship_type: i16,
+    pub user: PublicUserResponse,
+    pub since: DateTime<Utc>,
+}
+
+/// Login session of the current user
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionResponse {
+    pub id: String,
+    /// User agent of the client that logged in
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Last login or token refresh
+    pub last_used_at: DateTime<Utc>,
+    /// Whether the request was made with this session's access token
+    pub current: bool,
+}
+
 // ============================================================================
 // Guild Responses
 // ============================================================================
@@ -145,6 +222,8 @@ pub struct GuildResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub owner_id: String,
+    /// Two-factor requirement for moderators: 0 = none, 1 = elevated
+    pub mfa_level: i16,
     pub created_at: DateTime<Utc>,
 }
 
@@ -158,6 +237,7 @@ pub struct GuildWithCountsResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub owner_id: String,
+    pub mfa_level: i16,
     pub member_count: i64,
     pub channel_count: i64,
     pub created_at: DateTime<Utc>,
@@ -194,20 +274,91 @@ pub struct ChannelResponse {
     pub position: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_id: Option<String>,
+    /// Group DM owner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<String>,
+    /// Group DM icon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Thread channel response
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadResponse {
+    pub id: String,
+    pub guild_id: String,
+    /// Text channel the thread belongs to
+    pub parent_id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub channel_type: i32,
+    pub owner_id: String,
+    /// Message the thread was started from
+    pub message_id: String,
+    pub member_count: i32,
+    pub thread_metadata: ThreadMetadataResponse,
     pub created_at: DateTime<Utc>,
 }
 
+/// Thread-specific state
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadMetadataResponse {
+    pub archived: bool,
+    pub locked: bool,
+    pub auto_archive_duration: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_timestamp: Option<DateTime<Utc>>,
+    pub last_activity_at: DateTime<Utc>,
+}
+
+/// Thread list response
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadListResponse {
+    pub threads: Vec<ThreadResponse>,
+    /// Whether older archived threads exist
+    pub has_more: bool,
+}
+
+/// Thread member response
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadMemberResponse {
+    /// Thread ID
+    pub id: String,
+    pub user_id: String,
+    pub join_timestamp: DateTime<Utc>,
+}
+
 /// DM channel response with recipients
 #[derive(Debug, Clone, Serialize)]
 pub struct DmChannelResponse {
     pub id: String,
     #[serde(rename = "type")]
     pub channel_type: i32,
+    /// Group DM name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Group DM icon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Group DM owner
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<String>,
     pub recipients: Vec<UserResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_message_id: Option<String>,
 }
 
+/// Channel permission overwrite response
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionOverwriteResponse {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub overwrite_type: i16,
+    pub allow: String,
+    pub deny: String,
+}
+
 // ============================================================================
 // Message Responses
 // ============================================================================
@@ -221,6 +372,9 @@ pub struct MessageResponse {
     pub guild_id: Option<String>,
     pub author: UserResponse,
     pub content: String,
+    /// Message type: 0 = default, 6 = pin notice
+    #[serde(rename = "type")]
+    pub message_type: i32,
     pub timestamp: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_timestamp: Option<DateTime<Utc>>,
@@ -230,6 +384,15 @@ pub struct MessageResponse {
     pub reactions: Vec<ReactionResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message_reference: Option<MessageReferenceResponse>,
+    /// Users mentioned in the message
+    pub mentions: Vec<UserResponse>,
+    /// IDs of roles mentioned in the message
+    pub mention_roles: Vec<String>,
+    /// IDs of channels mentioned in the message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mention_channels: Vec<String>,
+    /// Whether the message mentions @everyone
+    pub mention_everyone: bool,
 }
 
 /// Attachment response
@@ -265,6 +428,37 @@ pub struct MessageReferenceResponse {
     pub guild_id: Option<String>,
 }
 
+/// Earlier version of an edited message
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageRevisionResponse {
+    pub id: String,
+    pub message_id: String,
+    pub content: String,
+    /// When this content was written by an edit (absent for the original content)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_timestamp: Option<DateTime<Utc>>,
+    /// When this content was replaced by a later edit
+    pub replaced_timestamp: DateTime<Utc>,
+}
+
+/// Message search results
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageSearchResponse {
+    /// Total number of matching messages across all pages
+    pub total_results: i64,
+    pub hits: Vec<MessageSearchHitResponse>,
+}
+
+/// A single search hit with the messages around it
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageSearchHitResponse {
+    pub message: MessageResponse,
+    /// Messages sent just before the hit, oldest first
+    pub context_before: Vec<MessageResponse>,
+    /// Messages sent just after the hit, oldest first
+    pub context_after: Vec<MessageResponse>,
+}
+
 // ============================================================================
 // Role Responses
 // ============================================================================
@@ -303,6 +497,27 @@ pub struct BanResponse {
     pub reason: Option<String>,
 }
 
+// ============================================================================
+// Audit Log Responses
+// ============================================================================
+
+/// Audit log entry response
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditLogEntryResponse {
+    pub id: String,
+    pub user_id: String,
+    pub action_type: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<AuditLogChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // Invite Responses
 // ============================================================================
@@ -357,6 +572,39 @@ pub struct PresenceResponse {
     pub last_seen: Option<DateTime<Utc>>,
 }
 
+/// Gateway connection info for bots (GET /gateway/bot)
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayBotResponse {
+    /// WebSocket URL to connect to
+    pub url: String,
+    /// Recommended number of shards
+    pub shards: u32,
+    pub session_start_limit: SessionStartLimitResponse,
+}
+
+/// Remaining gateway session starts (Identify) for the current user
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionStartLimitResponse {
+    /// Session starts allowed per window
+    pub total: u64,
+    /// Session starts left in the current window
+    pub remaining: u64,
+    /// Milliseconds until the window resets
+    pub reset_after: u64,
+    /// Shards that may Identify at the same time
+    pub max_concurrency: u32,
+}
+
+/// Read state of a channel for the current user
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadStateResponse {
+    pub channel_id: String,
+    /// Last acknowledged message
+    pub last_message_id: Option<String>,
+    /// Mentions since the last acknowledged message
+    pub mention_count: i32,
+}
+
 /// Typing indicator response
 #[derive(Debug, Clone, Serialize)]
 pub struct TypingResponse {
@@ -428,6 +676,9 @@ mod tests {
             avatar: None,
             bot: false,
             system: false,
+            dm_privacy: 0,
+            verified: false,
+            mfa_enabled: false,
             created_at: Utc::now(),
         };
 
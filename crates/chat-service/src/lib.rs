@@ -8,16 +8,20 @@
 //! ## Services
 //!
 //! - [`AuthService`] - Authentication (register, login, logout, token refresh)
+//! - [`MfaService`] - TOTP two-factor authentication and backup codes
+//! - [`SessionService`] - Login session listing and remote logout
 //! - [`PermissionService`] - Permission checking and role hierarchy
 //! - [`UserService`] - User profile management
 //! - [`GuildService`] - Guild (server) CRUD operations
 //! - [`ChannelService`] - Channel management within guilds
+//! - [`ThreadService`] - Message threads and thread membership
 //! - [`MessageService`] - Message creation, editing, deletion
+//! - [`AttachmentService`] - Attachment uploads and file serving
 //! - [`MemberService`] - Guild member and ban management
 //! - [`RoleService`] - Role creation and assignment
 //! - [`ReactionService`] - Message reactions
 //! - [`InviteService`] - Guild invitations
-//! - [`DmService`] - Direct message channels
+//! - [`DmService`] - Direct message and group DM channels
 //! - [`PresenceService`] - User online status
 //!
 //! ## DTOs
@@ -49,27 +53,41 @@ pub mod services;
 // Re-export DTOs
 pub use dto::{
     // Request types
-    AddReactionRequest, BulkDeleteMessagesRequest, CreateBanRequest, CreateChannelRequest,
+    AddReactionRequest, AllowedMentionType, AllowedMentions, BulkDeleteMessagesRequest,
+    ClientInfo, CreateBanRequest, CreateChannelRequest,
     CreateDmRequest, CreateGuildRequest, CreateInviteRequest, CreateMessageRequest,
-    CreateRoleRequest, LoginRequest, LogoutRequest, MessageReference, RefreshTokenRequest,
-    RegisterRequest, RolePosition, TypingRequest, UpdateChannelRequest, UpdateGuildRequest,
-    UpdateMemberRequest, UpdateMessageRequest, UpdatePresenceRequest, UpdateRoleRequest,
-    UpdateRolePositionsRequest, UpdateUserRequest,
+    CreateRelationshipRequest, CreateRoleRequest, EditPermissionOverwriteRequest,
+    EnableTotpRequest, ForgotPasswordRequest, LoginRequest, LogoutRequest, MessageReference,
+    MfaCodeRequest, MfaTotpRequest, RefreshTokenRequest,
+    RegisterRequest, ResetPasswordRequest, RolePosition, StartThreadRequest,
+    TypingRequest, UpdateChannelRequest, UpdateGuildRequest, UpdateMemberRequest, UpdateMessageRequest,
+    UpdatePresenceRequest, UpdateRelationshipRequest, UpdateRoleRequest,
+    UpdateRolePositionsRequest, UpdateUserRequest, VerifyEmailRequest,
     // Response types
-    ApiResponse, AttachmentResponse, AuthResponse, BanResponse, ChannelResponse,
-    CurrentUserResponse, DmChannelResponse, GuildPreviewResponse, GuildResponse,
+    ApiResponse, AttachmentResponse, AuditLogEntryResponse, AuthResponse, BackupCodesResponse,
+    BanResponse, LoginResponse, MfaRequiredResponse, TotpEnrollmentResponse,
+    ChannelResponse, CurrentUserResponse, DmChannelResponse, GatewayBotResponse,
+    GuildPreviewResponse, GuildResponse,
     GuildWithCountsResponse, HealthChecks, HealthResponse, InviteChannelResponse, InviteMinimalResponse,
-    InviteResponse, MemberResponse, MessageReferenceResponse, MessageResponse, PaginatedResponse,
-    PaginationMeta, PresenceResponse, PublicUserResponse, ReactionResponse, ReadinessResponse,
-    RoleResponse, TypingResponse, UserResponse,
+    InviteResponse, MemberResponse, MessageReferenceResponse, MessageResponse,
+    MessageRevisionResponse, MessageSearchHitResponse, MessageSearchResponse, PaginatedResponse,
+    PaginationMeta, PermissionOverwriteResponse, PresenceResponse, PublicUserResponse,
+    ReactionResponse, ReadStateResponse, ReadinessResponse, RelationshipResponse, RoleResponse,
+    SessionResponse, SessionStartLimitResponse, ThreadListResponse,
+    ThreadMemberResponse, ThreadMetadataResponse, ThreadResponse, TypingResponse, UserResponse,
     // Helper types
     DmChannelWithRecipients, GuildWithCounts, InviteWithDetails, MemberWithUser, MessageWithDetails,
-    ReactionWithMeta,
+    ReactionWithMeta, RelationshipWithUser,
 };
 
 // Re-export services
 pub use services::{
-    AuthService, ChannelService, DmService, GuildService, InviteService, MemberService,
-    MessageService, PermissionService, PresenceService, ReactionService, RoleService,
-    ServiceContext, ServiceContextBuilder, ServiceError, ServiceResult, UserService,
+    AttachmentService, AuditLogService, AuthService, ChannelService, DmService, GatewayService,
+    GuildService,
+    InviteService, MemberService, MessageService, MfaService, PendingAttachment, PermissionService,
+    PresenceService, ReactionService, ReadStateService, RelationshipService, RoleService,
+    ServiceContext,
+    ServiceContextBuilder,
+    ServiceError, ServiceResult, SessionService, ThreadService, UserService, MAX_ATTACHMENTS_PER_MESSAGE,
+    MAX_GROUP_DM_RECIPIENTS, MAX_PINS_PER_CHANNEL,
 };
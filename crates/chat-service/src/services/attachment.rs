@@ -0,0 +1,217 @@
+//! Attachment service
+//!
+//! Streams uploaded files to storage and serves them back by CDN path.
+
+use std::io;
+
+use bytes::Bytes;
+use chat_core::entities::Attachment;
+use chat_core::Snowflake;
+use chat_storage::{attachment_key, sanitize_filename, StoredObject};
+use futures_util::Stream;
+use tracing::{instrument, warn};
+
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+
+/// Maximum number of attachments on a single message
+pub const MAX_ATTACHMENTS_PER_MESSAGE: usize = 10;
+
+/// Content type used when neither the client nor probing provides one
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Maximum stored content type length (matches the attachments table)
+const MAX_CONTENT_TYPE_LEN: usize = 100;
+
+/// A stored file that has not been attached to a message yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingAttachment {
+    pub id: Snowflake,
+    pub channel_id: Snowflake,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i32,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub verified_image: bool,
+}
+
+impl PendingAttachment {
+    /// Storage key of the uploaded file
+    pub fn key(&self) -> String {
+        attachment_key(self.channel_id, self.id, &self.filename)
+    }
+}
+
+/// Attachment service
+pub struct AttachmentService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> AttachmentService<'a> {
+    /// Create a new AttachmentService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Stream a file into storage
+    ///
+    /// Enforces the configured size limit and records image dimensions. The
+    /// caller is responsible for permission checks and for attaching or
+    /// discarding the returned upload.
+    #[instrument(skip(self, content_type, body))]
+    pub async fn upload<'s, S>(
+        &self,
+        channel_id: Snowflake,
+        filename: &str,
+        content_type: Option<&str>,
+        body: S,
+    ) -> ServiceResult<PendingAttachment>
+    where
+        S: Stream<Item = io::Result<Bytes>> + Send + 's,
+    {
+        let id = self.ctx.generate_id();
+        let filename = sanitize_filename(filename);
+        let key = attachment_key(channel_id, id, &filename);
+
+        let uploaded = self.ctx.file_store().upload(&key, body).await?;
+
+        let Ok(size) = i32::try_from(uploaded.size) else {
+            self.delete_key(&key).await;
+            return Err(ServiceError::validation("File is too large"));
+        };
+
+        // Trust detected image types over the client-supplied header
+        let verified_image = uploaded.detected_content_type.is_some();
+        let content_type = uploaded
+            .detected_content_type
+            .map(str::to_string)
+            .or_else(|| {
+                content_type
+                    .map(str::trim)
+                    .filter(|ct| !ct.is_empty() && ct.len() <= MAX_CONTENT_TYPE_LEN)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+
+        Ok(PendingAttachment {
+            id,
+            channel_id,
+            filename,
+            content_type,
+            size,
+            width: uploaded.width.and_then(|w| i32::try_from(w).ok()),
+            height: uploaded.height.and_then(|h| i32::try_from(h).ok()),
+            verified_image,
+        })
+    }
+
+    /// Build the attachment entity for an upload once its message exists
+    pub fn attach(&self, pending: &PendingAttachment, message_id: Snowflake) -> Attachment {
+        let mut attachment = Attachment::new(
+            pending.id,
+            message_id,
+            pending.filename.clone(),
+            pending.content_type.clone(),
+            pending.size,
+            self.ctx.file_store().url_for(&pending.key()),
+        );
+        if let (Some(width), Some(height)) = (pending.width, pending.height) {
+            attachment.set_dimensions(width, height);
+        }
+        attachment.verified_image = pending.verified_image;
+        attachment
+    }
+
+    /// Remove uploads that never made it into a message (best-effort)
+    pub async fn discard(&self, pending: &[PendingAttachment]) {
+        for upload in pending {
+            self.delete_key(&upload.key()).await;
+        }
+    }
+
+    /// Remove the attachments of deleted messages (best-effort)
+    ///
+    /// Messages are soft-deleted, so the attachment rows are removed here
+    /// along with their stored files.
+    pub async fn delete_attachments(&self, channel_id: Snowflake, attachments: &[Attachment]) {
+        let mut message_ids: Vec<Snowflake> = attachments.iter().map(|a| a.message_id).collect();
+        message_ids.sort_unstable();
+        message_ids.dedup();
+
+        for message_id in message_ids {
+            if let Err(e) = self.ctx.attachment_repo().delete_by_message(message_id).await {
+                warn!(message_id = %message_id, error = %e, "Failed to delete attachment rows");
+            }
+        }
+
+        for attachment in attachments {
+            self.delete_key(&attachment_key(channel_id, attachment.id, &attachment.filename))
+                .await;
+        }
+    }
+
+    /// Open a stored attachment by its CDN path
+    #[instrument(skip(self))]
+    pub async fn open(
+        &self,
+        channel_id: Snowflake,
+        attachment_id: Snowflake,
+        filename: &str,
+    ) -> ServiceResult<(Attachment, StoredObject)> {
+        let not_found = || ServiceError::not_found("Attachment", attachment_id.to_string());
+
+        let attachment = self
+            .ctx
+            .attachment_repo()
+            .find_by_id(attachment_id)
+            .await?
+            .filter(|a| a.filename == filename)
+            .ok_or_else(not_found)?;
+
+        let message = self
+            .ctx
+            .message_repo()
+            .find_by_id(attachment.message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or_else(not_found)?;
+
+        let key = attachment_key(message.channel_id, attachment.id, &attachment.filename);
+        let object = self.ctx.file_store().open(&key).await.map_err(|e| {
+            if e.is_not_found() {
+                not_found()
+            } else {
+                e.into()
+            }
+        })?;
+
+        Ok((attachment, object))
+    }
+
+    async fn delete_key(&self, key: &str) {
+        if let Err(e) = self.ctx.file_store().delete(key).await {
+            warn!(key, error = %e, "Failed to delete stored file");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_attachment_key() {
+        let pending = PendingAttachment {
+            id: Snowflake::new(2),
+            channel_id: Snowflake::new(1),
+            filename: "cat.png".to_string(),
+            content_type: "image/png".to_string(),
+            size: 10,
+            width: Some(1),
+            height: Some(1),
+            verified_image: true,
+        };
+        assert_eq!(pending.key(), "attachments/1/2/cat.png");
+    }
+}
@@ -0,0 +1,78 @@
+//! Audit log service
+//!
+//! Records administrative actions taken in a guild and serves the audit log.
+
+use chat_core::entities::{AuditLogAction, AuditLogChange, AuditLogEntry};
+use chat_core::traits::AuditLogQuery;
+use chat_core::{Permissions, Snowflake};
+use tracing::{instrument, warn};
+
+use crate::dto::AuditLogEntryResponse;
+
+use super::context::ServiceContext;
+use super::error::ServiceResult;
+use super::permission::PermissionService;
+
+/// Audit log service
+pub struct AuditLogService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> AuditLogService<'a> {
+    /// Create a new AuditLogService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Record an audit log entry
+    ///
+    /// The action being audited has already happened by the time this is
+    /// called, so a failed write is logged instead of returned.
+    #[instrument(skip(self, changes))]
+    pub async fn record(
+        &self,
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        action: AuditLogAction,
+        target: (Snowflake, &str),
+        changes: Vec<AuditLogChange>,
+        reason: Option<String>,
+    ) {
+        let (target_id, target_type) = target;
+        let entry = AuditLogEntry::new(self.ctx.generate_id(), guild_id, user_id, action)
+            .with_target(target_id, target_type)
+            .with_changes(changes)
+            .with_reason(reason);
+
+        if let Err(e) = self.ctx.audit_log_repo().create(&entry).await {
+            warn!(guild_id = %guild_id, action = ?action, error = %e, "Failed to record audit log entry");
+        }
+    }
+
+    /// Get guild audit log entries, newest first
+    #[instrument(skip(self))]
+    pub async fn get_guild_audit_logs(
+        &self,
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        query: AuditLogQuery,
+    ) -> ServiceResult<Vec<AuditLogEntryResponse>> {
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission(guild_id, user_id, Permissions::VIEW_AUDIT_LOG)
+            .await?;
+
+        let entries = self
+            .ctx
+            .audit_log_repo()
+            .find_by_guild(guild_id, query)
+            .await?;
+
+        Ok(entries.iter().map(AuditLogEntryResponse::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would go here with mocked dependencies
+}
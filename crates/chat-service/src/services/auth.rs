@@ -1,21 +1,27 @@
 //! Authentication service
 //!
-//! Handles user registration, login, token refresh, and logout.
+//! Handles user registration, login (including the two-factor step), token
+//! refresh, and logout, plus the emailed password reset and email
+//! verification flows.
 
-use chat_cache::RefreshTokenData;
+use chat_cache::{AccountTokenData, AccountTokenKind, RefreshTokenData, MFA_TICKET_TTL};
 use chat_common::auth::{hash_password, validate_password_strength, verify_password};
 use uuid::Uuid;
-use chat_core::entities::User;
+use chat_core::entities::{DmPrivacy, User};
 use chat_core::Snowflake;
 use chrono::Utc;
 use tracing::{info, instrument, warn};
 
 use crate::dto::{
-    AuthResponse, CurrentUserResponse, LoginRequest, RefreshTokenRequest, RegisterRequest,
+    AuthResponse, ClientInfo, CurrentUserResponse, ForgotPasswordRequest, LoginRequest, LoginResponse,
+    MfaRequiredResponse, MfaTotpRequest, RefreshTokenRequest, RegisterRequest,
+    ResetPasswordRequest, VerifyEmailRequest,
 };
 
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
+use super::mfa::MfaService;
+use super::session::SessionService;
 
 /// Authentication service
 pub struct AuthService<'a> {
@@ -29,8 +35,12 @@ impl<'a> AuthService<'a> {
     }
 
     /// Register a new user
-    #[instrument(skip(self, request), fields(username = %request.username, email = %request.email))]
-    pub async fn register(&self, request: RegisterRequest) -> ServiceResult<AuthResponse> {
+    #[instrument(skip(self, request, client), fields(username = %request.username, email = %request.email))]
+    pub async fn register(
+        &self,
+        request: RegisterRequest,
+        client: ClientInfo,
+    ) -> ServiceResult<AuthResponse> {
         // Validate password strength before proceeding
         validate_password_strength(&request.password).map_err(ServiceError::from)?;
 
@@ -62,6 +72,10 @@ impl<'a> AuthService<'a> {
             avatar: None,
             bot: false,
             system: false,
+            privileged_intents: false,
+            dm_privacy: DmPrivacy::Everyone,
+            verified: false,
+            mfa_enabled: false,
             created_at: now,
             updated_at: now,
         };
@@ -71,33 +85,24 @@ impl<'a> AuthService<'a> {
 
         info!(user_id = %user_id, "User registered successfully");
 
-        // Generate tokens
-        let token_pair = self
-            .ctx
-            .jwt_service()
-            .generate_token_pair(user_id)
-            .map_err(|e| ServiceError::internal(e.to_string()))?;
-
-        // Store refresh token in Redis
-        let session_id = Uuid::new_v4().to_string();
-        let refresh_data = RefreshTokenData::new(user_id, session_id);
-        self.ctx
-            .refresh_token_store()
-            .store(&token_pair.refresh_token, &refresh_data)
-            .await
-            .map_err(|e| ServiceError::internal(e.to_string()))?;
+        // A failed email must not fail the registration; the user can ask again
+        if let Err(e) = self.send_verification_email(&user).await {
+            warn!(user_id = %user_id, error = %e, "Failed to send verification email");
+        }
 
-        Ok(AuthResponse::new(
-            token_pair.access_token,
-            token_pair.refresh_token,
-            token_pair.expires_in,
-            CurrentUserResponse::from(&user),
-        ))
+        self.issue_tokens(&user, Self::new_session(user.id, client)).await
     }
 
     /// Login with email and password
-    #[instrument(skip(self, request), fields(email = %request.email))]
-    pub async fn login(&self, request: LoginRequest) -> ServiceResult<AuthResponse> {
+    ///
+    /// Users with two-factor authentication get a short-lived ticket instead
+    /// of tokens; [`login_mfa_totp`](Self::login_mfa_totp) exchanges it.
+    #[instrument(skip(self, request, client), fields(email = %request.email))]
+    pub async fn login(
+        &self,
+        request: LoginRequest,
+        client: ClientInfo,
+    ) -> ServiceResult<LoginResponse> {
         // Find user by email
         let user = self
             .ctx
@@ -129,108 +134,347 @@ impl<'a> AuthService<'a> {
             return Err(ServiceError::App(chat_common::AppError::InvalidCredentials));
         }
 
+        if user.mfa_enabled {
+            let ticket = self
+                .ctx
+                .mfa_store()
+                .issue_ticket(user.id)
+                .await
+                .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+            info!(user_id = %user.id, "Password accepted, waiting for two-factor code");
+            return Ok(LoginResponse::MfaRequired(MfaRequiredResponse::new(
+                ticket,
+                MFA_TICKET_TTL,
+            )));
+        }
+
         info!(user_id = %user.id, "User logged in successfully");
 
-        // Generate tokens
-        let token_pair = self
+        self.issue_tokens(&user, Self::new_session(user.id, client))
+            .await
+            .map(LoginResponse::Tokens)
+    }
+
+    /// Finish a two-factor login with a TOTP or backup code
+    #[instrument(skip(self, request, client))]
+    pub async fn login_mfa_totp(
+        &self,
+        request: MfaTotpRequest,
+        client: ClientInfo,
+    ) -> ServiceResult<AuthResponse> {
+        let ticket = self
             .ctx
-            .jwt_service()
-            .generate_token_pair(user.id)
-            .map_err(|e| ServiceError::internal(e.to_string()))?;
+            .mfa_store()
+            .get_ticket(&request.ticket)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+            .ok_or_else(|| ServiceError::validation("Invalid or expired ticket"))?;
 
-        // Store refresh token in Redis
-        let session_id = Uuid::new_v4().to_string();
-        let refresh_data = RefreshTokenData::new(user.id, session_id);
+        // A wrong code leaves the ticket for another try, within the failure limit
+        MfaService::new(self.ctx)
+            .verify_code(ticket.user_id, &request.code)
+            .await?;
+
+        // Fails if a concurrent request already exchanged the ticket
         self.ctx
-            .refresh_token_store()
-            .store(&token_pair.refresh_token, &refresh_data)
+            .mfa_store()
+            .consume_ticket(&request.ticket)
             .await
-            .map_err(|e| ServiceError::internal(e.to_string()))?;
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+            .ok_or_else(|| ServiceError::validation("Invalid or expired ticket"))?;
 
-        Ok(AuthResponse::new(
-            token_pair.access_token,
-            token_pair.refresh_token,
-            token_pair.expires_in,
-            CurrentUserResponse::from(&user),
-        ))
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(ticket.user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", ticket.user_id.to_string()))?;
+
+        info!(user_id = %user.id, "User logged in with two-factor authentication");
+
+        self.issue_tokens(&user, Self::new_session(user.id, client)).await
     }
 
     /// Refresh access token using refresh token
+    ///
+    /// The new tokens continue the session of the old refresh token, which
+    /// is used up first so concurrent refreshes with it cannot both succeed.
     #[instrument(skip(self, request))]
     pub async fn refresh_tokens(
         &self,
         request: RefreshTokenRequest,
     ) -> ServiceResult<AuthResponse> {
-        // Validate refresh token exists in Redis
+        // Claim the refresh token; fails if it is unknown or already used
         let refresh_data = self
             .ctx
             .refresh_token_store()
-            .validate(&request.refresh_token)
+            .claim(&request.refresh_token)
             .await
             .map_err(|e| ServiceError::internal(e.to_string()))?
             .ok_or(ServiceError::App(chat_common::AppError::InvalidToken))?;
+        let session_id = refresh_data.session_id.clone();
+
+        let result = self.continue_session(refresh_data).await;
+
+        // Without a new token the session ends with the claimed one
+        if result.is_err() {
+            if let Err(e) = self.ctx.refresh_token_store().end_session(&session_id).await {
+                warn!(
+                    session_id = %session_id,
+                    error = %e,
+                    "Failed to end session after refresh failure"
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Logout user by revoking refresh token
+    #[instrument(skip(self, refresh_token))]
+    pub async fn logout(&self, user_id: Snowflake, refresh_token: Option<String>) -> ServiceResult<()> {
+        if let Some(token) = refresh_token {
+            // Revoke specific refresh token
+            self.ctx
+                .refresh_token_store()
+                .revoke(&token)
+                .await
+                .map_err(|e| ServiceError::internal(e.to_string()))?;
+        } else {
+            // Revoke all refresh tokens for user
+            self.ctx
+                .refresh_token_store()
+                .revoke_all_for_user(user_id)
+                .await
+                .map_err(|e| ServiceError::internal(e.to_string()))?;
+        }
+
+        info!(user_id = %user_id, "User logged out successfully");
+        Ok(())
+    }
+
+    /// Email a password reset link to the account using this email
+    ///
+    /// Succeeds whether or not the email is registered, so callers cannot
+    /// probe for accounts.
+    #[instrument(skip(self, request))]
+    pub async fn forgot_password(&self, request: ForgotPasswordRequest) -> ServiceResult<()> {
+        let Some(user) = self.ctx.user_repo().find_by_email(&request.email).await? else {
+            info!("Password reset requested for unknown email");
+            return Ok(());
+        };
+
+        let token = self
+            .ctx
+            .account_token_store()
+            .issue(
+                AccountTokenKind::PasswordReset,
+                &AccountTokenData::new(user.id, &user.email),
+            )
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+        let link = self.ctx.outbox().link("/reset-password", &token);
+        let body = format!(
+            "Hi {},\n\n\
+             Someone asked to reset the password for your account. If it was you, \
+             open this link within the next hour to choose a new password:\n\n\
+             {link}\n\n\
+             Or use this reset token: {token}\n\n\
+             If you did not ask for this, you can ignore this email.\n",
+            user.username
+        );
+
+        if let Err(e) = self
+            .ctx
+            .outbox()
+            .send(&user.email, "Reset your password", body)
+            .await
+        {
+            warn!(user_id = %user.id, error = %e, "Failed to send password reset email");
+            return Ok(());
+        }
+
+        info!(user_id = %user.id, "Password reset email sent");
+        Ok(())
+    }
+
+    /// Set a new password using a reset token
+    ///
+    /// Signs the user out everywhere: all refresh tokens are revoked and all
+    /// gateway sessions dropped, closing their open connections.
+    #[instrument(skip(self, request))]
+    pub async fn reset_password(&self, request: ResetPasswordRequest) -> ServiceResult<()> {
+        // Check the password first so a weak one does not use up the token
+        validate_password_strength(&request.password).map_err(ServiceError::from)?;
+
+        let data = self
+            .ctx
+            .account_token_store()
+            .consume(AccountTokenKind::PasswordReset, &request.token)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+            .ok_or_else(|| ServiceError::validation("Invalid or expired token"))?;
 
-        // Get user
         let user = self
             .ctx
             .user_repo()
-            .find_by_id(refresh_data.user_id)
+            .find_by_id(data.user_id)
             .await?
-            .ok_or_else(|| ServiceError::not_found("User", refresh_data.user_id.to_string()))?;
+            .filter(|user| user.email == data.email)
+            .ok_or_else(|| ServiceError::validation("Invalid or expired token"))?;
+
+        let password_hash =
+            hash_password(&request.password).map_err(|e| ServiceError::internal(e.to_string()))?;
+        self.ctx
+            .user_repo()
+            .update_password(user.id, &password_hash)
+            .await?;
 
-        // Revoke old refresh token
         self.ctx
             .refresh_token_store()
-            .revoke(&request.refresh_token)
+            .revoke_all_for_user(user.id)
             .await
             .map_err(|e| ServiceError::internal(e.to_string()))?;
+        SessionService::new(self.ctx)
+            .close_all_gateway_sessions(user.id)
+            .await?;
+
+        // Following the emailed link proves ownership of the address
+        self.ctx
+            .user_repo()
+            .mark_verified(user.id, &user.email)
+            .await?;
+
+        info!(user_id = %user.id, "Password reset");
+        Ok(())
+    }
+
+    /// Confirm the user's email address using a verification token
+    #[instrument(skip(self, request))]
+    pub async fn verify_email(&self, request: VerifyEmailRequest) -> ServiceResult<()> {
+        let data = self
+            .ctx
+            .account_token_store()
+            .consume(AccountTokenKind::EmailVerification, &request.token)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+            .ok_or_else(|| ServiceError::validation("Invalid or expired token"))?;
+
+        // Fails if the account is gone or the email changed since the link was sent
+        if !self
+            .ctx
+            .user_repo()
+            .mark_verified(data.user_id, &data.email)
+            .await?
+        {
+            return Err(ServiceError::validation("Invalid or expired token"));
+        }
+
+        info!(user_id = %data.user_id, "Email verified");
+        Ok(())
+    }
+
+    /// Send a new verification email to the current user
+    #[instrument(skip(self))]
+    pub async fn resend_verification_email(&self, user_id: Snowflake) -> ServiceResult<()> {
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+
+        if user.verified {
+            return Err(ServiceError::validation("Email is already verified"));
+        }
+
+        self.send_verification_email(&user).await
+    }
 
-        // Generate new tokens
+    /// Start a login session for a client
+    fn new_session(user_id: Snowflake, client: ClientInfo) -> RefreshTokenData {
+        let mut session = RefreshTokenData::new(user_id, Uuid::new_v4().to_string());
+        session.device_info = client.user_agent;
+        session.ip_address = client.ip_address;
+        session
+    }
+
+    /// Issue new tokens for the session of a claimed refresh token
+    async fn continue_session(&self, mut session: RefreshTokenData) -> ServiceResult<AuthResponse> {
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(session.user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", session.user_id.to_string()))?;
+
+        session.touch();
+        let response = self.issue_tokens(&user, session).await?;
+
+        info!(user_id = %user.id, "Tokens refreshed successfully");
+
+        Ok(response)
+    }
+
+    /// Generate a token pair for a login session and store its refresh token
+    ///
+    /// Both tokens carry the session ID, linking gateway connections to it.
+    async fn issue_tokens(
+        &self,
+        user: &User,
+        session: RefreshTokenData,
+    ) -> ServiceResult<AuthResponse> {
         let token_pair = self
             .ctx
             .jwt_service()
-            .generate_token_pair(user.id)
+            .generate_token_pair_with_session(user.id, Some(session.session_id.clone()))
             .map_err(|e| ServiceError::internal(e.to_string()))?;
 
-        // Store new refresh token
-        let session_id = Uuid::new_v4().to_string();
-        let new_refresh_data = RefreshTokenData::new(user.id, session_id);
+        // Store refresh token in Redis
         self.ctx
             .refresh_token_store()
-            .store(&token_pair.refresh_token, &new_refresh_data)
+            .store(&token_pair.refresh_token, &session)
             .await
             .map_err(|e| ServiceError::internal(e.to_string()))?;
 
-        info!(user_id = %user.id, "Tokens refreshed successfully");
-
         Ok(AuthResponse::new(
             token_pair.access_token,
             token_pair.refresh_token,
             token_pair.expires_in,
-            CurrentUserResponse::from(&user),
+            CurrentUserResponse::from(user),
         ))
     }
 
-    /// Logout user by revoking refresh token
-    #[instrument(skip(self, refresh_token))]
-    pub async fn logout(&self, user_id: Snowflake, refresh_token: Option<String>) -> ServiceResult<()> {
-        if let Some(token) = refresh_token {
-            // Revoke specific refresh token
-            self.ctx
-                .refresh_token_store()
-                .revoke(&token)
-                .await
-                .map_err(|e| ServiceError::internal(e.to_string()))?;
-        } else {
-            // Revoke all refresh tokens for user
-            self.ctx
-                .refresh_token_store()
-                .revoke_all_for_user(user_id)
-                .await
-                .map_err(|e| ServiceError::internal(e.to_string()))?;
-        }
+    /// Issue a verification token and email the link to the user
+    async fn send_verification_email(&self, user: &User) -> ServiceResult<()> {
+        let token = self
+            .ctx
+            .account_token_store()
+            .issue(
+                AccountTokenKind::EmailVerification,
+                &AccountTokenData::new(user.id, &user.email),
+            )
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
 
-        info!(user_id = %user_id, "User logged out successfully");
+        let link = self.ctx.outbox().link("/verify-email", &token);
+        let body = format!(
+            "Hi {},\n\n\
+             Please confirm your email address by opening this link within the next 24 hours:\n\n\
+             {link}\n\n\
+             Or use this verification token: {token}\n",
+            user.username
+        );
+
+        self.ctx
+            .outbox()
+            .send(&user.email, "Verify your email address", body)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+        info!(user_id = %user.id, "Verification email sent");
         Ok(())
     }
 
@@ -3,17 +3,25 @@
 //! Handles channel creation, management, and queries.
 
 use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::{Channel, ChannelType};
+use chat_core::entities::{
+    AuditLogAction, AuditLogEntry, Channel, ChannelType, OverwriteType, PermissionOverwrite,
+};
 use chat_core::{Permissions, Snowflake};
 use chrono::Utc;
-use serde_json::json;
+use serde_json::{json, Value};
 use tracing::{info, instrument};
 
-use crate::dto::{ChannelResponse, CreateChannelRequest, UpdateChannelRequest};
+use crate::dto::{
+    ChannelResponse, CreateChannelRequest, EditPermissionOverwriteRequest,
+    PermissionOverwriteResponse, UpdateChannelRequest,
+};
 
+use super::audit_log::AuditLogService;
 use super::context::ServiceContext;
+use super::dm::DmService;
 use super::error::{ServiceError, ServiceResult};
 use super::permission::PermissionService;
+use super::thread::ThreadService;
 
 /// Channel service
 pub struct ChannelService<'a> {
@@ -33,6 +41,7 @@ impl<'a> ChannelService<'a> {
         guild_id: Snowflake,
         user_id: Snowflake,
         request: CreateChannelRequest,
+        reason: Option<String>,
     ) -> ServiceResult<ChannelResponse> {
         // Check permissions
         let permission_service = PermissionService::new(self.ctx);
@@ -93,6 +102,8 @@ impl<'a> ChannelService<'a> {
             topic: request.topic,
             position,
             parent_id,
+            owner_id: None,
+            icon: None,
             created_at: now,
             updated_at: now,
         };
@@ -101,6 +112,17 @@ impl<'a> ChannelService<'a> {
 
         info!(channel_id = %channel_id, guild_id = %guild_id, "Channel created");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::ChannelCreate,
+                (channel_id, "channel"),
+                AuditLogEntry::diff(&Value::Null, &Self::audit_snapshot(&channel)),
+                reason,
+            )
+            .await;
+
         // Publish CHANNEL_CREATE event
         self.publish_channel_event("CHANNEL_CREATE", &channel).await;
 
@@ -140,10 +162,10 @@ impl<'a> ChannelService<'a> {
         let channel = self.get_channel_entity(channel_id).await?;
 
         // Check permissions for guild channels
-        if let Some(guild_id) = channel.guild_id {
+        if channel.guild_id.is_some() {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::VIEW_CHANNEL)
+                .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
                 .await?;
         } else {
             // DM channel - verify user is a recipient
@@ -163,6 +185,7 @@ impl<'a> ChannelService<'a> {
         channel_id: Snowflake,
         user_id: Snowflake,
         request: UpdateChannelRequest,
+        reason: Option<String>,
     ) -> ServiceResult<ChannelResponse> {
         let mut channel = self
             .ctx
@@ -171,6 +194,22 @@ impl<'a> ChannelService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
 
+        // Threads have their own permission rules
+        if channel.is_thread() {
+            ThreadService::new(self.ctx)
+                .update_thread(channel_id, user_id, request)
+                .await?;
+            return self.get_channel(channel_id).await;
+        }
+
+        // Group DMs are managed by their recipients
+        if channel.is_group_dm() {
+            DmService::new(self.ctx)
+                .update_group_dm(channel_id, user_id, request.name, request.icon)
+                .await?;
+            return self.get_channel(channel_id).await;
+        }
+
         // DM channels cannot be updated
         let guild_id = channel.guild_id.ok_or_else(|| {
             ServiceError::validation("DM channels cannot be updated")
@@ -182,6 +221,7 @@ impl<'a> ChannelService<'a> {
             .require_permission(guild_id, user_id, Permissions::MANAGE_CHANNELS)
             .await?;
 
+        let before = Self::audit_snapshot(&channel);
         let mut changed = false;
 
         // Update name
@@ -231,6 +271,17 @@ impl<'a> ChannelService<'a> {
             channel.updated_at = Utc::now();
             self.ctx.channel_repo().update(&channel).await?;
 
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::ChannelUpdate,
+                    (channel_id, "channel"),
+                    AuditLogEntry::diff(&before, &Self::audit_snapshot(&channel)),
+                    reason,
+                )
+                .await;
+
             // Publish CHANNEL_UPDATE event
             self.publish_channel_event("CHANNEL_UPDATE", &channel).await;
         }
@@ -244,6 +295,7 @@ impl<'a> ChannelService<'a> {
         &self,
         channel_id: Snowflake,
         user_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let channel = self
             .ctx
@@ -252,6 +304,19 @@ impl<'a> ChannelService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
 
+        if channel.is_thread() {
+            return ThreadService::new(self.ctx)
+                .delete_thread(channel_id, user_id)
+                .await;
+        }
+
+        // Deleting a group DM leaves it
+        if channel.is_group_dm() {
+            return DmService::new(self.ctx)
+                .remove_recipient(channel_id, user_id, user_id)
+                .await;
+        }
+
         // DM channels cannot be deleted
         let guild_id = channel.guild_id.ok_or_else(|| {
             ServiceError::validation("DM channels cannot be deleted")
@@ -262,11 +327,23 @@ impl<'a> ChannelService<'a> {
         permission_service
             .require_permission(guild_id, user_id, Permissions::MANAGE_CHANNELS)
             .await?;
+        permission_service.require_mfa(guild_id, user_id).await?;
 
         self.ctx.channel_repo().delete(channel_id).await?;
 
         info!(channel_id = %channel_id, guild_id = %guild_id, "Channel deleted");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::ChannelDelete,
+                (channel_id, "channel"),
+                AuditLogEntry::diff(&Self::audit_snapshot(&channel), &Value::Null),
+                reason,
+            )
+            .await;
+
         // Publish CHANNEL_DELETE event
         let event = PubSubEvent::new(
             "CHANNEL_DELETE",
@@ -299,20 +376,272 @@ impl<'a> ChannelService<'a> {
 
         let channels = self.ctx.channel_repo().find_by_guild(guild_id).await?;
 
-        // Filter channels based on VIEW_CHANNEL permission
-        let user_perms = permission_service
-            .get_member_permissions(guild_id, user_id)
+        // Filter channels based on VIEW_CHANNEL permission (after overwrites)
+        let permissions = permission_service
+            .compute_channels_permissions(guild_id, &channels, user_id)
             .await?;
-
-        let visible_channels: Vec<_> = channels
-            .into_iter()
-            .filter(|_| user_perms.has(Permissions::VIEW_CHANNEL))
+        let visible_channels = channels
+            .iter()
+            .filter(|channel| {
+                permissions
+                    .get(&channel.id)
+                    .is_some_and(|perms| perms.has(Permissions::VIEW_CHANNEL))
+            })
             .map(ChannelResponse::from)
             .collect();
 
         Ok(visible_channels)
     }
 
+    // ========================================================================
+    // Permission Overwrites
+    // ========================================================================
+
+    /// Get all permission overwrites for a channel
+    #[instrument(skip(self))]
+    pub async fn get_permission_overwrites(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<PermissionOverwriteResponse>> {
+        let (channel, _) = self.get_guild_channel(channel_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
+            .await?;
+
+        let overwrites = self
+            .ctx
+            .permission_overwrite_repo()
+            .find_by_channel(channel_id)
+            .await?;
+
+        Ok(overwrites.iter().map(PermissionOverwriteResponse::from).collect())
+    }
+
+    /// Get a single permission overwrite
+    #[instrument(skip(self))]
+    pub async fn get_permission_overwrite(
+        &self,
+        channel_id: Snowflake,
+        overwrite_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<PermissionOverwriteResponse> {
+        let (channel, _) = self.get_guild_channel(channel_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
+            .await?;
+
+        let overwrite = self
+            .ctx
+            .permission_overwrite_repo()
+            .find(channel_id, overwrite_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("PermissionOverwrite", overwrite_id.to_string()))?;
+
+        Ok(PermissionOverwriteResponse::from(&overwrite))
+    }
+
+    /// Create or replace a permission overwrite for a role or member
+    ///
+    /// Role overwrites target a role of the guild, @everyone included by its
+    /// role ID.
+    #[instrument(skip(self, request))]
+    pub async fn edit_permission_overwrite(
+        &self,
+        channel_id: Snowflake,
+        overwrite_id: Snowflake,
+        user_id: Snowflake,
+        request: EditPermissionOverwriteRequest,
+        reason: Option<String>,
+    ) -> ServiceResult<PermissionOverwriteResponse> {
+        let (channel, guild_id) = self.get_guild_channel(channel_id).await?;
+
+        // Threads are resolved with their parent channel's overwrites
+        if channel.is_thread() {
+            return Err(ServiceError::validation(
+                "Permission overwrites cannot be set on threads",
+            ));
+        }
+
+        let overwrite_type = OverwriteType::from_i16(request.overwrite_type)
+            .ok_or_else(|| ServiceError::validation("Invalid overwrite type"))?;
+
+        let allow = Self::parse_permissions(request.allow.as_deref())?;
+        let deny = Self::parse_permissions(request.deny.as_deref())?;
+
+        // Check permissions
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_ROLES)
+            .await?;
+
+        // Users can only allow or deny permissions they have in this channel
+        let actor_perms = permission_service
+            .compute_channel_permissions(&channel, user_id)
+            .await?;
+        if !actor_perms.contains(allow | deny) {
+            return Err(ServiceError::permission_denied(
+                ((allow | deny) - actor_perms).list().join(", "),
+            ));
+        }
+
+        // Verify the target belongs to this guild
+        match overwrite_type {
+            OverwriteType::Role => {
+                let role = self
+                    .ctx
+                    .role_repo()
+                    .find_by_id(overwrite_id)
+                    .await?
+                    .filter(|role| role.guild_id == guild_id);
+                if role.is_none() {
+                    return Err(ServiceError::not_found("Role", overwrite_id.to_string()));
+                }
+            }
+            OverwriteType::Member => {
+                if !self.ctx.member_repo().is_member(guild_id, overwrite_id).await? {
+                    return Err(ServiceError::not_found("Member", overwrite_id.to_string()));
+                }
+            }
+        }
+
+        let existing = self
+            .ctx
+            .permission_overwrite_repo()
+            .find(channel_id, overwrite_id)
+            .await?;
+        let before = Self::overwrite_audit_snapshot(existing.as_ref());
+
+        let overwrite = match existing {
+            Some(mut existing) => {
+                existing.overwrite_type = overwrite_type;
+                existing.set_permissions(allow, deny);
+                existing
+            }
+            None => PermissionOverwrite::new(channel_id, overwrite_id, overwrite_type, allow, deny),
+        };
+
+        self.ctx.permission_overwrite_repo().upsert(&overwrite).await?;
+
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::ChannelUpdate,
+                (channel_id, "channel"),
+                AuditLogEntry::diff(&before, &Self::overwrite_audit_snapshot(Some(&overwrite))),
+                reason,
+            )
+            .await;
+
+        info!(
+            channel_id = %channel_id,
+            target_id = %overwrite_id,
+            "Permission overwrite updated"
+        );
+
+        // Publish CHANNEL_UPDATE event
+        self.publish_channel_event("CHANNEL_UPDATE", &channel).await;
+
+        Ok(PermissionOverwriteResponse::from(&overwrite))
+    }
+
+    /// Delete a permission overwrite
+    #[instrument(skip(self))]
+    pub async fn delete_permission_overwrite(
+        &self,
+        channel_id: Snowflake,
+        overwrite_id: Snowflake,
+        user_id: Snowflake,
+        reason: Option<String>,
+    ) -> ServiceResult<()> {
+        let (channel, guild_id) = self.get_guild_channel(channel_id).await?;
+
+        // Check permissions
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_ROLES)
+            .await?;
+
+        let overwrite = self
+            .ctx
+            .permission_overwrite_repo()
+            .find(channel_id, overwrite_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("PermissionOverwrite", overwrite_id.to_string()))?;
+
+        self.ctx
+            .permission_overwrite_repo()
+            .delete(channel_id, overwrite_id)
+            .await?;
+
+        let before = Self::overwrite_audit_snapshot(Some(&overwrite));
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::ChannelUpdate,
+                (channel_id, "channel"),
+                AuditLogEntry::diff(&before, &Value::Null),
+                reason,
+            )
+            .await;
+
+        info!(
+            channel_id = %channel_id,
+            target_id = %overwrite_id,
+            "Permission overwrite deleted"
+        );
+
+        // Publish CHANNEL_UPDATE event
+        self.publish_channel_event("CHANNEL_UPDATE", &channel).await;
+
+        Ok(())
+    }
+
+    /// Load a channel and its guild ID, rejecting DM channels
+    async fn get_guild_channel(&self, channel_id: Snowflake) -> ServiceResult<(Channel, Snowflake)> {
+        let channel = self.get_channel_entity(channel_id).await?;
+        let guild_id = channel.guild_id.ok_or_else(|| {
+            ServiceError::validation("DM channels do not support permission overwrites")
+        })?;
+        Ok((channel, guild_id))
+    }
+
+    /// Parse an optional permission bitset string
+    fn parse_permissions(value: Option<&str>) -> ServiceResult<Permissions> {
+        value.map_or(Ok(Permissions::empty()), |perms_str| {
+            Permissions::parse(perms_str)
+                .map_err(|_| ServiceError::validation("Invalid permissions format"))
+        })
+    }
+
+    /// Fields of a channel tracked in the audit log
+    fn audit_snapshot(channel: &Channel) -> Value {
+        json!({
+            "name": channel.name,
+            "topic": channel.topic,
+            "position": channel.position,
+            "parent_id": channel.parent_id.map(|id| id.to_string())
+        })
+    }
+
+    /// Audit log view of a single permission overwrite, `Null` if absent
+    fn overwrite_audit_snapshot(overwrite: Option<&PermissionOverwrite>) -> Value {
+        overwrite.map_or(Value::Null, |overwrite| {
+            json!({
+                "overwrite_id": overwrite.target_id.to_string(),
+                "overwrite_type": overwrite.overwrite_type.as_i16(),
+                "allow": overwrite.allow.to_string(),
+                "deny": overwrite.deny.to_string()
+            })
+        })
+    }
+
     /// Helper to publish channel events
     async fn publish_channel_event(&self, event_type: &str, channel: &Channel) {
         if let Some(guild_id) = channel.guild_id {
@@ -323,7 +652,9 @@ impl<'a> ChannelService<'a> {
                 "type": match channel.channel_type {
                     ChannelType::GuildText => 0,
                     ChannelType::Dm => 1,
+                    ChannelType::GroupDm => 3,
                     ChannelType::GuildCategory => 4,
+                    ChannelType::GuildThread => 11,
                 },
                 "topic": channel.topic,
                 "position": channel.position,
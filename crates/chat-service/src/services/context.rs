@@ -4,14 +4,21 @@
 
 use std::sync::Arc;
 
-use chat_cache::{PresenceStore, Publisher, RefreshTokenStore, SharedRedisPool, WebSocketSessionStore};
-use chat_common::auth::JwtService;
+use chat_cache::{
+    AccountTokenStore, GatewayNodeStore, MfaStore, PresenceStore, Publisher, ReadStateStore,
+    RefreshTokenStore, SharedRedisPool, WebSocketSessionStore,
+};
+use chat_common::auth::{JwtService, TotpService};
 use chat_core::traits::{
-    AttachmentRepository, BanRepository, ChannelRepository, GuildRepository, InviteRepository,
-    MemberRepository, MessageRepository, ReactionRepository, RoleRepository, UserRepository,
+    AttachmentRepository, AuditLogRepository, BanRepository, ChannelRepository, GuildRepository,
+    InviteRepository, MemberRepository, MessageRepository, MessageRevisionRepository,
+    PermissionOverwriteRepository, PinRepository, ReactionRepository, ReadStateRepository,
+    RelationshipRepository, RoleRepository, ThreadRepository, UserRepository,
 };
 use chat_core::SnowflakeGenerator;
 use chat_db::PgPool;
+use chat_mail::Outbox;
+use chat_storage::FileStore;
 
 /// Service context containing all dependencies
 ///
@@ -19,9 +26,11 @@ use chat_db::PgPool;
 /// It provides access to:
 /// - Database repositories
 /// - Redis cache stores
-/// - JWT service for authentication
+/// - JWT and TOTP services for authentication
 /// - Snowflake generator for ID generation
 /// - Redis pub/sub for events
+/// - File store for attachments
+/// - Outbox for account email
 #[derive(Clone)]
 pub struct ServiceContext {
     // Database pool
@@ -41,17 +50,35 @@ pub struct ServiceContext {
     invite_repo: Arc<dyn InviteRepository>,
     ban_repo: Arc<dyn BanRepository>,
     attachment_repo: Arc<dyn AttachmentRepository>,
+    permission_overwrite_repo: Arc<dyn PermissionOverwriteRepository>,
+    audit_log_repo: Arc<dyn AuditLogRepository>,
+    thread_repo: Arc<dyn ThreadRepository>,
+    pin_repo: Arc<dyn PinRepository>,
+    message_revision_repo: Arc<dyn MessageRevisionRepository>,
+    read_state_repo: Arc<dyn ReadStateRepository>,
+    relationship_repo: Arc<dyn RelationshipRepository>,
 
     // Cache stores
     refresh_token_store: RefreshTokenStore,
+    account_token_store: AccountTokenStore,
+    mfa_store: MfaStore,
     session_store: WebSocketSessionStore,
+    gateway_node_store: GatewayNodeStore,
     presence_store: PresenceStore,
+    read_state_store: ReadStateStore,
 
     // Pub/Sub
     publisher: Publisher,
 
+    // File storage
+    file_store: Arc<FileStore>,
+
+    // Mail
+    outbox: Arc<Outbox>,
+
     // Services
     jwt_service: Arc<JwtService>,
+    totp_service: Arc<TotpService>,
     snowflake_generator: Arc<SnowflakeGenerator>,
 }
 
@@ -71,14 +98,28 @@ impl ServiceContext {
         invite_repo: Arc<dyn InviteRepository>,
         ban_repo: Arc<dyn BanRepository>,
         attachment_repo: Arc<dyn AttachmentRepository>,
+        permission_overwrite_repo: Arc<dyn PermissionOverwriteRepository>,
+        audit_log_repo: Arc<dyn AuditLogRepository>,
+        thread_repo: Arc<dyn ThreadRepository>,
+        pin_repo: Arc<dyn PinRepository>,
+        message_revision_repo: Arc<dyn MessageRevisionRepository>,
+        read_state_repo: Arc<dyn ReadStateRepository>,
+        relationship_repo: Arc<dyn RelationshipRepository>,
+        file_store: Arc<FileStore>,
+        outbox: Arc<Outbox>,
         jwt_service: Arc<JwtService>,
+        totp_service: Arc<TotpService>,
         snowflake_generator: Arc<SnowflakeGenerator>,
     ) -> Self {
         // Clone the inner RedisPool from the Arc
         let inner_pool = (*redis_pool).clone();
         let refresh_token_store = RefreshTokenStore::new(inner_pool.clone());
+        let account_token_store = AccountTokenStore::new(inner_pool.clone());
+        let mfa_store = MfaStore::new(inner_pool.clone());
         let session_store = WebSocketSessionStore::new(inner_pool.clone());
+        let gateway_node_store = GatewayNodeStore::new(inner_pool.clone());
         let presence_store = PresenceStore::new(inner_pool.clone());
+        let read_state_store = ReadStateStore::new(inner_pool.clone());
         let publisher = Publisher::new(inner_pool);
 
         Self {
@@ -94,11 +135,25 @@ impl ServiceContext {
             invite_repo,
             ban_repo,
             attachment_repo,
+            permission_overwrite_repo,
+            audit_log_repo,
+            thread_repo,
+            pin_repo,
+            message_revision_repo,
+            read_state_repo,
+            relationship_repo,
             refresh_token_store,
+            account_token_store,
+            mfa_store,
             session_store,
+            gateway_node_store,
             presence_store,
+            read_state_store,
             publisher,
+            file_store,
+            outbox,
             jwt_service,
+            totp_service,
             snowflake_generator,
         }
     }
@@ -167,6 +222,41 @@ impl ServiceContext {
         self.attachment_repo.as_ref()
     }
 
+    /// Get the permission overwrite repository
+    pub fn permission_overwrite_repo(&self) -> &dyn PermissionOverwriteRepository {
+        self.permission_overwrite_repo.as_ref()
+    }
+
+    /// Get the audit log repository
+    pub fn audit_log_repo(&self) -> &dyn AuditLogRepository {
+        self.audit_log_repo.as_ref()
+    }
+
+    /// Get the thread repository
+    pub fn thread_repo(&self) -> &dyn ThreadRepository {
+        self.thread_repo.as_ref()
+    }
+
+    /// Get the pin repository
+    pub fn pin_repo(&self) -> &dyn PinRepository {
+        self.pin_repo.as_ref()
+    }
+
+    /// Get the message revision repository
+    pub fn message_revision_repo(&self) -> &dyn MessageRevisionRepository {
+        self.message_revision_repo.as_ref()
+    }
+
+    /// Get the read state repository
+    pub fn read_state_repo(&self) -> &dyn ReadStateRepository {
+        self.read_state_repo.as_ref()
+    }
+
+    /// Get the relationship repository
+    pub fn relationship_repo(&self) -> &dyn RelationshipRepository {
+        self.relationship_repo.as_ref()
+    }
+
     // === Cache Stores ===
 
     /// Get the refresh token store
@@ -174,16 +264,36 @@ impl ServiceContext {
         &self.refresh_token_store
     }
 
+    /// Get the password reset and email verification token store
+    pub fn account_token_store(&self) -> &AccountTokenStore {
+        &self.account_token_store
+    }
+
+    /// Get the two-factor authentication store
+    pub fn mfa_store(&self) -> &MfaStore {
+        &self.mfa_store
+    }
+
     /// Get the WebSocket session store
     pub fn session_store(&self) -> &WebSocketSessionStore {
         &self.session_store
     }
 
+    /// Get the gateway node registry
+    pub fn gateway_node_store(&self) -> &GatewayNodeStore {
+        &self.gateway_node_store
+    }
+
     /// Get the presence store
     pub fn presence_store(&self) -> &PresenceStore {
         &self.presence_store
     }
 
+    /// Get the read state store
+    pub fn read_state_store(&self) -> &ReadStateStore {
+        &self.read_state_store
+    }
+
     // === Pub/Sub ===
 
     /// Get the Redis pub/sub publisher
@@ -191,6 +301,20 @@ impl ServiceContext {
         &self.publisher
     }
 
+    // === File Storage ===
+
+    /// Get the attachment file store
+    pub fn file_store(&self) -> &FileStore {
+        self.file_store.as_ref()
+    }
+
+    // === Mail ===
+
+    /// Get the outgoing mail outbox
+    pub fn outbox(&self) -> &Outbox {
+        self.outbox.as_ref()
+    }
+
     // === Services ===
 
     /// Get the JWT service
@@ -198,6 +322,11 @@ impl ServiceContext {
         self.jwt_service.as_ref()
     }
 
+    /// Get the TOTP service
+    pub fn totp_service(&self) -> &TotpService {
+        self.totp_service.as_ref()
+    }
+
     /// Get the snowflake ID generator
     pub fn snowflake_generator(&self) -> &SnowflakeGenerator {
         self.snowflake_generator.as_ref()
@@ -234,7 +363,17 @@ pub struct ServiceContextBuilder {
     invite_repo: Option<Arc<dyn InviteRepository>>,
     ban_repo: Option<Arc<dyn BanRepository>>,
     attachment_repo: Option<Arc<dyn AttachmentRepository>>,
+    permission_overwrite_repo: Option<Arc<dyn PermissionOverwriteRepository>>,
+    audit_log_repo: Option<Arc<dyn AuditLogRepository>>,
+    thread_repo: Option<Arc<dyn ThreadRepository>>,
+    pin_repo: Option<Arc<dyn PinRepository>>,
+    message_revision_repo: Option<Arc<dyn MessageRevisionRepository>>,
+    read_state_repo: Option<Arc<dyn ReadStateRepository>>,
+    relationship_repo: Option<Arc<dyn RelationshipRepository>>,
+    file_store: Option<Arc<FileStore>>,
+    outbox: Option<Arc<Outbox>>,
     jwt_service: Option<Arc<JwtService>>,
+    totp_service: Option<Arc<TotpService>>,
     snowflake_generator: Option<Arc<SnowflakeGenerator>>,
 }
 
@@ -253,7 +392,17 @@ impl ServiceContextBuilder {
             invite_repo: None,
             ban_repo: None,
             attachment_repo: None,
+            permission_overwrite_repo: None,
+            audit_log_repo: None,
+            thread_repo: None,
+            pin_repo: None,
+            message_revision_repo: None,
+            read_state_repo: None,
+            relationship_repo: None,
+            file_store: None,
+            outbox: None,
             jwt_service: None,
+            totp_service: None,
             snowflake_generator: None,
         }
     }
@@ -318,11 +467,61 @@ impl ServiceContextBuilder {
         self
     }
 
+    pub fn permission_overwrite_repo(mut self, repo: Arc<dyn PermissionOverwriteRepository>) -> Self {
+        self.permission_overwrite_repo = Some(repo);
+        self
+    }
+
+    pub fn audit_log_repo(mut self, repo: Arc<dyn AuditLogRepository>) -> Self {
+        self.audit_log_repo = Some(repo);
+        self
+    }
+
+    pub fn thread_repo(mut self, repo: Arc<dyn ThreadRepository>) -> Self {
+        self.thread_repo = Some(repo);
+        self
+    }
+
+    pub fn pin_repo(mut self, repo: Arc<dyn PinRepository>) -> Self {
+        self.pin_repo = Some(repo);
+        self
+    }
+
+    pub fn message_revision_repo(mut self, repo: Arc<dyn MessageRevisionRepository>) -> Self {
+        self.message_revision_repo = Some(repo);
+        self
+    }
+
+    pub fn read_state_repo(mut self, repo: Arc<dyn ReadStateRepository>) -> Self {
+        self.read_state_repo = Some(repo);
+        self
+    }
+
+    pub fn relationship_repo(mut self, repo: Arc<dyn RelationshipRepository>) -> Self {
+        self.relationship_repo = Some(repo);
+        self
+    }
+
+    pub fn file_store(mut self, store: Arc<FileStore>) -> Self {
+        self.file_store = Some(store);
+        self
+    }
+
+    pub fn outbox(mut self, outbox: Arc<Outbox>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
     pub fn jwt_service(mut self, service: Arc<JwtService>) -> Self {
         self.jwt_service = Some(service);
         self
     }
 
+    pub fn totp_service(mut self, service: Arc<TotpService>) -> Self {
+        self.totp_service = Some(service);
+        self
+    }
+
     pub fn snowflake_generator(mut self, generator: Arc<SnowflakeGenerator>) -> Self {
         self.snowflake_generator = Some(generator);
         self
@@ -346,7 +545,17 @@ impl ServiceContextBuilder {
             self.invite_repo.ok_or_else(|| super::error::ServiceError::validation("invite_repo is required"))?,
             self.ban_repo.ok_or_else(|| super::error::ServiceError::validation("ban_repo is required"))?,
             self.attachment_repo.ok_or_else(|| super::error::ServiceError::validation("attachment_repo is required"))?,
+            self.permission_overwrite_repo.ok_or_else(|| super::error::ServiceError::validation("permission_overwrite_repo is required"))?,
+            self.audit_log_repo.ok_or_else(|| super::error::ServiceError::validation("audit_log_repo is required"))?,
+            self.thread_repo.ok_or_else(|| super::error::ServiceError::validation("thread_repo is required"))?,
+            self.pin_repo.ok_or_else(|| super::error::ServiceError::validation("pin_repo is required"))?,
+            self.message_revision_repo.ok_or_else(|| super::error::ServiceError::validation("message_revision_repo is required"))?,
+            self.read_state_repo.ok_or_else(|| super::error::ServiceError::validation("read_state_repo is required"))?,
+            self.relationship_repo.ok_or_else(|| super::error::ServiceError::validation("relationship_repo is required"))?,
+            self.file_store.ok_or_else(|| super::error::ServiceError::validation("file_store is required"))?,
+            self.outbox.ok_or_else(|| super::error::ServiceError::validation("outbox is required"))?,
             self.jwt_service.ok_or_else(|| super::error::ServiceError::validation("jwt_service is required"))?,
+            self.totp_service.ok_or_else(|| super::error::ServiceError::validation("totp_service is required"))?,
             self.snowflake_generator.ok_or_else(|| super::error::ServiceError::validation("snowflake_generator is required"))?,
         ))
     }
@@ -1,9 +1,9 @@
 //! DM (Direct Message) service
 //!
-//! Handles direct message channel creation and management.
+//! Handles direct message and group DM channel creation and management.
 
-use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::{Channel, ChannelType};
+use chat_cache::PubSubEvent;
+use chat_core::entities::{Channel, ChannelType, User};
 use chat_core::Snowflake;
 use chrono::Utc;
 use serde_json::json;
@@ -13,6 +13,10 @@ use crate::dto::{DmChannelResponse, DmChannelWithRecipients};
 
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
+use super::relationship::RelationshipService;
+
+/// Maximum number of recipients in a group DM, including the owner
+pub const MAX_GROUP_DM_RECIPIENTS: usize = 10;
 
 /// DM service
 pub struct DmService<'a> {
@@ -45,6 +49,11 @@ impl<'a> DmService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("User", recipient_id.to_string()))?;
 
+        // Blocks and the recipient's privacy setting apply to existing DMs too
+        RelationshipService::new(self.ctx)
+            .require_can_dm(user_id, &recipient)
+            .await?;
+
         // Check if DM channel already exists between these users
         if let Some(existing_channel) = self
             .ctx
@@ -87,6 +96,8 @@ impl<'a> DmService<'a> {
             topic: None,
             position: 0,
             parent_id: None,
+            owner_id: None,
+            icon: None,
             created_at: now,
             updated_at: now,
         };
@@ -136,7 +147,7 @@ impl<'a> DmService<'a> {
         let event = PubSubEvent::new("CHANNEL_CREATE", channel_data);
         self.ctx
             .publisher()
-            .publish(&PubSubChannel::user(user_id), &event)
+            .publish_user(user_id, &event)
             .await
             .ok();
 
@@ -157,7 +168,7 @@ impl<'a> DmService<'a> {
         let recipient_event = PubSubEvent::new("CHANNEL_CREATE", recipient_channel_data);
         self.ctx
             .publisher()
-            .publish(&PubSubChannel::user(recipient_id), &recipient_event)
+            .publish_user(recipient_id, &recipient_event)
             .await
             .ok();
 
@@ -295,13 +306,365 @@ impl<'a> DmService<'a> {
         );
         self.ctx
             .publisher()
-            .publish(&PubSubChannel::user(user_id), &event)
+            .publish_user(user_id, &event)
             .await
             .ok();
 
         Ok(())
     }
 
+    /// Create a group DM owned by `owner_id`
+    #[instrument(skip(self))]
+    pub async fn create_group_dm(
+        &self,
+        owner_id: Snowflake,
+        recipient_ids: Vec<Snowflake>,
+        name: Option<String>,
+    ) -> ServiceResult<DmChannelResponse> {
+        let mut participant_ids = vec![owner_id];
+        for recipient_id in recipient_ids {
+            if !participant_ids.contains(&recipient_id) {
+                participant_ids.push(recipient_id);
+            }
+        }
+
+        if participant_ids.len() < 2 {
+            return Err(ServiceError::validation(
+                "Group DMs need at least one other recipient",
+            ));
+        }
+        if participant_ids.len() > MAX_GROUP_DM_RECIPIENTS {
+            return Err(ServiceError::validation(format!(
+                "Group DMs cannot have more than {MAX_GROUP_DM_RECIPIENTS} recipients"
+            )));
+        }
+
+        // Verify every recipient exists
+        let mut participants = Vec::with_capacity(participant_ids.len());
+        for &participant_id in &participant_ids {
+            let user = self
+                .ctx
+                .user_repo()
+                .find_by_id(participant_id)
+                .await?
+                .ok_or_else(|| ServiceError::not_found("User", participant_id.to_string()))?;
+            participants.push(user);
+        }
+
+        let relationships = RelationshipService::new(self.ctx);
+        for recipient in participants.iter().skip(1) {
+            relationships.require_can_dm(owner_id, recipient).await?;
+        }
+
+        let channel = Channel::new_group_dm(self.ctx.generate_id(), owner_id, name);
+        self.ctx.channel_repo().create(&channel).await?;
+
+        for &participant_id in &participant_ids {
+            self.ctx
+                .channel_repo()
+                .add_dm_recipient(channel.id, participant_id)
+                .await?;
+        }
+
+        info!(
+            channel_id = %channel.id,
+            owner_id = %owner_id,
+            recipients = participant_ids.len(),
+            "Group DM channel created"
+        );
+
+        for &participant_id in &participant_ids {
+            self.publish_channel_to("CHANNEL_CREATE", &channel, &participants, participant_id)
+                .await;
+        }
+
+        Ok(Self::dm_response(channel, &participants, owner_id))
+    }
+
+    /// Add a recipient to a group DM
+    ///
+    /// Any recipient can add users, up to [`MAX_GROUP_DM_RECIPIENTS`].
+    #[instrument(skip(self))]
+    pub async fn add_recipient(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        recipient_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let (channel, recipient_ids) = self.get_group_dm(channel_id, user_id).await?;
+
+        if recipient_ids.contains(&recipient_id) {
+            return Ok(());
+        }
+        if recipient_ids.len() >= MAX_GROUP_DM_RECIPIENTS {
+            return Err(ServiceError::validation(format!(
+                "Group DMs cannot have more than {MAX_GROUP_DM_RECIPIENTS} recipients"
+            )));
+        }
+
+        let recipient = self
+            .ctx
+            .user_repo()
+            .find_by_id(recipient_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", recipient_id.to_string()))?;
+
+        RelationshipService::new(self.ctx)
+            .require_can_dm(user_id, &recipient)
+            .await?;
+
+        self.ctx
+            .channel_repo()
+            .add_dm_recipient(channel_id, recipient_id)
+            .await?;
+
+        info!(
+            channel_id = %channel_id,
+            user_id = %user_id,
+            recipient_id = %recipient_id,
+            "Group DM recipient added"
+        );
+
+        let mut participants = self.find_users(&recipient_ids).await?;
+        participants.push(recipient.clone());
+
+        // The new recipient learns about the channel before the recipient event
+        self.publish_channel_to("CHANNEL_CREATE", &channel, &participants, recipient_id)
+            .await;
+
+        let notify: Vec<Snowflake> = participants.iter().map(|user| user.id).collect();
+        self.publish_recipient_event("CHANNEL_RECIPIENT_ADD", channel_id, &recipient, &notify)
+            .await;
+
+        Ok(())
+    }
+
+    /// Remove a recipient from a group DM
+    ///
+    /// Recipients can remove themselves; only the owner can remove others.
+    /// When the owner leaves, ownership passes to the longest-standing
+    /// recipient, and the channel is deleted once nobody is left.
+    #[instrument(skip(self))]
+    pub async fn remove_recipient(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        recipient_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let (mut channel, recipient_ids) = self.get_group_dm(channel_id, user_id).await?;
+
+        if recipient_id != user_id && channel.owner_id != Some(user_id) {
+            return Err(ServiceError::permission_denied(
+                "Only owner can remove recipients",
+            ));
+        }
+        if !recipient_ids.contains(&recipient_id) {
+            return Err(ServiceError::not_found("Recipient", recipient_id.to_string()));
+        }
+
+        self.ctx
+            .channel_repo()
+            .remove_dm_recipient(channel_id, recipient_id)
+            .await?;
+
+        info!(
+            channel_id = %channel_id,
+            user_id = %user_id,
+            recipient_id = %recipient_id,
+            "Group DM recipient removed"
+        );
+
+        let participants = self.find_users(&recipient_ids).await?;
+        if let Some(recipient) = participants.iter().find(|user| user.id == recipient_id) {
+            self.publish_recipient_event(
+                "CHANNEL_RECIPIENT_REMOVE",
+                channel_id,
+                recipient,
+                &recipient_ids,
+            )
+            .await;
+        }
+        self.publish_channel_to("CHANNEL_DELETE", &channel, &participants, recipient_id)
+            .await;
+
+        let remaining: Vec<Snowflake> = recipient_ids
+            .into_iter()
+            .filter(|&id| id != recipient_id)
+            .collect();
+
+        let Some(&next_owner) = remaining.first() else {
+            self.ctx.channel_repo().delete(channel_id).await?;
+            info!(channel_id = %channel_id, "Empty group DM channel deleted");
+            return Ok(());
+        };
+
+        if channel.owner_id == Some(recipient_id) {
+            channel.set_owner(next_owner);
+            self.ctx.channel_repo().update(&channel).await?;
+
+            info!(
+                channel_id = %channel_id,
+                old_owner = %recipient_id,
+                new_owner = %next_owner,
+                "Group DM ownership transferred"
+            );
+
+            let participants: Vec<User> = participants
+                .into_iter()
+                .filter(|user| user.id != recipient_id)
+                .collect();
+            for &participant_id in &remaining {
+                self.publish_channel_to("CHANNEL_UPDATE", &channel, &participants, participant_id)
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rename a group DM or change its icon
+    #[instrument(skip(self))]
+    pub async fn update_group_dm(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        name: Option<String>,
+        icon: Option<String>,
+    ) -> ServiceResult<DmChannelResponse> {
+        let (mut channel, recipient_ids) = self.get_group_dm(channel_id, user_id).await?;
+        let participants = self.find_users(&recipient_ids).await?;
+
+        if name.is_none() && icon.is_none() {
+            return Ok(Self::dm_response(channel, &participants, user_id));
+        }
+
+        if let Some(name) = name {
+            channel.name = Some(name);
+        }
+        if let Some(icon) = icon {
+            channel.icon = Some(icon);
+        }
+        channel.updated_at = Utc::now();
+        self.ctx.channel_repo().update(&channel).await?;
+
+        info!(channel_id = %channel_id, user_id = %user_id, "Group DM channel updated");
+
+        for &participant_id in &recipient_ids {
+            self.publish_channel_to("CHANNEL_UPDATE", &channel, &participants, participant_id)
+                .await;
+        }
+
+        Ok(Self::dm_response(channel, &participants, user_id))
+    }
+
+    /// Load a group DM and its recipients, checking that the user is one of them
+    async fn get_group_dm(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<(Channel, Vec<Snowflake>)> {
+        let channel = self
+            .ctx
+            .channel_repo()
+            .find_by_id(channel_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
+
+        if channel.guild_id.is_some() {
+            return Err(ServiceError::not_found("DM Channel", channel_id.to_string()));
+        }
+
+        let recipients = self
+            .ctx
+            .channel_repo()
+            .get_dm_recipients(channel_id)
+            .await?;
+
+        if !recipients.contains(&user_id) {
+            return Err(ServiceError::not_found("DM Channel", channel_id.to_string()));
+        }
+
+        if !channel.is_group_dm() {
+            return Err(ServiceError::validation("Channel is not a group DM"));
+        }
+
+        Ok((channel, recipients))
+    }
+
+    /// Load users by ID, skipping any that no longer exist
+    async fn find_users(&self, user_ids: &[Snowflake]) -> ServiceResult<Vec<User>> {
+        let mut users = Vec::with_capacity(user_ids.len());
+        for &user_id in user_ids {
+            if let Some(user) = self.ctx.user_repo().find_by_id(user_id).await? {
+                users.push(user);
+            }
+        }
+        Ok(users)
+    }
+
+    /// Build the channel as seen by one participant, who is left out of the recipients
+    fn dm_response(
+        channel: Channel,
+        participants: &[User],
+        viewer_id: Snowflake,
+    ) -> DmChannelResponse {
+        DmChannelResponse::from(DmChannelWithRecipients {
+            channel,
+            recipients: participants
+                .iter()
+                .filter(|user| user.id != viewer_id)
+                .cloned()
+                .collect(),
+            last_message_id: None,
+        })
+    }
+
+    /// Publish a channel event to one participant
+    async fn publish_channel_to(
+        &self,
+        event_type: &str,
+        channel: &Channel,
+        participants: &[User],
+        user_id: Snowflake,
+    ) {
+        let data = serde_json::to_value(Self::dm_response(channel.clone(), participants, user_id))
+            .unwrap_or_default();
+        let event = PubSubEvent::new(event_type, data);
+        self.ctx
+            .publisher()
+            .publish_user(user_id, &event)
+            .await
+            .ok();
+    }
+
+    /// Publish a recipient add or remove event to every participant
+    async fn publish_recipient_event(
+        &self,
+        event_type: &str,
+        channel_id: Snowflake,
+        recipient: &User,
+        participant_ids: &[Snowflake],
+    ) {
+        let data = json!({
+            "channel_id": channel_id.to_string(),
+            "user": {
+                "id": recipient.id.to_string(),
+                "username": recipient.username,
+                "discriminator": recipient.discriminator,
+                "avatar": recipient.avatar
+            }
+        });
+
+        let event = PubSubEvent::new(event_type, data);
+        for &participant_id in participant_ids {
+            self.ctx
+                .publisher()
+                .publish_user(participant_id, &event)
+                .await
+                .ok();
+        }
+    }
+
     /// Get DM channel between two specific users (internal use)
     pub async fn find_dm_between(
         &self,
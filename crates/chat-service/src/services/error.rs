@@ -4,6 +4,7 @@
 
 use chat_common::AppError;
 use chat_core::DomainError;
+use chat_storage::StorageError;
 use std::fmt;
 
 /// Service layer error type
@@ -139,6 +140,18 @@ impl From<AppError> for ServiceError {
     }
 }
 
+impl From<StorageError> for ServiceError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            StorageError::NotFound(key) => Self::not_found("File", key),
+            StorageError::TooLarge { .. } | StorageError::InvalidKey(_) => {
+                Self::Validation(err.to_string())
+            }
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
+
 impl From<ServiceError> for AppError {
     fn from(err: ServiceError) -> Self {
         match err {
@@ -193,6 +206,13 @@ mod tests {
         assert_eq!(err.error_code(), "CONFLICT");
     }
 
+    #[test]
+    fn test_storage_too_large_is_validation() {
+        let err = ServiceError::from(StorageError::TooLarge { max: 1024 });
+        assert_eq!(err.status_code(), 400);
+        assert_eq!(err.error_code(), "VALIDATION_ERROR");
+    }
+
     #[test]
     fn test_convert_to_app_error() {
         let service_err = ServiceError::not_found("Guild", "456");
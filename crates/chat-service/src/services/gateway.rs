@@ -0,0 +1,76 @@
+//! Gateway service
+//!
+//! Provides gateway connection info such as recommended shard counts.
+
+use chat_cache::SESSION_START_LIMIT;
+use chat_core::Snowflake;
+use tracing::instrument;
+
+use crate::dto::{GatewayBotResponse, SessionStartLimitResponse};
+
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+
+/// Guilds per recommended shard
+const GUILDS_PER_SHARD: usize = 1000;
+
+/// Shards that may Identify at the same time
+const MAX_CONCURRENCY: u32 = 1;
+
+/// Gateway service
+pub struct GatewayService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> GatewayService<'a> {
+    /// Create a new GatewayService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Get the gateway URL, recommended shard count and session start limits for a user
+    #[instrument(skip(self))]
+    pub async fn get_gateway_bot(
+        &self,
+        user_id: Snowflake,
+        url: String,
+    ) -> ServiceResult<GatewayBotResponse> {
+        let guilds = self.ctx.guild_repo().find_by_user(user_id).await?;
+
+        let (used, reset_after) = self
+            .ctx
+            .session_store()
+            .session_starts(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("Failed to load session starts: {e}")))?;
+
+        Ok(GatewayBotResponse {
+            url,
+            shards: recommended_shards(guilds.len()),
+            session_start_limit: SessionStartLimitResponse {
+                total: SESSION_START_LIMIT,
+                remaining: SESSION_START_LIMIT.saturating_sub(used),
+                reset_after: reset_after * 1000,
+                max_concurrency: MAX_CONCURRENCY,
+            },
+        })
+    }
+}
+
+/// One shard per [`GUILDS_PER_SHARD`] guilds, at least one
+fn recommended_shards(guild_count: usize) -> u32 {
+    u32::try_from(guild_count.div_ceil(GUILDS_PER_SHARD).max(1)).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommended_shards() {
+        assert_eq!(recommended_shards(0), 1);
+        assert_eq!(recommended_shards(1000), 1);
+        assert_eq!(recommended_shards(1001), 2);
+        assert_eq!(recommended_shards(25_000), 25);
+    }
+}
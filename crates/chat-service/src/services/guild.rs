@@ -3,16 +3,19 @@
 //! Handles guild (server) creation, management, and queries.
 
 use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::{Channel, Guild, GuildMember, Role};
+use chat_core::entities::{
+    AuditLogAction, AuditLogEntry, Channel, Guild, GuildMember, MfaLevel, Role,
+};
 use chat_core::{Permissions, Snowflake};
 use chrono::Utc;
-use serde_json::json;
+use serde_json::{json, Value};
 use tracing::{info, instrument};
 
 use crate::dto::{
     CreateGuildRequest, GuildResponse, GuildWithCounts, GuildWithCountsResponse, UpdateGuildRequest,
 };
 
+use super::audit_log::AuditLogService;
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
 use super::permission::PermissionService;
@@ -44,6 +47,7 @@ impl<'a> GuildService<'a> {
             icon: request.icon,
             description: request.description,
             owner_id,
+            mfa_level: MfaLevel::None,
             created_at: now,
             updated_at: now,
         };
@@ -132,6 +136,7 @@ impl<'a> GuildService<'a> {
         guild_id: Snowflake,
         user_id: Snowflake,
         request: UpdateGuildRequest,
+        reason: Option<String>,
     ) -> ServiceResult<GuildResponse> {
         // Check permissions
         let permission_service = PermissionService::new(self.ctx);
@@ -145,6 +150,7 @@ impl<'a> GuildService<'a> {
             .find_by_id(guild_id)
             .await?
             .ok_or_else(|| ServiceError::not_found("Guild", guild_id.to_string()))?;
+        let before = Self::audit_snapshot(&guild);
 
         let mut changed = false;
 
@@ -188,10 +194,50 @@ impl<'a> GuildService<'a> {
             info!(guild_id = %guild_id, old_owner = %user_id, new_owner = %new_owner, "Guild ownership transferred");
         }
 
+        // Change the two-factor requirement for moderators
+        if let Some(mfa_level) = request.mfa_level {
+            let mfa_level = MfaLevel::from_i16(mfa_level)
+                .ok_or_else(|| ServiceError::validation("Invalid MFA level"))?;
+
+            // Checked after any transfer above, so a former owner cannot change it
+            if !guild.is_owner(user_id) {
+                return Err(ServiceError::permission_denied("Only owner can change the MFA level"));
+            }
+
+            // The owner must meet the requirement they impose
+            if mfa_level == MfaLevel::Elevated {
+                let owner = self
+                    .ctx
+                    .user_repo()
+                    .find_by_id(user_id)
+                    .await?
+                    .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+                if !owner.mfa_enabled {
+                    return Err(ServiceError::validation(
+                        "Enable two-factor authentication before requiring it for moderators",
+                    ));
+                }
+            }
+
+            guild.mfa_level = mfa_level;
+            changed = true;
+        }
+
         if changed {
             guild.updated_at = Utc::now();
             self.ctx.guild_repo().update(&guild).await?;
 
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::GuildUpdate,
+                    (guild_id, "guild"),
+                    AuditLogEntry::diff(&before, &Self::audit_snapshot(&guild)),
+                    reason,
+                )
+                .await;
+
             // Publish GUILD_UPDATE event
             self.publish_guild_event("GUILD_UPDATE", &guild).await;
         }
@@ -289,6 +335,17 @@ impl<'a> GuildService<'a> {
         Ok(())
     }
 
+    /// Fields of a guild tracked in the audit log
+    fn audit_snapshot(guild: &Guild) -> Value {
+        json!({
+            "name": guild.name,
+            "icon": guild.icon,
+            "description": guild.description,
+            "owner_id": guild.owner_id.to_string(),
+            "mfa_level": guild.mfa_level.as_i16()
+        })
+    }
+
     /// Helper to publish guild events
     async fn publish_guild_event(&self, event_type: &str, guild: &Guild) {
         let data = json!({
@@ -297,6 +354,7 @@ impl<'a> GuildService<'a> {
             "icon": guild.icon,
             "description": guild.description,
             "owner_id": guild.owner_id.to_string(),
+            "mfa_level": guild.mfa_level.as_i16(),
             "created_at": guild.created_at.to_rfc3339()
         });
 
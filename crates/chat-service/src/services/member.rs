@@ -3,15 +3,17 @@
 //! Handles guild member management including adding, removing, and updating members.
 
 use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::GuildMember;
+use chat_core::entities::{AuditLogAction, AuditLogChange, AuditLogEntry, GuildMember};
 use chat_core::traits::Ban;
 use chat_core::{Permissions, Snowflake};
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use serde_json::json;
-use tracing::{info, instrument};
+use std::collections::BTreeMap;
+use tracing::{info, instrument, warn};
 
 use crate::dto::{BanResponse, CreateBanRequest, MemberResponse, MemberWithUser, UpdateMemberRequest, UserResponse};
 
+use super::audit_log::AuditLogService;
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
 use super::permission::PermissionService;
@@ -113,6 +115,7 @@ impl<'a> MemberService<'a> {
         target_id: Snowflake,
         actor_id: Snowflake,
         request: UpdateMemberRequest,
+        reason: Option<String>,
     ) -> ServiceResult<MemberResponse> {
         // Check if actor can manage this member
         let permission_service = PermissionService::new(self.ctx);
@@ -153,7 +156,9 @@ impl<'a> MemberService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("User", target_id.to_string()))?;
 
+        let old_roles = json!({ "roles": Self::role_id_strings(&member.role_ids) });
         let mut changed = false;
+        let mut roles_changed = false;
 
         // Update nickname
         if let Some(nickname) = request.nickname {
@@ -191,6 +196,7 @@ impl<'a> MemberService<'a> {
                     }
                 }
 
+                roles_changed = member.role_ids != new_roles;
                 member.role_ids = new_roles;
                 changed = true;
             }
@@ -202,6 +208,20 @@ impl<'a> MemberService<'a> {
 
             info!(guild_id = %guild_id, user_id = %target_id, "Member updated");
 
+            if roles_changed {
+                let new_roles = json!({ "roles": Self::role_id_strings(&member.role_ids) });
+                AuditLogService::new(self.ctx)
+                    .record(
+                        guild_id,
+                        actor_id,
+                        AuditLogAction::MemberRoleUpdate,
+                        (target_id, "user"),
+                        AuditLogEntry::diff(&old_roles, &new_roles),
+                        reason,
+                    )
+                    .await;
+            }
+
             // Publish GUILD_MEMBER_UPDATE event
             self.publish_member_event("GUILD_MEMBER_UPDATE", guild_id, &member, &user)
                 .await;
@@ -217,6 +237,7 @@ impl<'a> MemberService<'a> {
         guild_id: Snowflake,
         target_id: Snowflake,
         actor_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -224,6 +245,7 @@ impl<'a> MemberService<'a> {
         permission_service
             .require_permission(guild_id, actor_id, Permissions::KICK_MEMBERS)
             .await?;
+        permission_service.require_mfa(guild_id, actor_id).await?;
 
         // Check hierarchy
         if !permission_service
@@ -242,6 +264,17 @@ impl<'a> MemberService<'a> {
 
         info!(guild_id = %guild_id, user_id = %target_id, actor_id = %actor_id, "Member kicked");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                actor_id,
+                AuditLogAction::MemberKick,
+                (target_id, "user"),
+                Vec::new(),
+                reason,
+            )
+            .await;
+
         // Publish GUILD_MEMBER_REMOVE event
         let event = PubSubEvent::new(
             "GUILD_MEMBER_REMOVE",
@@ -299,6 +332,7 @@ impl<'a> MemberService<'a> {
         target_id: Snowflake,
         role_id: Snowflake,
         actor_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -322,6 +356,9 @@ impl<'a> MemberService<'a> {
 
         info!(guild_id = %guild_id, user_id = %target_id, role_id = %role_id, "Role added to member");
 
+        self.record_role_change(guild_id, target_id, actor_id, "$add", role_id, reason)
+            .await;
+
         Ok(())
     }
 
@@ -333,6 +370,7 @@ impl<'a> MemberService<'a> {
         target_id: Snowflake,
         role_id: Snowflake,
         actor_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -356,6 +394,9 @@ impl<'a> MemberService<'a> {
 
         info!(guild_id = %guild_id, user_id = %target_id, role_id = %role_id, "Role removed from member");
 
+        self.record_role_change(guild_id, target_id, actor_id, "$remove", role_id, reason)
+            .await;
+
         Ok(())
     }
 
@@ -371,6 +412,7 @@ impl<'a> MemberService<'a> {
         target_id: Snowflake,
         actor_id: Snowflake,
         request: CreateBanRequest,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -378,12 +420,24 @@ impl<'a> MemberService<'a> {
         permission_service
             .require_permission(guild_id, actor_id, Permissions::BAN_MEMBERS)
             .await?;
+        permission_service.require_mfa(guild_id, actor_id).await?;
+
+        // Verify user exists
+        self.ctx
+            .user_repo()
+            .find_by_id(target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", target_id.to_string()))?;
 
         // Cannot ban the owner
         if permission_service.is_guild_owner(guild_id, target_id).await? {
             return Err(ServiceError::conflict("Cannot ban the guild owner"));
         }
 
+        if self.ctx.ban_repo().is_banned(guild_id, target_id).await? {
+            return Err(ServiceError::conflict("User is already banned"));
+        }
+
         // Check hierarchy
         if self.ctx.member_repo().is_member(guild_id, target_id).await?
             && !permission_service
@@ -393,11 +447,12 @@ impl<'a> MemberService<'a> {
                 return Err(ServiceError::permission_denied("Cannot ban this member"));
             }
 
-        // Create ban record
+        // Create ban record, preferring the reason given in the body
+        let reason = request.reason.or(reason);
         let ban = Ban {
             guild_id,
             user_id: target_id,
-            reason: request.reason,
+            reason: reason.clone(),
         };
 
         self.ctx.ban_repo().create(&ban).await?;
@@ -409,19 +464,34 @@ impl<'a> MemberService<'a> {
 
         info!(guild_id = %guild_id, user_id = %target_id, actor_id = %actor_id, "User banned");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                actor_id,
+                AuditLogAction::MemberBan,
+                (target_id, "user"),
+                Vec::new(),
+                reason,
+            )
+            .await;
+
+        // Purge recent messages; the ban stands even if this fails
+        if request.delete_message_days > 0 {
+            if let Err(e) = self
+                .purge_member_messages(guild_id, target_id, request.delete_message_days)
+                .await
+            {
+                warn!(
+                    guild_id = %guild_id,
+                    user_id = %target_id,
+                    error = %e,
+                    "Failed to delete banned user's messages"
+                );
+            }
+        }
+
         // Publish GUILD_BAN_ADD event
-        let event = PubSubEvent::new(
-            "GUILD_BAN_ADD",
-            json!({
-                "guild_id": guild_id.to_string(),
-                "user_id": target_id.to_string()
-            }),
-        );
-        self.ctx
-            .publisher()
-            .publish(&PubSubChannel::guild(guild_id), &event)
-            .await
-            .ok();
+        self.publish_ban_event("GUILD_BAN_ADD", guild_id, target_id).await;
 
         Ok(())
     }
@@ -433,6 +503,7 @@ impl<'a> MemberService<'a> {
         guild_id: Snowflake,
         user_id: Snowflake,
         actor_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -450,23 +521,58 @@ impl<'a> MemberService<'a> {
 
         info!(guild_id = %guild_id, user_id = %user_id, actor_id = %actor_id, "User unbanned");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                actor_id,
+                AuditLogAction::MemberUnban,
+                (user_id, "user"),
+                Vec::new(),
+                reason,
+            )
+            .await;
+
         // Publish GUILD_BAN_REMOVE event
-        let event = PubSubEvent::new(
-            "GUILD_BAN_REMOVE",
-            json!({
-                "guild_id": guild_id.to_string(),
-                "user_id": user_id.to_string()
-            }),
-        );
-        self.ctx
-            .publisher()
-            .publish(&PubSubChannel::guild(guild_id), &event)
-            .await
-            .ok();
+        self.publish_ban_event("GUILD_BAN_REMOVE", guild_id, user_id).await;
 
         Ok(())
     }
 
+    /// Get a single ban in a guild
+    #[instrument(skip(self))]
+    pub async fn get_guild_ban(
+        &self,
+        guild_id: Snowflake,
+        target_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<BanResponse> {
+        let permission_service = PermissionService::new(self.ctx);
+
+        // Check BAN_MEMBERS permission
+        permission_service
+            .require_permission(guild_id, user_id, Permissions::BAN_MEMBERS)
+            .await?;
+
+        let ban = self
+            .ctx
+            .ban_repo()
+            .find(guild_id, target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Ban", format!("{guild_id}/{target_id}")))?;
+
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", target_id.to_string()))?;
+
+        Ok(BanResponse {
+            user: UserResponse::from(&user),
+            reason: ban.reason,
+        })
+    }
+
     /// Get all bans in a guild
     #[instrument(skip(self))]
     pub async fn get_guild_bans(
@@ -497,6 +603,115 @@ impl<'a> MemberService<'a> {
         Ok(responses)
     }
 
+    /// Delete a user's messages in all guild channels from the last `days` days
+    async fn purge_member_messages(
+        &self,
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        days: i32,
+    ) -> ServiceResult<()> {
+        let since = Utc::now() - Duration::days(i64::from(days));
+        let deleted = self
+            .ctx
+            .message_repo()
+            .delete_by_author_in_guild(guild_id, user_id, since)
+            .await?;
+
+        info!(
+            guild_id = %guild_id,
+            user_id = %user_id,
+            count = deleted.len(),
+            "Banned user's messages deleted"
+        );
+
+        // Group deleted message IDs by channel
+        let mut by_channel: BTreeMap<Snowflake, Vec<String>> = BTreeMap::new();
+        for message in deleted {
+            by_channel
+                .entry(message.channel_id)
+                .or_default()
+                .push(message.id.to_string());
+        }
+
+        // Publish MESSAGE_DELETE_BULK event per channel
+        for (channel_id, ids) in by_channel {
+            let event = PubSubEvent::new(
+                "MESSAGE_DELETE_BULK",
+                json!({
+                    "ids": ids,
+                    "channel_id": channel_id.to_string(),
+                    "guild_id": guild_id.to_string()
+                }),
+            );
+            self.ctx
+                .publisher()
+                .publish(&PubSubChannel::channel(channel_id), &event)
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    /// Record a single role being added to or removed from a member
+    async fn record_role_change(
+        &self,
+        guild_id: Snowflake,
+        target_id: Snowflake,
+        actor_id: Snowflake,
+        key: &str,
+        role_id: Snowflake,
+        reason: Option<String>,
+    ) {
+        let change = AuditLogChange {
+            key: key.to_string(),
+            old_value: None,
+            new_value: Some(json!([role_id.to_string()])),
+        };
+
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                actor_id,
+                AuditLogAction::MemberRoleUpdate,
+                (target_id, "user"),
+                vec![change],
+                reason,
+            )
+            .await;
+    }
+
+    /// Role IDs as strings for audit log snapshots
+    fn role_id_strings(role_ids: &[Snowflake]) -> Vec<String> {
+        role_ids.iter().map(ToString::to_string).collect()
+    }
+
+    /// Helper to publish ban events
+    async fn publish_ban_event(&self, event_type: &str, guild_id: Snowflake, user_id: Snowflake) {
+        let user = match self.ctx.user_repo().find_by_id(user_id).await {
+            Ok(Some(user)) => json!({
+                "id": user.id.to_string(),
+                "username": user.username,
+                "discriminator": user.discriminator,
+                "avatar": user.avatar
+            }),
+            _ => json!({ "id": user_id.to_string() }),
+        };
+
+        let event = PubSubEvent::new(
+            event_type,
+            json!({
+                "guild_id": guild_id.to_string(),
+                "user": user
+            }),
+        );
+        self.ctx
+            .publisher()
+            .publish(&PubSubChannel::guild(guild_id), &event)
+            .await
+            .ok();
+    }
+
     /// Helper to publish member events
     async fn publish_member_event(
         &self,
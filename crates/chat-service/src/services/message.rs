@@ -2,21 +2,49 @@
 //!
 //! Handles message creation, editing, deletion, and queries.
 
+use std::collections::{HashMap, HashSet};
+
 use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::{Channel, Message};
-use chat_core::traits::MessageQuery;
-use chat_core::{Permissions, Snowflake};
-use chrono::Utc;
-use serde_json::json;
+use chat_core::entities::{
+    Attachment, AuditLogAction, AuditLogEntry, Channel, DmPrivacy, Message, MessageType, User,
+};
+use chat_core::traits::{MessageQuery, MessageRevision, MessageSearchQuery, Pin};
+use chat_core::{MessageMentions, Permissions, Snowflake};
+use chrono::{DateTime, SubsecRound, Utc};
+use serde_json::{json, Value};
 use tracing::{info, instrument, warn};
 
 use crate::dto::{
-    CreateMessageRequest, MessageResponse, MessageWithDetails, UpdateMessageRequest, UserResponse,
+    AllowedMentionType, AllowedMentions, CreateMessageRequest, MessageResponse,
+    MessageRevisionResponse, MessageSearchHitResponse, MessageSearchResponse, MessageWithDetails,
+    UpdateMessageRequest, UserResponse,
 };
 
+use super::attachment::{AttachmentService, PendingAttachment, MAX_ATTACHMENTS_PER_MESSAGE};
+use super::audit_log::AuditLogService;
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
 use super::permission::PermissionService;
+use super::relationship::RelationshipService;
+use super::thread::ThreadService;
+
+/// Maximum number of search hits per page
+const MAX_SEARCH_LIMIT: i64 = 25;
+
+/// Deepest offset a search may page to
+const MAX_SEARCH_OFFSET: i64 = 5000;
+
+/// Maximum length of a full-text search query
+const MAX_SEARCH_QUERY_LENGTH: usize = 1024;
+
+/// Number of messages shown on each side of a search hit
+const SEARCH_CONTEXT_SIZE: i64 = 2;
+
+/// Maximum number of pinned messages per channel
+pub const MAX_PINS_PER_CHANNEL: i64 = 50;
+
+/// Page size used when collecting members reached by role or @everyone mentions
+const MENTION_MEMBER_PAGE_SIZE: i64 = 1000;
 
 /// Message service
 pub struct MessageService<'a> {
@@ -37,29 +65,50 @@ impl<'a> MessageService<'a> {
         author_id: Snowflake,
         request: CreateMessageRequest,
     ) -> ServiceResult<MessageResponse> {
-        // Get channel and verify access
+        self.create_message_with_attachments(channel_id, author_id, request, Vec::new())
+            .await
+    }
+
+    /// Verify a user may upload attachments to a channel
+    ///
+    /// Called before any file is streamed so rejected uploads never hit storage.
+    #[instrument(skip(self))]
+    pub async fn verify_can_attach(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<()> {
+        self.verify_send_access(channel_id, user_id, true).await?;
+        Ok(())
+    }
+
+    /// Create a new message with previously uploaded attachments
+    #[instrument(skip(self, request, uploads))]
+    pub async fn create_message_with_attachments(
+        &self,
+        channel_id: Snowflake,
+        author_id: Snowflake,
+        request: CreateMessageRequest,
+        uploads: Vec<PendingAttachment>,
+    ) -> ServiceResult<MessageResponse> {
         let channel = self
-            .ctx
-            .channel_repo()
-            .find_by_id(channel_id)
-            .await?
-            .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
+            .verify_send_access(channel_id, author_id, !uploads.is_empty())
+            .await?;
 
-        // Check permissions based on channel type
-        if let Some(guild_id) = channel.guild_id {
-            let permission_service = PermissionService::new(self.ctx);
-            permission_service
-                .require_permission(guild_id, author_id, Permissions::VIEW_CHANNEL)
-                .await?;
-            permission_service
-                .require_permission(guild_id, author_id, Permissions::SEND_MESSAGES)
-                .await?;
-        } else {
-            // DM channel - verify user is a recipient
-            let recipients = self.ctx.channel_repo().get_dm_recipients(channel_id).await?;
-            if !recipients.contains(&author_id) {
-                return Err(ServiceError::not_found("Channel", channel_id.to_string()));
-            }
+        if request.content.trim().is_empty() && uploads.is_empty() {
+            return Err(ServiceError::validation(
+                "Message must have content or attachments",
+            ));
+        }
+        if uploads.len() > MAX_ATTACHMENTS_PER_MESSAGE {
+            return Err(ServiceError::validation(format!(
+                "Cannot attach more than {MAX_ATTACHMENTS_PER_MESSAGE} files"
+            )));
+        }
+        if uploads.iter().any(|upload| upload.channel_id != channel_id) {
+            return Err(ServiceError::validation(
+                "Attachments were uploaded to a different channel",
+            ));
         }
 
         // Parse message reference if replying
@@ -86,6 +135,15 @@ impl<'a> MessageService<'a> {
             None
         };
 
+        let (mentions, mentioned_users) = self
+            .resolve_mentions(
+                &channel,
+                author_id,
+                &request.content,
+                request.allowed_mentions.as_ref(),
+            )
+            .await?;
+
         let message_id = self.ctx.generate_id();
         let now = Utc::now();
 
@@ -94,13 +152,23 @@ impl<'a> MessageService<'a> {
             channel_id,
             author_id,
             content: request.content,
+            message_type: MessageType::Default,
             created_at: now,
             edited_at: None,
             reference_id,
+            mentions,
         };
 
         self.ctx.message_repo().create(&message).await?;
 
+        let attachment_service = AttachmentService::new(self.ctx);
+        let mut attachments = Vec::with_capacity(uploads.len());
+        for upload in &uploads {
+            let attachment = attachment_service.attach(upload, message_id);
+            self.ctx.attachment_repo().create(&attachment).await?;
+            attachments.push(attachment);
+        }
+
         // Get author for response
         let author = self
             .ctx
@@ -109,18 +177,36 @@ impl<'a> MessageService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("User", author_id.to_string()))?;
 
-        info!(message_id = %message_id, channel_id = %channel_id, "Message created");
+        info!(
+            message_id = %message_id,
+            channel_id = %channel_id,
+            attachments = attachments.len(),
+            "Message created"
+        );
+
+        if channel.is_thread() {
+            ThreadService::new(self.ctx)
+                .record_message(&channel, author_id)
+                .await?;
+        }
 
         // Publish MESSAGE_CREATE event
-        self.publish_message_create(&channel, &message, &author).await;
+        let data =
+            Self::message_create_data(&channel, &message, &author, &attachments, &mentioned_users);
+        self.publish_message_create(&channel, data.clone()).await;
+
+        if let Err(e) = self.notify_mentions(&channel, &message, data).await {
+            warn!(message_id = %message_id, error = %e, "Failed to notify mentioned users");
+        }
 
         Ok(MessageResponse::from(MessageWithDetails {
             message,
             author,
             guild_id: channel.guild_id,
-            attachments: vec![],
+            attachments,
             reactions: vec![],
             reference: None,
+            mentioned_users,
         }))
     }
 
@@ -184,6 +270,8 @@ impl<'a> MessageService<'a> {
             result
         };
 
+        let mentioned_users = self.load_mentioned_users(&message).await?;
+
         Ok(MessageResponse::from(MessageWithDetails {
             message,
             author,
@@ -191,6 +279,7 @@ impl<'a> MessageService<'a> {
             attachments,
             reactions,
             reference: None,
+            mentioned_users,
         }))
     }
 
@@ -217,15 +306,39 @@ impl<'a> MessageService<'a> {
             return Err(ServiceError::permission_denied("Can only edit own messages"));
         }
 
+        if message.is_system() {
+            return Err(ServiceError::validation("System messages cannot be edited"));
+        }
+
         // Verify message is in this channel
         if message.channel_id != channel_id {
             return Err(ServiceError::not_found("Message", message_id.to_string()));
         }
 
-        message.content = request.content;
-        message.edited_at = Some(Utc::now());
+        let previous_edited_at = message.edited_at;
+        // Match the stored precision so the timestamp clients see is the one saved
+        let now = Utc::now().trunc_subsecs(6);
 
-        self.ctx.message_repo().update(&message).await?;
+        // Edits re-resolve mentions but never notify again
+        let (mentions, mentioned_users) = self
+            .resolve_mentions(&channel, user_id, &request.content, None)
+            .await?;
+        message.mentions = mentions;
+
+        // Keep the replaced content so moderators can review edits
+        let revision = (message.content != request.content).then(|| MessageRevision {
+            id: self.ctx.generate_id(),
+            message_id,
+            content: std::mem::replace(&mut message.content, request.content),
+            edited_at: previous_edited_at,
+            replaced_at: now,
+        });
+        message.edited_at = Some(now);
+
+        self.ctx
+            .message_repo()
+            .update(&message, revision.as_ref())
+            .await?;
 
         // Get author for response
         let author = self
@@ -238,7 +351,8 @@ impl<'a> MessageService<'a> {
         info!(message_id = %message_id, "Message updated");
 
         // Publish MESSAGE_UPDATE event
-        self.publish_message_update(&channel, &message).await;
+        self.publish_message_update(&channel, &message, &mentioned_users, previous_edited_at)
+            .await;
 
         Ok(MessageResponse::from(MessageWithDetails {
             message,
@@ -247,9 +361,44 @@ impl<'a> MessageService<'a> {
             attachments: vec![],
             reactions: vec![],
             reference: None,
+            mentioned_users,
         }))
     }
 
+    /// Get earlier versions of an edited message, oldest first
+    #[instrument(skip(self))]
+    pub async fn get_message_revisions(
+        &self,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<MessageRevisionResponse>> {
+        let channel = self.verify_channel_access(channel_id, user_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
+            .await?;
+
+        self.ctx
+            .message_repo()
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or_else(|| ServiceError::not_found("Message", message_id.to_string()))?;
+
+        let revisions = self
+            .ctx
+            .message_revision_repo()
+            .find_by_message(message_id)
+            .await?;
+
+        Ok(revisions
+            .into_iter()
+            .map(MessageRevisionResponse::from)
+            .collect())
+    }
+
     /// Delete message
     #[instrument(skip(self))]
     pub async fn delete_message(
@@ -257,6 +406,7 @@ impl<'a> MessageService<'a> {
         channel_id: Snowflake,
         message_id: Snowflake,
         user_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let channel = self.verify_channel_access(channel_id, user_id).await?;
 
@@ -274,14 +424,18 @@ impl<'a> MessageService<'a> {
 
         // Can delete if:
         // 1. Own message
-        // 2. Has MANAGE_MESSAGES permission in guild
+        // 2. Has MANAGE_MESSAGES permission in the channel
         let can_delete = if message.author_id == user_id {
             true
         } else if let Some(guild_id) = channel.guild_id {
             let permission_service = PermissionService::new(self.ctx);
-            permission_service
-                .check_permission(guild_id, user_id, Permissions::MANAGE_MESSAGES)
-                .await?
+            let allowed = permission_service
+                .check_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
+                .await?;
+            if allowed {
+                permission_service.require_mfa(guild_id, user_id).await?;
+            }
+            allowed
         } else {
             // DM - can only delete own messages
             false
@@ -291,10 +445,34 @@ impl<'a> MessageService<'a> {
             return Err(ServiceError::permission_denied("MANAGE_MESSAGES"));
         }
 
+        let attachments = self.ctx.attachment_repo().find_by_message(message_id).await?;
+
         self.ctx.message_repo().delete(message_id).await?;
 
+        AttachmentService::new(self.ctx)
+            .delete_attachments(channel_id, &attachments)
+            .await;
+
         info!(message_id = %message_id, "Message deleted");
 
+        // Only moderator deletions are audited
+        if let Some(guild_id) = channel.guild_id.filter(|_| message.author_id != user_id) {
+            let snapshot = json!({
+                "channel_id": channel_id.to_string(),
+                "author_id": message.author_id.to_string()
+            });
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::MessageDelete,
+                    (message_id, "message"),
+                    AuditLogEntry::diff(&snapshot, &Value::Null),
+                    reason,
+                )
+                .await;
+        }
+
         // Publish MESSAGE_DELETE event
         self.publish_message_delete(&channel, message_id).await;
 
@@ -319,8 +497,9 @@ impl<'a> MessageService<'a> {
         // Check MANAGE_MESSAGES permission
         let permission_service = PermissionService::new(self.ctx);
         permission_service
-            .require_permission(guild_id, user_id, Permissions::MANAGE_MESSAGES)
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
             .await?;
+        permission_service.require_mfa(guild_id, user_id).await?;
 
         // Parse message IDs
         let snowflake_ids: Result<Vec<_>, _> = message_ids
@@ -333,12 +512,30 @@ impl<'a> MessageService<'a> {
             .collect();
         let snowflake_ids = snowflake_ids?;
 
+        // Only attachments of this channel's messages go with them
+        let mut attachments = Vec::new();
+        for id in &snowflake_ids {
+            let in_channel = self
+                .ctx
+                .message_repo()
+                .find_by_id(*id)
+                .await?
+                .is_some_and(|m| m.channel_id == channel_id);
+            if in_channel {
+                attachments.extend(self.ctx.attachment_repo().find_by_message(*id).await?);
+            }
+        }
+
         let deleted_count = self
             .ctx
             .message_repo()
             .bulk_delete(channel_id, &snowflake_ids)
             .await?;
 
+        AttachmentService::new(self.ctx)
+            .delete_attachments(channel_id, &attachments)
+            .await;
+
         info!(
             channel_id = %channel_id,
             count = deleted_count,
@@ -363,6 +560,166 @@ impl<'a> MessageService<'a> {
         Ok(deleted_count)
     }
 
+    /// Get pinned messages in a channel, most recently pinned first
+    #[instrument(skip(self))]
+    pub async fn get_pinned_messages(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<MessageResponse>> {
+        let channel = self.verify_channel_access(channel_id, user_id).await?;
+
+        let pins = self.ctx.pin_repo().find_by_channel(channel_id).await?;
+        let mut messages = Vec::with_capacity(pins.len());
+        for pin in pins {
+            if let Some(message) = self.ctx.message_repo().find_by_id(pin.message_id).await? {
+                messages.push(message);
+            }
+        }
+
+        self.build_responses(messages, channel.guild_id, user_id).await
+    }
+
+    /// Pin a message in its channel
+    #[instrument(skip(self))]
+    pub async fn pin_message(
+        &self,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+        reason: Option<String>,
+    ) -> ServiceResult<()> {
+        let channel = self.verify_channel_access(channel_id, user_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
+            .await?;
+
+        let message = self
+            .ctx
+            .message_repo()
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or_else(|| ServiceError::not_found("Message", message_id.to_string()))?;
+
+        if message.is_system() {
+            return Err(ServiceError::validation("System messages cannot be pinned"));
+        }
+
+        let pin = Pin {
+            channel_id,
+            message_id,
+            pinned_by: user_id,
+            pinned_at: Utc::now(),
+        };
+
+        // Pinning is idempotent
+        if !self
+            .ctx
+            .pin_repo()
+            .create(&pin, MAX_PINS_PER_CHANNEL)
+            .await?
+        {
+            return Ok(());
+        }
+
+        info!(channel_id = %channel_id, message_id = %message_id, "Message pinned");
+
+        if let Some(guild_id) = channel.guild_id {
+            let snapshot = json!({
+                "channel_id": channel_id.to_string(),
+                "message_id": message_id.to_string()
+            });
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::MessagePin,
+                    (message_id, "message"),
+                    AuditLogEntry::diff(&Value::Null, &snapshot),
+                    reason,
+                )
+                .await;
+        }
+
+        // Announce the pin in the channel
+        let notice =
+            Message::new_pin_notice(self.ctx.generate_id(), channel_id, user_id, message_id);
+        self.ctx.message_repo().create(&notice).await?;
+
+        let author = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+        self.publish_message_create(
+            &channel,
+            Self::message_create_data(&channel, &notice, &author, &[], &[]),
+        )
+        .await;
+
+        self.publish_channel_pins_update(&channel, Some(pin.pinned_at))
+            .await;
+
+        Ok(())
+    }
+
+    /// Unpin a message
+    #[instrument(skip(self))]
+    pub async fn unpin_message(
+        &self,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+        reason: Option<String>,
+    ) -> ServiceResult<()> {
+        let channel = self.verify_channel_access(channel_id, user_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
+            .await?;
+
+        if !self
+            .ctx
+            .pin_repo()
+            .is_pinned(channel_id, message_id)
+            .await?
+        {
+            return Err(ServiceError::not_found("Pin", message_id.to_string()));
+        }
+
+        self.ctx.pin_repo().delete(channel_id, message_id).await?;
+
+        info!(channel_id = %channel_id, message_id = %message_id, "Message unpinned");
+
+        if let Some(guild_id) = channel.guild_id {
+            let snapshot = json!({
+                "channel_id": channel_id.to_string(),
+                "message_id": message_id.to_string()
+            });
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::MessageUnpin,
+                    (message_id, "message"),
+                    AuditLogEntry::diff(&snapshot, &Value::Null),
+                    reason,
+                )
+                .await;
+        }
+
+        let last_pin_timestamp = self.ctx.pin_repo().last_pin_timestamp(channel_id).await?;
+        self.publish_channel_pins_update(&channel, last_pin_timestamp)
+            .await;
+
+        Ok(())
+    }
+
     /// Get messages in a channel with pagination
     #[instrument(skip(self))]
     pub async fn get_channel_messages(
@@ -387,12 +744,171 @@ impl<'a> MessageService<'a> {
             .find_by_channel(channel_id, query)
             .await?;
 
+        self.build_responses(messages, channel.guild_id, user_id).await
+    }
+
+    /// Search messages in a single channel
+    #[instrument(skip(self, query))]
+    pub async fn search_channel_messages(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        query: MessageSearchQuery,
+    ) -> ServiceResult<MessageSearchResponse> {
+        let channel = self.verify_channel_access(channel_id, user_id).await?;
+        self.search(&[channel], user_id, query).await
+    }
+
+    /// Search messages across the guild channels the user can view
+    ///
+    /// `channel_id` narrows the search to one of those channels.
+    #[instrument(skip(self, query))]
+    pub async fn search_guild_messages(
+        &self,
+        guild_id: Snowflake,
+        user_id: Snowflake,
+        channel_id: Option<Snowflake>,
+        query: MessageSearchQuery,
+    ) -> ServiceResult<MessageSearchResponse> {
+        let permission_service = PermissionService::new(self.ctx);
+        if !permission_service.is_guild_member(guild_id, user_id).await? {
+            return Err(ServiceError::not_found("Guild", guild_id.to_string()));
+        }
+
+        let channels: Vec<Channel> = self
+            .ctx
+            .channel_repo()
+            .find_by_guild(guild_id)
+            .await?
+            .into_iter()
+            .filter(|c| c.is_text() && channel_id.is_none_or(|id| id == c.id))
+            .collect();
+
+        let permissions = permission_service
+            .compute_channels_permissions(guild_id, &channels, user_id)
+            .await?;
+        let searchable: Vec<Channel> = channels
+            .into_iter()
+            .filter(|c| {
+                permissions
+                    .get(&c.id)
+                    .is_some_and(|p| p.has(Permissions::VIEW_CHANNEL))
+            })
+            .collect();
+
+        if let Some(channel_id) = channel_id {
+            if searchable.is_empty() {
+                return Err(ServiceError::not_found("Channel", channel_id.to_string()));
+            }
+        }
+
+        self.search(&searchable, user_id, query).await
+    }
+
+    /// Run a search over already authorized channels and attach context
+    async fn search(
+        &self,
+        channels: &[Channel],
+        user_id: Snowflake,
+        mut query: MessageSearchQuery,
+    ) -> ServiceResult<MessageSearchResponse> {
+        if let Some(content) = &query.content {
+            if content.len() > MAX_SEARCH_QUERY_LENGTH {
+                return Err(ServiceError::validation(format!(
+                    "Search query must be at most {MAX_SEARCH_QUERY_LENGTH} characters"
+                )));
+            }
+        }
+        if let (Some(min), Some(max)) = (query.min_created_at, query.max_created_at) {
+            if min >= max {
+                return Err(ServiceError::validation(
+                    "min_created_at must be earlier than max_created_at",
+                ));
+            }
+        }
+        if query.offset < 0 || query.offset > MAX_SEARCH_OFFSET {
+            return Err(ServiceError::validation(format!(
+                "offset must be between 0 and {MAX_SEARCH_OFFSET}"
+            )));
+        }
+        query.limit = query.limit.clamp(1, MAX_SEARCH_LIMIT);
+
+        let channel_ids: Vec<Snowflake> = channels.iter().map(|c| c.id).collect();
+        let (total_results, messages) = self
+            .ctx
+            .message_repo()
+            .search(&channel_ids, &query)
+            .await?;
+
+        // Searches never span guilds, so every hit shares the same guild
+        let guild_id = channels.first().and_then(|c| c.guild_id);
+
+        let contexts = self
+            .ctx
+            .message_repo()
+            .find_context(&messages, SEARCH_CONTEXT_SIZE)
+            .await?;
+
+        // Build hits and their context together, then split them back apart
+        let mut sizes = Vec::with_capacity(contexts.len());
+        let mut all = Vec::new();
+        for (message, (before, after)) in messages.into_iter().zip(contexts) {
+            sizes.push((before.len(), after.len()));
+            all.push(message);
+            all.extend(before);
+            all.extend(after);
+        }
+
+        let mut responses = self
+            .build_responses(all, guild_id, user_id)
+            .await?
+            .into_iter();
+        let hits = sizes
+            .into_iter()
+            .filter_map(|(before, after)| {
+                Some(MessageSearchHitResponse {
+                    message: responses.next()?,
+                    context_before: responses.by_ref().take(before).collect(),
+                    context_after: responses.by_ref().take(after).collect(),
+                })
+            })
+            .collect();
+
+        Ok(MessageSearchResponse {
+            total_results,
+            hits,
+        })
+    }
+
+    /// Build message responses with author, attachment and reaction details
+    async fn build_responses(
+        &self,
+        messages: Vec<Message>,
+        guild_id: Option<Snowflake>,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<MessageResponse>> {
+        // Load every author and mentioned user in one query
+        let mut user_ids: Vec<Snowflake> = messages
+            .iter()
+            .flat_map(|m| std::iter::once(m.author_id).chain(m.mentions.users.iter().copied()))
+            .collect();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+        let users: HashMap<Snowflake, User> = self
+            .ctx
+            .user_repo()
+            .find_by_ids(&user_ids)
+            .await?
+            .into_iter()
+            .map(|u| (u.id, u))
+            .collect();
+
         // Build responses with author info
         let mut responses = Vec::with_capacity(messages.len());
 
         for message in messages {
-            let author = match self.ctx.user_repo().find_by_id(message.author_id).await? {
-                Some(u) => crate::dto::UserResponse::from(&u),
+            let author = match users.get(&message.author_id) {
+                Some(u) => crate::dto::UserResponse::from(u),
                 None => UserResponse {
                     id: message.author_id.to_string(),
                     username: "[Deleted User]".to_string(),
@@ -427,9 +943,16 @@ impl<'a> MessageService<'a> {
                 result
             };
 
+            let mentioned_users = message
+                .mentions
+                .users
+                .iter()
+                .filter_map(|id| users.get(id).cloned())
+                .collect();
+
             responses.push(MessageResponse::from(MessageWithDetails {
                 message,
-                author: chat_core::entities::User {
+                author: User {
                     id: author.id.parse::<i64>().map(Snowflake::new).unwrap_or_default(),
                     username: author.username,
                     discriminator: author.discriminator,
@@ -437,19 +960,255 @@ impl<'a> MessageService<'a> {
                     avatar: author.avatar,
                     bot: author.bot,
                     system: author.system,
+                    privileged_intents: false,
+                    dm_privacy: DmPrivacy::Everyone,
+                    verified: false,
+                    mfa_enabled: false,
                     created_at: author.created_at,
                     updated_at: author.created_at,
                 },
-                guild_id: channel.guild_id,
+                guild_id,
                 attachments,
                 reactions,
                 reference: None,
+                mentioned_users,
             }));
         }
 
         Ok(responses)
     }
 
+    /// Resolve the mentions in message content against the channel
+    ///
+    /// Keeps guild members (or DM recipients), roles that are mentionable,
+    /// channels of the same guild, and `@everyone`. Authors with
+    /// MENTION_EVERYONE may mention any role and `@everyone`. Returns the
+    /// resolved mentions and the mentioned users.
+    async fn resolve_mentions(
+        &self,
+        channel: &Channel,
+        author_id: Snowflake,
+        content: &str,
+        allowed: Option<&AllowedMentions>,
+    ) -> ServiceResult<(MessageMentions, Vec<User>)> {
+        let mut mentions = MessageMentions::parse(content);
+        if let Some(allowed) = allowed {
+            apply_allowed_mentions(&mut mentions, allowed)?;
+        }
+        if mentions.is_empty() {
+            return Ok((mentions, Vec::new()));
+        }
+
+        if let Some(guild_id) = channel.guild_id {
+            let can_mention_everyone = PermissionService::new(self.ctx)
+                .check_permission_in(channel, author_id, Permissions::MENTION_EVERYONE)
+                .await?;
+            mentions.everyone &= can_mention_everyone;
+
+            let mut members = Vec::with_capacity(mentions.users.len());
+            for user_id in mentions.users {
+                if self.ctx.member_repo().is_member(guild_id, user_id).await? {
+                    members.push(user_id);
+                }
+            }
+            mentions.users = members;
+
+            if !mentions.roles.is_empty() {
+                let roles = self.ctx.role_repo().find_by_guild(guild_id).await?;
+                mentions.roles.retain(|id| {
+                    roles.iter().any(|role| {
+                        role.id == *id
+                            && !role.is_everyone
+                            && (role.mentionable || can_mention_everyone)
+                    })
+                });
+            }
+
+            if !mentions.channels.is_empty() {
+                let channels = self.ctx.channel_repo().find_by_guild(guild_id).await?;
+                mentions
+                    .channels
+                    .retain(|id| channels.iter().any(|c| c.id == *id));
+            }
+        } else {
+            let recipients = self.ctx.channel_repo().get_dm_recipients(channel.id).await?;
+            mentions.users.retain(|id| recipients.contains(id));
+            mentions.roles.clear();
+            mentions.channels.clear();
+            mentions.everyone = false;
+        }
+
+        let mut users = Vec::with_capacity(mentions.users.len());
+        for user_id in &mentions.users {
+            if let Some(user) = self.ctx.user_repo().find_by_id(*user_id).await? {
+                users.push(user);
+            }
+        }
+        mentions.users = users.iter().map(|u| u.id).collect();
+
+        Ok((mentions, users))
+    }
+
+    /// Load the users mentioned by a stored message, skipping deleted ones
+    async fn load_mentioned_users(&self, message: &Message) -> ServiceResult<Vec<User>> {
+        let mut users = Vec::with_capacity(message.mentions.users.len());
+        for user_id in &message.mentions.users {
+            if let Some(user) = self.ctx.user_repo().find_by_id(*user_id).await? {
+                users.push(user);
+            }
+        }
+        Ok(users)
+    }
+
+    /// Notify users mentioned by a new message
+    ///
+    /// Each recipient's unread mention count is bumped and a MESSAGE_MENTION
+    /// event is sent to their user channel, so it arrives even without a
+    /// subscription to the guild.
+    async fn notify_mentions(
+        &self,
+        channel: &Channel,
+        message: &Message,
+        data: Value,
+    ) -> ServiceResult<()> {
+        let recipients = self.mention_recipients(channel, message).await?;
+        if recipients.is_empty() {
+            return Ok(());
+        }
+
+        self.ctx
+            .read_state_repo()
+            .increment_mentions(channel.id, &recipients)
+            .await?;
+
+        let event = PubSubEvent::new("MESSAGE_MENTION", data);
+        self.ctx
+            .read_state_store()
+            .invalidate_many(&recipients)
+            .await
+            .ok();
+        self.ctx
+            .publisher()
+            .publish_users(&recipients, &event)
+            .await
+            .ok();
+
+        Ok(())
+    }
+
+    /// Collect users reached by a message's mentions who can see the channel
+    async fn mention_recipients(
+        &self,
+        channel: &Channel,
+        message: &Message,
+    ) -> ServiceResult<Vec<Snowflake>> {
+        let mentions = &message.mentions;
+        let mut seen: HashSet<Snowflake> = HashSet::from([message.author_id]);
+        let mut candidates: Vec<Snowflake> = mentions
+            .users
+            .iter()
+            .copied()
+            .filter(|id| seen.insert(*id))
+            .collect();
+
+        let Some(guild_id) = channel.guild_id else {
+            return Ok(candidates);
+        };
+
+        if mentions.everyone || !mentions.roles.is_empty() {
+            let mut after = None;
+            loop {
+                let page = self
+                    .ctx
+                    .member_repo()
+                    .find_by_guild(guild_id, MENTION_MEMBER_PAGE_SIZE, after)
+                    .await?;
+                let is_last_page = (page.len() as i64) < MENTION_MEMBER_PAGE_SIZE;
+                after = page.last().map(|m| m.user_id);
+
+                for member in page {
+                    let reached = mentions.everyone
+                        || member.role_ids.iter().any(|id| mentions.roles.contains(id));
+                    if reached && seen.insert(member.user_id) {
+                        candidates.push(member.user_id);
+                    }
+                }
+
+                if is_last_page || after.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let permission_service = PermissionService::new(self.ctx);
+        let mut recipients = Vec::with_capacity(candidates.len());
+        for chunk in candidates.chunks(MENTION_MEMBER_PAGE_SIZE as usize) {
+            let permissions = permission_service
+                .compute_channel_permissions_many(channel, chunk)
+                .await?;
+            recipients.extend(chunk.iter().copied().filter(|user_id| {
+                permissions
+                    .get(user_id)
+                    .is_some_and(|perms| perms.has(Permissions::VIEW_CHANNEL))
+            }));
+        }
+
+        Ok(recipients)
+    }
+
+    /// Verify user can send messages (and optionally attach files) in a channel
+    async fn verify_send_access(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        with_attachments: bool,
+    ) -> ServiceResult<Channel> {
+        let channel = self
+            .ctx
+            .channel_repo()
+            .find_by_id(channel_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
+
+        // Check permissions based on channel type
+        if channel.guild_id.is_some() {
+            let mut required = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+            if with_attachments {
+                required |= Permissions::ATTACH_FILES;
+            }
+            let permission_service = PermissionService::new(self.ctx);
+            permission_service
+                .require_permission_in(&channel, user_id, required)
+                .await?;
+
+            if channel.is_thread() {
+                ThreadService::new(self.ctx)
+                    .prepare_for_message(&channel, user_id)
+                    .await?;
+            }
+        } else {
+            // DM channel - verify user is a recipient
+            let recipients = self.ctx.channel_repo().get_dm_recipients(channel_id).await?;
+            if !recipients.contains(&user_id) {
+                return Err(ServiceError::not_found("Channel", channel_id.to_string()));
+            }
+
+            // A block by either side closes a one-to-one DM
+            if channel.is_dm() {
+                let relationships = RelationshipService::new(self.ctx);
+                for &recipient_id in recipients.iter().filter(|&&id| id != user_id) {
+                    if relationships.is_blocked(user_id, recipient_id).await? {
+                        return Err(ServiceError::permission_denied(
+                            "Cannot send messages to this user",
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(channel)
+    }
+
     /// Verify user has access to channel
     async fn verify_channel_access(
         &self,
@@ -463,10 +1222,10 @@ impl<'a> MessageService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
 
-        if let Some(guild_id) = channel.guild_id {
+        if channel.guild_id.is_some() {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::VIEW_CHANNEL)
+                .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
                 .await?;
         } else {
             // DM channel - verify user is a recipient
@@ -479,16 +1238,15 @@ impl<'a> MessageService<'a> {
         Ok(channel)
     }
 
-    /// Helper to publish MESSAGE_CREATE event
-    async fn publish_message_create(
-        &self,
+    /// Build the MESSAGE_CREATE payload (also sent as MESSAGE_MENTION)
+    fn message_create_data(
         channel: &Channel,
         message: &Message,
-        author: &chat_core::entities::User,
-    ) {
-        let pubsub_channel = PubSubChannel::channel(channel.id);
-
-        let data = json!({
+        author: &User,
+        attachments: &[Attachment],
+        mentioned_users: &[User],
+    ) -> Value {
+        json!({
             "id": message.id.to_string(),
             "channel_id": message.channel_id.to_string(),
             "guild_id": channel.guild_id.map(|id| id.to_string()),
@@ -499,29 +1257,61 @@ impl<'a> MessageService<'a> {
                 "avatar": author.avatar
             },
             "content": message.content,
+            "type": message.message_type.as_i16(),
             "timestamp": message.created_at.to_rfc3339(),
             "edited_timestamp": message.edited_at.map(|t| t.to_rfc3339()),
+            "attachments": attachments.iter().map(|a| json!({
+                "id": a.id.to_string(),
+                "filename": a.filename,
+                "content_type": a.content_type,
+                "size": a.size,
+                "url": a.url,
+                "proxy_url": a.proxy_url,
+                "width": a.width,
+                "height": a.height
+            })).collect::<Vec<_>>(),
             "message_reference": message.reference_id.map(|id| {
                 json!({"message_id": id.to_string()})
-            })
-        });
+            }),
+            "mentions": mentioned_users.iter().map(mentioned_user_json).collect::<Vec<_>>(),
+            "mention_roles": id_strings(&message.mentions.roles),
+            "mention_channels": id_strings(&message.mentions.channels),
+            "mention_everyone": message.mentions.everyone
+        })
+    }
 
+    /// Helper to publish MESSAGE_CREATE event
+    async fn publish_message_create(&self, channel: &Channel, data: Value) {
         let event = PubSubEvent::new("MESSAGE_CREATE", data);
         self.ctx
             .publisher()
-            .publish(&pubsub_channel, &event)
+            .publish(&PubSubChannel::channel(channel.id), &event)
             .await
             .ok();
     }
 
     /// Helper to publish MESSAGE_UPDATE event
-    async fn publish_message_update(&self, channel: &Channel, message: &Message) {
+    ///
+    /// `previous_edited_at` is the edit timestamp the message had before this
+    /// update, so clients can detect edits that raced with their own.
+    async fn publish_message_update(
+        &self,
+        channel: &Channel,
+        message: &Message,
+        mentioned_users: &[User],
+        previous_edited_at: Option<DateTime<Utc>>,
+    ) {
         let data = json!({
             "id": message.id.to_string(),
             "channel_id": message.channel_id.to_string(),
             "guild_id": channel.guild_id.map(|id| id.to_string()),
             "content": message.content,
-            "edited_timestamp": message.edited_at.map(|t| t.to_rfc3339())
+            "edited_timestamp": message.edited_at.map(|t| t.to_rfc3339()),
+            "previous_edited_timestamp": previous_edited_at.map(|t| t.to_rfc3339()),
+            "mentions": mentioned_users.iter().map(mentioned_user_json).collect::<Vec<_>>(),
+            "mention_roles": id_strings(&message.mentions.roles),
+            "mention_channels": id_strings(&message.mentions.channels),
+            "mention_everyone": message.mentions.everyone
         });
 
         let event = PubSubEvent::new("MESSAGE_UPDATE", data);
@@ -532,6 +1322,26 @@ impl<'a> MessageService<'a> {
             .ok();
     }
 
+    /// Helper to publish CHANNEL_PINS_UPDATE event
+    async fn publish_channel_pins_update(
+        &self,
+        channel: &Channel,
+        last_pin_timestamp: Option<DateTime<Utc>>,
+    ) {
+        let data = json!({
+            "channel_id": channel.id.to_string(),
+            "guild_id": channel.guild_id.map(|id| id.to_string()),
+            "last_pin_timestamp": last_pin_timestamp.map(|t| t.to_rfc3339())
+        });
+
+        let event = PubSubEvent::new("CHANNEL_PINS_UPDATE", data);
+        self.ctx
+            .publisher()
+            .publish(&PubSubChannel::channel(channel.id), &event)
+            .await
+            .ok();
+    }
+
     /// Helper to publish MESSAGE_DELETE event
     async fn publish_message_delete(&self, channel: &Channel, message_id: Snowflake) {
         let data = json!({
@@ -549,6 +1359,63 @@ impl<'a> MessageService<'a> {
     }
 }
 
+/// Drop mentions that `allowed_mentions` does not permit
+fn apply_allowed_mentions(
+    mentions: &mut MessageMentions,
+    allowed: &AllowedMentions,
+) -> ServiceResult<()> {
+    let parse_users = allowed.parse.contains(&AllowedMentionType::Users);
+    let parse_roles = allowed.parse.contains(&AllowedMentionType::Roles);
+    if parse_users && !allowed.users.is_empty() {
+        return Err(ServiceError::validation(
+            "allowed_mentions cannot both parse users and list user IDs",
+        ));
+    }
+    if parse_roles && !allowed.roles.is_empty() {
+        return Err(ServiceError::validation(
+            "allowed_mentions cannot both parse roles and list role IDs",
+        ));
+    }
+
+    if !parse_users {
+        let users = parse_allowed_ids(&allowed.users, "users")?;
+        mentions.users.retain(|id| users.contains(id));
+    }
+    if !parse_roles {
+        let roles = parse_allowed_ids(&allowed.roles, "roles")?;
+        mentions.roles.retain(|id| roles.contains(id));
+    }
+    mentions.everyone &= allowed.parse.contains(&AllowedMentionType::Everyone);
+
+    Ok(())
+}
+
+/// Parse the IDs listed in `allowed_mentions.users` or `allowed_mentions.roles`
+fn parse_allowed_ids(ids: &[String], field: &str) -> ServiceResult<Vec<Snowflake>> {
+    ids.iter()
+        .map(|id| {
+            id.parse::<Snowflake>().map_err(|_| {
+                ServiceError::validation(format!("Invalid ID in allowed_mentions.{field}"))
+            })
+        })
+        .collect()
+}
+
+/// Format IDs as strings for event payloads
+fn id_strings(ids: &[Snowflake]) -> Vec<String> {
+    ids.iter().map(ToString::to_string).collect()
+}
+
+/// Mentioned user as included in message events
+fn mentioned_user_json(user: &User) -> Value {
+    json!({
+        "id": user.id.to_string(),
+        "username": user.username,
+        "discriminator": user.discriminator,
+        "avatar": user.avatar
+    })
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests would go here with mocked dependencies
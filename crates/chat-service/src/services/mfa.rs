@@ -0,0 +1,252 @@
+//! Two-factor authentication service
+//!
+//! Handles TOTP enrollment, backup codes, and checking codes for logins and
+//! account changes.
+
+use chat_common::auth::{generate_backup_codes, hash_backup_code, is_totp_code, verify_password};
+use chat_common::AppError;
+use chat_core::Snowflake;
+use tracing::{info, instrument, warn};
+
+use crate::dto::{BackupCodesResponse, EnableTotpRequest, MfaCodeRequest, TotpEnrollmentResponse};
+
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+
+/// Wrong codes allowed per user before code checks are refused for a while
+const MAX_FAILED_CODES: u32 = 10;
+
+/// Two-factor authentication service
+pub struct MfaService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> MfaService<'a> {
+    /// Create a new MfaService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Start TOTP enrollment
+    ///
+    /// The secret is kept aside until [`confirm_totp`](Self::confirm_totp)
+    /// proves the authenticator app was set up.
+    #[instrument(skip(self, request))]
+    pub async fn enable_totp(
+        &self,
+        user_id: Snowflake,
+        request: EnableTotpRequest,
+    ) -> ServiceResult<TotpEnrollmentResponse> {
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+
+        if user.mfa_enabled {
+            return Err(ServiceError::conflict("Two-factor authentication is already enabled"));
+        }
+
+        let password_hash = self
+            .ctx
+            .user_repo()
+            .get_password_hash(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+        if !verify_password(&request.password, &password_hash)
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+        {
+            return Err(ServiceError::validation("Invalid password"));
+        }
+
+        let totp = self.ctx.totp_service();
+        let secret = totp.generate_secret();
+        self.ctx
+            .mfa_store()
+            .set_pending_secret(user_id, &secret)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+        info!(user_id = %user_id, "TOTP enrollment started");
+
+        Ok(TotpEnrollmentResponse {
+            uri: totp.provisioning_uri(&secret, &user.email),
+            secret,
+        })
+    }
+
+    /// Finish TOTP enrollment with the first code from the authenticator app
+    ///
+    /// Returns the backup codes; only their hashes are kept.
+    #[instrument(skip(self, request))]
+    pub async fn confirm_totp(
+        &self,
+        user_id: Snowflake,
+        request: MfaCodeRequest,
+    ) -> ServiceResult<BackupCodesResponse> {
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+
+        if user.mfa_enabled {
+            return Err(ServiceError::conflict("Two-factor authentication is already enabled"));
+        }
+
+        let secret = self
+            .ctx
+            .mfa_store()
+            .get_pending_secret(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+            .ok_or_else(|| ServiceError::validation("No pending two-factor enrollment"))?;
+
+        self.ensure_not_locked(user_id).await?;
+        if !self.check_totp(user_id, &secret, &request.code).await? {
+            return Err(self.record_failure(user_id).await);
+        }
+
+        let backup_codes = generate_backup_codes();
+        let hashes: Vec<String> = backup_codes.iter().map(|c| hash_backup_code(c)).collect();
+        self.ctx
+            .user_repo()
+            .enable_mfa(user_id, &secret, &hashes)
+            .await?;
+
+        let store = self.ctx.mfa_store();
+        store
+            .clear_pending_secret(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+        store
+            .clear_failed_attempts(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+        info!(user_id = %user_id, "Two-factor authentication enabled");
+
+        Ok(BackupCodesResponse { backup_codes })
+    }
+
+    /// Turn off two-factor authentication
+    #[instrument(skip(self, request))]
+    pub async fn disable_totp(&self, user_id: Snowflake, request: MfaCodeRequest) -> ServiceResult<()> {
+        self.verify_code(user_id, &request.code).await?;
+        self.ctx.user_repo().disable_mfa(user_id).await?;
+
+        info!(user_id = %user_id, "Two-factor authentication disabled");
+        Ok(())
+    }
+
+    /// Replace all backup codes with a fresh set
+    #[instrument(skip(self, request))]
+    pub async fn regenerate_backup_codes(
+        &self,
+        user_id: Snowflake,
+        request: MfaCodeRequest,
+    ) -> ServiceResult<BackupCodesResponse> {
+        self.verify_code(user_id, &request.code).await?;
+
+        let backup_codes = generate_backup_codes();
+        let hashes: Vec<String> = backup_codes.iter().map(|c| hash_backup_code(c)).collect();
+        self.ctx
+            .user_repo()
+            .replace_backup_codes(user_id, &hashes)
+            .await?;
+
+        info!(user_id = %user_id, "Backup codes regenerated");
+
+        Ok(BackupCodesResponse { backup_codes })
+    }
+
+    /// Check a TOTP code or backup code of a user with two-factor authentication
+    ///
+    /// A backup code is used up by a successful check.
+    #[instrument(skip(self, code))]
+    pub async fn verify_code(&self, user_id: Snowflake, code: &str) -> ServiceResult<()> {
+        let secret = self
+            .ctx
+            .user_repo()
+            .get_totp_secret(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::validation("Two-factor authentication is not enabled"))?;
+
+        self.ensure_not_locked(user_id).await?;
+
+        let valid = if is_totp_code(code) {
+            self.check_totp(user_id, &secret, code).await?
+        } else {
+            let used = self
+                .ctx
+                .user_repo()
+                .consume_backup_code(user_id, &hash_backup_code(code))
+                .await?;
+            if used {
+                info!(user_id = %user_id, "Backup code used");
+            }
+            used
+        };
+
+        if !valid {
+            return Err(self.record_failure(user_id).await);
+        }
+
+        self.ctx
+            .mfa_store()
+            .clear_failed_attempts(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Check a TOTP code, refusing a code whose step was already used
+    async fn check_totp(&self, user_id: Snowflake, secret: &str, code: &str) -> ServiceResult<bool> {
+        let Some(step) = self
+            .ctx
+            .totp_service()
+            .verify(secret, code)
+            .map_err(|e| ServiceError::internal(e.to_string()))?
+        else {
+            return Ok(false);
+        };
+
+        self.ctx
+            .mfa_store()
+            .claim_step(user_id, step)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))
+    }
+
+    /// Refuse code checks for users with too many recent wrong codes
+    async fn ensure_not_locked(&self, user_id: Snowflake) -> ServiceResult<()> {
+        let failures = self
+            .ctx
+            .mfa_store()
+            .failed_attempts(user_id)
+            .await
+            .map_err(|e| ServiceError::internal(e.to_string()))?;
+
+        if failures >= MAX_FAILED_CODES {
+            warn!(user_id = %user_id, "Two-factor code checks locked after repeated failures");
+            return Err(ServiceError::App(AppError::RateLimitExceeded));
+        }
+        Ok(())
+    }
+
+    /// Count a wrong code and build the error to return
+    async fn record_failure(&self, user_id: Snowflake) -> ServiceError {
+        if let Err(e) = self.ctx.mfa_store().record_failed_attempt(user_id).await {
+            return ServiceError::internal(e.to_string());
+        }
+        warn!(user_id = %user_id, "Invalid two-factor code");
+        ServiceError::validation("Invalid two-factor code")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would go here with mocked dependencies
+}
@@ -3,33 +3,49 @@
 //! This module contains all service layer implementations that handle
 //! business logic, validation, and orchestration of domain operations.
 
+pub mod attachment;
+pub mod audit_log;
 pub mod auth;
 pub mod channel;
 pub mod context;
 pub mod dm;
 pub mod error;
+pub mod gateway;
 pub mod guild;
 pub mod invite;
 pub mod member;
 pub mod message;
+pub mod mfa;
 pub mod permission;
 pub mod presence;
 pub mod reaction;
+pub mod read_state;
+pub mod relationship;
 pub mod role;
+pub mod session;
+pub mod thread;
 pub mod user;
 
 // Re-export all services for convenience
+pub use attachment::{AttachmentService, PendingAttachment, MAX_ATTACHMENTS_PER_MESSAGE};
+pub use audit_log::AuditLogService;
 pub use auth::AuthService;
 pub use channel::ChannelService;
 pub use context::{ServiceContext, ServiceContextBuilder};
-pub use dm::DmService;
+pub use dm::{DmService, MAX_GROUP_DM_RECIPIENTS};
 pub use error::{ServiceError, ServiceResult};
+pub use gateway::GatewayService;
 pub use guild::GuildService;
 pub use invite::InviteService;
 pub use member::MemberService;
-pub use message::MessageService;
+pub use message::{MessageService, MAX_PINS_PER_CHANNEL};
+pub use mfa::MfaService;
 pub use permission::PermissionService;
 pub use presence::PresenceService;
 pub use reaction::ReactionService;
+pub use read_state::ReadStateService;
+pub use relationship::RelationshipService;
 pub use role::RoleService;
+pub use session::SessionService;
+pub use thread::ThreadService;
 pub use user::UserService;
@@ -2,7 +2,9 @@
 //!
 //! Handles permission checking and computation for guild members.
 
-use chat_core::entities::Channel;
+use std::collections::HashMap;
+
+use chat_core::entities::{Channel, Guild, GuildMember, PermissionOverwrite, Role};
 use chat_core::Permissions;
 use chat_core::Snowflake;
 use tracing::{debug, instrument};
@@ -48,12 +50,22 @@ impl<'a> PermissionService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
 
+        self.check_permission_in(&channel, user_id, permission).await
+    }
+
+    /// Check if a user has a specific permission in an already loaded channel
+    #[instrument(skip(self, channel), fields(channel_id = %channel.id))]
+    pub async fn check_permission_in(
+        &self,
+        channel: &Channel,
+        user_id: Snowflake,
+        permission: Permissions,
+    ) -> ServiceResult<bool> {
         // DM channels don't have guild-based permissions
-        let guild_id = match channel.guild_id {
-            Some(id) => id,
-            None => return Ok(true),
-        };
-        let permissions = self.get_member_permissions(guild_id, user_id).await?;
+        if channel.guild_id.is_none() {
+            return Ok(true);
+        }
+        let permissions = self.compute_channel_permissions(channel, user_id).await?;
         Ok(permissions.has(permission))
     }
 
@@ -90,6 +102,51 @@ impl<'a> PermissionService<'a> {
         Ok(())
     }
 
+    /// Check permission in an already loaded channel and return error if denied
+    #[instrument(skip(self, channel), fields(channel_id = %channel.id))]
+    pub async fn require_permission_in(
+        &self,
+        channel: &Channel,
+        user_id: Snowflake,
+        permission: Permissions,
+    ) -> ServiceResult<()> {
+        if !self.check_permission_in(channel, user_id, permission).await? {
+            let perm_names = permission.list().join(", ");
+            return Err(ServiceError::permission_denied(perm_names));
+        }
+        Ok(())
+    }
+
+    /// Require two-factor authentication for a destructive moderation action
+    ///
+    /// Only applies in guilds whose owner set the elevated MFA level.
+    #[instrument(skip(self))]
+    pub async fn require_mfa(&self, guild_id: Snowflake, user_id: Snowflake) -> ServiceResult<()> {
+        let guild = self
+            .ctx
+            .guild_repo()
+            .find_by_id(guild_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Guild", guild_id.to_string()))?;
+
+        if !guild.requires_mfa() {
+            return Ok(());
+        }
+
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+
+        if !user.mfa_enabled {
+            debug!(user_id = %user_id, guild_id = %guild_id, "Moderation refused without two-factor authentication");
+            return Err(ServiceError::permission_denied("two-factor authentication"));
+        }
+        Ok(())
+    }
+
     /// Get all permissions for a member in a guild
     #[instrument(skip(self))]
     pub async fn get_member_permissions(
@@ -97,7 +154,6 @@ impl<'a> PermissionService<'a> {
         guild_id: Snowflake,
         user_id: Snowflake,
     ) -> ServiceResult<Permissions> {
-        // Get the guild to check if user is owner
         let guild = self
             .ctx
             .guild_repo()
@@ -105,13 +161,6 @@ impl<'a> PermissionService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Guild", guild_id.to_string()))?;
 
-        // Guild owners have all permissions
-        if guild.owner_id == user_id {
-            debug!(user_id = %user_id, guild_id = %guild_id, "User is guild owner, granting all permissions");
-            return Ok(Permissions::ALL);
-        }
-
-        // Check if user is a member
         let member = self
             .ctx
             .member_repo()
@@ -119,20 +168,8 @@ impl<'a> PermissionService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Member", format!("{guild_id}/{user_id}")))?;
 
-        // Get all roles for the member
-        let mut permissions = Permissions::empty();
-
-        // Always include @everyone role
-        if let Some(everyone_role) = self.ctx.role_repo().find_everyone(guild_id).await? {
-            permissions |= everyone_role.permissions;
-        }
-
-        // Add permissions from member's roles
-        for role_id in &member.role_ids {
-            if let Some(role) = self.ctx.role_repo().find_by_id(*role_id).await? {
-                permissions |= role.permissions;
-            }
-        }
+        let roles = self.ctx.role_repo().find_by_guild(guild_id).await?;
+        let permissions = base_permissions(&guild, &roles, &member);
 
         debug!(
             user_id = %user_id,
@@ -146,21 +183,165 @@ impl<'a> PermissionService<'a> {
 
     /// Compute permissions for a specific channel (with overwrites)
     ///
-    /// For MVP, we don't implement channel permission overwrites.
-    /// This returns the member's guild permissions.
-    #[instrument(skip(self))]
+    /// Starts from the member's guild permissions and applies the channel's
+    /// @everyone, role and member overwrites in that order.
+    #[instrument(skip(self, channel), fields(channel_id = %channel.id))]
     pub async fn compute_channel_permissions(
         &self,
         channel: &Channel,
         user_id: Snowflake,
     ) -> ServiceResult<Permissions> {
-        match channel.guild_id {
-            Some(guild_id) => self.get_member_permissions(guild_id, user_id).await,
+        let guild_id = match channel.guild_id {
+            Some(id) => id,
             None => {
                 // DM channels - full permissions for participants
-                Ok(Permissions::DEFAULT)
+                return Ok(Permissions::DEFAULT);
             }
+        };
+
+        let permissions = self
+            .compute_channels_permissions(guild_id, std::slice::from_ref(channel), user_id)
+            .await?
+            .remove(&channel.id)
+            .unwrap_or_else(Permissions::empty);
+
+        debug!(
+            user_id = %user_id,
+            channel_id = %channel.id,
+            permissions = %permissions,
+            "Computed channel permissions"
+        );
+
+        Ok(permissions)
+    }
+
+    /// Compute channel permissions for several users at once
+    ///
+    /// Loads the guild, its roles, the channel overwrites and the members in
+    /// a fixed number of queries and resolves each user in memory. Users who
+    /// are not members of the channel's guild are left out of the result.
+    #[instrument(skip(self, channel, user_ids), fields(channel_id = %channel.id, users = user_ids.len()))]
+    pub async fn compute_channel_permissions_many(
+        &self,
+        channel: &Channel,
+        user_ids: &[Snowflake],
+    ) -> ServiceResult<HashMap<Snowflake, Permissions>> {
+        let Some(guild_id) = channel.guild_id else {
+            // DM channels - full permissions for participants
+            return Ok(user_ids.iter().map(|id| (*id, Permissions::DEFAULT)).collect());
+        };
+
+        let guild = self
+            .ctx
+            .guild_repo()
+            .find_by_id(guild_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Guild", guild_id.to_string()))?;
+
+        let roles = self.ctx.role_repo().find_by_guild(guild_id).await?;
+        let everyone_id = roles
+            .iter()
+            .find(|role| role.is_everyone)
+            .map(|role| role.id);
+
+        let overwrites = self
+            .ctx
+            .permission_overwrite_repo()
+            .find_by_channel(overwrite_channel_id(channel))
+            .await?;
+
+        let members = self.ctx.member_repo().find_many(guild_id, user_ids).await?;
+
+        let permissions = members
+            .into_iter()
+            .map(|member| {
+                let resolved = PermissionOverwrite::resolve(
+                    base_permissions(&guild, &roles, &member),
+                    &overwrites,
+                    everyone_id,
+                    &member.role_ids,
+                    member.user_id,
+                );
+                (member.user_id, resolved)
+            })
+            .collect();
+
+        Ok(permissions)
+    }
+
+    /// Compute one user's permissions for several channels of a guild
+    ///
+    /// Resolves the member's base permissions and roles once and loads every
+    /// channel's overwrites in a single query, so the cost does not grow with
+    /// the number of channels.
+    #[instrument(skip(self, channels), fields(channels = channels.len()))]
+    pub async fn compute_channels_permissions(
+        &self,
+        guild_id: Snowflake,
+        channels: &[Channel],
+        user_id: Snowflake,
+    ) -> ServiceResult<HashMap<Snowflake, Permissions>> {
+        let guild = self
+            .ctx
+            .guild_repo()
+            .find_by_id(guild_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Guild", guild_id.to_string()))?;
+
+        let member = self
+            .ctx
+            .member_repo()
+            .find(guild_id, user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Member", format!("{guild_id}/{user_id}")))?;
+
+        let roles = self.ctx.role_repo().find_by_guild(guild_id).await?;
+        let everyone_id = roles
+            .iter()
+            .find(|role| role.is_everyone)
+            .map(|role| role.id);
+        let base = base_permissions(&guild, &roles, &member);
+
+        // Administrators (and owners) are not affected by overwrites
+        let overwrites = if base.contains(Permissions::ADMINISTRATOR) {
+            Vec::new()
+        } else {
+            let mut channel_ids: Vec<Snowflake> =
+                channels.iter().map(overwrite_channel_id).collect();
+            channel_ids.sort_unstable();
+            channel_ids.dedup();
+            self.ctx
+                .permission_overwrite_repo()
+                .find_by_channels(&channel_ids)
+                .await?
+        };
+
+        let mut by_channel: HashMap<Snowflake, Vec<PermissionOverwrite>> = HashMap::new();
+        for overwrite in overwrites {
+            by_channel
+                .entry(overwrite.channel_id)
+                .or_default()
+                .push(overwrite);
         }
+
+        let permissions = channels
+            .iter()
+            .map(|channel| {
+                let channel_overwrites = by_channel
+                    .get(&overwrite_channel_id(channel))
+                    .map_or(&[][..], Vec::as_slice);
+                let resolved = PermissionOverwrite::resolve(
+                    base,
+                    channel_overwrites,
+                    everyone_id,
+                    &member.role_ids,
+                    user_id,
+                );
+                (channel.id, resolved)
+            })
+            .collect();
+
+        Ok(permissions)
     }
 
     /// Check if user is guild owner
@@ -300,6 +481,30 @@ impl<'a> PermissionService<'a> {
     }
 }
 
+/// Fold a member's guild permissions from the @everyone role and their roles
+///
+/// Guild owners have all permissions.
+fn base_permissions(guild: &Guild, roles: &[Role], member: &GuildMember) -> Permissions {
+    if guild.owner_id == member.user_id {
+        return Permissions::ALL;
+    }
+
+    roles
+        .iter()
+        .filter(|role| role.is_everyone || member.role_ids.contains(&role.id))
+        .fold(Permissions::empty(), |acc, role| acc | role.permissions)
+}
+
+/// Channel whose overwrites apply to `channel`
+///
+/// Threads inherit the overwrites of their parent channel.
+fn overwrite_channel_id(channel: &Channel) -> Snowflake {
+    match channel.parent_id {
+        Some(parent_id) if channel.is_thread() => parent_id,
+        _ => channel.id,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,10 +51,10 @@ impl<'a> ReactionService<'a> {
         }
 
         // Check if user can add reactions (ADD_REACTIONS permission for guild channels)
-        if let Some(guild_id) = channel.guild_id {
+        if channel.guild_id.is_some() {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::ADD_REACTIONS)
+                .require_permission_in(&channel, user_id, Permissions::ADD_REACTIONS)
                 .await?;
         }
 
@@ -190,10 +190,10 @@ impl<'a> ReactionService<'a> {
         }
 
         // Check MANAGE_MESSAGES permission for removing others' reactions
-        if let Some(guild_id) = channel.guild_id {
+        if channel.guild_id.is_some() {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, actor_id, Permissions::MANAGE_MESSAGES)
+                .require_permission_in(&channel, actor_id, Permissions::MANAGE_MESSAGES)
                 .await?;
         } else {
             // DMs - can only remove own reactions
@@ -263,8 +263,9 @@ impl<'a> ReactionService<'a> {
         if let Some(guild_id) = channel.guild_id {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::MANAGE_MESSAGES)
+                .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
                 .await?;
+            permission_service.require_mfa(guild_id, user_id).await?;
         } else {
             return Err(ServiceError::permission_denied(
                 "Cannot clear reactions in DMs",
@@ -324,8 +325,9 @@ impl<'a> ReactionService<'a> {
         if let Some(guild_id) = channel.guild_id {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::MANAGE_MESSAGES)
+                .require_permission_in(&channel, user_id, Permissions::MANAGE_MESSAGES)
                 .await?;
+            permission_service.require_mfa(guild_id, user_id).await?;
         } else {
             return Err(ServiceError::permission_denied(
                 "Cannot clear reactions in DMs",
@@ -485,10 +487,10 @@ impl<'a> ReactionService<'a> {
             .await?
             .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
 
-        if let Some(guild_id) = channel.guild_id {
+        if channel.guild_id.is_some() {
             let permission_service = PermissionService::new(self.ctx);
             permission_service
-                .require_permission(guild_id, user_id, Permissions::VIEW_CHANNEL)
+                .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
                 .await?;
         } else {
             // DM channel - verify user is a recipient
@@ -0,0 +1,104 @@
+//! Read state service
+//!
+//! Tracks which messages each user has read and syncs acks across sessions.
+
+use chat_cache::{PubSubEvent, ReadStateData};
+use chat_core::Snowflake;
+use serde_json::json;
+use tracing::{info, instrument, warn};
+
+use crate::dto::ReadStateResponse;
+
+use super::channel::ChannelService;
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+
+/// Read state service
+pub struct ReadStateService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> ReadStateService<'a> {
+    /// Create a new ReadStateService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Mark a channel as read up to a message
+    #[instrument(skip(self))]
+    pub async fn ack_message(
+        &self,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<ReadStateResponse> {
+        ChannelService::new(self.ctx)
+            .get_channel_with_permission(channel_id, user_id)
+            .await?;
+
+        let message = self
+            .ctx
+            .message_repo()
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or_else(|| ServiceError::not_found("Message", message_id.to_string()))?;
+
+        let state = self
+            .ctx
+            .read_state_repo()
+            .ack(user_id, channel_id, message.id)
+            .await?;
+        let data = ReadStateData::from(&state);
+
+        // The database is authoritative; a stale cache entry is dropped instead
+        if let Err(e) = self.ctx.read_state_store().update(user_id, &data).await {
+            warn!(user_id = %user_id, error = %e, "Failed to update read state cache");
+            self.ctx.read_state_store().invalidate(user_id).await.ok();
+        }
+
+        info!(user_id = %user_id, channel_id = %channel_id, message_id = %message_id, "Message acked");
+
+        self.publish_message_ack(user_id, &data).await;
+
+        Ok(ReadStateResponse::from(&data))
+    }
+
+    /// Get all read states of a user, loading them into the cache on miss
+    #[instrument(skip(self))]
+    pub async fn get_read_states(&self, user_id: Snowflake) -> ServiceResult<Vec<ReadStateData>> {
+        let store = self.ctx.read_state_store();
+
+        if let Ok(Some(states)) = store.get_all(user_id).await {
+            return Ok(states);
+        }
+
+        let states: Vec<ReadStateData> = self
+            .ctx
+            .read_state_repo()
+            .find_by_user(user_id)
+            .await?
+            .iter()
+            .map(ReadStateData::from)
+            .collect();
+        store.set_all(user_id, &states).await.ok();
+
+        Ok(states)
+    }
+
+    /// Helper to publish MESSAGE_ACK event to the user's sessions
+    async fn publish_message_ack(&self, user_id: Snowflake, state: &ReadStateData) {
+        let data = json!({
+            "channel_id": state.channel_id.to_string(),
+            "message_id": state.last_message_id.map(|id| id.to_string()),
+            "mention_count": state.mention_count
+        });
+
+        let event = PubSubEvent::new("MESSAGE_ACK", data);
+        self.ctx
+            .publisher()
+            .publish_user(user_id, &event)
+            .await
+            .ok();
+    }
+}
@@ -0,0 +1,348 @@
+//! Relationship service
+//!
+//! Handles friend requests, friendships and blocks, and decides who may
+//! open a DM with whom.
+
+use chat_cache::PubSubEvent;
+use chat_core::entities::{DmPrivacy, Relationship, RelationshipType, User};
+use chat_core::Snowflake;
+use serde_json::json;
+use tracing::{info, instrument};
+
+use crate::dto::{
+    CreateRelationshipRequest, RelationshipResponse, RelationshipWithUser,
+    UpdateRelationshipRequest,
+};
+
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+
+/// Relationship service
+pub struct RelationshipService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> RelationshipService<'a> {
+    /// Create a new RelationshipService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// List the user's friends, pending requests and blocks
+    #[instrument(skip(self))]
+    pub async fn get_relationships(
+        &self,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<RelationshipResponse>> {
+        let relationships = self.ctx.relationship_repo().find_by_user(user_id).await?;
+
+        let mut responses = Vec::with_capacity(relationships.len());
+        for relationship in relationships {
+            // Deleted accounts drop out of the list
+            if let Some(user) = self.ctx.user_repo().find_by_id(relationship.target_id).await? {
+                responses.push(RelationshipResponse::from(RelationshipWithUser {
+                    relationship,
+                    user,
+                }));
+            }
+        }
+
+        Ok(responses)
+    }
+
+    /// Send a friend request to the user with the given tag
+    #[instrument(skip(self))]
+    pub async fn send_friend_request_by_tag(
+        &self,
+        user_id: Snowflake,
+        request: CreateRelationshipRequest,
+    ) -> ServiceResult<()> {
+        let target = self
+            .ctx
+            .user_repo()
+            .find_by_tag(&request.username, &request.discriminator)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::not_found(
+                    "User",
+                    format!("{}#{}", request.username, request.discriminator),
+                )
+            })?;
+
+        self.send_friend_request(user_id, target.id).await
+    }
+
+    /// Create or change the relationship with a user
+    ///
+    /// Type 1 (or no type) sends or accepts a friend request; type 2 blocks.
+    #[instrument(skip(self))]
+    pub async fn update_relationship(
+        &self,
+        user_id: Snowflake,
+        target_id: Snowflake,
+        request: UpdateRelationshipRequest,
+    ) -> ServiceResult<()> {
+        let relationship_type = match request.relationship_type {
+            None => RelationshipType::Friend,
+            Some(value) => RelationshipType::from_i16(value)
+                .ok_or_else(|| ServiceError::validation("Invalid relationship type"))?,
+        };
+
+        match relationship_type {
+            RelationshipType::Friend => self.send_friend_request(user_id, target_id).await,
+            RelationshipType::Blocked => self.block_user(user_id, target_id).await,
+            RelationshipType::IncomingRequest | RelationshipType::OutgoingRequest => Err(
+                ServiceError::validation("Relationship type must be 1 (friend) or 2 (blocked)"),
+            ),
+        }
+    }
+
+    /// Send a friend request, or accept the target's pending request
+    #[instrument(skip(self))]
+    pub async fn send_friend_request(
+        &self,
+        user_id: Snowflake,
+        target_id: Snowflake,
+    ) -> ServiceResult<()> {
+        if user_id == target_id {
+            return Err(ServiceError::validation("Cannot add yourself as a friend"));
+        }
+
+        let (user, target) = self.find_pair(user_id, target_id).await?;
+        if target.is_bot() {
+            return Err(ServiceError::validation("Cannot add a bot as a friend"));
+        }
+
+        let repo = self.ctx.relationship_repo();
+        let mine = repo.find(user_id, target_id).await?;
+        let theirs = repo.find(target_id, user_id).await?;
+
+        if mine.as_ref().is_some_and(Relationship::is_blocked) {
+            return Err(ServiceError::validation(
+                "Unblock this user before sending a friend request",
+            ));
+        }
+        if theirs.as_ref().is_some_and(Relationship::is_blocked) {
+            return Err(ServiceError::validation(
+                "Cannot send a friend request to this user",
+            ));
+        }
+
+        let (user_type, target_type) = match mine.map(|r| r.relationship_type) {
+            // Already friends, or the request is already pending
+            Some(RelationshipType::Friend | RelationshipType::OutgoingRequest) => return Ok(()),
+            Some(RelationshipType::IncomingRequest) => {
+                (RelationshipType::Friend, RelationshipType::Friend)
+            }
+            _ => (
+                RelationshipType::OutgoingRequest,
+                RelationshipType::IncomingRequest,
+            ),
+        };
+
+        let user_side = Relationship::new(user_id, target_id, user_type);
+        let target_side = Relationship::new(target_id, user_id, target_type);
+        repo.upsert(&user_side).await?;
+        repo.upsert(&target_side).await?;
+
+        info!(
+            user_id = %user_id,
+            target_id = %target_id,
+            accepted = user_type == RelationshipType::Friend,
+            "Friend request sent"
+        );
+
+        self.publish_add(user_side, target).await;
+        self.publish_add(target_side, user).await;
+
+        Ok(())
+    }
+
+    /// Block a user
+    ///
+    /// Any friendship or pending request between the users is removed on
+    /// both sides; the target's own block, if any, is kept.
+    #[instrument(skip(self))]
+    pub async fn block_user(&self, user_id: Snowflake, target_id: Snowflake) -> ServiceResult<()> {
+        if user_id == target_id {
+            return Err(ServiceError::validation("Cannot block yourself"));
+        }
+
+        let target = self
+            .ctx
+            .user_repo()
+            .find_by_id(target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", target_id.to_string()))?;
+
+        let repo = self.ctx.relationship_repo();
+        if repo
+            .find(user_id, target_id)
+            .await?
+            .is_some_and(|r| r.is_blocked())
+        {
+            return Ok(());
+        }
+
+        if let Some(theirs) = repo.find(target_id, user_id).await? {
+            if !theirs.is_blocked() {
+                repo.delete(target_id, user_id).await?;
+                self.publish_remove(&theirs).await;
+            }
+        }
+
+        let block = Relationship::new(user_id, target_id, RelationshipType::Blocked);
+        repo.upsert(&block).await?;
+
+        info!(user_id = %user_id, target_id = %target_id, "User blocked");
+
+        self.publish_add(block, target).await;
+
+        Ok(())
+    }
+
+    /// Remove a friend, cancel or decline a friend request, or unblock a user
+    #[instrument(skip(self))]
+    pub async fn remove_relationship(
+        &self,
+        user_id: Snowflake,
+        target_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let repo = self.ctx.relationship_repo();
+        let mine = repo
+            .find(user_id, target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Relationship", target_id.to_string()))?;
+
+        repo.delete(user_id, target_id).await?;
+        self.publish_remove(&mine).await;
+
+        // Unblocking leaves the other side alone; friendships end for both
+        if !mine.is_blocked() {
+            if let Some(theirs) = repo.find(target_id, user_id).await? {
+                if !theirs.is_blocked() {
+                    repo.delete(target_id, user_id).await?;
+                    self.publish_remove(&theirs).await;
+                }
+            }
+        }
+
+        info!(
+            user_id = %user_id,
+            target_id = %target_id,
+            relationship_type = mine.relationship_type.as_i16(),
+            "Relationship removed"
+        );
+
+        Ok(())
+    }
+
+    /// Check if either user blocked the other
+    #[instrument(skip(self))]
+    pub async fn is_blocked(&self, user_id: Snowflake, other_id: Snowflake) -> ServiceResult<bool> {
+        let repo = self.ctx.relationship_repo();
+        if repo
+            .find(user_id, other_id)
+            .await?
+            .is_some_and(|r| r.is_blocked())
+        {
+            return Ok(true);
+        }
+
+        Ok(repo
+            .find(other_id, user_id)
+            .await?
+            .is_some_and(|r| r.is_blocked()))
+    }
+
+    /// Verify that `sender_id` may open a DM with `recipient`
+    ///
+    /// Fails if either user blocked the other, or if the recipient's DM
+    /// privacy setting excludes the sender.
+    #[instrument(skip(self, recipient), fields(recipient_id = %recipient.id))]
+    pub async fn require_can_dm(&self, sender_id: Snowflake, recipient: &User) -> ServiceResult<()> {
+        if self.is_blocked(sender_id, recipient.id).await? {
+            return Err(ServiceError::permission_denied(
+                "Cannot send messages to this user",
+            ));
+        }
+
+        if recipient.dm_privacy == DmPrivacy::Everyone {
+            return Ok(());
+        }
+
+        let friends = self
+            .ctx
+            .relationship_repo()
+            .find(sender_id, recipient.id)
+            .await?
+            .is_some_and(|r| r.is_friend());
+        if friends {
+            return Ok(());
+        }
+
+        if recipient.dm_privacy == DmPrivacy::FriendsAndGuildMates
+            && self
+                .ctx
+                .member_repo()
+                .shares_guild(sender_id, recipient.id)
+                .await?
+        {
+            return Ok(());
+        }
+
+        Err(ServiceError::permission_denied(
+            "Recipient does not accept DMs from this user",
+        ))
+    }
+
+    /// Load both users of a relationship
+    async fn find_pair(&self, user_id: Snowflake, target_id: Snowflake) -> ServiceResult<(User, User)> {
+        let user = self
+            .ctx
+            .user_repo()
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", user_id.to_string()))?;
+        let target = self
+            .ctx
+            .user_repo()
+            .find_by_id(target_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("User", target_id.to_string()))?;
+
+        Ok((user, target))
+    }
+
+    /// Publish RELATIONSHIP_ADD to the relationship's owner
+    async fn publish_add(&self, relationship: Relationship, target: User) {
+        let user_id = relationship.user_id;
+        let response = RelationshipResponse::from(RelationshipWithUser {
+            relationship,
+            user: target,
+        });
+
+        let event = PubSubEvent::new("RELATIONSHIP_ADD", json!(response));
+        self.ctx
+            .publisher()
+            .publish_user(user_id, &event)
+            .await
+            .ok();
+    }
+
+    /// Publish RELATIONSHIP_REMOVE to the relationship's owner
+    async fn publish_remove(&self, relationship: &Relationship) {
+        let event = PubSubEvent::new(
+            "RELATIONSHIP_REMOVE",
+            json!({
+                "id": relationship.target_id.to_string(),
+                "type": relationship.relationship_type.as_i16()
+            }),
+        );
+        self.ctx
+            .publisher()
+            .publish_user(relationship.user_id, &event)
+            .await
+            .ok();
+    }
+}
@@ -3,14 +3,15 @@
 //! Handles role creation, management, and permission assignment.
 
 use chat_cache::{PubSubChannel, PubSubEvent};
-use chat_core::entities::Role;
+use chat_core::entities::{AuditLogAction, AuditLogEntry, Role};
 use chat_core::{Permissions, Snowflake};
 use chrono::Utc;
-use serde_json::json;
+use serde_json::{json, Value};
 use tracing::{info, instrument};
 
 use crate::dto::{CreateRoleRequest, RoleResponse, RolePosition, UpdateRoleRequest};
 
+use super::audit_log::AuditLogService;
 use super::context::ServiceContext;
 use super::error::{ServiceError, ServiceResult};
 use super::permission::PermissionService;
@@ -33,6 +34,7 @@ impl<'a> RoleService<'a> {
         guild_id: Snowflake,
         user_id: Snowflake,
         request: CreateRoleRequest,
+        reason: Option<String>,
     ) -> ServiceResult<RoleResponse> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -90,6 +92,17 @@ impl<'a> RoleService<'a> {
 
         info!(role_id = %role_id, guild_id = %guild_id, "Role created");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::RoleCreate,
+                (role_id, "role"),
+                AuditLogEntry::diff(&Value::Null, &Self::audit_snapshot(&role)),
+                reason,
+            )
+            .await;
+
         // Publish GUILD_ROLE_CREATE event
         self.publish_role_event("GUILD_ROLE_CREATE", guild_id, &role).await;
 
@@ -117,6 +130,7 @@ impl<'a> RoleService<'a> {
         role_id: Snowflake,
         user_id: Snowflake,
         request: UpdateRoleRequest,
+        reason: Option<String>,
     ) -> ServiceResult<RoleResponse> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -151,6 +165,7 @@ impl<'a> RoleService<'a> {
             return Err(ServiceError::permission_denied("Cannot edit this role"));
         }
 
+        let before = Self::audit_snapshot(&role);
         let mut changed = false;
 
         // Update name
@@ -203,6 +218,17 @@ impl<'a> RoleService<'a> {
 
             info!(role_id = %role_id, "Role updated");
 
+            AuditLogService::new(self.ctx)
+                .record(
+                    guild_id,
+                    user_id,
+                    AuditLogAction::RoleUpdate,
+                    (role_id, "role"),
+                    AuditLogEntry::diff(&before, &Self::audit_snapshot(&role)),
+                    reason,
+                )
+                .await;
+
             // Publish GUILD_ROLE_UPDATE event
             self.publish_role_event("GUILD_ROLE_UPDATE", guild_id, &role).await;
         }
@@ -217,6 +243,7 @@ impl<'a> RoleService<'a> {
         guild_id: Snowflake,
         role_id: Snowflake,
         user_id: Snowflake,
+        reason: Option<String>,
     ) -> ServiceResult<()> {
         let permission_service = PermissionService::new(self.ctx);
 
@@ -224,6 +251,7 @@ impl<'a> RoleService<'a> {
         permission_service
             .require_permission(guild_id, user_id, Permissions::MANAGE_ROLES)
             .await?;
+        permission_service.require_mfa(guild_id, user_id).await?;
 
         let role = self
             .ctx
@@ -254,6 +282,17 @@ impl<'a> RoleService<'a> {
 
         info!(role_id = %role_id, guild_id = %guild_id, "Role deleted");
 
+        AuditLogService::new(self.ctx)
+            .record(
+                guild_id,
+                user_id,
+                AuditLogAction::RoleDelete,
+                (role_id, "role"),
+                AuditLogEntry::diff(&Self::audit_snapshot(&role), &Value::Null),
+                reason,
+            )
+            .await;
+
         // Publish GUILD_ROLE_DELETE event
         let event = PubSubEvent::new(
             "GUILD_ROLE_DELETE",
@@ -378,6 +417,18 @@ impl<'a> RoleService<'a> {
         Ok(highest)
     }
 
+    /// Fields of a role tracked in the audit log
+    fn audit_snapshot(role: &Role) -> Value {
+        json!({
+            "name": role.name,
+            "color": role.color,
+            "hoist": role.hoist,
+            "position": role.position,
+            "permissions": role.permissions.to_string(),
+            "mentionable": role.mentionable
+        })
+    }
+
     /// Helper to publish role events
     async fn publish_role_event(&self, event_type: &str, guild_id: Snowflake, role: &Role) {
         let data = json!({
@@ -0,0 +1,575 @@
+//! Thread service
+//!
+//! Handles threads started from messages, thread membership, and auto-archiving.
+
+use chat_cache::{PubSubChannel, PubSubEvent};
+use chat_core::entities::{
+    Channel, ChannelType, Thread, ThreadMember, ThreadMetadata, DEFAULT_AUTO_ARCHIVE_DURATION,
+};
+use chat_core::{Permissions, Snowflake};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use tracing::{info, instrument};
+
+use crate::dto::{
+    StartThreadRequest, ThreadListResponse, ThreadMemberResponse, ThreadResponse,
+    UpdateChannelRequest,
+};
+
+use super::context::ServiceContext;
+use super::error::{ServiceError, ServiceResult};
+use super::permission::PermissionService;
+
+/// Default number of archived threads per page
+const DEFAULT_ARCHIVED_LIMIT: i64 = 50;
+
+/// Maximum number of archived threads per page
+const MAX_ARCHIVED_LIMIT: i64 = 100;
+
+/// Thread service
+pub struct ThreadService<'a> {
+    ctx: &'a ServiceContext,
+}
+
+impl<'a> ThreadService<'a> {
+    /// Create a new ThreadService
+    pub fn new(ctx: &'a ServiceContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Start a thread from a message in a text channel
+    #[instrument(skip(self, request))]
+    pub async fn start_thread(
+        &self,
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+        request: StartThreadRequest,
+    ) -> ServiceResult<ThreadResponse> {
+        let parent = self.get_parent_channel(channel_id).await?;
+        let guild_id = parent
+            .guild_id
+            .ok_or_else(|| ServiceError::validation("Threads can only be started in guild channels"))?;
+
+        PermissionService::new(self.ctx)
+            .require_permission_in(
+                &parent,
+                user_id,
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            )
+            .await?;
+
+        // Verify the message belongs to the parent channel
+        let message = self
+            .ctx
+            .message_repo()
+            .find_by_id(message_id)
+            .await?
+            .filter(|message| message.channel_id == channel_id)
+            .ok_or_else(|| ServiceError::not_found("Message", message_id.to_string()))?;
+
+        if self.ctx.thread_repo().find_by_message(message.id).await?.is_some() {
+            return Err(ServiceError::conflict("A thread has already been started from this message"));
+        }
+
+        let auto_archive_duration = request
+            .auto_archive_duration
+            .unwrap_or(DEFAULT_AUTO_ARCHIVE_DURATION);
+        Self::validate_auto_archive_duration(auto_archive_duration)?;
+
+        let mut thread = Thread::new(
+            self.ctx.generate_id(),
+            guild_id,
+            channel_id,
+            message_id,
+            user_id,
+            request.name,
+            auto_archive_duration,
+        );
+
+        self.ctx.thread_repo().create(&thread).await?;
+
+        // The creator is always the first member
+        self.ctx
+            .thread_repo()
+            .add_member(&ThreadMember::new(thread.id(), user_id))
+            .await?;
+        thread.member_count = 1;
+
+        info!(thread_id = %thread.id(), channel_id = %channel_id, "Thread created");
+
+        self.publish_thread_event("THREAD_CREATE", &thread).await;
+
+        Ok(ThreadResponse::from(&thread))
+    }
+
+    /// Get active threads in a channel
+    #[instrument(skip(self))]
+    pub async fn get_active_threads(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<ThreadListResponse> {
+        self.get_viewable_parent(channel_id, user_id).await?;
+        self.archive_inactive_threads(channel_id).await?;
+
+        let threads = self.ctx.thread_repo().find_active_by_parent(channel_id).await?;
+
+        Ok(ThreadListResponse {
+            threads: threads.iter().map(ThreadResponse::from).collect(),
+            has_more: false,
+        })
+    }
+
+    /// Get archived threads in a channel, newest archive first
+    #[instrument(skip(self))]
+    pub async fn get_archived_threads(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+        before: Option<DateTime<Utc>>,
+        limit: Option<i64>,
+    ) -> ServiceResult<ThreadListResponse> {
+        self.get_viewable_parent(channel_id, user_id).await?;
+        self.archive_inactive_threads(channel_id).await?;
+
+        let limit = limit
+            .unwrap_or(DEFAULT_ARCHIVED_LIMIT)
+            .clamp(1, MAX_ARCHIVED_LIMIT);
+
+        // Fetch one extra row to know whether another page exists
+        let mut threads = self
+            .ctx
+            .thread_repo()
+            .find_archived_by_parent(channel_id, before, limit + 1)
+            .await?;
+        let has_more = threads.len() as i64 > limit;
+        threads.truncate(limit as usize);
+
+        Ok(ThreadListResponse {
+            threads: threads.iter().map(ThreadResponse::from).collect(),
+            has_more,
+        })
+    }
+
+    /// Update a thread's name and state
+    ///
+    /// The owner may rename, archive, and unarchive their thread; everything
+    /// else (locking, other users' threads, reopening locked threads) needs
+    /// MANAGE_THREADS.
+    #[instrument(skip(self, request))]
+    pub async fn update_thread(
+        &self,
+        thread_id: Snowflake,
+        user_id: Snowflake,
+        request: UpdateChannelRequest,
+    ) -> ServiceResult<ThreadResponse> {
+        let mut thread = self.get_thread(thread_id).await?;
+
+        let perms = PermissionService::new(self.ctx)
+            .compute_channel_permissions(&thread.channel, user_id)
+            .await?;
+        if !perms.has(Permissions::VIEW_CHANNEL) {
+            return Err(ServiceError::permission_denied("VIEW_CHANNEL"));
+        }
+
+        let can_manage = perms.has(Permissions::MANAGE_THREADS);
+        if !can_manage {
+            if !thread.is_owner(user_id) {
+                return Err(ServiceError::permission_denied("MANAGE_THREADS"));
+            }
+            if request.locked.is_some() || thread.metadata.locked {
+                return Err(ServiceError::permission_denied("MANAGE_THREADS"));
+            }
+        }
+
+        let mut channel_changed = false;
+        let mut metadata_changed = false;
+
+        if let Some(name) = request.name {
+            thread.channel.name = Some(name);
+            channel_changed = true;
+        }
+
+        if let Some(duration) = request.auto_archive_duration {
+            Self::validate_auto_archive_duration(duration)?;
+            thread.metadata.auto_archive_duration = duration;
+            metadata_changed = true;
+        }
+
+        if let Some(locked) = request.locked {
+            thread.metadata.locked = locked;
+            metadata_changed = true;
+        }
+
+        if let Some(archived) = request.archived {
+            if thread.metadata.archived && !archived {
+                // Reopening restarts the inactivity timer
+                thread.metadata.touch();
+            }
+            thread.metadata.set_archived(archived);
+            metadata_changed = true;
+        }
+
+        if channel_changed {
+            thread.channel.updated_at = Utc::now();
+            self.ctx.channel_repo().update(&thread.channel).await?;
+        }
+        if metadata_changed {
+            self.ctx
+                .thread_repo()
+                .update_metadata(thread_id, &thread.metadata)
+                .await?;
+        }
+
+        if channel_changed || metadata_changed {
+            self.publish_thread_event("THREAD_UPDATE", &thread).await;
+        }
+
+        Ok(ThreadResponse::from(&thread))
+    }
+
+    /// Delete a thread
+    #[instrument(skip(self))]
+    pub async fn delete_thread(&self, thread_id: Snowflake, user_id: Snowflake) -> ServiceResult<()> {
+        let thread = self.get_thread(thread_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(&thread.channel, user_id, Permissions::MANAGE_THREADS)
+            .await?;
+        if let Some(guild_id) = thread.channel.guild_id {
+            permission_service.require_mfa(guild_id, user_id).await?;
+        }
+
+        self.ctx.channel_repo().delete(thread_id).await?;
+
+        info!(thread_id = %thread_id, "Thread deleted");
+
+        if let (Some(guild_id), Some(parent_id)) = (thread.channel.guild_id, thread.parent_id()) {
+            let event = PubSubEvent::new(
+                "THREAD_DELETE",
+                json!({
+                    "id": thread_id.to_string(),
+                    "guild_id": guild_id.to_string(),
+                    "parent_id": parent_id.to_string(),
+                    "type": 11
+                }),
+            );
+            self.ctx
+                .publisher()
+                .publish(&PubSubChannel::channel(parent_id), &event)
+                .await
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Thread Members
+    // ========================================================================
+
+    /// Get members of a thread
+    #[instrument(skip(self))]
+    pub async fn get_thread_members(
+        &self,
+        thread_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<Vec<ThreadMemberResponse>> {
+        let thread = self.get_thread(thread_id).await?;
+
+        PermissionService::new(self.ctx)
+            .require_permission_in(&thread.channel, user_id, Permissions::VIEW_CHANNEL)
+            .await?;
+
+        let members = self.ctx.thread_repo().find_members(thread_id).await?;
+
+        Ok(members.iter().map(ThreadMemberResponse::from).collect())
+    }
+
+    /// Join a thread as the current user
+    #[instrument(skip(self))]
+    pub async fn join_thread(&self, thread_id: Snowflake, user_id: Snowflake) -> ServiceResult<()> {
+        let thread = self.get_thread(thread_id).await?;
+
+        PermissionService::new(self.ctx)
+            .require_permission_in(&thread.channel, user_id, Permissions::VIEW_CHANNEL)
+            .await?;
+
+        if thread.metadata.archived {
+            return Err(ServiceError::validation("Cannot join an archived thread"));
+        }
+
+        self.add_thread_member(&thread, user_id).await
+    }
+
+    /// Leave a thread as the current user
+    #[instrument(skip(self))]
+    pub async fn leave_thread(&self, thread_id: Snowflake, user_id: Snowflake) -> ServiceResult<()> {
+        let thread = self.get_thread(thread_id).await?;
+        self.remove_thread_member(&thread, user_id).await
+    }
+
+    /// Add another user to a thread
+    #[instrument(skip(self))]
+    pub async fn add_member(
+        &self,
+        thread_id: Snowflake,
+        user_id: Snowflake,
+        target_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let thread = self.get_thread(thread_id).await?;
+
+        let permission_service = PermissionService::new(self.ctx);
+        permission_service
+            .require_permission_in(
+                &thread.channel,
+                user_id,
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            )
+            .await?;
+
+        if thread.metadata.archived {
+            return Err(ServiceError::validation("Cannot add members to an archived thread"));
+        }
+
+        // The target must be able to see the thread
+        let target_perms = permission_service
+            .compute_channel_permissions(&thread.channel, target_id)
+            .await?;
+        if !target_perms.has(Permissions::VIEW_CHANNEL) {
+            return Err(ServiceError::not_found("Member", target_id.to_string()));
+        }
+
+        self.add_thread_member(&thread, target_id).await
+    }
+
+    /// Remove another user from a thread
+    #[instrument(skip(self))]
+    pub async fn remove_member(
+        &self,
+        thread_id: Snowflake,
+        user_id: Snowflake,
+        target_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let thread = self.get_thread(thread_id).await?;
+
+        if !thread.is_owner(user_id) {
+            PermissionService::new(self.ctx)
+                .require_permission_in(&thread.channel, user_id, Permissions::MANAGE_THREADS)
+                .await?;
+        }
+
+        self.remove_thread_member(&thread, target_id).await
+    }
+
+    // ========================================================================
+    // Messaging
+    // ========================================================================
+
+    /// Verify a user may post in a thread, reopening it if it was archived
+    ///
+    /// Locked threads only accept messages from users with MANAGE_THREADS.
+    pub async fn prepare_for_message(
+        &self,
+        channel: &Channel,
+        user_id: Snowflake,
+    ) -> ServiceResult<()> {
+        let mut thread = self.get_thread(channel.id).await?;
+
+        if thread.metadata.locked {
+            PermissionService::new(self.ctx)
+                .require_permission_in(channel, user_id, Permissions::MANAGE_THREADS)
+                .await?;
+        }
+
+        if thread.metadata.archived {
+            thread.metadata.touch();
+            thread.metadata.set_archived(false);
+            self.ctx
+                .thread_repo()
+                .update_metadata(thread.id(), &thread.metadata)
+                .await?;
+            self.publish_thread_event("THREAD_UPDATE", &thread).await;
+        }
+
+        Ok(())
+    }
+
+    /// Record a new message in a thread
+    ///
+    /// Resets the auto-archive timer and adds the author as a member.
+    pub async fn record_message(&self, channel: &Channel, author_id: Snowflake) -> ServiceResult<()> {
+        let mut thread = self.get_thread(channel.id).await?;
+
+        thread.metadata.touch();
+        self.ctx
+            .thread_repo()
+            .update_metadata(thread.id(), &thread.metadata)
+            .await?;
+
+        self.add_thread_member(&thread, author_id).await
+    }
+
+    // ========================================================================
+    // Helpers
+    // ========================================================================
+
+    /// Load a thread, archiving it first if it has gone inactive
+    async fn get_thread(&self, thread_id: Snowflake) -> ServiceResult<Thread> {
+        let mut thread = self
+            .ctx
+            .thread_repo()
+            .find_by_id(thread_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Thread", thread_id.to_string()))?;
+
+        if thread.metadata.is_inactive(Utc::now()) {
+            thread.metadata.set_archived(true);
+            self.ctx
+                .thread_repo()
+                .update_metadata(thread_id, &thread.metadata)
+                .await?;
+            self.publish_thread_event("THREAD_UPDATE", &thread).await;
+        }
+
+        Ok(thread)
+    }
+
+    /// Archive every inactive thread under a parent channel
+    async fn archive_inactive_threads(&self, parent_id: Snowflake) -> ServiceResult<()> {
+        let archived = self.ctx.thread_repo().archive_inactive(parent_id).await?;
+        for thread in &archived {
+            self.publish_thread_event("THREAD_UPDATE", thread).await;
+        }
+        Ok(())
+    }
+
+    /// Load a text channel that can hold threads
+    async fn get_parent_channel(&self, channel_id: Snowflake) -> ServiceResult<Channel> {
+        let channel = self
+            .ctx
+            .channel_repo()
+            .find_by_id(channel_id)
+            .await?
+            .ok_or_else(|| ServiceError::not_found("Channel", channel_id.to_string()))?;
+
+        if channel.channel_type != ChannelType::GuildText {
+            return Err(ServiceError::validation("Threads can only be started in text channels"));
+        }
+
+        Ok(channel)
+    }
+
+    /// Load a parent channel the user can view
+    async fn get_viewable_parent(
+        &self,
+        channel_id: Snowflake,
+        user_id: Snowflake,
+    ) -> ServiceResult<Channel> {
+        let channel = self.get_parent_channel(channel_id).await?;
+
+        PermissionService::new(self.ctx)
+            .require_permission_in(&channel, user_id, Permissions::VIEW_CHANNEL)
+            .await?;
+
+        Ok(channel)
+    }
+
+    /// Add a member and publish THREAD_MEMBERS_UPDATE if they were not already in
+    async fn add_thread_member(&self, thread: &Thread, user_id: Snowflake) -> ServiceResult<()> {
+        let member = ThreadMember::new(thread.id(), user_id);
+        if self.ctx.thread_repo().add_member(&member).await? {
+            self.publish_members_update(
+                thread,
+                thread.member_count + 1,
+                json!([{
+                    "id": member.thread_id.to_string(),
+                    "user_id": member.user_id.to_string(),
+                    "join_timestamp": member.joined_at.to_rfc3339()
+                }]),
+                json!([]),
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Remove a member and publish THREAD_MEMBERS_UPDATE if they were in the thread
+    async fn remove_thread_member(&self, thread: &Thread, user_id: Snowflake) -> ServiceResult<()> {
+        if !self.ctx.thread_repo().remove_member(thread.id(), user_id).await? {
+            return Err(ServiceError::not_found("ThreadMember", user_id.to_string()));
+        }
+
+        self.publish_members_update(
+            thread,
+            (thread.member_count - 1).max(0),
+            json!([]),
+            json!([user_id.to_string()]),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Check an auto-archive duration against the allowed values
+    fn validate_auto_archive_duration(minutes: i32) -> ServiceResult<()> {
+        if ThreadMetadata::is_valid_auto_archive_duration(minutes) {
+            Ok(())
+        } else {
+            Err(ServiceError::validation(
+                "auto_archive_duration must be one of 60, 1440, 4320, 10080",
+            ))
+        }
+    }
+
+    /// Helper to publish THREAD_CREATE / THREAD_UPDATE events to the parent channel
+    async fn publish_thread_event(&self, event_type: &str, thread: &Thread) {
+        let Some(parent_id) = thread.parent_id() else {
+            return;
+        };
+
+        let data = serde_json::to_value(ThreadResponse::from(thread)).unwrap_or_default();
+        let event = PubSubEvent::new(event_type, data);
+        self.ctx
+            .publisher()
+            .publish(&PubSubChannel::channel(parent_id), &event)
+            .await
+            .ok();
+    }
+
+    /// Helper to publish THREAD_MEMBERS_UPDATE events to the parent channel
+    async fn publish_members_update(
+        &self,
+        thread: &Thread,
+        member_count: i32,
+        added_members: serde_json::Value,
+        removed_member_ids: serde_json::Value,
+    ) {
+        let (Some(guild_id), Some(parent_id)) = (thread.channel.guild_id, thread.parent_id()) else {
+            return;
+        };
+
+        let event = PubSubEvent::new(
+            "THREAD_MEMBERS_UPDATE",
+            json!({
+                "id": thread.id().to_string(),
+                "guild_id": guild_id.to_string(),
+                "member_count": member_count,
+                "added_members": added_members,
+                "removed_member_ids": removed_member_ids
+            }),
+        );
+        self.ctx
+            .publisher()
+            .publish(&PubSubChannel::channel(parent_id), &event)
+            .await
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would go here with mocked dependencies
+}
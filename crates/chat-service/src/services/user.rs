@@ -2,7 +2,7 @@
 //!
 //! Handles user profile operations.
 
-use chat_core::entities::User;
+use chat_core::entities::{DmPrivacy, User};
 use chat_core::Snowflake;
 use chrono::Utc;
 use tracing::{info, instrument};
@@ -96,6 +96,13 @@ impl<'a> UserService<'a> {
             changed = true;
         }
 
+        // Update DM privacy if provided
+        if let Some(dm_privacy) = request.dm_privacy {
+            user.dm_privacy = DmPrivacy::from_i16(dm_privacy)
+                .ok_or_else(|| ServiceError::validation("Invalid DM privacy setting"))?;
+            changed = true;
+        }
+
         if changed {
             user.updated_at = Utc::now();
             self.ctx.user_repo().update(&user).await?;
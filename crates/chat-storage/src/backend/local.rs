@@ -0,0 +1,179 @@
+//! Local filesystem storage backend
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+use tracing::{debug, instrument, warn};
+
+use super::{validate_key, ByteStream, StorageBackend, StoredObject};
+use crate::error::{StorageError, StorageResult};
+
+/// Stores objects as files below a root directory
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Create a new LocalStorage rooted at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root directory of this backend
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn resolve(&self, key: &str) -> StorageResult<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorage {
+    #[instrument(skip(self, body))]
+    async fn put(&self, key: &str, mut body: ByteStream<'_>) -> StorageResult<u64> {
+        let path = self.resolve(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // Write to a temporary file first so readers never see partial uploads
+        let tmp_path = path.with_extension("part");
+        let mut file = fs::File::create(&tmp_path).await?;
+
+        let result: StorageResult<u64> = async {
+            let mut written = 0u64;
+            while let Some(chunk) = body.next().await {
+                let chunk = chunk?;
+                file.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            file.flush().await?;
+            Ok(written)
+        }
+        .await;
+
+        match result {
+            Ok(written) => {
+                drop(file);
+                fs::rename(&tmp_path, &path).await?;
+                debug!(key, size = written, "Stored file");
+                Ok(written)
+            }
+            Err(e) => {
+                drop(file);
+                if let Err(cleanup) = fs::remove_file(&tmp_path).await {
+                    warn!(key, error = %cleanup, "Failed to remove partial upload");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn get(&self, key: &str) -> StorageResult<StoredObject> {
+        let path = self.resolve(key)?;
+        let file = match fs::File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(key.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let size = file.metadata().await?.len();
+
+        Ok(StoredObject {
+            size,
+            body: ReaderStream::new(file).boxed(),
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        let path = self.resolve(key)?;
+        match fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use futures_util::stream;
+
+    fn temp_root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("chat-storage-{name}-{}", std::process::id()))
+    }
+
+    fn body(chunks: &[&'static [u8]]) -> ByteStream<'static> {
+        let chunks: Vec<_> = chunks.iter().map(|c| Ok(Bytes::from_static(c))).collect();
+        stream::iter(chunks).boxed()
+    }
+
+    async fn read_all(mut body: ByteStream<'_>) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(chunk) = body.next().await {
+            out.extend_from_slice(&chunk.unwrap());
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn test_put_get_delete_roundtrip() {
+        let root = temp_root("roundtrip");
+        let storage = LocalStorage::new(&root);
+
+        let written = storage
+            .put("a/b/hello.txt", body(&[b"hello ", b"world"]))
+            .await
+            .unwrap();
+        assert_eq!(written, 11);
+
+        let object = storage.get("a/b/hello.txt").await.unwrap();
+        assert_eq!(object.size, 11);
+        assert_eq!(read_all(object.body).await, b"hello world");
+
+        storage.delete("a/b/hello.txt").await.unwrap();
+        assert!(storage.get("a/b/hello.txt").await.unwrap_err().is_not_found());
+        // Deleting twice is fine
+        storage.delete("a/b/hello.txt").await.unwrap();
+
+        let _ = fs::remove_dir_all(&root).await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_leaves_no_file() {
+        let root = temp_root("failed");
+        let storage = LocalStorage::new(&root);
+
+        let failing: ByteStream<'_> = stream::iter(vec![
+            Ok(Bytes::from_static(b"partial")),
+            Err(std::io::Error::other("client went away")),
+        ])
+        .boxed();
+
+        assert!(storage.put("x/file.bin", failing).await.is_err());
+        assert!(storage.get("x/file.bin").await.unwrap_err().is_not_found());
+        assert!(!root.join("x/file.part").exists());
+
+        let _ = fs::remove_dir_all(&root).await;
+    }
+
+    #[tokio::test]
+    async fn test_rejects_traversal() {
+        let storage = LocalStorage::new(temp_root("traversal"));
+        let err = storage.get("../outside").await.unwrap_err();
+        assert!(matches!(err, StorageError::InvalidKey(_)));
+    }
+}
@@ -0,0 +1,86 @@
+//! Storage backends
+//!
+//! A backend stores opaque objects under slash-separated keys. Higher level
+//! concerns (size limits, image probing, URLs) live in [`crate::FileStore`].
+
+mod local;
+mod s3;
+
+pub use local::LocalStorage;
+pub use s3::S3Storage;
+
+use std::io;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+
+use crate::error::{StorageError, StorageResult};
+
+/// Streaming body used for uploads and downloads
+pub type ByteStream<'a> = BoxStream<'a, io::Result<Bytes>>;
+
+/// Object returned by [`StorageBackend::get`]
+pub struct StoredObject {
+    /// Object size in bytes
+    pub size: u64,
+    /// Object contents
+    pub body: ByteStream<'static>,
+}
+
+impl std::fmt::Debug for StoredObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredObject")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// Pluggable object storage backend
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Stream `body` into the object at `key`, returning the number of bytes written
+    ///
+    /// If the body yields an error the partial object must not become visible.
+    async fn put(&self, key: &str, body: ByteStream<'_>) -> StorageResult<u64>;
+
+    /// Open the object at `key` for streaming
+    async fn get(&self, key: &str) -> StorageResult<StoredObject>;
+
+    /// Delete the object at `key` (deleting a missing object is not an error)
+    async fn delete(&self, key: &str) -> StorageResult<()>;
+}
+
+/// Validate a storage key
+///
+/// Keys are relative, slash-separated and may not contain empty, `.` or `..`
+/// segments, so they are safe to join onto a filesystem root.
+pub fn validate_key(key: &str) -> StorageResult<()> {
+    let valid = !key.is_empty()
+        && !key.contains('\\')
+        && !key.contains('\0')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("attachments/1/2/file.png").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("attachments/../secret").is_err());
+        assert!(validate_key("attachments//file").is_err());
+        assert!(validate_key("attachments\\file").is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! S3-compatible storage backend (AWS S3, MinIO, ...)
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chat_common::S3Config;
+use futures_util::{StreamExt, TryStreamExt};
+use object_store::aws::AmazonS3Builder;
+use object_store::path::Path;
+use object_store::{ObjectStore, WriteMultipart};
+use tracing::{debug, instrument, warn};
+
+use super::{validate_key, ByteStream, StorageBackend, StoredObject};
+use crate::error::{StorageError, StorageResult};
+
+/// Maximum number of multipart chunks in flight per upload
+const MAX_CONCURRENT_PARTS: usize = 4;
+
+/// Stores objects in an S3-compatible bucket
+#[derive(Clone)]
+pub struct S3Storage {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl S3Storage {
+    /// Create a new S3Storage from configuration
+    pub fn new(config: &S3Config) -> StorageResult<Self> {
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(&config.bucket)
+            .with_region(&config.region)
+            .with_access_key_id(&config.access_key_id)
+            .with_secret_access_key(&config.secret_access_key)
+            .with_allow_http(config.allow_http);
+
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+
+        let store = builder
+            .build()
+            .map_err(|e| StorageError::Config(e.to_string()))?;
+
+        Ok(Self::from_store(Arc::new(store)))
+    }
+
+    /// Wrap an existing object store
+    pub fn from_store(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+
+    fn path(key: &str) -> StorageResult<Path> {
+        validate_key(key)?;
+        Path::parse(key).map_err(|e| StorageError::InvalidKey(e.to_string()))
+    }
+}
+
+impl std::fmt::Debug for S3Storage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Storage")
+            .field("store", &self.store.to_string())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    #[instrument(skip(self, body))]
+    async fn put(&self, key: &str, mut body: ByteStream<'_>) -> StorageResult<u64> {
+        let path = Self::path(key)?;
+        let upload = self.store.put_multipart(&path).await?;
+        let mut writer = WriteMultipart::new(upload);
+
+        let mut written = 0u64;
+        while let Some(chunk) = body.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    if let Err(abort) = writer.abort().await {
+                        warn!(key, error = %abort, "Failed to abort multipart upload");
+                    }
+                    return Err(e.into());
+                }
+            };
+            written += chunk.len() as u64;
+            writer.wait_for_capacity(MAX_CONCURRENT_PARTS).await?;
+            writer.put(chunk);
+        }
+
+        writer.finish().await?;
+        debug!(key, size = written, "Stored object");
+        Ok(written)
+    }
+
+    #[instrument(skip(self))]
+    async fn get(&self, key: &str) -> StorageResult<StoredObject> {
+        let path = Self::path(key)?;
+        let result = self.store.get(&path).await?;
+        let size = result.meta.size as u64;
+
+        Ok(StoredObject {
+            size,
+            body: result.into_stream().map_err(std::io::Error::from).boxed(),
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, key: &str) -> StorageResult<()> {
+        let path = Self::path(key)?;
+        match self.store.delete(&path).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
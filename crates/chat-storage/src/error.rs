@@ -0,0 +1,85 @@
+//! Storage error types
+
+use std::io;
+
+/// Error type for storage operations
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Object not found: {0}")]
+    NotFound(String),
+
+    #[error("File exceeds maximum size of {max} bytes")]
+    TooLarge { max: u64 },
+
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+
+    #[error("Storage I/O error: {0}")]
+    Io(io::Error),
+
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+
+    #[error("Storage configuration error: {0}")]
+    Config(String),
+}
+
+impl StorageError {
+    /// Check if this is a not found error
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+}
+
+/// Marker carried inside an `io::Error` when an upload stream exceeds its size limit
+///
+/// Backends only see the body as an `io::Result<Bytes>` stream, so the limit
+/// violation travels through them as an I/O error and is recovered here.
+#[derive(Debug, thiserror::Error)]
+#[error("upload exceeds {0} bytes")]
+pub(crate) struct SizeLimitExceeded(pub u64);
+
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        if let Some(SizeLimitExceeded(max)) = err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<SizeLimitExceeded>())
+        {
+            return Self::TooLarge { max: *max };
+        }
+        Self::Io(err)
+    }
+}
+
+impl From<object_store::Error> for StorageError {
+    fn from(err: object_store::Error) -> Self {
+        match err {
+            object_store::Error::NotFound { path, .. } => Self::NotFound(path),
+            object_store::Error::InvalidPath { source } => Self::InvalidKey(source.to_string()),
+            other => Self::Backend(other.to_string()),
+        }
+    }
+}
+
+/// Result type for storage operations
+pub type StorageResult<T> = Result<T, StorageError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_limit_recovered_from_io_error() {
+        let err = io::Error::other(SizeLimitExceeded(1024));
+        assert!(matches!(
+            StorageError::from(err),
+            StorageError::TooLarge { max: 1024 }
+        ));
+    }
+
+    #[test]
+    fn test_plain_io_error() {
+        let err = io::Error::new(io::ErrorKind::BrokenPipe, "closed");
+        assert!(matches!(StorageError::from(err), StorageError::Io(_)));
+    }
+}
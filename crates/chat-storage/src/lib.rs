@@ -0,0 +1,52 @@
+//! # chat-storage
+//!
+//! File storage layer for message attachments.
+//!
+//! ## Features
+//!
+//! - **Pluggable Backends**: [`StorageBackend`] trait with local filesystem and
+//!   S3-compatible (AWS S3, MinIO) implementations
+//! - **Streaming Uploads**: Files are streamed to the backend, never fully buffered
+//! - **Limits & Probing**: Size limits and image type/dimension detection
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use chat_storage::{attachment_key, FileStore};
+//!
+//! let store = chat_storage::from_config(&config.storage)?;
+//! let key = attachment_key(channel_id, attachment_id, "cat.png");
+//! let uploaded = store.upload(&key, body).await?;
+//! let url = store.url_for(&key);
+//! ```
+
+pub mod backend;
+pub mod error;
+pub mod store;
+
+use std::sync::Arc;
+
+use chat_common::{StorageBackendKind, StorageConfig};
+
+pub use backend::{ByteStream, LocalStorage, S3Storage, StorageBackend, StoredObject};
+pub use error::{StorageError, StorageResult};
+pub use store::{attachment_key, sanitize_filename, FileStore, UploadedFile};
+
+/// Build a [`FileStore`] from application configuration
+pub fn from_config(config: &StorageConfig) -> StorageResult<FileStore> {
+    let backend: Arc<dyn StorageBackend> = match config.backend {
+        StorageBackendKind::Local => Arc::new(LocalStorage::new(&config.upload_dir)),
+        StorageBackendKind::S3 => {
+            let s3 = config.s3.as_ref().ok_or_else(|| {
+                StorageError::Config("S3 backend selected but S3_BUCKET is not set".to_string())
+            })?;
+            Arc::new(S3Storage::new(s3)?)
+        }
+    };
+
+    Ok(FileStore::new(
+        backend,
+        config.max_file_size_bytes(),
+        config.public_url.clone(),
+    ))
+}
@@ -0,0 +1,316 @@
+//! Attachment file store
+//!
+//! Wraps a [`StorageBackend`] with upload size limits, image probing and
+//! CDN-style URL generation.
+
+use std::io;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt};
+
+use crate::backend::{ByteStream, StorageBackend, StoredObject};
+use crate::error::{SizeLimitExceeded, StorageResult};
+
+/// Number of leading bytes buffered to detect image type and dimensions
+const PROBE_LEN: usize = 64 * 1024;
+
+/// Maximum length of a stored filename
+const MAX_FILENAME_LEN: usize = 255;
+
+/// Result of a successful upload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// Storage key the file was written to
+    pub key: String,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type detected from the file contents (images only)
+    pub detected_content_type: Option<&'static str>,
+    /// Image width in pixels
+    pub width: Option<u32>,
+    /// Image height in pixels
+    pub height: Option<u32>,
+}
+
+/// Attachment file store shared by the service layer
+#[derive(Clone)]
+pub struct FileStore {
+    backend: Arc<dyn StorageBackend>,
+    max_file_size: u64,
+    public_url: String,
+}
+
+impl FileStore {
+    /// Create a new FileStore
+    ///
+    /// `public_url` is prepended to generated URLs; pass an empty string for
+    /// relative URLs.
+    pub fn new(
+        backend: Arc<dyn StorageBackend>,
+        max_file_size: u64,
+        public_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            backend,
+            max_file_size,
+            public_url: public_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Maximum accepted file size in bytes
+    pub fn max_file_size(&self) -> u64 {
+        self.max_file_size
+    }
+
+    /// Stream `body` to `key`, enforcing the size limit and probing image metadata
+    pub async fn upload<'a, S>(&self, key: &str, body: S) -> StorageResult<UploadedFile>
+    where
+        S: Stream<Item = io::Result<Bytes>> + Send + 'a,
+    {
+        let mut body = limit_stream(body, self.max_file_size);
+
+        // Buffer the head of the file for probing, then replay it in front of the rest
+        let mut head = Vec::new();
+        let mut buffered = Vec::new();
+        while head.len() < PROBE_LEN {
+            match body.next().await {
+                Some(chunk) => {
+                    let chunk = chunk?;
+                    head.extend_from_slice(&chunk);
+                    buffered.push(Ok(chunk));
+                }
+                None => break,
+            }
+        }
+        let probe = probe_image(&head);
+
+        let body = stream::iter(buffered).chain(body).boxed();
+        let size = self.backend.put(key, body).await?;
+
+        Ok(UploadedFile {
+            key: key.to_string(),
+            size,
+            detected_content_type: probe.map(|p| p.content_type),
+            width: probe.and_then(|p| p.width),
+            height: probe.and_then(|p| p.height),
+        })
+    }
+
+    /// Open a stored file
+    pub async fn open(&self, key: &str) -> StorageResult<StoredObject> {
+        self.backend.get(key).await
+    }
+
+    /// Delete a stored file
+    pub async fn delete(&self, key: &str) -> StorageResult<()> {
+        self.backend.delete(key).await
+    }
+
+    /// Public URL for a storage key
+    pub fn url_for(&self, key: &str) -> String {
+        format!("{}/{key}", self.public_url)
+    }
+}
+
+impl std::fmt::Debug for FileStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileStore")
+            .field("max_file_size", &self.max_file_size)
+            .field("public_url", &self.public_url)
+            .finish()
+    }
+}
+
+/// Storage key for a message attachment
+///
+/// Matches the CDN route `/attachments/{channel_id}/{attachment_id}/{filename}`.
+pub fn attachment_key(
+    channel_id: impl std::fmt::Display,
+    attachment_id: impl std::fmt::Display,
+    filename: &str,
+) -> String {
+    format!("attachments/{channel_id}/{attachment_id}/{filename}")
+}
+
+/// Sanitize a client-supplied filename for use in storage keys and URLs
+///
+/// Strips any directory components and replaces characters outside
+/// `[A-Za-z0-9._-]` with underscores.
+pub fn sanitize_filename(filename: &str) -> String {
+    let base = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+
+    let mut sanitized: String = base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let trimmed = sanitized.trim_start_matches('.');
+    if trimmed.is_empty() {
+        return "unknown".to_string();
+    }
+    sanitized = trimmed.to_string();
+
+    if sanitized.len() > MAX_FILENAME_LEN {
+        // Keep the extension when truncating
+        let ext = sanitized
+            .rfind('.')
+            .map(|i| sanitized[i..].to_string())
+            .filter(|ext| ext.len() < 16)
+            .unwrap_or_default();
+        sanitized.truncate(MAX_FILENAME_LEN - ext.len());
+        sanitized.push_str(&ext);
+    }
+
+    sanitized
+}
+
+/// Wrap a stream so it fails once more than `max` bytes have been read
+fn limit_stream<'a, S>(body: S, max: u64) -> ByteStream<'a>
+where
+    S: Stream<Item = io::Result<Bytes>> + Send + 'a,
+{
+    let mut total = 0u64;
+    body.map(move |chunk| {
+        let chunk = chunk?;
+        total += chunk.len() as u64;
+        if total > max {
+            Err(io::Error::other(SizeLimitExceeded(max)))
+        } else {
+            Ok(chunk)
+        }
+    })
+    .boxed()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ImageProbe {
+    content_type: &'static str,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// Detect a web image format and its dimensions from the head of a file
+fn probe_image(head: &[u8]) -> Option<ImageProbe> {
+    let content_type = match imagesize::image_type(head).ok()? {
+        imagesize::ImageType::Png => "image/png",
+        imagesize::ImageType::Jpeg => "image/jpeg",
+        imagesize::ImageType::Gif => "image/gif",
+        imagesize::ImageType::Webp => "image/webp",
+        imagesize::ImageType::Bmp => "image/bmp",
+        imagesize::ImageType::Ico => "image/x-icon",
+        imagesize::ImageType::Tiff => "image/tiff",
+        _ => return None,
+    };
+    let size = imagesize::blob_size(head).ok();
+
+    Some(ImageProbe {
+        content_type,
+        width: size.and_then(|s| u32::try_from(s.width).ok()),
+        height: size.and_then(|s| u32::try_from(s.height).ok()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::LocalStorage;
+    use crate::error::StorageError;
+
+    /// 1x1 transparent PNG
+    const PNG_1X1: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48,
+        0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00,
+        0x00, 0x1F, 0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78,
+        0x9C, 0x63, 0x00, 0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00,
+        0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    fn store(name: &str, max: u64) -> (FileStore, std::path::PathBuf) {
+        let root = std::env::temp_dir().join(format!("chat-store-{name}-{}", std::process::id()));
+        let backend = Arc::new(LocalStorage::new(&root));
+        (FileStore::new(backend, max, "http://cdn.local/"), root)
+    }
+
+    fn chunks(data: &'static [u8], size: usize) -> impl Stream<Item = io::Result<Bytes>> + Send {
+        stream::iter(data.chunks(size).map(|c| Ok(Bytes::from_static(c))).collect::<Vec<_>>())
+    }
+
+    #[tokio::test]
+    async fn test_upload_probes_image() {
+        let (store, root) = store("probe", 1024);
+        let uploaded = store.upload("img/pixel.png", chunks(PNG_1X1, 7)).await.unwrap();
+
+        assert_eq!(uploaded.size, PNG_1X1.len() as u64);
+        assert_eq!(uploaded.detected_content_type, Some("image/png"));
+        assert_eq!(uploaded.width, Some(1));
+        assert_eq!(uploaded.height, Some(1));
+        assert_eq!(store.open("img/pixel.png").await.unwrap().size, PNG_1X1.len() as u64);
+
+        let _ = tokio::fs::remove_dir_all(root).await;
+    }
+
+    #[tokio::test]
+    async fn test_upload_enforces_size_limit() {
+        let (store, root) = store("limit", 16);
+        let err = store
+            .upload("big/file.bin", chunks(&[0u8; 64], 8))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, StorageError::TooLarge { max: 16 }));
+        assert!(store.open("big/file.bin").await.unwrap_err().is_not_found());
+
+        let _ = tokio::fs::remove_dir_all(root).await;
+    }
+
+    #[tokio::test]
+    async fn test_upload_non_image() {
+        let (store, root) = store("text", 1024);
+        let uploaded = store.upload("t/notes.txt", chunks(b"plain text", 4)).await.unwrap();
+
+        assert_eq!(uploaded.size, 10);
+        assert_eq!(uploaded.detected_content_type, None);
+        assert_eq!(uploaded.width, None);
+
+        let _ = tokio::fs::remove_dir_all(root).await;
+    }
+
+    #[test]
+    fn test_url_for() {
+        let (store, _) = store("url", 1);
+        assert_eq!(
+            store.url_for("attachments/1/2/a.png"),
+            "http://cdn.local/attachments/1/2/a.png"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("photo.png"), "photo.png");
+        assert_eq!(sanitize_filename("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_filename("C:\\Users\\me\\cat.jpg"), "cat.jpg");
+        assert_eq!(sanitize_filename("my file (1).txt"), "my_file__1_.txt");
+        assert_eq!(sanitize_filename(".."), "unknown");
+        assert_eq!(sanitize_filename(""), "unknown");
+
+        let long = format!("{}.png", "a".repeat(300));
+        let sanitized = sanitize_filename(&long);
+        assert_eq!(sanitized.len(), MAX_FILENAME_LEN);
+        assert_eq!(
+            std::path::Path::new(&sanitized).extension(),
+            Some("png".as_ref())
+        );
+    }
+
+    #[test]
+    fn test_attachment_key() {
+        assert_eq!(attachment_key(10, 20, "a.png"), "attachments/10/20/a.png");
+    }
+}
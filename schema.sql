@@ -18,7 +18,36 @@ CREATE EXTENSION IF NOT EXISTS "pgcrypto";
 CREATE TYPE channel_type AS ENUM (
     'text',
     'category',
-    'dm'
+    'dm',
+    'thread',
+    'group_dm'
+);
+
+-- Permission overwrite target types
+CREATE TYPE overwrite_type AS ENUM (
+    'role',
+    'member'
+);
+
+-- Relationship types, from the owning user's point of view
+CREATE TYPE relationship_type AS ENUM (
+    'friend',
+    'blocked',
+    'incoming_request',
+    'outgoing_request'
+);
+
+-- Who may open a DM with a user
+CREATE TYPE dm_privacy AS ENUM (
+    'everyone',
+    'friends_and_guild_mates',
+    'friends'
+);
+
+-- Two-factor requirement for guild moderators
+CREATE TYPE mfa_level AS ENUM (
+    'none',
+    'elevated'
 );
 
 -- Presence status
@@ -42,7 +71,14 @@ CREATE TYPE audit_action AS ENUM (
     'member_ban',
     'member_unban',
     'member_role_update',
-    'message_delete'
+    'message_delete',
+    'message_pin',
+    'message_unpin'
+);
+
+CREATE TYPE message_type AS ENUM (
+    'default',
+    'channel_pinned_message'
 );
 
 -- ============================================================================
@@ -58,6 +94,11 @@ CREATE TABLE users (
     avatar          VARCHAR(255),
     bot             BOOLEAN NOT NULL DEFAULT FALSE,
     system          BOOLEAN NOT NULL DEFAULT FALSE,
+    privileged_intents BOOLEAN NOT NULL DEFAULT FALSE,
+    dm_privacy      dm_privacy NOT NULL DEFAULT 'everyone',
+    verified        BOOLEAN NOT NULL DEFAULT FALSE,
+    mfa_enabled     BOOLEAN NOT NULL DEFAULT FALSE,
+    totp_secret     VARCHAR(64),                     -- Base32, set while mfa_enabled
     created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at      TIMESTAMPTZ,
@@ -70,6 +111,30 @@ CREATE INDEX idx_users_email ON users(email) WHERE deleted_at IS NULL;
 CREATE INDEX idx_users_username ON users(username) WHERE deleted_at IS NULL;
 CREATE INDEX idx_users_bot ON users(bot) WHERE deleted_at IS NULL;
 
+CREATE TABLE user_backup_codes (
+    user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash       CHAR(64) NOT NULL,               -- SHA-256 hex of the normalized code
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, code_hash)
+);
+
+-- ============================================================================
+-- RELATIONSHIPS (Friends and blocks)
+-- ============================================================================
+
+CREATE TABLE relationships (
+    user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    target_id       BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    type            relationship_type NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, target_id),
+    CONSTRAINT relationships_not_self CHECK (user_id <> target_id)
+);
+
+CREATE INDEX idx_relationships_target ON relationships(target_id);
+
 -- ============================================================================
 -- GUILDS (Servers)
 -- ============================================================================
@@ -80,6 +145,7 @@ CREATE TABLE guilds (
     icon            VARCHAR(255),
     description     TEXT,
     owner_id        BIGINT NOT NULL REFERENCES users(id),
+    mfa_level       mfa_level NOT NULL DEFAULT 'none',
     created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at      TIMESTAMPTZ
@@ -99,13 +165,19 @@ CREATE TABLE channels (
     type            channel_type NOT NULL DEFAULT 'text',
     topic           TEXT,
     position        INTEGER NOT NULL DEFAULT 0,
-    parent_id       BIGINT REFERENCES channels(id), -- Category reference
+    parent_id       BIGINT REFERENCES channels(id), -- Category, or parent channel for threads
+    owner_id        BIGINT REFERENCES users(id),    -- Group DM owner
+    icon            VARCHAR(255),                   -- Group DM icon
     created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     deleted_at      TIMESTAMPTZ,
 
     CONSTRAINT channels_category_no_parent
-        CHECK (type != 'category' OR parent_id IS NULL)
+        CHECK (type != 'category' OR parent_id IS NULL),
+    CONSTRAINT channels_thread_has_parent
+        CHECK (type != 'thread' OR parent_id IS NOT NULL),
+    CONSTRAINT channels_group_dm_has_owner
+        CHECK (type != 'group_dm' OR owner_id IS NOT NULL)
 );
 
 CREATE INDEX idx_channels_guild ON channels(guild_id) WHERE deleted_at IS NULL;
@@ -119,13 +191,31 @@ CREATE INDEX idx_channels_type ON channels(type) WHERE deleted_at IS NULL;
 CREATE TABLE dm_channel_recipients (
     channel_id      BIGINT NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
     user_id         BIGINT NOT NULL REFERENCES users(id),
-    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),   -- Join order, for ownership transfer
 
     PRIMARY KEY (channel_id, user_id)
 );
 
 CREATE INDEX idx_dm_recipients_user ON dm_channel_recipients(user_id);
 
+-- ============================================================================
+-- CHANNEL PERMISSION OVERWRITES
+-- ============================================================================
+
+CREATE TABLE permission_overwrites (
+    channel_id      BIGINT NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    target_id       BIGINT NOT NULL,                 -- Role ID or user ID
+    type            overwrite_type NOT NULL,
+    allow           BIGINT NOT NULL DEFAULT 0,       -- Permission bitfield
+    deny            BIGINT NOT NULL DEFAULT 0,       -- Permission bitfield
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (channel_id, target_id)
+);
+
+CREATE INDEX idx_permission_overwrites_target ON permission_overwrites(target_id);
+
 -- ============================================================================
 -- ROLES
 -- ============================================================================
@@ -194,12 +284,19 @@ CREATE TABLE messages (
     channel_id      BIGINT NOT NULL REFERENCES channels(id),
     author_id       BIGINT NOT NULL REFERENCES users(id),
     content         TEXT NOT NULL,
+    type            message_type NOT NULL DEFAULT 'default',
     created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
     edited_at       TIMESTAMPTZ,
     deleted_at      TIMESTAMPTZ,
 
-    -- For future: reply/thread support
-    reference_id    BIGINT REFERENCES messages(id)
+    -- Message being replied to (or pinned, for pin notices)
+    reference_id    BIGINT REFERENCES messages(id),
+
+    -- Resolved mentions
+    mention_everyone    BOOLEAN NOT NULL DEFAULT FALSE,
+    mention_user_ids    BIGINT[] NOT NULL DEFAULT '{}',
+    mention_role_ids    BIGINT[] NOT NULL DEFAULT '{}',
+    mention_channel_ids BIGINT[] NOT NULL DEFAULT '{}'
 );
 
 -- Primary index for message fetching (cursor pagination)
@@ -208,11 +305,90 @@ CREATE INDEX idx_messages_channel_id ON messages(channel_id, id DESC)
 
 CREATE INDEX idx_messages_author ON messages(author_id) WHERE deleted_at IS NULL;
 
+-- For searching messages that mention a user
+CREATE INDEX idx_messages_mention_users ON messages USING gin(mention_user_ids)
+    WHERE deleted_at IS NULL;
+
 -- For search (basic)
 CREATE INDEX idx_messages_content_search ON messages
     USING gin(to_tsvector('english', content))
     WHERE deleted_at IS NULL;
 
+-- ============================================================================
+-- THREADS (metadata for channels of type 'thread')
+-- ============================================================================
+
+CREATE TABLE threads (
+    channel_id              BIGINT PRIMARY KEY REFERENCES channels(id) ON DELETE CASCADE,
+    owner_id                BIGINT NOT NULL REFERENCES users(id),
+    message_id              BIGINT NOT NULL UNIQUE REFERENCES messages(id), -- Starter message
+    archived                BOOLEAN NOT NULL DEFAULT FALSE,
+    locked                  BOOLEAN NOT NULL DEFAULT FALSE,
+    auto_archive_duration   INTEGER NOT NULL DEFAULT 1440,  -- Minutes of inactivity
+    archive_timestamp       TIMESTAMPTZ,
+    last_activity_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT threads_auto_archive_duration_valid
+        CHECK (auto_archive_duration IN (60, 1440, 4320, 10080))
+);
+
+CREATE INDEX idx_threads_archived ON threads(archived, archive_timestamp DESC);
+
+CREATE TABLE thread_members (
+    thread_id       BIGINT NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    user_id         BIGINT NOT NULL REFERENCES users(id),
+    joined_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (thread_id, user_id)
+);
+
+CREATE INDEX idx_thread_members_user ON thread_members(user_id);
+
+-- ============================================================================
+-- MESSAGE REVISIONS
+-- ============================================================================
+
+CREATE TABLE message_revisions (
+    id              BIGINT PRIMARY KEY,                   -- Snowflake ID
+    message_id      BIGINT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    content         TEXT NOT NULL,                        -- Content before the edit
+    edited_at       TIMESTAMPTZ,                          -- When this content was written (NULL = original)
+    replaced_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- For listing a message's revisions in order
+CREATE INDEX idx_message_revisions_message ON message_revisions(message_id, id);
+
+-- ============================================================================
+-- PINNED MESSAGES
+-- ============================================================================
+
+CREATE TABLE pins (
+    channel_id      BIGINT NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    message_id      BIGINT NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    pinned_by       BIGINT NOT NULL REFERENCES users(id),
+    pinned_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (channel_id, message_id)
+);
+
+-- For listing pins newest first
+CREATE INDEX idx_pins_channel ON pins(channel_id, pinned_at DESC);
+
+-- ============================================================================
+-- READ STATES
+-- ============================================================================
+
+CREATE TABLE read_states (
+    user_id         BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel_id      BIGINT NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    last_message_id BIGINT,                               -- Last acknowledged message
+    mention_count   INTEGER NOT NULL DEFAULT 0,           -- Mentions since last_message_id
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (user_id, channel_id)
+);
+
 -- ============================================================================
 -- MESSAGE ATTACHMENTS
 -- ============================================================================
@@ -227,6 +403,7 @@ CREATE TABLE attachments (
     proxy_url       VARCHAR(512),
     width           INTEGER,  -- For images
     height          INTEGER,  -- For images
+    verified_image  BOOLEAN NOT NULL DEFAULT FALSE,  -- Raster image detected from the file contents
     created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
@@ -373,6 +550,10 @@ CREATE TRIGGER update_guild_members_updated_at
     BEFORE UPDATE ON guild_members
     FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
 
+CREATE TRIGGER update_permission_overwrites_updated_at
+    BEFORE UPDATE ON permission_overwrites
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
 -- ============================================================================
 -- DEFAULT DATA HELPERS
 -- ============================================================================
@@ -460,17 +641,33 @@ ON messages(channel_id, id) WHERE deleted_at IS NULL;
 -- ============================================================================
 
 COMMENT ON TABLE users IS 'Platform user accounts (including bots)';
+COMMENT ON TABLE user_backup_codes IS 'Unused two-factor backup codes; a row is deleted when its code is used';
+COMMENT ON TABLE relationships IS 'Friendships, pending friend requests and blocks; one row per side, blocks only on the blocking side';
 COMMENT ON TABLE guilds IS 'Servers/communities (Discord calls these "servers")';
-COMMENT ON TABLE channels IS 'Text channels, categories, and DM channels';
+COMMENT ON TABLE channels IS 'Text channels, categories, DM channels, and threads';
+COMMENT ON TABLE threads IS 'Archive state and ownership for thread channels';
+COMMENT ON TABLE thread_members IS 'Users who joined a thread';
 COMMENT ON TABLE roles IS 'Permission roles within a guild';
+COMMENT ON TABLE permission_overwrites IS 'Per-channel allow/deny permissions for roles and members';
 COMMENT ON TABLE guild_members IS 'User membership in guilds';
 COMMENT ON TABLE member_roles IS 'Role assignments for guild members';
 COMMENT ON TABLE messages IS 'Text messages in channels';
+COMMENT ON TABLE message_revisions IS 'Earlier versions of edited messages; removed with the message';
+COMMENT ON TABLE pins IS 'Pinned messages per channel';
+COMMENT ON TABLE read_states IS 'Per-user read position and mention count per channel (cached in Redis)';
 COMMENT ON TABLE reactions IS 'Emoji reactions on messages';
 COMMENT ON TABLE invites IS 'Guild invitation links';
 COMMENT ON TABLE bans IS 'Banned users per guild';
 COMMENT ON TABLE audit_logs IS 'Moderation action audit trail';
 
-COMMENT ON COLUMN roles.permissions IS 'Bitfield: VIEW_CHANNEL=1, SEND_MESSAGES=2, MANAGE_MESSAGES=4, MANAGE_CHANNELS=8, MANAGE_ROLES=16, MANAGE_GUILD=32, KICK_MEMBERS=64, BAN_MEMBERS=128, ADMINISTRATOR=256, ATTACH_FILES=512, ADD_REACTIONS=1024';
+COMMENT ON COLUMN users.dm_privacy IS 'Who may open a DM with the user: everyone, friends and guild mates, or friends only';
+COMMENT ON COLUMN users.verified IS 'User confirmed ownership of the email address via a verification link';
+COMMENT ON COLUMN users.mfa_enabled IS 'User enrolled in TOTP two-factor authentication; login then needs a code';
+COMMENT ON COLUMN users.totp_secret IS 'Base32 TOTP secret, NULL unless mfa_enabled';
+COMMENT ON COLUMN guilds.mfa_level IS 'elevated: moderators need two-factor authentication for destructive actions';
+COMMENT ON COLUMN users.privileged_intents IS 'Opt-in for bots to request privileged gateway intents (GUILD_MEMBERS, GUILD_PRESENCES)';
+COMMENT ON COLUMN roles.permissions IS 'Bitfield: VIEW_CHANNEL=1, SEND_MESSAGES=2, MANAGE_MESSAGES=4, MANAGE_CHANNELS=8, MANAGE_ROLES=16, MANAGE_GUILD=32, KICK_MEMBERS=64, BAN_MEMBERS=128, ADMINISTRATOR=256, ATTACH_FILES=512, ADD_REACTIONS=1024, VIEW_AUDIT_LOG=2048, MANAGE_THREADS=4096, MENTION_EVERYONE=8192';
+COMMENT ON COLUMN permission_overwrites.target_id IS 'Role ID for type=role (the @everyone role''s ID targets everyone), user ID for type=member';
 COMMENT ON COLUMN roles.is_everyone IS 'TRUE for the default @everyone role (one per guild)';
-COMMENT ON COLUMN messages.reference_id IS 'For reply/thread support (future feature)';
+COMMENT ON COLUMN messages.reference_id IS 'Message being replied to, or the pinned message for pin notices';
+COMMENT ON COLUMN messages.mention_user_ids IS 'Mentioned users after resolution and allowed_mentions filtering';
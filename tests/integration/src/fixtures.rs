@@ -65,6 +65,9 @@ pub struct UserResponse {
     pub email: Option<String>,
     pub avatar: Option<String>,
     pub bot: bool,
+    /// Only present for the current user
+    #[serde(default)]
+    pub verified: Option<bool>,
     pub created_at: String,
 }
 
@@ -228,6 +231,17 @@ pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
+/// Login session response
+#[derive(Debug, Deserialize)]
+pub struct SessionResponse {
+    pub id: String,
+    pub device: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: String,
+    pub last_used_at: String,
+    pub current: bool,
+}
+
 /// Token pair response
 #[derive(Debug, Deserialize)]
 pub struct TokenPairResponse {
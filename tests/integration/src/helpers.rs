@@ -4,16 +4,26 @@
 //! and managing test data cleanup.
 
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU16, Ordering};
 use std::time::Duration;
 
 use anyhow::Result;
 use chat_api::{create_app, create_app_state};
-use chat_common::AppConfig;
+use chat_common::{AppConfig, MailBackendKind};
+use chat_gateway::GatewayState;
+use chat_mail::{EmailMessage, FileMailer};
+use futures_util::{SinkExt, StreamExt};
 use reqwest::{Client, Response, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
-use tokio::net::TcpListener;
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
 use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// User agent sent by test HTTP clients, recorded with their login sessions
+pub const TEST_USER_AGENT: &str = "integration-tests";
 
 /// Counter for unique test ports
 static PORT_COUNTER: AtomicU16 = AtomicU16::new(19000);
@@ -54,7 +64,9 @@ impl TestServer {
 
         // Spawn server task
         let handle = tokio::spawn(async move {
-            axum::serve(listener, app).await.ok();
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .ok();
         });
 
         // Wait for server to be ready
@@ -63,6 +75,7 @@ impl TestServer {
         // Create HTTP client
         let client = Client::builder()
             .timeout(Duration::from_secs(10))
+            .user_agent(TEST_USER_AGENT)
             .build()?;
 
         Ok(Self {
@@ -72,6 +85,14 @@ impl TestServer {
         })
     }
 
+    /// Start a test server whose outgoing email is captured
+    pub async fn start_with_mail() -> Result<(Self, MailCapture)> {
+        let mail = MailCapture::new();
+        let mut config = test_config()?;
+        mail.configure(&mut config);
+        Ok((Self::start_with_config(config).await?, mail))
+    }
+
     /// Get base URL for the server
     pub fn base_url(&self) -> String {
         format!("http://{}", self.addr)
@@ -117,6 +138,25 @@ impl TestServer {
             .await?)
     }
 
+    /// Make a POST request with auth token and an audit log reason
+    pub async fn post_auth_with_reason<T: Serialize>(
+        &self,
+        path: &str,
+        token: &str,
+        reason: &str,
+        body: &T,
+    ) -> Result<Response> {
+        let url = format!("{}{}", self.base_url(), path);
+        Ok(self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("X-Audit-Log-Reason", reason)
+            .json(body)
+            .send()
+            .await?)
+    }
+
     /// Make a PATCH request with auth token
     pub async fn patch_auth<T: Serialize>(
         &self,
@@ -163,12 +203,196 @@ impl TestServer {
     }
 }
 
+/// Gateway node instance; several can run against the same Redis
+pub struct TestGateway {
+    pub addr: SocketAddr,
+    pub state: GatewayState,
+    _handle: JoinHandle<()>,
+}
+
+impl TestGateway {
+    /// Start a new gateway node
+    pub async fn start() -> Result<Self> {
+        let config = test_config()?;
+        let addr = SocketAddr::from(([127, 0, 0, 1], get_test_port()));
+
+        let state = chat_gateway::create_gateway_state(config).await?;
+        let app = chat_gateway::create_app(state.clone());
+
+        let listener = TcpListener::bind(addr).await?;
+        let actual_addr = listener.local_addr()?;
+
+        let handle = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .ok();
+        });
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Ok(Self {
+            addr: actual_addr,
+            state,
+            _handle: handle,
+        })
+    }
+
+    /// Get the ID of this gateway node
+    pub fn node_id(&self) -> &str {
+        self.state.node().id()
+    }
+
+    /// Open a WebSocket connection and read Hello
+    pub async fn connect(&self) -> Result<GatewayClient> {
+        let url = format!("ws://{}/gateway", self.addr);
+        let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+        let mut client = GatewayClient { socket };
+
+        let hello = client.recv().await?;
+        anyhow::ensure!(hello["op"] == 10, "Expected Hello, got {hello}");
+
+        Ok(client)
+    }
+}
+
+/// JSON gateway client
+pub struct GatewayClient {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl GatewayClient {
+    /// Send a payload
+    pub async fn send(&mut self, op: u8, data: Value) -> Result<()> {
+        let payload = json!({ "op": op, "d": data });
+        self.socket.send(Message::Text(payload.to_string())).await?;
+        Ok(())
+    }
+
+    /// Receive the next payload
+    pub async fn recv(&mut self) -> Result<Value> {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(5), self.socket.next())
+                .await
+                .map_err(|_| anyhow::anyhow!("Timed out waiting for a payload"))?
+                .ok_or_else(|| anyhow::anyhow!("Connection closed"))??;
+
+            match message {
+                Message::Text(text) => return Ok(serde_json::from_str(&text)?),
+                Message::Close(frame) => anyhow::bail!("Connection closed: {frame:?}"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Receive payloads until a Dispatch event of the given type
+    pub async fn recv_event(&mut self, event_type: &str) -> Result<Value> {
+        loop {
+            let payload = self.recv().await?;
+            if payload["op"] == 0 && payload["t"] == event_type {
+                return Ok(payload);
+            }
+        }
+    }
+
+    /// Receive payloads until the connection is closed, returning the close code
+    pub async fn recv_close(&mut self) -> Result<Option<u16>> {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(5), self.socket.next())
+                .await
+                .map_err(|_| anyhow::anyhow!("Timed out waiting for close"))?;
+
+            match message {
+                Some(Ok(Message::Close(frame))) => return Ok(frame.map(|f| u16::from(f.code))),
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => return Ok(None),
+            }
+        }
+    }
+
+    /// Identify and wait for READY
+    pub async fn identify(&mut self, token: &str) -> Result<Value> {
+        self.send(2, json!({ "token": token })).await?;
+        self.recv_event("READY").await
+    }
+
+    /// Resume a session
+    pub async fn resume(&mut self, token: &str, session_id: &str, seq: u64) -> Result<()> {
+        self.send(4, json!({ "token": token, "session_id": session_id, "seq": seq }))
+            .await
+    }
+
+    /// Close the connection
+    pub async fn close(mut self) -> Result<()> {
+        self.socket.close(None).await?;
+        Ok(())
+    }
+}
+
+/// Email captured by a test server, via the file mail backend
+pub struct MailCapture {
+    dir: PathBuf,
+}
+
+impl MailCapture {
+    /// Capture into a fresh temporary directory
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("chat-mail-{}", uuid::Uuid::new_v4()));
+        Self { dir }
+    }
+
+    /// Point a server configuration at this capture
+    pub fn configure(&self, config: &mut AppConfig) {
+        config.mail.backend = MailBackendKind::File;
+        config.mail.capture_dir = self.dir.to_string_lossy().into_owned();
+    }
+
+    /// The most recent email sent to `to`
+    pub async fn last_to(&self, to: &str) -> Result<EmailMessage> {
+        FileMailer::read_dir(&self.dir)
+            .await?
+            .into_iter()
+            .rev()
+            .find(|m| m.to == to)
+            .ok_or_else(|| anyhow::anyhow!("No email sent to {to}"))
+    }
+
+    /// The token from the link in the most recent email sent to `to`
+    pub async fn token_for(&self, to: &str) -> Result<String> {
+        let message = self.last_to(to).await?;
+        let token = message
+            .body
+            .split("?token=")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .ok_or_else(|| anyhow::anyhow!("No token in email: {}", message.body))?;
+        Ok(token.to_string())
+    }
+}
+
+impl Default for MailCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MailCapture {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
+
 /// Create a test configuration
 pub fn test_config() -> Result<AppConfig> {
     // Load from environment or use defaults
     dotenvy::dotenv().ok();
 
-    let config = AppConfig::from_env().map_err(|e| anyhow::anyhow!("Config error: {e}"))?;
+    let mut config = AppConfig::from_env().map_err(|e| anyhow::anyhow!("Config error: {e}"))?;
+
+    // Keep registration emails out of the working directory
+    config.mail.backend = MailBackendKind::Memory;
 
     Ok(config)
 }
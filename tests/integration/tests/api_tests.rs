@@ -7,8 +7,10 @@
 //!
 //! Run with: cargo test -p integration-tests --test api_tests
 
+use chat_common::auth::{totp_code, TOTP_STEP_SECONDS};
 use integration_tests::{
-    assert_json, assert_status, check_test_env, fixtures::*, TestServer,
+    assert_json, assert_status, check_test_env, fixtures::*, TestGateway, TestServer,
+    TEST_USER_AGENT,
 };
 use reqwest::StatusCode;
 
@@ -137,6 +139,41 @@ async fn test_refresh_token() {
     assert!(!tokens.refresh_token.is_empty());
 }
 
+#[tokio::test]
+async fn test_refresh_token_single_use() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Concurrent refreshes with the same token: only one succeeds
+    let first_req = RefreshTokenRequest {
+        refresh_token: auth.refresh_token.clone(),
+    };
+    let second_req = RefreshTokenRequest {
+        refresh_token: auth.refresh_token.clone(),
+    };
+    let (first, second) = tokio::join!(
+        server.post("/auth/refresh", &first_req),
+        server.post("/auth/refresh", &second_req),
+    );
+    let statuses = [first.unwrap().status(), second.unwrap().status()];
+    assert_eq!(
+        statuses.iter().filter(|s| **s == StatusCode::OK).count(),
+        1,
+        "statuses: {statuses:?}"
+    );
+
+    // The used token cannot be refreshed again
+    let response = server.post("/auth/refresh", &first_req).await.unwrap();
+    assert_status(response, StatusCode::UNAUTHORIZED).await.unwrap();
+}
+
 #[tokio::test]
 async fn test_logout() {
     if !check_test_env().await {
@@ -158,183 +195,1792 @@ async fn test_logout() {
     assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 }
 
-// ============================================================================
-// User Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_get_current_user() {
+async fn test_sessions() {
     if !check_test_env().await {
         return;
     }
 
     let server = TestServer::start().await.expect("Failed to start server");
 
-    // Register
     let register_req = RegisterRequest::unique();
     let response = server.post("/auth/register", &register_req).await.unwrap();
-    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let first: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
 
-    // Get current user
     let response = server
-        .get_auth("/users/@me", &auth.access_token)
+        .post("/auth/login", &LoginRequest::from_register(&register_req))
         .await
         .unwrap();
-    let user: UserResponse = assert_json(response, StatusCode::OK).await.unwrap();
+    let second: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
 
-    assert_eq!(user.id, auth.user.id);
-    assert_eq!(user.username, register_req.username);
+    let response = server
+        .get_auth("/users/@me/sessions", &second.access_token)
+        .await
+        .unwrap();
+    let sessions: Vec<SessionResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions.iter().filter(|s| s.current).count(), 1);
+    for session in &sessions {
+        assert_eq!(session.device.as_deref(), Some(TEST_USER_AGENT));
+        assert_eq!(session.ip_address.as_deref(), Some("127.0.0.1"));
+    }
+
+    // Refreshing keeps the session
+    let current_id = sessions.iter().find(|s| s.current).unwrap().id.clone();
+    let response = server
+        .post(
+            "/auth/refresh",
+            &RefreshTokenRequest {
+                refresh_token: second.refresh_token,
+            },
+        )
+        .await
+        .unwrap();
+    let second: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
+
+    let response = server
+        .get_auth("/users/@me/sessions", &second.access_token)
+        .await
+        .unwrap();
+    let sessions: Vec<SessionResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions.iter().find(|s| s.current).unwrap().id, current_id);
+
+    // Log out everywhere else
+    let response = server
+        .post_auth("/users/@me/sessions/logout-others", &second.access_token, &())
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .post(
+            "/auth/refresh",
+            &RefreshTokenRequest {
+                refresh_token: first.refresh_token,
+            },
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::UNAUTHORIZED).await.unwrap();
+
+    let response = server
+        .get_auth("/users/@me/sessions", &second.access_token)
+        .await
+        .unwrap();
+    let sessions: Vec<SessionResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, current_id);
+
+    // Log out a single session
+    let response = server
+        .delete_auth(&format!("/users/@me/sessions/{current_id}"), &second.access_token)
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .delete_auth(&format!("/users/@me/sessions/{current_id}"), &second.access_token)
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
 }
 
 #[tokio::test]
-async fn test_get_current_user_unauthorized() {
+async fn test_password_reset() {
     if !check_test_env().await {
         return;
     }
 
-    let server = TestServer::start().await.expect("Failed to start server");
+    let (server, mail) = TestServer::start_with_mail()
+        .await
+        .expect("Failed to start server");
 
-    let response = server.get("/users/@me").await.unwrap();
+    let gateway = TestGateway::start().await.expect("Failed to start gateway");
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let mut client = gateway.connect().await.unwrap();
+    client.identify(&auth.access_token).await.unwrap();
+
+    // Unknown emails get the same response
+    let response = server
+        .post(
+            "/auth/forgot-password",
+            &serde_json::json!({ "email": "nobody-here@example.com" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .post(
+            "/auth/forgot-password",
+            &serde_json::json!({ "email": register_req.email }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let email = mail.last_to(&register_req.email).await.unwrap();
+    assert_eq!(email.subject, "Reset your password");
+    let token = mail.token_for(&register_req.email).await.unwrap();
+
+    // A weak password is rejected without using up the token
+    let response = server
+        .post(
+            "/auth/reset-password",
+            &serde_json::json!({ "token": token, "password": "weakpassword" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    let new_password = "NewPass456!";
+    let response = server
+        .post(
+            "/auth/reset-password",
+            &serde_json::json!({ "token": token, "password": new_password }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    // Tokens are single-use
+    let response = server
+        .post(
+            "/auth/reset-password",
+            &serde_json::json!({ "token": token, "password": "OtherPass789!" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    // Existing sessions are signed out and their connections closed
+    assert_eq!(client.recv_close().await.unwrap(), Some(4004));
+
+    let response = server
+        .post(
+            "/auth/refresh",
+            &RefreshTokenRequest {
+                refresh_token: auth.refresh_token,
+            },
+        )
+        .await
+        .unwrap();
     assert_status(response, StatusCode::UNAUTHORIZED).await.unwrap();
-}
 
-// ============================================================================
-// Guild Tests
-// ============================================================================
+    let response = server
+        .post("/auth/login", &LoginRequest::from_register(&register_req))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::UNAUTHORIZED).await.unwrap();
+
+    let login_req = LoginRequest {
+        email: register_req.email.clone(),
+        password: new_password.to_string(),
+    };
+    let response = server.post("/auth/login", &login_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
+
+    // Following the reset link also verified the email
+    assert_eq!(auth.user.verified, Some(true));
+}
 
 #[tokio::test]
-async fn test_create_guild() {
+async fn test_email_verification() {
     if !check_test_env().await {
         return;
     }
 
-    let server = TestServer::start().await.expect("Failed to start server");
+    let (server, mail) = TestServer::start_with_mail()
+        .await
+        .expect("Failed to start server");
 
-    // Register
+    // Registering sends the verification email
     let register_req = RegisterRequest::unique();
     let response = server.post("/auth/register", &register_req).await.unwrap();
     let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_eq!(auth.user.verified, Some(false));
 
-    // Create guild
-    let guild_req = CreateGuildRequest::unique();
+    let first_token = mail.token_for(&register_req.email).await.unwrap();
+
+    // Resending replaces the previous link
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .post_auth("/auth/verify-email/resend", &auth.access_token, &())
         .await
         .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+    let token = mail.token_for(&register_req.email).await.unwrap();
+    assert_ne!(token, first_token);
 
-    assert_eq!(guild.name, guild_req.name);
-    assert_eq!(guild.owner_id, auth.user.id);
+    let response = server
+        .post(
+            "/auth/verify-email",
+            &serde_json::json!({ "token": first_token }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    let response = server
+        .post("/auth/verify-email", &serde_json::json!({ "token": token }))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .post("/auth/verify-email", &serde_json::json!({ "token": token }))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    let response = server
+        .get_auth("/users/@me", &auth.access_token)
+        .await
+        .unwrap();
+    let user: UserResponse = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(user.verified, Some(true));
+
+    let response = server
+        .post_auth("/auth/verify-email/resend", &auth.access_token, &())
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
 }
 
 #[tokio::test]
-async fn test_get_guild() {
+async fn test_totp_two_factor() {
     if !check_test_env().await {
         return;
     }
 
     let server = TestServer::start().await.expect("Failed to start server");
 
-    // Register and create guild
     let register_req = RegisterRequest::unique();
     let response = server.post("/auth/register", &register_req).await.unwrap();
     let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
 
-    let guild_req = CreateGuildRequest::unique();
+    // Enrollment needs the password
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .post_auth(
+            "/users/@me/mfa/totp/enable",
+            &auth.access_token,
+            &serde_json::json!({ "password": "WrongPass123!" }),
+        )
         .await
         .unwrap();
-    let created: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
 
-    // Get guild
     let response = server
-        .get_auth(&format!("/guilds/{}", created.id), &auth.access_token)
+        .post_auth(
+            "/users/@me/mfa/totp/enable",
+            &auth.access_token,
+            &serde_json::json!({ "password": register_req.password }),
+        )
         .await
         .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::OK).await.unwrap();
+    let enrollment: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let secret = enrollment["secret"].as_str().unwrap().to_string();
+    assert!(enrollment["uri"].as_str().unwrap().starts_with("otpauth://totp/"));
 
-    assert_eq!(guild.id, created.id);
-    assert_eq!(guild.name, guild_req.name);
+    let step = chrono::Utc::now().timestamp() as u64 / TOTP_STEP_SECONDS;
+    let response = server
+        .post_auth(
+            "/users/@me/mfa/totp/confirm",
+            &auth.access_token,
+            &serde_json::json!({ "code": totp_code(&secret, step).unwrap() }),
+        )
+        .await
+        .unwrap();
+    let codes: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let backup_codes = codes["backup_codes"].as_array().unwrap();
+    assert_eq!(backup_codes.len(), 10);
+    let backup_code = backup_codes[0].as_str().unwrap().to_string();
+
+    // Login now stops at a ticket
+    let response = server
+        .post("/auth/login", &LoginRequest::from_register(&register_req))
+        .await
+        .unwrap();
+    let login: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(login["mfa"], true);
+    assert!(login.get("access_token").is_none());
+    let ticket = login["ticket"].as_str().unwrap().to_string();
+
+    // The code used for enrollment cannot be replayed
+    let response = server
+        .post(
+            "/auth/mfa/totp",
+            &serde_json::json!({ "ticket": ticket, "code": totp_code(&secret, step).unwrap() }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    let response = server
+        .post(
+            "/auth/mfa/totp",
+            &serde_json::json!({ "ticket": ticket, "code": totp_code(&secret, step + 1).unwrap() }),
+        )
+        .await
+        .unwrap();
+    let mfa_auth: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(mfa_auth.user.id, auth.user.id);
+
+    // Tickets are single-use
+    let response = server
+        .post(
+            "/auth/mfa/totp",
+            &serde_json::json!({ "ticket": ticket, "code": backup_code }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    // Backup codes are accepted in place of a TOTP code
+    let response = server
+        .post_auth(
+            "/users/@me/mfa/totp/disable",
+            &mfa_auth.access_token,
+            &serde_json::json!({ "code": backup_code }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .post("/auth/login", &LoginRequest::from_register(&register_req))
+        .await
+        .unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(!auth.access_token.is_empty());
 }
 
 #[tokio::test]
-async fn test_delete_guild() {
+async fn test_guild_mfa_level_requires_owner_mfa() {
     if !check_test_env().await {
         return;
     }
 
     let server = TestServer::start().await.expect("Failed to start server");
 
-    // Register and create guild
     let register_req = RegisterRequest::unique();
     let response = server.post("/auth/register", &register_req).await.unwrap();
     let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
 
-    let guild_req = CreateGuildRequest::unique();
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .post_auth("/guilds", &auth.access_token, &CreateGuildRequest::unique())
         .await
         .unwrap();
     let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
 
-    // Delete guild
     let response = server
-        .delete_auth(&format!("/guilds/{}", guild.id), &auth.access_token)
+        .patch_auth(
+            &format!("/guilds/{}", guild.id),
+            &auth.access_token,
+            &serde_json::json!({ "mfa_level": 1 }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+}
+
+// ============================================================================
+// User Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_current_user() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Register
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Get current user
+    let response = server
+        .get_auth("/users/@me", &auth.access_token)
+        .await
+        .unwrap();
+    let user: UserResponse = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert_eq!(user.id, auth.user.id);
+    assert_eq!(user.username, register_req.username);
+}
+
+#[tokio::test]
+async fn test_get_current_user_unauthorized() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    let response = server.get("/users/@me").await.unwrap();
+    assert_status(response, StatusCode::UNAUTHORIZED).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_get_gateway_bot() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .get_auth("/gateway/bot", &auth.access_token)
+        .await
+        .unwrap();
+    let gateway: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert!(gateway["url"].as_str().unwrap().ends_with("/gateway"));
+    assert_eq!(gateway["shards"], 1);
+    let limit = &gateway["session_start_limit"];
+    assert_eq!(limit["total"], 1000);
+    assert_eq!(limit["remaining"], 1000);
+    assert_eq!(limit["max_concurrency"], 1);
+}
+
+// ============================================================================
+// Guild Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_create_guild() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Register
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Create guild
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    assert_eq!(guild.name, guild_req.name);
+    assert_eq!(guild.owner_id, auth.user.id);
+}
+
+#[tokio::test]
+async fn test_get_guild() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Register and create guild
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let created: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Get guild
+    let response = server
+        .get_auth(&format!("/guilds/{}", created.id), &auth.access_token)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert_eq!(guild.id, created.id);
+    assert_eq!(guild.name, guild_req.name);
+}
+
+#[tokio::test]
+async fn test_delete_guild() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Register and create guild
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Delete guild
+    let response = server
+        .delete_auth(&format!("/guilds/{}", guild.id), &auth.access_token)
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    // Verify deleted
+    let response = server
+        .get_auth(&format!("/guilds/{}", guild.id), &auth.access_token)
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_guild_audit_logs() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: owner and a member who joins by invite
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let owner: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let member: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &owner.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Two channels, the first created with a reason, then a role
+    let channels_path = format!("/guilds/{}/channels", guild.id);
+    let response = server
+        .post_auth_with_reason(
+            &channels_path,
+            &owner.access_token,
+            "Room for announcements",
+            &CreateChannelRequest::text_channel(),
+        )
+        .await
+        .unwrap();
+    let first: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth(&channels_path, &owner.access_token, &CreateChannelRequest::text_channel())
+        .await
+        .unwrap();
+    let second: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth_with_reason(
+            &format!("/guilds/{}/roles", guild.id),
+            &owner.access_token,
+            "Moderators",
+            &CreateRoleRequest::unique(),
+        )
+        .await
+        .unwrap();
+    let role: RoleResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let invite_req = CreateInviteRequest::default();
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/invites", first.id),
+            &owner.access_token,
+            &invite_req,
+        )
+        .await
+        .unwrap();
+    let invite: InviteResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let audit_path = format!("/guilds/{}/audit-logs", guild.id);
+
+    // Members without VIEW_AUDIT_LOG cannot read the log
+    let response = server.get_auth(&audit_path, &member.access_token).await.unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // Filtered by action, newest first, with the header reason saved
+    let response = server
+        .get_auth(&format!("{audit_path}?action_type=10"), &owner.access_token)
+        .await
+        .unwrap();
+    let entries: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["target_id"], second.id.as_str());
+    assert!(entries[0].get("reason").is_none());
+    assert_eq!(entries[1]["target_id"], first.id.as_str());
+    assert_eq!(entries[1]["reason"], "Room for announcements");
+    assert_eq!(entries[1]["user_id"], owner.user.id.as_str());
+    assert!(entries.iter().all(|e| e["action_type"] == 10));
+
+    let response = server
+        .get_auth(&format!("{audit_path}?action_type=30"), &owner.access_token)
+        .await
+        .unwrap();
+    let entries: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["target_id"], role.id.as_str());
+    assert_eq!(entries[0]["reason"], "Moderators");
+
+    // Limit and before page through the log
+    let response = server
+        .get_auth(&format!("{audit_path}?action_type=10&limit=1"), &owner.access_token)
+        .await
+        .unwrap();
+    let page: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["target_id"], second.id.as_str());
+
+    let before = page[0]["id"].as_str().unwrap();
+    let response = server
+        .get_auth(
+            &format!("{audit_path}?action_type=10&before={before}"),
+            &owner.access_token,
+        )
+        .await
+        .unwrap();
+    let page: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["target_id"], first.id.as_str());
+
+    // Filtered by user
+    let response = server
+        .get_auth(&format!("{audit_path}?user_id={}", owner.user.id), &owner.access_token)
+        .await
+        .unwrap();
+    let entries: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(entries.len() >= 3);
+    assert!(entries.iter().all(|e| e["user_id"] == owner.user.id.as_str()));
+
+    let response = server
+        .get_auth(&format!("{audit_path}?user_id={}", member.user.id), &owner.access_token)
+        .await
+        .unwrap();
+    let entries: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(entries.is_empty());
+
+    // Unknown action types are rejected
+    let response = server
+        .get_auth(&format!("{audit_path}?action_type=999"), &owner.access_token)
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+}
+
+// ============================================================================
+// Channel Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_create_channel() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: register and create guild
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Create channel
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    assert_eq!(channel.name, Some(channel_req.name));
+    assert_eq!(channel.guild_id, Some(guild.id));
+}
+
+#[tokio::test]
+async fn test_get_guild_channels() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Create some channels
+    let channel_req = CreateChannelRequest::text_channel();
+    server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+
+    // Get channels
+    let response = server
+        .get_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let channels: Vec<ChannelResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+
+    // Should have at least the one we created
+    assert!(!channels.is_empty());
+}
+
+#[tokio::test]
+async fn test_channel_permission_overwrites() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: owner and a second member who joins by invite
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let owner: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let member: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &owner.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &owner.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let invite_req = CreateInviteRequest::default();
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/invites", channel.id),
+            &owner.access_token,
+            &invite_req,
+        )
+        .await
+        .unwrap();
+    let invite: InviteResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let response = server
+        .get_auth(&format!("/guilds/{}/roles", guild.id), &owner.access_token)
+        .await
+        .unwrap();
+    let roles: Vec<RoleResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    let everyone_id = roles.iter().find(|r| r.name == "@everyone").unwrap().id.clone();
+
+    let messages_path = format!("/channels/{}/messages", channel.id);
+    let everyone_path = format!("/channels/{}/permissions/{everyone_id}", channel.id);
+    let member_path = format!("/channels/{}/permissions/{}", channel.id, member.user.id);
+
+    // Denying VIEW_CHANNEL to @everyone hides the channel from the member
+    let response = server
+        .put_auth(
+            &everyone_path,
+            &owner.access_token,
+            &serde_json::json!({ "type": 0, "deny": "1" }),
+        )
+        .await
+        .unwrap();
+    let overwrite: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(overwrite["id"], everyone_id.as_str());
+    assert_eq!(overwrite["type"], 0);
+    assert_eq!(overwrite["allow"], "0");
+    assert_eq!(overwrite["deny"], "1");
+
+    let response = server.get_auth(&messages_path, &member.access_token).await.unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // Members without MANAGE_ROLES cannot edit overwrites
+    let response = server
+        .put_auth(
+            &member_path,
+            &member.access_token,
+            &serde_json::json!({ "type": 1, "allow": "1" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // A member overwrite lets the member back in
+    let response = server
+        .put_auth(
+            &member_path,
+            &owner.access_token,
+            &serde_json::json!({ "type": 1, "allow": "1" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::OK).await.unwrap();
+
+    let response = server.get_auth(&messages_path, &member.access_token).await.unwrap();
+    assert_status(response, StatusCode::OK).await.unwrap();
+
+    let response = server
+        .get_auth(&format!("/channels/{}/permissions", channel.id), &owner.access_token)
+        .await
+        .unwrap();
+    let overwrites: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(overwrites.len(), 2);
+
+    // Deleting the member overwrite hides the channel again
+    let response = server.delete_auth(&member_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server.get_auth(&member_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
+
+    let response = server.get_auth(&messages_path, &member.access_token).await.unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // Deleting the @everyone overwrite restores the guild permissions
+    let response = server.delete_auth(&everyone_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server.get_auth(&messages_path, &member.access_token).await.unwrap();
+    assert_status(response, StatusCode::OK).await.unwrap();
+}
+
+// ============================================================================
+// Message Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_create_message() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: register, create guild, create channel
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Create message
+    let message_req = CreateMessageRequest::simple("Hello, World!");
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &auth.access_token,
+            &message_req,
+        )
+        .await
+        .unwrap();
+    let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    assert_eq!(message.content, "Hello, World!");
+    assert_eq!(message.channel_id, channel.id);
+    assert_eq!(message.author.id, auth.user.id);
+}
+
+#[tokio::test]
+async fn test_get_channel_messages() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Create some messages
+    for i in 0..3 {
+        let message_req = CreateMessageRequest::simple(&format!("Message {}", i));
+        server
+            .post_auth(
+                &format!("/channels/{}/messages", channel.id),
+                &auth.access_token,
+                &message_req,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Get messages
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let messages: Vec<MessageResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert_eq!(messages.len(), 3);
+}
+
+#[tokio::test]
+async fn test_search_channel_messages() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    for content in ["hello there", "deploying the release", "hello again"] {
+        let message_req = CreateMessageRequest::simple(content);
+        server
+            .post_auth(
+                &format!("/channels/{}/messages", channel.id),
+                &auth.access_token,
+                &message_req,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Channel search
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/messages/search?content=hello", channel.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let results: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert_eq!(results["total_results"], 2);
+    assert_eq!(results["hits"][0]["message"]["content"], "hello again");
+    assert_eq!(results["hits"][0]["context_before"].as_array().unwrap().len(), 2);
+
+    // Guild search
+    let response = server
+        .get_auth(
+            &format!("/guilds/{}/messages/search?content=release", guild.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let results: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+
+    assert_eq!(results["total_results"], 1);
+}
+
+#[tokio::test]
+async fn test_message_threads() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let message_req = CreateMessageRequest::simple("Release planning");
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &auth.access_token,
+            &message_req,
+        )
+        .await
+        .unwrap();
+    let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Start a thread from the message
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages/{}/threads", channel.id, message.id),
+            &auth.access_token,
+            &serde_json::json!({"name": "release", "auto_archive_duration": 60}),
+        )
+        .await
+        .unwrap();
+    let thread: serde_json::Value = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let thread_id = thread["id"].as_str().unwrap().to_string();
+
+    assert_eq!(thread["type"], 11);
+    assert_eq!(thread["parent_id"], channel.id.as_str());
+    assert_eq!(thread["member_count"], 1);
+    assert_eq!(thread["thread_metadata"]["archived"], false);
+
+    // Only one thread per message
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages/{}/threads", channel.id, message.id),
+            &auth.access_token,
+            &serde_json::json!({"name": "again"}),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::CONFLICT).await.unwrap();
+
+    // Threads use their parent's overwrites and cannot have their own
+    let response = server
+        .put_auth(
+            &format!("/channels/{thread_id}/permissions/{}", auth.user.id),
+            &auth.access_token,
+            &serde_json::json!({ "type": 1, "deny": "2" }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    // Post in the thread
+    let message_req = CreateMessageRequest::simple("First reply");
+    let response = server
+        .post_auth(
+            &format!("/channels/{thread_id}/messages"),
+            &auth.access_token,
+            &message_req,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/threads/active", channel.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let active: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(active["threads"][0]["id"], thread_id.as_str());
+
+    // Archive the thread and find it in the archived list
+    let response = server
+        .patch_auth(
+            &format!("/channels/{thread_id}"),
+            &auth.access_token,
+            &serde_json::json!({"archived": true}),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::OK).await.unwrap();
+
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/threads/archived", channel.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let archived: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(archived["threads"][0]["id"], thread_id.as_str());
+    assert_eq!(archived["has_more"], false);
+
+    // Threads do not show up in the guild channel list
+    let response = server
+        .get_auth(&format!("/guilds/{}/channels", guild.id), &auth.access_token)
+        .await
+        .unwrap();
+    let channels: Vec<ChannelResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(channels.iter().all(|c| c.id != thread_id));
+}
+
+#[tokio::test]
+async fn test_pinned_messages() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let message_req = CreateMessageRequest::simple("Read the rules");
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &auth.access_token,
+            &message_req,
+        )
+        .await
+        .unwrap();
+    let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Pin the message
+    let response = server
+        .put_auth(
+            &format!("/channels/{}/pins/{}", channel.id, message.id),
+            &auth.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .get_auth(&format!("/channels/{}/pins", channel.id), &auth.access_token)
+        .await
+        .unwrap();
+    let pins: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(pins.as_array().unwrap().len(), 1);
+    assert_eq!(pins[0]["id"], message.id.as_str());
+
+    // Pinning posts a system message referencing the pinned message
+    let response = server
+        .get_auth(&format!("/channels/{}/messages", channel.id), &auth.access_token)
+        .await
+        .unwrap();
+    let messages: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let notice = messages
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["type"] == 6)
+        .expect("pin notice");
+    assert_eq!(notice["message_reference"]["message_id"], message.id.as_str());
+
+    // Unpin it again
+    let response = server
+        .delete_auth(
+            &format!("/channels/{}/pins/{}", channel.id, message.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .get_auth(&format!("/channels/{}/pins", channel.id), &auth.access_token)
+        .await
+        .unwrap();
+    let pins: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(pins.as_array().unwrap().is_empty());
+
+    // Unpinning a message that is not pinned fails
+    let response = server
+        .delete_auth(
+            &format!("/channels/{}/pins/{}", channel.id, message.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_message_revisions() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let message_req = CreateMessageRequest::simple("first draft");
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &auth.access_token,
+            &message_req,
+        )
+        .await
+        .unwrap();
+    let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    // Edit twice
+    let mut edited_timestamps = Vec::new();
+    for content in ["second draft", "final"] {
+        let response = server
+            .patch_auth(
+                &format!("/channels/{}/messages/{}", channel.id, message.id),
+                &auth.access_token,
+                &serde_json::json!({"content": content}),
+            )
+            .await
+            .unwrap();
+        let edited: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+        edited_timestamps.push(edited["edited_timestamp"].clone());
+    }
+
+    // The stored edit time is the one the edit returned
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/messages/{}", channel.id, message.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let fetched: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(fetched["edited_timestamp"], edited_timestamps[1]);
+
+    let response = server
+        .get_auth(
+            &format!("/channels/{}/messages/{}/revisions", channel.id, message.id),
+            &auth.access_token,
+        )
+        .await
+        .unwrap();
+    let revisions: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let revisions = revisions.as_array().unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0]["content"], "first draft");
+    assert!(revisions[0].get("edited_timestamp").is_none());
+    assert_eq!(revisions[1]["content"], "second draft");
+    assert_eq!(revisions[1]["edited_timestamp"], edited_timestamps[0]);
+}
+
+#[tokio::test]
+async fn test_message_ack() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &auth.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let mut messages = Vec::new();
+    for content in ["one", "two"] {
+        let message_req = CreateMessageRequest::simple(content);
+        let response = server
+            .post_auth(
+                &format!("/channels/{}/messages", channel.id),
+                &auth.access_token,
+                &message_req,
+            )
+            .await
+            .unwrap();
+        let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        messages.push(message);
+    }
+
+    // Ack the newest message
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages/{}/ack", channel.id, messages[1].id),
+            &auth.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    let state: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(state["channel_id"], channel.id.as_str());
+    assert_eq!(state["last_message_id"], messages[1].id.as_str());
+    assert_eq!(state["mention_count"], 0);
+
+    // Acking an older message does not move the read position back
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages/{}/ack", channel.id, messages[0].id),
+            &auth.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    let state: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(state["last_message_id"], messages[1].id.as_str());
+}
+
+#[tokio::test]
+async fn test_message_mentions() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: owner and a second member who joins by invite
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let owner: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let member: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &owner.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &owner.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let invite_req = CreateInviteRequest::default();
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/invites", channel.id),
+            &owner.access_token,
+            &invite_req,
+        )
+        .await
+        .unwrap();
+    let invite: InviteResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    // Mentions are resolved; the owner may mention @everyone
+    let content = format!("<@{}> <@999> <#{}> @everyone", member.user.id, channel.id);
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &owner.access_token,
+            &serde_json::json!({ "content": content }),
+        )
+        .await
+        .unwrap();
+    let message: serde_json::Value = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let mentions = message["mentions"].as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0]["id"], member.user.id.as_str());
+    assert_eq!(message["mention_channels"][0], channel.id.as_str());
+    assert_eq!(message["mention_everyone"], true);
+
+    // allowed_mentions suppresses everything not listed
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &owner.access_token,
+            &serde_json::json!({ "content": content, "allowed_mentions": { "parse": [] } }),
+        )
+        .await
+        .unwrap();
+    let message: serde_json::Value = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_eq!(message["mentions"].as_array().unwrap().len(), 0);
+    assert_eq!(message["mention_everyone"], false);
+
+    // Parsing users while also listing user IDs is rejected
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &owner.access_token,
+            &serde_json::json!({
+                "content": content,
+                "allowed_mentions": { "parse": ["users"], "users": [member.user.id] }
+            }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    // Members without MENTION_EVERYONE cannot ping everyone
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/messages", channel.id),
+            &member.access_token,
+            &serde_json::json!({ "content": "@everyone hello" }),
+        )
+        .await
+        .unwrap();
+    let message: serde_json::Value = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_eq!(message["mention_everyone"], false);
+}
+
+// ============================================================================
+// Group DM Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_group_dm_recipients() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup
+    let mut users = Vec::new();
+    for _ in 0..4 {
+        let register_req = RegisterRequest::unique();
+        let response = server.post("/auth/register", &register_req).await.unwrap();
+        let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        users.push(auth);
+    }
+    let (owner, first, second, third) = (&users[0], &users[1], &users[2], &users[3]);
+
+    // Create group DM
+    let response = server
+        .post_auth(
+            "/users/@me/channels",
+            &owner.access_token,
+            &serde_json::json!({
+                "recipients": [first.user.id, second.user.id],
+                "name": "weekend plans"
+            }),
+        )
+        .await
+        .unwrap();
+    let group: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let channel_id = group["id"].as_str().unwrap().to_string();
+
+    assert_eq!(group["type"], 3);
+    assert_eq!(group["name"], "weekend plans");
+    assert_eq!(group["owner_id"], owner.user.id.as_str());
+    assert_eq!(group["recipients"].as_array().unwrap().len(), 2);
+
+    // Any recipient can add users
+    let response = server
+        .put_auth(
+            &format!("/channels/{channel_id}/recipients/{}", third.user.id),
+            &first.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .get_auth("/users/@me/channels", &third.access_token)
+        .await
+        .unwrap();
+    let dms: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(dms.as_array().unwrap().iter().any(|dm| dm["id"] == channel_id.as_str()));
+
+    // Only the owner can remove others
+    let response = server
+        .delete_auth(
+            &format!("/channels/{channel_id}/recipients/{}", first.user.id),
+            &second.access_token,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // Ownership passes to the longest-standing recipient when the owner leaves
+    let response = server
+        .delete_auth(
+            &format!("/channels/{channel_id}/recipients/{}", owner.user.id),
+            &owner.access_token,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .get_auth(&format!("/channels/{channel_id}"), &second.access_token)
         .await
         .unwrap();
-    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+    let channel: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(channel["owner_id"], first.user.id.as_str());
 
-    // Verify deleted
     let response = server
-        .get_auth(&format!("/guilds/{}", guild.id), &auth.access_token)
+        .get_auth(&format!("/channels/{channel_id}"), &owner.access_token)
         .await
         .unwrap();
     assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
 }
 
 // ============================================================================
-// Channel Tests
+// Relationship Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_create_channel() {
+async fn test_friend_requests() {
     if !check_test_env().await {
         return;
     }
 
     let server = TestServer::start().await.expect("Failed to start server");
 
-    // Setup: register and create guild
-    let register_req = RegisterRequest::unique();
-    let response = server.post("/auth/register", &register_req).await.unwrap();
-    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    // Setup
+    let mut users = Vec::new();
+    for _ in 0..2 {
+        let register_req = RegisterRequest::unique();
+        let response = server.post("/auth/register", &register_req).await.unwrap();
+        let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        users.push(auth);
+    }
+    let (alice, bob) = (&users[0], &users[1]);
 
-    let guild_req = CreateGuildRequest::unique();
+    // Send friend request by tag
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .post_auth(
+            "/users/@me/relationships",
+            &alice.access_token,
+            &serde_json::json!({
+                "username": bob.user.username,
+                "discriminator": bob.user.discriminator
+            }),
+        )
         .await
         .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 
-    // Create channel
-    let channel_req = CreateChannelRequest::text_channel();
     let response = server
-        .post_auth(
-            &format!("/guilds/{}/channels", guild.id),
-            &auth.access_token,
-            &channel_req,
+        .get_auth("/users/@me/relationships", &bob.access_token)
+        .await
+        .unwrap();
+    let relationships: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(relationships[0]["id"], alice.user.id.as_str());
+    assert_eq!(relationships[0]["type"], 3);
+
+    // Accept
+    let response = server
+        .put_auth(
+            &format!("/users/@me/relationships/{}", alice.user.id),
+            &bob.access_token,
+            &serde_json::json!({}),
         )
         .await
         .unwrap();
-    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 
-    assert_eq!(channel.name, Some(channel_req.name));
-    assert_eq!(channel.guild_id, Some(guild.id));
+    let response = server
+        .get_auth("/users/@me/relationships", &alice.access_token)
+        .await
+        .unwrap();
+    let relationships: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(relationships[0]["type"], 1);
+    assert_eq!(relationships[0]["user"]["id"], bob.user.id.as_str());
+
+    // Removing a friend ends the friendship on both sides
+    let response = server
+        .delete_auth(
+            &format!("/users/@me/relationships/{}", alice.user.id),
+            &bob.access_token,
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server
+        .get_auth("/users/@me/relationships", &alice.access_token)
+        .await
+        .unwrap();
+    let relationships: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(relationships.as_array().unwrap().is_empty());
 }
 
 #[tokio::test]
-async fn test_get_guild_channels() {
+async fn test_block_prevents_dms() {
     if !check_test_env().await {
         return;
     }
@@ -342,96 +1988,84 @@ async fn test_get_guild_channels() {
     let server = TestServer::start().await.expect("Failed to start server");
 
     // Setup
-    let register_req = RegisterRequest::unique();
-    let response = server.post("/auth/register", &register_req).await.unwrap();
-    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let mut users = Vec::new();
+    for _ in 0..2 {
+        let register_req = RegisterRequest::unique();
+        let response = server.post("/auth/register", &register_req).await.unwrap();
+        let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        users.push(auth);
+    }
+    let (alice, bob) = (&users[0], &users[1]);
 
-    let guild_req = CreateGuildRequest::unique();
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
-        .await
-        .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
-
-    // Create some channels
-    let channel_req = CreateChannelRequest::text_channel();
-    server
         .post_auth(
-            &format!("/guilds/{}/channels", guild.id),
-            &auth.access_token,
-            &channel_req,
+            "/users/@me/channels",
+            &alice.access_token,
+            &serde_json::json!({"recipient_id": bob.user.id}),
         )
         .await
         .unwrap();
+    let dm: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let channel_id = dm["id"].as_str().unwrap().to_string();
 
-    // Get channels
+    // Bob blocks Alice
     let response = server
-        .get_auth(
-            &format!("/guilds/{}/channels", guild.id),
-            &auth.access_token,
+        .put_auth(
+            &format!("/users/@me/relationships/{}", alice.user.id),
+            &bob.access_token,
+            &serde_json::json!({"type": 2}),
         )
         .await
         .unwrap();
-    let channels: Vec<ChannelResponse> = assert_json(response, StatusCode::OK).await.unwrap();
-
-    // Should have at least the one we created
-    assert!(!channels.is_empty());
-}
-
-// ============================================================================
-// Message Tests
-// ============================================================================
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 
-#[tokio::test]
-async fn test_create_message() {
-    if !check_test_env().await {
-        return;
+    // Neither side can message the other in the existing DM
+    for sender in [alice, bob] {
+        let response = server
+            .post_auth(
+                &format!("/channels/{channel_id}/messages"),
+                &sender.access_token,
+                &serde_json::json!({"content": "hello"}),
+            )
+            .await
+            .unwrap();
+        assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
     }
 
-    let server = TestServer::start().await.expect("Failed to start server");
-
-    // Setup: register, create guild, create channel
-    let register_req = RegisterRequest::unique();
-    let response = server.post("/auth/register", &register_req).await.unwrap();
-    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
-
-    let guild_req = CreateGuildRequest::unique();
+    // Nor open a DM
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .post_auth(
+            "/users/@me/channels",
+            &alice.access_token,
+            &serde_json::json!({"recipient_id": bob.user.id}),
+        )
         .await
         .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
 
-    let channel_req = CreateChannelRequest::text_channel();
+    // Unblocking reopens the DM
     let response = server
-        .post_auth(
-            &format!("/guilds/{}/channels", guild.id),
-            &auth.access_token,
-            &channel_req,
+        .delete_auth(
+            &format!("/users/@me/relationships/{}", alice.user.id),
+            &bob.access_token,
         )
         .await
         .unwrap();
-    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 
-    // Create message
-    let message_req = CreateMessageRequest::simple("Hello, World!");
     let response = server
         .post_auth(
-            &format!("/channels/{}/messages", channel.id),
-            &auth.access_token,
-            &message_req,
+            &format!("/channels/{channel_id}/messages"),
+            &alice.access_token,
+            &serde_json::json!({"content": "hello again"}),
         )
         .await
         .unwrap();
-    let message: MessageResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
-
-    assert_eq!(message.content, "Hello, World!");
-    assert_eq!(message.channel_id, channel.id);
-    assert_eq!(message.author.id, auth.user.id);
+    assert_eq!(response.status(), StatusCode::CREATED);
 }
 
 #[tokio::test]
-async fn test_get_channel_messages() {
+async fn test_dm_privacy_setting() {
     if !check_test_env().await {
         return;
     }
@@ -439,52 +2073,66 @@ async fn test_get_channel_messages() {
     let server = TestServer::start().await.expect("Failed to start server");
 
     // Setup
-    let register_req = RegisterRequest::unique();
-    let response = server.post("/auth/register", &register_req).await.unwrap();
-    let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let mut users = Vec::new();
+    for _ in 0..2 {
+        let register_req = RegisterRequest::unique();
+        let response = server.post("/auth/register", &register_req).await.unwrap();
+        let auth: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        users.push(auth);
+    }
+    let (alice, bob) = (&users[0], &users[1]);
 
-    let guild_req = CreateGuildRequest::unique();
+    // Bob only accepts DMs from friends
     let response = server
-        .post_auth("/guilds", &auth.access_token, &guild_req)
+        .patch_auth(
+            "/users/@me",
+            &bob.access_token,
+            &serde_json::json!({"dm_privacy": 2}),
+        )
         .await
         .unwrap();
-    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let me: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(me["dm_privacy"], 2);
 
-    let channel_req = CreateChannelRequest::text_channel();
     let response = server
         .post_auth(
-            &format!("/guilds/{}/channels", guild.id),
-            &auth.access_token,
-            &channel_req,
+            "/users/@me/channels",
+            &alice.access_token,
+            &serde_json::json!({"recipient_id": bob.user.id}),
         )
         .await
         .unwrap();
-    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
-
-    // Create some messages
-    for i in 0..3 {
-        let message_req = CreateMessageRequest::simple(&format!("Message {}", i));
-        server
-            .post_auth(
-                &format!("/channels/{}/messages", channel.id),
-                &auth.access_token,
-                &message_req,
-            )
-            .await
-            .unwrap();
-    }
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
 
-    // Get messages
+    // Becoming friends allows the DM
     let response = server
-        .get_auth(
-            &format!("/channels/{}/messages", channel.id),
-            &auth.access_token,
+        .put_auth(
+            &format!("/users/@me/relationships/{}", bob.user.id),
+            &alice.access_token,
+            &serde_json::json!({}),
         )
         .await
         .unwrap();
-    let messages: Vec<MessageResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+    let response = server
+        .put_auth(
+            &format!("/users/@me/relationships/{}", alice.user.id),
+            &bob.access_token,
+            &serde_json::json!({"type": 1}),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
 
-    assert_eq!(messages.len(), 3);
+    let response = server
+        .post_auth(
+            "/users/@me/channels",
+            &alice.access_token,
+            &serde_json::json!({"recipient_id": bob.user.id}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
 }
 
 // ============================================================================
@@ -595,6 +2243,158 @@ async fn test_get_guild_members() {
     assert!(members.iter().any(|m| m.user.id == auth.user.id));
 }
 
+#[tokio::test]
+async fn test_guild_bans() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+
+    // Setup: owner and a member who joins by invite and posts a message
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let owner: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let member: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let guild_req = CreateGuildRequest::unique();
+    let response = server
+        .post_auth("/guilds", &owner.access_token, &guild_req)
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let channel_req = CreateChannelRequest::text_channel();
+    let response = server
+        .post_auth(
+            &format!("/guilds/{}/channels", guild.id),
+            &owner.access_token,
+            &channel_req,
+        )
+        .await
+        .unwrap();
+    let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let invite_req = CreateInviteRequest::default();
+    let response = server
+        .post_auth(
+            &format!("/channels/{}/invites", channel.id),
+            &owner.access_token,
+            &invite_req,
+        )
+        .await
+        .unwrap();
+    let invite: InviteResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let messages_path = format!("/channels/{}/messages", channel.id);
+    let response = server
+        .post_auth(&messages_path, &member.access_token, &CreateMessageRequest::simple("spam"))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::CREATED).await.unwrap();
+    let response = server
+        .post_auth(&messages_path, &owner.access_token, &CreateMessageRequest::simple("kept"))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::CREATED).await.unwrap();
+
+    // Members cannot ban
+    let ban_path = format!("/guilds/{}/bans/{}", guild.id, member.user.id);
+    let owner_ban_path = format!("/guilds/{}/bans/{}", guild.id, owner.user.id);
+    let response = server
+        .put_auth(&owner_ban_path, &member.access_token, &serde_json::json!({}))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::FORBIDDEN).await.unwrap();
+
+    // Banning removes the member and purges their recent messages
+    let response = server
+        .put_auth(
+            &ban_path,
+            &owner.access_token,
+            &serde_json::json!({ "reason": "spam", "delete_message_days": 1 }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server.get_auth(&messages_path, &owner.access_token).await.unwrap();
+    let messages: Vec<MessageResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].content, "kept");
+
+    let response = server
+        .get_auth(&format!("/guilds/{}/members", guild.id), &owner.access_token)
+        .await
+        .unwrap();
+    let members: Vec<MemberResponse> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert!(!members.iter().any(|m| m.user.id == member.user.id));
+
+    let response = server
+        .get_auth(&format!("/guilds/{}/bans", guild.id), &owner.access_token)
+        .await
+        .unwrap();
+    let bans: Vec<serde_json::Value> = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(bans.len(), 1);
+    assert_eq!(bans[0]["user"]["id"], member.user.id.as_str());
+    assert_eq!(bans[0]["reason"], "spam");
+
+    let response = server.get_auth(&ban_path, &owner.access_token).await.unwrap();
+    let ban: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    assert_eq!(ban["user"]["id"], member.user.id.as_str());
+
+    // Banning twice conflicts, and the banned user cannot rejoin
+    let response = server
+        .put_auth(&ban_path, &owner.access_token, &serde_json::json!({}))
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::CONFLICT).await.unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(!response.status().is_success());
+
+    // Out-of-range purge windows are rejected
+    let response = server
+        .put_auth(
+            &owner_ban_path,
+            &owner.access_token,
+            &serde_json::json!({ "delete_message_days": 8 }),
+        )
+        .await
+        .unwrap();
+    assert_status(response, StatusCode::BAD_REQUEST).await.unwrap();
+
+    // Unbanning lifts the ban
+    let response = server.delete_auth(&ban_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NO_CONTENT).await.unwrap();
+
+    let response = server.get_auth(&ban_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
+
+    let response = server.delete_auth(&ban_path, &owner.access_token).await.unwrap();
+    assert_status(response, StatusCode::NOT_FOUND).await.unwrap();
+}
+
 // ============================================================================
 // Invite Tests
 // ============================================================================
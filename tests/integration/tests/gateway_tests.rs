@@ -0,0 +1,482 @@
+//! Gateway Integration Tests
+//!
+//! Run two gateway nodes against the same Redis to cover node-routed user
+//! events and resuming a session on another node, plus the group DM events
+//! delivered to every recipient's sessions, channel messages and typing
+//! withheld from members who cannot view the channel, and closing the
+//! connections of revoked login sessions.
+//!
+//! These tests require:
+//! - Running PostgreSQL instance
+//! - Running Redis instance
+//! - Environment variables: DATABASE_URL, REDIS_URL, JWT_SECRET
+//!
+//! Run with: cargo test -p integration-tests --test gateway_tests
+
+use chat_core::Snowflake;
+use integration_tests::{assert_json, check_test_env, fixtures::*, TestGateway, TestServer};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Register a new user
+async fn register(server: &TestServer) -> AuthResponse {
+    let request = RegisterRequest::unique();
+    let response = server.post("/auth/register", &request).await.unwrap();
+    assert_json(response, StatusCode::CREATED).await.unwrap()
+}
+
+// ============================================================================
+// Node Routing Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_user_events_routed_to_owning_node() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node_a = TestGateway::start().await.expect("Failed to start gateway");
+    let node_b = TestGateway::start().await.expect("Failed to start gateway");
+    assert_ne!(node_a.node_id(), node_b.node_id());
+
+    let auth = register(&server).await;
+    let user_id: Snowflake = auth.user.id.parse().unwrap();
+
+    let mut client = node_a.connect().await.unwrap();
+    client.identify(&auth.access_token).await.unwrap();
+
+    // Only the node holding the session receives the user's events
+    let nodes = node_b
+        .state
+        .service_context()
+        .gateway_node_store()
+        .user_nodes(user_id)
+        .await
+        .unwrap();
+    assert_eq!(nodes, vec![node_a.node_id().to_string()]);
+
+    // A user event published through the other node reaches the client
+    let receivers = node_b
+        .state
+        .service_context()
+        .publisher()
+        .publish_to_user(user_id, "MESSAGE_ACK", serde_json::json!({"channel_id": "1"}))
+        .await
+        .unwrap();
+    assert_eq!(receivers, 1);
+
+    let event = client.recv_event("MESSAGE_ACK").await.unwrap();
+    assert_eq!(event["d"]["channel_id"], "1");
+}
+
+#[tokio::test]
+async fn test_user_node_kept_until_last_session() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node = TestGateway::start().await.expect("Failed to start gateway");
+
+    let auth = register(&server).await;
+    let user_id: Snowflake = auth.user.id.parse().unwrap();
+    let store = node.state.service_context().gateway_node_store();
+
+    store
+        .add_session("node-x", "session-1", user_id)
+        .await
+        .unwrap();
+    store
+        .add_session("node-x", "session-2", user_id)
+        .await
+        .unwrap();
+
+    // Another session on the node keeps routing the user's events there,
+    // even if a session is released twice
+    store
+        .remove_session("node-x", "session-1", user_id)
+        .await
+        .unwrap();
+    store
+        .remove_session("node-x", "session-1", user_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        store.user_nodes(user_id).await.unwrap(),
+        vec!["node-x".to_string()]
+    );
+
+    store
+        .remove_session("node-x", "session-2", user_id)
+        .await
+        .unwrap();
+    assert!(store.user_nodes(user_id).await.unwrap().is_empty());
+}
+
+// ============================================================================
+// Cross-Node Resume Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_resume_on_other_node() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node_a = TestGateway::start().await.expect("Failed to start gateway");
+    let node_b = TestGateway::start().await.expect("Failed to start gateway");
+
+    let auth = register(&server).await;
+    let user_id: Snowflake = auth.user.id.parse().unwrap();
+
+    let mut client = node_a.connect().await.unwrap();
+    let ready = client.identify(&auth.access_token).await.unwrap();
+    let session_id = ready["d"]["session_id"].as_str().unwrap().to_string();
+    let seq = ready["s"].as_u64().unwrap();
+    client.close().await.unwrap();
+
+    let mut client = node_b.connect().await.unwrap();
+    client
+        .resume(&auth.access_token, &session_id, seq)
+        .await
+        .unwrap();
+    client.recv_event("RESUMED").await.unwrap();
+
+    // User events now go to the new node only
+    let nodes = node_b
+        .state
+        .service_context()
+        .gateway_node_store()
+        .user_nodes(user_id)
+        .await
+        .unwrap();
+    assert_eq!(nodes, vec![node_b.node_id().to_string()]);
+}
+
+#[tokio::test]
+async fn test_resume_takes_over_open_session() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node_a = TestGateway::start().await.expect("Failed to start gateway");
+    let node_b = TestGateway::start().await.expect("Failed to start gateway");
+
+    let auth = register(&server).await;
+
+    let mut old_client = node_a.connect().await.unwrap();
+    let ready = old_client.identify(&auth.access_token).await.unwrap();
+    let session_id = ready["d"]["session_id"].as_str().unwrap().to_string();
+    let seq = ready["s"].as_u64().unwrap();
+
+    // Resume on the other node while the first connection is still open
+    let mut new_client = node_b.connect().await.unwrap();
+    new_client
+        .resume(&auth.access_token, &session_id, seq)
+        .await
+        .unwrap();
+
+    assert_eq!(old_client.recv_close().await.unwrap(), Some(4009));
+    new_client.recv_event("RESUMED").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_session_of_dead_node() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node_a = TestGateway::start().await.expect("Failed to start gateway");
+    let node_b = TestGateway::start().await.expect("Failed to start gateway");
+
+    let auth = register(&server).await;
+    let user_id: Snowflake = auth.user.id.parse().unwrap();
+
+    let mut client = node_a.connect().await.unwrap();
+    let ready = client.identify(&auth.access_token).await.unwrap();
+    let session_id = ready["d"]["session_id"].as_str().unwrap().to_string();
+    let seq = ready["s"].as_u64().unwrap();
+
+    // The node's lease ends without its connections being cleaned up
+    node_a.state.node().stop().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The user's events are no longer routed to the dead node
+    let nodes = node_b
+        .state
+        .service_context()
+        .gateway_node_store()
+        .user_nodes(user_id)
+        .await
+        .unwrap();
+    assert!(nodes.is_empty());
+
+    let mut client = node_b.connect().await.unwrap();
+    client
+        .resume(&auth.access_token, &session_id, seq)
+        .await
+        .unwrap();
+    client.recv_event("RESUMED").await.unwrap();
+}
+
+// ============================================================================
+// Group DM Event Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_group_dm_recipient_events() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node = TestGateway::start().await.expect("Failed to start gateway");
+
+    let owner = register(&server).await;
+    let friend = register(&server).await;
+    let newcomer = register(&server).await;
+
+    let mut friend_client = node.connect().await.unwrap();
+    friend_client.identify(&friend.access_token).await.unwrap();
+    let mut newcomer_client = node.connect().await.unwrap();
+    newcomer_client.identify(&newcomer.access_token).await.unwrap();
+
+    let response = server
+        .post_auth(
+            "/users/@me/channels",
+            &owner.access_token,
+            &serde_json::json!({"recipients": [friend.user.id]}),
+        )
+        .await
+        .unwrap();
+    let group: serde_json::Value = assert_json(response, StatusCode::OK).await.unwrap();
+    let channel_id = group["id"].as_str().unwrap().to_string();
+    friend_client.recv_event("CHANNEL_CREATE").await.unwrap();
+
+    let response = server
+        .put_auth(
+            &format!("/channels/{channel_id}/recipients/{}", newcomer.user.id),
+            &owner.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // The new recipient gets the channel, then everyone sees the addition
+    let event = newcomer_client.recv_event("CHANNEL_CREATE").await.unwrap();
+    assert_eq!(event["d"]["id"], channel_id.as_str());
+    assert_eq!(event["d"]["type"], 3);
+    newcomer_client.recv_event("CHANNEL_RECIPIENT_ADD").await.unwrap();
+
+    let event = friend_client.recv_event("CHANNEL_RECIPIENT_ADD").await.unwrap();
+    assert_eq!(event["d"]["channel_id"], channel_id.as_str());
+    assert_eq!(event["d"]["user"]["id"], newcomer.user.id.as_str());
+
+    // Leaving notifies the remaining recipients
+    let response = server
+        .delete_auth(
+            &format!("/channels/{channel_id}/recipients/{}", newcomer.user.id),
+            &newcomer.access_token,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let event = friend_client.recv_event("CHANNEL_RECIPIENT_REMOVE").await.unwrap();
+    assert_eq!(event["d"]["user"]["id"], newcomer.user.id.as_str());
+    newcomer_client.recv_event("CHANNEL_DELETE").await.unwrap();
+}
+
+// ============================================================================
+// Channel Permission Tests
+// ============================================================================
+
+/// Create a guild with two text channels and a member denied the second one
+///
+/// Returns the visible and hidden channel IDs.
+async fn hidden_channel_guild(
+    server: &TestServer,
+    owner: &AuthResponse,
+    member: &AuthResponse,
+) -> (String, String) {
+    let response = server
+        .post_auth("/guilds", &owner.access_token, &CreateGuildRequest::unique())
+        .await
+        .unwrap();
+    let guild: GuildResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+
+    let mut channels = Vec::new();
+    for _ in 0..2 {
+        let response = server
+            .post_auth(
+                &format!("/guilds/{}/channels", guild.id),
+                &owner.access_token,
+                &CreateChannelRequest::text_channel(),
+            )
+            .await
+            .unwrap();
+        let channel: ChannelResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+        channels.push(channel.id);
+    }
+    let hidden = channels.pop().unwrap();
+    let visible = channels.pop().unwrap();
+
+    let response = server
+        .post_auth(
+            &format!("/channels/{visible}/invites"),
+            &owner.access_token,
+            &CreateInviteRequest::default(),
+        )
+        .await
+        .unwrap();
+    let invite: InviteResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let response = server
+        .post_auth(
+            &format!("/invites/{}", invite.code),
+            &member.access_token,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    // Only the member is denied the hidden channel
+    let response = server
+        .put_auth(
+            &format!("/channels/{hidden}/permissions/{}", member.user.id),
+            &owner.access_token,
+            &serde_json::json!({ "type": 1, "deny": "1" }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    (visible, hidden)
+}
+
+#[tokio::test]
+async fn test_hidden_channel_messages_not_delivered() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node = TestGateway::start().await.expect("Failed to start gateway");
+
+    let owner = register(&server).await;
+    let member = register(&server).await;
+    let (visible, hidden) = hidden_channel_guild(&server, &owner, &member).await;
+
+    let mut client = node.connect().await.unwrap();
+    client.identify(&member.access_token).await.unwrap();
+
+    for channel_id in [&hidden, &visible] {
+        let response = server
+            .post_auth(
+                &format!("/channels/{channel_id}/messages"),
+                &owner.access_token,
+                &CreateMessageRequest::simple("hello"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    // The first message the member receives is the one in the visible channel
+    let event = client.recv_event("MESSAGE_CREATE").await.unwrap();
+    assert_eq!(event["d"]["channel_id"], visible.as_str());
+}
+
+#[tokio::test]
+async fn test_hidden_channel_typing_not_delivered() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node = TestGateway::start().await.expect("Failed to start gateway");
+
+    let owner = register(&server).await;
+    let member = register(&server).await;
+    let (visible, hidden) = hidden_channel_guild(&server, &owner, &member).await;
+
+    let mut client = node.connect().await.unwrap();
+    client.identify(&member.access_token).await.unwrap();
+
+    for channel_id in [&hidden, &visible] {
+        let response = server
+            .post_auth(
+                &format!("/channels/{channel_id}/typing"),
+                &owner.access_token,
+                &(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // The first typing event the member receives is the one in the visible channel
+    let event = client.recv_event("TYPING_START").await.unwrap();
+    assert_eq!(event["d"]["channel_id"], visible.as_str());
+}
+
+// ============================================================================
+// Session Revocation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_revoked_login_closes_connection() {
+    if !check_test_env().await {
+        return;
+    }
+
+    let server = TestServer::start().await.expect("Failed to start server");
+    let node_a = TestGateway::start().await.expect("Failed to start gateway");
+    let node_b = TestGateway::start().await.expect("Failed to start gateway");
+
+    let register_req = RegisterRequest::unique();
+    let response = server.post("/auth/register", &register_req).await.unwrap();
+    let first: AuthResponse = assert_json(response, StatusCode::CREATED).await.unwrap();
+    let response = server
+        .post("/auth/login", &LoginRequest::from_register(&register_req))
+        .await
+        .unwrap();
+    let second: AuthResponse = assert_json(response, StatusCode::OK).await.unwrap();
+
+    let mut revoked = node_a.connect().await.unwrap();
+    revoked.identify(&first.access_token).await.unwrap();
+    let mut kept = node_b.connect().await.unwrap();
+    kept.identify(&second.access_token).await.unwrap();
+
+    let response = server
+        .post_auth("/users/@me/sessions/logout-others", &second.access_token, &())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(revoked.recv_close().await.unwrap(), Some(4004));
+
+    // The revoked login's access token cannot identify again
+    let mut retry = node_b.connect().await.unwrap();
+    retry
+        .send(2, serde_json::json!({ "token": first.access_token }))
+        .await
+        .unwrap();
+    assert_eq!(retry.recv_close().await.unwrap(), Some(4004));
+
+    // The current login's connection stays open
+    let user_id: Snowflake = first.user.id.parse().unwrap();
+    node_a
+        .state
+        .service_context()
+        .publisher()
+        .publish_to_user(user_id, "MESSAGE_ACK", serde_json::json!({"channel_id": "1"}))
+        .await
+        .unwrap();
+    kept.recv_event("MESSAGE_ACK").await.unwrap();
+}